@@ -214,6 +214,28 @@ mod gpkg {
     }
 }
 
+mod shp {
+    use geozero::geo_types::GeoWriter;
+    use geozero_shp::Reader;
+
+    /// Read all geometries with a .shp buffer of `capacity` bytes
+    pub(super) fn shp_to_geo(
+        fpath: &str,
+        capacity: usize,
+        count: usize,
+    ) -> Result<(), geozero_shp::Error> {
+        let reader = Reader::from_path_with_capacity(fpath, capacity)?;
+        let mut geo = GeoWriter::new();
+        let mut cnt = 0;
+        for result in reader.iter_geometries(&mut geo) {
+            result?;
+            cnt += 1;
+        }
+        assert_eq!(cnt, count);
+        Ok(())
+    }
+}
+
 mod gdal {
     use super::Extent;
     use gdal::vector::{Geometry, Layer, LayerAccess};
@@ -403,6 +425,18 @@ fn buildings_benchmark(c: &mut Criterion) {
     group.bench_function("1-shp", |b| {
         b.iter(|| gdal::gdal_read("tests/data/osm-buildings-3857-ch.shp", &bbox, 2407771))
     });
+    group.bench_function("1-shp_geozero", |b| {
+        b.iter(|| {
+            shp::shp_to_geo(
+                "tests/data/osm-buildings-3857-ch.shp",
+                geozero_shp::DEFAULT_BUF_CAPACITY,
+                2407771,
+            )
+        })
+    });
+    group.bench_function("1-shp_geozero_1mb", |b| {
+        b.iter(|| shp::shp_to_geo("tests/data/osm-buildings-3857-ch.shp", 1024 * 1024, 2407771))
+    });
     if std::env::var("SKIP_GPKG_BIG").is_err() {
         // A test machine freezes when running this bench !!??
         group.bench_function("3-gpkg", |b| {
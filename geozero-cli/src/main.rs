@@ -119,23 +119,17 @@ async fn process(args: Cli) -> Result<()> {
             fgb.write(&mut fout).map_err(fgb_to_geozero_err)?;
         }
         Some("svg") => {
-            let mut processor = SvgWriter::new(&mut fout, true);
-            set_dimensions(&mut processor, args.extent);
+            let mut processor = SvgWriter::new(&mut fout, 800, 600);
+            if let Some(extent) = &args.extent {
+                processor =
+                    processor.with_viewbox(extent.minx, extent.miny, extent.maxx, extent.maxy);
+            }
             transform(args, &mut processor).await?;
         }
         _ => panic!("Unknown output file extension"),
     }
     Ok(())
 }
-fn set_dimensions(processor: &mut SvgWriter<&mut BufWriter<File>>, extent: Option<Extent>) {
-    if let Some(extent) = extent {
-        processor.set_dimensions(extent.minx, extent.miny, extent.maxx, extent.maxy, 800, 600);
-    } else {
-        // TODO: get image size as opts and full extent from data
-        processor.set_dimensions(-180.0, -90.0, 180.0, 90.0, 800, 600);
-    }
-}
-
 fn fgb_to_geozero_err(fgb_err: flatgeobuf::Error) -> GeozeroError {
     match fgb_err {
         flatgeobuf::Error::MissingMagicBytes => {
@@ -0,0 +1,71 @@
+use crate::Error;
+use dbase::encoding::UnicodeLossy;
+use std::io::{Read, Seek};
+use yore::code_pages::{CP1250, CP1251, CP1252, CP1253, CP437, CP850, CP852, CP866};
+
+/// Offset of the language driver / code page byte in the DBF header
+pub(crate) const CODE_PAGE_MARK_OFFSET: usize = 29;
+
+/// Character encoding of the DBF attribute values
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum DbaseEncoding {
+    /// UTF-8, invalid characters are replaced
+    #[default]
+    Utf8,
+    /// DOS USA (code page 437)
+    Cp437,
+    /// DOS Multilingual Latin-1 (code page 850)
+    Cp850,
+    /// DOS Latin-2 (code page 852)
+    Cp852,
+    /// DOS Cyrillic (code page 866)
+    Cp866,
+    /// Windows Central European (code page 1250)
+    Windows1250,
+    /// Windows Cyrillic (code page 1251)
+    Windows1251,
+    /// Windows Western European / Latin-1 (code page 1252)
+    Windows1252,
+    /// Windows Greek (code page 1253)
+    Windows1253,
+}
+
+impl DbaseEncoding {
+    /// Returns the encoding declared by the language driver byte of the DBF header
+    /// if it is known
+    /// ```
+    /// use geozero_shp::DbaseEncoding;
+    ///
+    /// assert_eq!(DbaseEncoding::from_code_page_mark(0xC9), Some(DbaseEncoding::Windows1251));
+    /// assert_eq!(DbaseEncoding::from_code_page_mark(0x00), None);
+    /// ```
+    pub fn from_code_page_mark(mark: u8) -> Option<DbaseEncoding> {
+        match mark {
+            0x01 => Some(DbaseEncoding::Cp437),
+            0x02 | 0x37 => Some(DbaseEncoding::Cp850),
+            0x03 | 0x57 | 0x58 | 0x59 => Some(DbaseEncoding::Windows1252),
+            0x26 | 0x65 => Some(DbaseEncoding::Cp866),
+            0x64 => Some(DbaseEncoding::Cp852),
+            0xC8 => Some(DbaseEncoding::Windows1250),
+            0xC9 => Some(DbaseEncoding::Windows1251),
+            0xCB => Some(DbaseEncoding::Windows1253),
+            _ => None,
+        }
+    }
+
+    /// Creates a dbase reader decoding character values with this encoding
+    pub(crate) fn dbf_reader<T: Read + Seek>(self, source: T) -> Result<dbase::Reader<T>, Error> {
+        let reader = match self {
+            DbaseEncoding::Utf8 => dbase::Reader::new_with_encoding(source, UnicodeLossy)?,
+            DbaseEncoding::Cp437 => dbase::Reader::new_with_encoding(source, CP437)?,
+            DbaseEncoding::Cp850 => dbase::Reader::new_with_encoding(source, CP850)?,
+            DbaseEncoding::Cp852 => dbase::Reader::new_with_encoding(source, CP852)?,
+            DbaseEncoding::Cp866 => dbase::Reader::new_with_encoding(source, CP866)?,
+            DbaseEncoding::Windows1250 => dbase::Reader::new_with_encoding(source, CP1250)?,
+            DbaseEncoding::Windows1251 => dbase::Reader::new_with_encoding(source, CP1251)?,
+            DbaseEncoding::Windows1252 => dbase::Reader::new_with_encoding(source, CP1252)?,
+            DbaseEncoding::Windows1253 => dbase::Reader::new_with_encoding(source, CP1253)?,
+        };
+        Ok(reader)
+    }
+}
@@ -1,8 +1,8 @@
 use crate::point_z::BBoxZ;
 use crate::Error;
-use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::fmt;
-use std::io::Read;
+use std::io::{Read, Write};
 
 pub(crate) const HEADER_SIZE: i32 = 100;
 const FILE_CODE: i32 = 9994;
@@ -71,6 +71,34 @@ impl Header {
 
         Ok(hdr)
     }
+
+    /// Total file length in bytes
+    ///
+    /// The length in 16bit words is interpreted as unsigned 32bit integer,
+    /// so the length of files exceeding the 2 GB limit of the specification
+    /// is not negative.
+    pub fn file_length_bytes(&self) -> u64 {
+        u64::from(self.file_length as u32) * 2
+    }
+
+    pub(crate) fn write_to<T: Write>(&self, dest: &mut T) -> Result<(), Error> {
+        dest.write_i32::<BigEndian>(FILE_CODE)?;
+        dest.write_all(&[0; SIZE_OF_SKIP])?;
+        dest.write_i32::<BigEndian>(self.file_length)?;
+        dest.write_i32::<LittleEndian>(self.version)?;
+        dest.write_i32::<LittleEndian>(self.shape_type as i32)?;
+
+        dest.write_f64::<LittleEndian>(self.bbox.min.x)?;
+        dest.write_f64::<LittleEndian>(self.bbox.min.y)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.x)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.y)?;
+        dest.write_f64::<LittleEndian>(self.bbox.min.z)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.z)?;
+        dest.write_f64::<LittleEndian>(self.bbox.min.m)?;
+        dest.write_f64::<LittleEndian>(self.bbox.max.m)?;
+
+        Ok(())
+    }
 }
 
 /// The enum for the ShapeType as defined in the
@@ -205,4 +233,25 @@ mod tests {
         src.seek(SeekFrom::Start(0)).unwrap();
         assert!(Header::read_from(&mut src).is_err());
     }
+
+    #[test]
+    fn write_read() {
+        use std::io::Cursor;
+
+        let mut hdr = Header {
+            shape_type: ShapeType::PolygonZ,
+            file_length: 136,
+            ..Default::default()
+        };
+        hdr.bbox.min.x = 1.0;
+        hdr.bbox.max.y = 5.0;
+        hdr.bbox.max.z = 2.0;
+
+        let mut src = Cursor::new(vec![]);
+        hdr.write_to(&mut src).unwrap();
+        assert_eq!(src.get_ref().len(), HEADER_SIZE as usize);
+
+        src.seek(SeekFrom::Start(0)).unwrap();
+        assert!(Header::read_from(&mut src).unwrap() == hdr);
+    }
 }
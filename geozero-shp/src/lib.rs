@@ -1,13 +1,17 @@
+mod encoding;
 mod header;
 mod point_z;
 mod property_processor;
 pub mod reader;
 mod shp_reader;
+mod shp_writer;
 mod shx_reader;
 
+pub use crate::encoding::DbaseEncoding;
 pub use crate::header::ShapeType;
-pub use crate::reader::Reader;
-pub use crate::shp_reader::NO_DATA;
+pub use crate::reader::{Reader, DEFAULT_BUF_CAPACITY};
+pub use crate::shp_reader::{NodataPolicy, NO_DATA};
+pub use crate::shp_writer::ShapeWriter;
 
 // Re-export GeoZero to help avoid version conflicts
 pub use geozero;
@@ -28,6 +32,9 @@ pub enum Error {
     /// The Multipatch shape read from the file had an invalid [PatchType](enum.PatchType.html) code
     #[error("Invalid patch type `{0}`")]
     InvalidPatchType(i32),
+    /// The Multipatch shape read from the file has a part type which can't be represented
+    #[error("Unsupported patch type `{0}`")]
+    UnsupportedPatchType(i32),
     /// Error returned when trying to read the shape records as a certain shape type
     /// but the actual shape type does not correspond to the one asked
     #[error("The requested type: '{requested}' does not correspond to the actual shape type: '{actual}'")]
@@ -37,14 +44,83 @@ pub enum Error {
         /// The actual type of the shape
         actual: ShapeType,
     },
+    /// Error returned when writing a shape with another type than the previous shapes
+    #[error("Shapes of type '{found}' can not be written to a file with shape type '{expected}'")]
+    MixedShapeTypes {
+        /// The ShapeType of the file
+        expected: ShapeType,
+        /// The type of the shape to write
+        found: ShapeType,
+    },
+    /// The property name is not a valid dbf field name
+    #[error("Invalid field name `{0}`")]
+    InvalidFieldName(String),
     #[error("Invalid shape record size")]
     InvalidShapeRecordSize,
+    /// The parts of a multipart shape don't start at the first point or aren't in ascending order
+    #[error("Invalid part start index `{0}`")]
+    InvalidPartsIndex(i32),
+    /// A shape record extends beyond the end of the file given by the header or index
+    #[error("Shape record {record_number} at offset {offset} exceeds the file length of {file_length} bytes")]
+    RecordOutOfBounds {
+        /// Record number from the record header (starting at 1)
+        record_number: i32,
+        /// Byte offset of the record header in the .shp file
+        offset: u64,
+        /// File length in bytes
+        file_length: u64,
+    },
+    /// A shape record could not be decoded
+    #[error("Shape record {record_number} at offset {offset} could not be decoded")]
+    ShapeDecode {
+        /// Record number from the record header (starting at 1)
+        record_number: i32,
+        /// Byte offset of the record header in the .shp file
+        offset: u64,
+        /// The error raised while decoding
+        #[source]
+        source: Box<Error>,
+    },
     #[error("Dbase Error")]
     DbaseError(#[from] dbase::Error),
     #[error("Dbf missing")]
     MissingDbf,
+    /// The .dbf file has fewer records than the .shp file
+    #[error("Dbf record missing for feature {0}")]
+    MissingDbfRecord(u64),
+    /// The requested field is not contained in the .dbf file
+    #[error("Unknown field `{0}`")]
+    UnknownField(String),
+    /// The resume offset is not the start of a shape record
+    #[error("Invalid resume position `{0}`: not on a shape record boundary")]
+    InvalidResumePosition(u64),
     #[error("Index file missing")]
     MissingIndexFile,
+    /// The reader was not created from a file path
+    #[error("File path missing")]
+    MissingPath,
+    /// The requested shape index is not contained in the index file
+    #[error("Shape index `{index}` out of bounds (number of shapes: {len})")]
+    IndexOutOfBounds { index: usize, len: usize },
     #[error("Geozero error")]
     GeozeroError(#[from] geozero::error::GeozeroError),
+    #[cfg(feature = "zip")]
+    #[error("ZIP error")]
+    ZipError(#[from] zip::result::ZipError),
+    /// No (matching) .shp file was found in the archive
+    #[error("Shapefile missing in archive")]
+    MissingShapefile,
+    /// The archive contains multiple shapefiles, but no name was given
+    #[error("Archive contains multiple shapefiles: {}", .0.join(", "))]
+    AmbiguousShapefile(Vec<String>),
+}
+
+impl From<Error> for geozero::error::GeozeroError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::GeozeroError(e) => e,
+            Error::IoError(e) => geozero::error::GeozeroError::IoError(e),
+            other => geozero::error::GeozeroError::Dataset(other.to_string()),
+        }
+    }
 }
@@ -6,50 +6,58 @@ use geozero::{ColumnValue, FeatureProperties, PropertyProcessor};
 impl FeatureProperties for ShapeRecord {
     /// Process feature properties.
     fn process_properties<P: PropertyProcessor>(&self, processor: &mut P) -> Result<bool> {
-        let mut i = 0;
-        for (name, value) in self.record.as_ref().iter() {
-            let finish = match value {
-                FieldValue::Character(Some(val)) => {
-                    processor.property(i, name, &ColumnValue::String(val))?
-                }
-                FieldValue::Numeric(Some(val)) => {
-                    processor.property(i, name, &ColumnValue::Double(*val))?
-                }
-                FieldValue::Logical(Some(val)) => {
-                    processor.property(i, name, &ColumnValue::Bool(*val))?
-                }
-                FieldValue::Date(Some(_)) => {
-                    let s = value.to_string();
-                    processor.property(i, name, &ColumnValue::DateTime(&s))?
-                }
-                FieldValue::Float(Some(val)) => {
-                    processor.property(i, name, &ColumnValue::Float(*val))?
-                }
-                FieldValue::Integer(val) => processor.property(i, name, &ColumnValue::Int(*val))?,
-                FieldValue::Double(val) => {
-                    processor.property(i, name, &ColumnValue::Double(*val))?
-                }
-                FieldValue::Currency(val) => {
-                    processor.property(i, name, &ColumnValue::Double(*val))?
-                }
-                FieldValue::DateTime(_) => {
-                    let s = value.to_string();
-                    processor.property(i, name, &ColumnValue::DateTime(&s))?
-                }
-                FieldValue::Memo(val) => processor.property(i, name, &ColumnValue::String(val))?,
-                FieldValue::Character(None)
-                | FieldValue::Numeric(None)
-                | FieldValue::Logical(None)
-                | FieldValue::Date(None)
-                | FieldValue::Float(None) => {
-                    continue; // Ignore NULL values
-                }
-            };
-            if finish {
-                return Ok(true);
+        process_record_properties(&self.record, processor)
+    }
+}
+
+/// Process the values of a dbf record as feature properties.
+///
+/// Properties are processed ordered by field name, since dbf records don't keep the field order.
+pub(crate) fn process_record_properties<P: PropertyProcessor>(
+    record: &dbase::Record,
+    processor: &mut P,
+) -> Result<bool> {
+    let mut values: Vec<_> = record.as_ref().iter().collect();
+    values.sort_unstable_by_key(|&(name, _)| name);
+    let mut i = 0;
+    for (name, value) in values {
+        let finish = match value {
+            FieldValue::Character(Some(val)) => {
+                processor.property(i, name, &ColumnValue::String(val))?
+            }
+            FieldValue::Numeric(Some(val)) => {
+                processor.property(i, name, &ColumnValue::Double(*val))?
+            }
+            FieldValue::Logical(Some(val)) => {
+                processor.property(i, name, &ColumnValue::Bool(*val))?
+            }
+            FieldValue::Date(Some(_)) => {
+                let s = value.to_string();
+                processor.property(i, name, &ColumnValue::DateTime(&s))?
+            }
+            FieldValue::Float(Some(val)) => {
+                processor.property(i, name, &ColumnValue::Float(*val))?
+            }
+            FieldValue::Integer(val) => processor.property(i, name, &ColumnValue::Int(*val))?,
+            FieldValue::Double(val) => processor.property(i, name, &ColumnValue::Double(*val))?,
+            FieldValue::Currency(val) => processor.property(i, name, &ColumnValue::Double(*val))?,
+            FieldValue::DateTime(_) => {
+                let s = value.to_string();
+                processor.property(i, name, &ColumnValue::DateTime(&s))?
+            }
+            FieldValue::Memo(val) => processor.property(i, name, &ColumnValue::String(val))?,
+            FieldValue::Character(None)
+            | FieldValue::Numeric(None)
+            | FieldValue::Logical(None)
+            | FieldValue::Date(None)
+            | FieldValue::Float(None) => {
+                continue; // Ignore NULL values
             }
-            i += 1;
+        };
+        if finish {
+            return Ok(true);
         }
-        Ok(false)
+        i += 1;
     }
+    Ok(false)
 }
@@ -1,36 +1,301 @@
-use crate::shp_reader::{read_shape, RecordHeader};
+use crate::encoding::{DbaseEncoding, CODE_PAGE_MARK_OFFSET};
+use crate::property_processor::process_record_properties;
+use crate::shp_reader::{
+    is_no_data, read_record, read_shape, read_shape_filtered, read_shape_rec, BBoxFilter,
+    NodataPolicy, RecordHeader,
+};
 use crate::shx_reader::{read_index_file, ShapeIndex};
-use crate::{header, Error};
+use crate::{header, Error, ShapeType};
 pub use dbase::{FieldInfo, FieldType};
-use geozero::{FeatureProcessor, FeatureProperties, GeomProcessor};
+use geozero::{FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry, ProcessorSink};
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufReader, Read, Seek};
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
 use std::iter::FusedIterator;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Struct that handle iteration over the shapes of a .shp file
 pub struct ShapeIterator<'a, P: GeomProcessor, T: Read> {
     processor: &'a mut P,
     source: T,
-    current_pos: usize,
-    file_length: usize,
+    current_pos: u64,
+    file_length: u64,
+    bbox: Option<BBoxFilter>,
+    lenient: bool,
+    m_nodata: NodataPolicy,
+    /// Number of shapes from the .shx index
+    total: Option<u64>,
+    progress: Option<Progress<'a>>,
+    /// Error positioning the source at the first record, returned by the first `next`
+    seek_error: Option<Error>,
 }
 
-impl<'a, P: GeomProcessor, T: Read + 'a> Iterator for ShapeIterator<'a, P, T> {
+/// Progress notification every `interval` shapes
+struct Progress<'a> {
+    interval: u64,
+    processed: u64,
+    reported: u64,
+    total: Option<u64>,
+    callback: Box<dyn FnMut(u64, Option<u64>) + 'a>,
+}
+
+impl Progress<'_> {
+    fn inc(&mut self) {
+        self.processed += 1;
+        if self.processed.is_multiple_of(self.interval) {
+            self.report();
+        }
+    }
+    fn finish(&mut self) {
+        if self.processed != self.reported {
+            self.report();
+        }
+    }
+    fn report(&mut self) {
+        self.reported = self.processed;
+        (self.callback)(self.processed, self.total);
+    }
+}
+
+/// Shape record which was read and decoded successfully
+struct ValidRecord {
+    record_number: i32,
+    offset: u64,
+    content: Vec<u8>,
+}
+
+impl<'a, P: GeomProcessor, T: Read + 'a> ShapeIterator<'a, P, T> {
+    /// Only process shapes intersecting the given bounding box
+    ///
+    /// Shapes outside of the bounding box are skipped without decoding the geometry.
+    /// The bbox stored in each record is used for the intersection test,
+    /// for points the coordinate itself is tested.
+    pub fn with_bbox(mut self, min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Self {
+        self.bbox = Some(BBoxFilter {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        });
+        self
+    }
+
+    /// Continue with the next record if a shape record can't be decoded
+    ///
+    /// Each record is decoded before being passed to the processor, so the processor
+    /// doesn't receive any callbacks for invalid records.
+    /// An [`Error::ShapeDecode`] is returned for every skipped record.
+    /// Iteration ends after a record header can't be read or a record is truncated.
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Byte offset of the next record in the .shp file
+    ///
+    /// The position can be saved to continue the iteration later with
+    /// [Reader::iter_geometries_from()](struct.Reader.html#method.iter_geometries_from).
+    pub fn position(&self) -> u64 {
+        self.current_pos
+    }
+
+    /// Call `callback` with the number of processed shapes after every `interval` shapes
+    /// and at the end of the iteration
+    ///
+    /// The total number of shapes is passed, if the .shx index was added.
+    pub fn with_progress(
+        mut self,
+        interval: u64,
+        callback: impl FnMut(u64, Option<u64>) + 'a,
+    ) -> Self {
+        self.progress = Some(Progress {
+            interval: interval.max(1),
+            processed: 0,
+            reported: 0,
+            total: self.total,
+            callback: Box::new(callback),
+        });
+        self
+    }
+
+    fn report_progress(&mut self, done: bool) {
+        if let Some(progress) = self.progress.as_mut() {
+            if done {
+                progress.finish();
+            } else {
+                progress.inc();
+            }
+        }
+    }
+}
+
+impl<'a, P: GeomProcessor, T: Read + Seek + 'a> ShapeIterator<'a, P, T> {
+    /// Process the next shape and return its record number and file offset
+    fn next_record(&mut self) -> Option<Result<(i32, u64), Error>> {
+        if let Some(e) = self.take_seek_error() {
+            return Some(Err(e));
+        }
+        if self.lenient {
+            return self
+                .next_valid_record()
+                .map(|result| self.process_valid_record(result?));
+        }
+        while self.current_pos < self.file_length {
+            let offset = self.current_pos;
+            let result = match &self.bbox {
+                None => read_shape(self.processor, &mut self.source, offset, self.m_nodata)
+                    .map(|hdr| (hdr, true)),
+                Some(bbox) => read_shape_filtered(
+                    self.processor,
+                    &mut self.source,
+                    offset,
+                    bbox,
+                    self.m_nodata,
+                ),
+            };
+            let (hdr, processed) = match result
+                .and_then(|(hdr, processed)| self.advance(offset, &hdr).map(|()| (hdr, processed)))
+            {
+                Err(e) => {
+                    // The start of the next record is unknown
+                    self.current_pos = self.file_length;
+                    return Some(Err(e));
+                }
+                Ok(hdr_and_processed) => hdr_and_processed,
+            };
+            if processed {
+                return Some(Ok((hdr.record_number, offset)));
+            }
+        }
+        None
+    }
+
+    /// Read the next shape record and check whether it can be decoded
+    ///
+    /// The position is advanced to the next record, also if decoding fails.
+    fn next_valid_record(&mut self) -> Option<Result<ValidRecord, Error>> {
+        if let Some(e) = self.take_seek_error() {
+            return Some(Err(e));
+        }
+        while self.current_pos < self.file_length {
+            let offset = self.current_pos;
+            let record = read_record(&mut self.source, offset)
+                .and_then(|(hdr, content)| self.advance(offset, &hdr).map(|()| (hdr, content)));
+            let (hdr, content) = match record {
+                Err(e) => {
+                    // The start of the next record is unknown
+                    self.current_pos = self.file_length;
+                    return Some(Err(e));
+                }
+                Ok(record) => record,
+            };
+            let checked = match &self.bbox {
+                None => Ok(true),
+                Some(bbox) => bbox.intersects_record(&content),
+            }
+            .and_then(|intersects| {
+                if intersects {
+                    let mut sink = ProcessorSink::new();
+                    read_shape_rec(
+                        &mut sink,
+                        &mut content.as_slice(),
+                        content.len(),
+                        self.m_nodata,
+                    )?;
+                }
+                Ok(intersects)
+            });
+            match checked {
+                Err(e) => return Some(Err(hdr.decode_error(offset, e))),
+                Ok(true) => {
+                    return Some(Ok(ValidRecord {
+                        record_number: hdr.record_number,
+                        offset,
+                        content,
+                    }))
+                }
+                Ok(false) => {}
+            }
+        }
+        None
+    }
+
+    /// Read the next shape record without decoding it
+    fn next_raw_record(&mut self) -> Option<Result<ValidRecord, Error>> {
+        if let Some(e) = self.take_seek_error() {
+            return Some(Err(e));
+        }
+        if self.current_pos >= self.file_length {
+            return None;
+        }
+        let offset = self.current_pos;
+        let record = read_record(&mut self.source, offset)
+            .and_then(|(hdr, content)| self.advance(offset, &hdr).map(|()| (hdr, content)));
+        let (hdr, content) = match record {
+            Err(e) => {
+                self.current_pos = self.file_length;
+                return Some(Err(e));
+            }
+            Ok(record) => record,
+        };
+        Some(Ok(ValidRecord {
+            record_number: hdr.record_number,
+            offset,
+            content,
+        }))
+    }
+
+    /// Take the error of positioning the source at the first record and end the iteration
+    fn take_seek_error(&mut self) -> Option<Error> {
+        let e = self.seek_error.take()?;
+        self.current_pos = self.file_length;
+        Some(e)
+    }
+
+    /// Advance the position to the end of the record starting at `offset`
+    fn advance(&mut self, offset: u64, hdr: &RecordHeader) -> Result<(), Error> {
+        self.current_pos = hdr.record_end(offset, self.file_length)?;
+        Ok(())
+    }
+
+    /// Process a record returned by `next_valid_record`
+    fn process_valid_record(&mut self, record: ValidRecord) -> Result<(i32, u64), Error> {
+        decode_record(self.processor, record, self.m_nodata)
+    }
+}
+
+/// Decode the content of `record` and return its record number and file offset
+fn decode_record<P: GeomProcessor>(
+    processor: &mut P,
+    record: ValidRecord,
+    m_nodata: NodataPolicy,
+) -> Result<(i32, u64), Error> {
+    let ValidRecord {
+        record_number,
+        offset,
+        content,
+    } = record;
+    read_shape_rec(processor, &mut content.as_slice(), content.len(), m_nodata).map_err(|e| {
+        Error::ShapeDecode {
+            record_number,
+            offset,
+            source: Box::new(e),
+        }
+    })?;
+    Ok((record_number, offset))
+}
+
+impl<'a, P: GeomProcessor, T: Read + Seek + 'a> Iterator for ShapeIterator<'a, P, T> {
     type Item = Result<(), Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current_pos >= self.file_length {
-            None
-        } else {
-            let hdr = match read_shape(self.processor, &mut self.source) {
-                Err(e) => return Some(Err(e)),
-                Ok(hdr_and_shape) => hdr_and_shape,
-            };
-            self.current_pos += RecordHeader::SIZE;
-            self.current_pos += hdr.record_size as usize * 2;
-            Some(Ok(()))
+        let result = self.next_record().map(|result| result.map(|_| ()));
+        match &result {
+            Some(Ok(())) => self.report_progress(false),
+            Some(Err(_)) => {}
+            None => self.report_progress(true),
         }
+        result
     }
 }
 
@@ -39,56 +304,246 @@ impl<'a, P: FeatureProcessor, T: Read + Seek + 'a> FusedIterator for ShapeIterat
 pub struct ShapeRecordIterator<'a, P: FeatureProcessor, T: Read + Seek> {
     shape_iter: ShapeIterator<'a, P, T>,
     dbf_reader: dbase::Reader<T>,
+    crs: Option<String>,
+    /// Selected fields (all fields if None)
+    fields: Option<Vec<String>>,
     featno: u64,
+    /// `dataset_begin` was emitted
+    started: bool,
+    /// `dataset_end` was emitted
+    finished: bool,
 }
 
 pub struct ShapeRecord {
     pub record: dbase::Record,
+    /// Record number from the .shp record header (starting at 1)
+    pub record_number: i32,
+    /// Byte offset of the record header in the .shp file
+    pub file_offset: u64,
 }
 
 impl<'a, P: FeatureProcessor, T: Read + Seek + 'a> Iterator for ShapeRecordIterator<'a, P, T> {
     type Item = Result<ShapeRecord, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.featno == 0 {
+        if self.finished {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            if let Some(crs) = &self.crs {
+                self.shape_iter.processor.crs(crs).ok();
+            }
             self.shape_iter.processor.dataset_begin(None).ok();
         }
-        let record = match self.dbf_reader.iter_records().next() {
+        match process_next_feature(
+            &mut self.shape_iter,
+            &mut self.dbf_reader,
+            self.fields.as_deref(),
+            self.featno,
+        ) {
             None => {
+                self.finished = true;
                 self.shape_iter.processor.dataset_end().ok();
-                return None;
+                self.shape_iter.report_progress(true);
+                None
             }
-            Some(Err(e)) => return Some(Err(Error::DbaseError(e))),
-            Some(Ok(rcd)) => rcd,
-        };
-        let shprec = ShapeRecord { record };
-
-        {
-            self.shape_iter.processor.feature_begin(self.featno).ok();
-            self.shape_iter.processor.properties_begin().ok();
-            if let Err(e) = shprec.process_properties(self.shape_iter.processor) {
-                return Some(Err(Error::GeozeroError(e)));
+            Some(result) => {
+                if result.is_ok() {
+                    self.featno += 1;
+                    self.shape_iter.report_progress(false);
+                }
+                Some(result)
             }
-            self.shape_iter.processor.properties_end().ok();
-
-            self.shape_iter.processor.geometry_begin().ok();
         }
+    }
+}
 
-        if let Err(e) = self.shape_iter.next()? {
+/// Read the next shape and dbf record and process them as feature
+///
+/// Returns `None` if all dbf records are processed.
+fn process_next_feature<'a, P: FeatureProcessor, T: Read + Seek + 'a, D: Read + Seek>(
+    shape_iter: &mut ShapeIterator<'a, P, T>,
+    dbf_reader: &mut dbase::Reader<D>,
+    fields: Option<&[String]>,
+    featno: u64,
+) -> Option<Result<ShapeRecord, Error>> {
+    let record = match dbf_reader.iter_records().next()? {
+        Err(e) => return Some(Err(Error::DbaseError(e))),
+        Ok(rcd) => rcd,
+    };
+    let record = match fields {
+        None => record,
+        Some(fields) => fields
+            .iter()
+            .filter_map(|name| Some((name.clone(), record.get(name)?.clone())))
+            .collect::<HashMap<_, _>>()
+            .into(),
+    };
+    // Invalid shapes are skipped before processing the feature in lenient mode
+    let valid_record = if shape_iter.lenient {
+        shape_iter.next_valid_record()?
+    } else {
+        shape_iter.next_raw_record()?
+    };
+    let valid_record = match valid_record {
+        Err(e) => return Some(Err(e)),
+        Ok(valid_record) => valid_record,
+    };
+    let record_number = valid_record.record_number;
+    let file_offset = valid_record.offset;
+    if let Err(e) = process_feature(
+        shape_iter.processor,
+        Some(&record),
+        valid_record,
+        shape_iter.m_nodata,
+        featno,
+    ) {
+        return Some(Err(e));
+    }
+    Some(Ok(ShapeRecord {
+        record,
+        record_number,
+        file_offset,
+    }))
+}
+
+/// Process a shape record and its dbf record as feature `featno`
+///
+/// Null shapes are passed as features without geometry.
+/// The dbf record is processed first, so shapes are not decoded if the processor
+/// skips the geometry.
+fn process_feature<P: FeatureProcessor>(
+    processor: &mut P,
+    record: Option<&dbase::Record>,
+    shape: ValidRecord,
+    m_nodata: NodataPolicy,
+    featno: u64,
+) -> Result<(), Error> {
+    processor.feature_begin(featno)?;
+    if let Some(record) = record {
+        processor.properties_begin()?;
+        process_record_properties(record, processor)?;
+        processor.properties_end()?;
+    }
+    let skip_geometry = processor.skip_geometry();
+    let has_geometry = !shape.content.starts_with(&[0; 4]) && !skip_geometry;
+    if has_geometry {
+        processor.geometry_begin()?;
+    }
+    if !skip_geometry {
+        decode_record(processor, shape, m_nodata)?;
+    }
+    if has_geometry {
+        processor.geometry_end()?;
+    }
+    processor.feature_end(featno)?;
+    Ok(())
+}
+
+impl<'a, P: FeatureProcessor, T: Read + Seek + 'a> ShapeRecordIterator<'a, P, T> {
+    /// Call `callback` with the number of processed features after every `interval` features
+    /// and at the end of the iteration
+    ///
+    /// The total number of features is passed, if the .shx index was added.
+    pub fn with_progress(
+        mut self,
+        interval: u64,
+        callback: impl FnMut(u64, Option<u64>) + 'a,
+    ) -> Self {
+        self.shape_iter = self.shape_iter.with_progress(interval, callback);
+        self
+    }
+
+    /// Byte offset of the next record in the .shp file
+    ///
+    /// The position can be saved to continue the iteration later with
+    /// [Reader::iter_features_from()](struct.Reader.html#method.iter_features_from).
+    pub fn position(&self) -> u64 {
+        self.shape_iter.position()
+    }
+}
+
+impl<'a, P: FeatureProcessor, T: Read + Seek + 'a> FusedIterator for ShapeRecordIterator<'a, P, T> {}
+
+/// Undecoded shape record
+///
+/// The content is the on-disk shapefile geometry encoding (starting with the shape type),
+/// not WKB. It can be decoded later with [`GeozeroGeometry::process_geom`].
+#[derive(Clone, Debug)]
+pub struct RawShape {
+    /// Record number from the .shp record header (starting at 1)
+    pub record_number: i32,
+    pub shape_type: ShapeType,
+    /// Record content in shapefile geometry encoding
+    pub content: Vec<u8>,
+}
+
+impl RawShape {
+    /// Raw shape of the record with header `hdr` at `offset`
+    fn from_record(offset: u64, hdr: &RecordHeader, content: Vec<u8>) -> Result<Self, Error> {
+        let shape_type = ShapeType::read_from(&mut content.as_slice())
+            .map_err(|e| hdr.decode_error(offset, e))?;
+        Ok(RawShape {
+            record_number: hdr.record_number,
+            shape_type,
+            content,
+        })
+    }
+}
+
+impl GeozeroGeometry for RawShape {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> geozero::error::Result<()> {
+        read_shape_rec(
+            processor,
+            &mut self.content.as_slice(),
+            self.content.len(),
+            NodataPolicy::default(),
+        )?;
+        Ok(())
+    }
+}
+
+/// Iterator over the undecoded shape records of a .shp file
+pub struct RawShapeIterator<T: Read> {
+    source: T,
+    current_pos: u64,
+    file_length: u64,
+    /// Error positioning the source at the first record, returned by the first `next`
+    seek_error: Option<Error>,
+}
+
+impl<T: Read> Iterator for RawShapeIterator<T> {
+    type Item = Result<RawShape, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(e) = self.seek_error.take() {
+            self.current_pos = self.file_length;
             return Some(Err(e));
         }
-
-        {
-            let processor = &mut self.shape_iter.processor;
-            processor.geometry_end().ok();
-            processor.feature_end(self.featno).ok();
+        if self.current_pos >= self.file_length {
+            return None;
         }
-        self.featno += 1;
-        Some(Ok(shprec))
+        let offset = self.current_pos;
+        let record = read_record(&mut self.source, offset).and_then(|(hdr, content)| {
+            hdr.record_end(offset, self.file_length)
+                .map(|end| (hdr, content, end))
+        });
+        let (hdr, content) = match record {
+            Err(e) => {
+                self.current_pos = self.file_length;
+                return Some(Err(e));
+            }
+            Ok((hdr, content, end)) => {
+                self.current_pos = end;
+                (hdr, content)
+            }
+        };
+        Some(RawShape::from_record(offset, &hdr, content))
     }
 }
 
-impl<'a, P: FeatureProcessor, T: Read + Seek + 'a> FusedIterator for ShapeRecordIterator<'a, P, T> {}
+impl<T: Read> FusedIterator for RawShapeIterator<T> {}
 
 /// struct that reads the content of a shapefile
 pub struct Reader<T: Read + Seek> {
@@ -96,8 +551,19 @@ pub struct Reader<T: Read + Seek> {
     header: header::Header,
     shapes_index: Option<Vec<ShapeIndex>>,
     dbf_reader: Option<dbase::Reader<T>>,
+    dbf_encoding: DbaseEncoding,
+    crs: Option<String>,
+    m_nodata: NodataPolicy,
+    /// Paths of .shp and .dbf file, if created with `from_path`
+    shp_path: Option<PathBuf>,
+    dbf_path: Option<PathBuf>,
+    /// Buffer capacity for files opened from `shp_path` and `dbf_path`
+    buf_capacity: usize,
 }
 
+/// Buffer capacity of [from_path()](struct.Reader.html#method.from_path), same as `std::io::BufReader`
+pub const DEFAULT_BUF_CAPACITY: usize = 8 * 1024;
+
 impl<T: Read + Seek> Reader<T> {
     /// Creates a new Reader from a source that implements the `Read` trait
     ///
@@ -118,21 +584,100 @@ impl<T: Read + Seek> Reader<T> {
             header,
             shapes_index: None,
             dbf_reader: None,
+            dbf_encoding: DbaseEncoding::default(),
+            crs: None,
+            m_nodata: NodataPolicy::default(),
+            shp_path: None,
+            dbf_path: None,
+            buf_capacity: DEFAULT_BUF_CAPACITY,
         })
     }
 
+    /// Handling of measures with no-data values (below -10^38)
+    ///
+    /// By default, no-data measures are passed as `None` to the processor.
+    /// Use [`NodataPolicy::AsValue`] for output formats which can't represent missing measures.
+    pub fn with_m_nodata_policy(mut self, policy: NodataPolicy) -> Self {
+        self.m_nodata = policy;
+        self
+    }
+
     /// Returns a non-mutable reference to the header read
     pub fn header(&self) -> &header::Header {
         &self.header
     }
 
+    /// Returns the type of all shapes in the file
+    pub fn shape_type(&self) -> ShapeType {
+        self.header.shape_type
+    }
+
+    /// Returns the bounding box of all shapes as `[min_x, min_y, max_x, max_y]`
+    pub fn bbox(&self) -> [f64; 4] {
+        let bbox = &self.header.bbox;
+        [bbox.min.x, bbox.min.y, bbox.max.x, bbox.max.y]
+    }
+
+    /// Returns the range of `z` values as `[min, max]`, if the shape type has a `z` dimension
+    pub fn z_range(&self) -> Option<[f64; 2]> {
+        self.header
+            .shape_type
+            .has_z()
+            .then(|| self.header.bbox.z_range())
+    }
+
+    /// Returns the range of `m` values as `[min, max]`, if the shape type has measures
+    /// and the header range is not NO_DATA
+    pub fn m_range(&self) -> Option<[f64; 2]> {
+        let range = self.header.bbox.m_range();
+        if !self.header.shape_type.has_m() || range.iter().all(|m| is_no_data(*m)) {
+            return None;
+        }
+        Some(range)
+    }
+
+    /// Returns the number of shapes, if an index source was added
+    ///
+    /// The count is derived from the .shx index (one 8 byte entry per shape)
+    /// and does not require reading the shapes.
+    pub fn feature_count(&self) -> Option<usize> {
+        self.shapes_index.as_ref().map(Vec::len)
+    }
+
+    /// Returns the coordinate reference system read from the .prj file as WKT
+    pub fn crs(&self) -> Option<&str> {
+        self.crs.as_deref()
+    }
+
     /// Read and return _only_ the records contained in the *.dbf* file
     pub fn read_records(self) -> Result<Vec<dbase::Record>, Error> {
         let mut dbf_reader = self.dbf_reader.ok_or(Error::MissingDbf)?;
         dbf_reader.read().map_err(Error::DbaseError)
     }
-    ///Return the FieldInfo from the dbf file
-    ///Note that the deletion flag is not included in the results
+    /// Returns an iterator over the records of the *.dbf* file, read one at a time
+    ///
+    /// Unlike [read_records()](struct.Reader.html#method.read_records), the records are not
+    /// collected into memory, which makes this suitable for very large attribute tables.
+    /// Iteration continues after the last record read with this reader.
+    ///
+    /// # Errors
+    ///
+    /// The `Result` will be an error if the .dbf wasn't found
+    pub fn records_iter(
+        &mut self,
+    ) -> Result<impl Iterator<Item = Result<dbase::Record, Error>> + '_, Error> {
+        let dbf_reader = self.dbf_reader.as_mut().ok_or(Error::MissingDbf)?;
+        Ok(dbf_reader
+            .iter_records()
+            .map(|record| record.map_err(Error::DbaseError)))
+    }
+    /// Return the FieldInfo (name, type and length) of each column in the dbf file
+    ///
+    /// The field descriptors are parsed with the dbf header, so this can be called
+    /// before [iter_features()](struct.Reader.html#method.iter_features) without
+    /// advancing the record cursor.
+    ///
+    /// Note that the deletion flag is not included in the results
     pub fn dbf_fields(&self) -> Result<Vec<&FieldInfo>, Error> {
         let dbf_reader = self.dbf_reader.as_ref().ok_or(Error::MissingDbf)?;
         //Do not return FieldInfo { Name: DeletionFlag, Field Type: dbase::Character }
@@ -144,31 +689,328 @@ impl<T: Read + Seek> Reader<T> {
         Ok(fields)
     }
 
-    pub fn iter_geometries<P: FeatureProcessor>(self, processor: &mut P) -> ShapeIterator<P, T> {
+    /// Reads the `n`th shape of the .shp file and passes it to the `processor`
+    ///
+    /// The index file has to be added before (see [add_index_source()](struct.Reader.html#method.add_index_source)),
+    /// which is done automatically by [from_path()](struct.Reader.html#method.from_path) if the .shx exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::MissingIndexFile` if no index source was added and
+    /// `Error::IndexOutOfBounds` if `n` is not a valid shape index.
+    pub fn read_nth_shape<P: GeomProcessor>(
+        &mut self,
+        n: usize,
+        processor: &mut P,
+    ) -> Result<(), Error> {
+        let offset = self.shape_offset(n)?;
+        self.source.seek(SeekFrom::Start(offset))?;
+        read_shape(processor, &mut self.source, offset, self.m_nodata)?;
+        Ok(())
+    }
+
+    /// Reads the `n`th shape record of the .shp file without decoding it
+    ///
+    /// # Errors
+    ///
+    /// Like [read_nth_shape()](struct.Reader.html#method.read_nth_shape).
+    pub fn read_nth_raw_shape(&mut self, n: usize) -> Result<RawShape, Error> {
+        let offset = self.shape_offset(n)?;
+        self.source.seek(SeekFrom::Start(offset))?;
+        let (hdr, content) = read_record(&mut self.source, offset)?;
+        RawShape::from_record(offset, &hdr, content)
+    }
+
+    /// Byte offset of the `n`th shape record from the index
+    fn shape_offset(&self, n: usize) -> Result<u64, Error> {
+        let shapes_index = self.shapes_index.as_ref().ok_or(Error::MissingIndexFile)?;
+        let index = shapes_index.get(n).ok_or(Error::IndexOutOfBounds {
+            index: n,
+            len: shapes_index.len(),
+        })?;
+        Ok(index.byte_offset())
+    }
+
+    /// Positions the source at the first shape record
+    fn rewind(&mut self) -> Result<(), Error> {
+        self.source
+            .seek(SeekFrom::Start(header::HEADER_SIZE as u64))?;
+        Ok(())
+    }
+
+    /// Number of the shape records before the record starting at `offset`
+    ///
+    /// Returns `None` if `offset` is the end of the shape records. Without index,
+    /// the record headers are walked from the start of the file up to `offset`,
+    /// since the bytes at an arbitrary offset can't be told apart from a record header.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidResumePosition` if `offset` is not on a record boundary.
+    fn records_before(&mut self, offset: u64) -> Result<Option<usize>, Error> {
+        let file_length = self.records_end();
+        if offset == file_length {
+            return Ok(None);
+        }
+        if let Some(shapes_index) = &self.shapes_index {
+            return shapes_index
+                .iter()
+                .position(|index| index.byte_offset() == offset)
+                .map(Some)
+                .ok_or(Error::InvalidResumePosition(offset));
+        }
+        if offset < header::HEADER_SIZE as u64 || offset > file_length || offset % 2 == 1 {
+            return Err(Error::InvalidResumePosition(offset));
+        }
+        let mut pos = header::HEADER_SIZE as u64;
+        let mut count = 0;
+        while pos < offset {
+            self.source.seek(SeekFrom::Start(pos))?;
+            let hdr = RecordHeader::read_from(&mut self.source)?;
+            pos = hdr.record_end(pos, file_length)?;
+            count += 1;
+        }
+        if pos != offset {
+            return Err(Error::InvalidResumePosition(offset));
+        }
+        Ok(Some(count))
+    }
+
+    /// End of the shape records in bytes
+    ///
+    /// The index is preferred, since the file length in the header is limited
+    /// to 32 bits and not always maintained by writers of oversized files.
+    fn records_end(&self) -> u64 {
+        self.shapes_index
+            .as_ref()
+            .and_then(|index| index.iter().map(ShapeIndex::record_end).max())
+            .unwrap_or_else(|| self.header.file_length_bytes())
+    }
+
+    /// Returns an iterator over the shapes, passing them to the `processor`
+    ///
+    /// Iteration starts with the first shape, also after random access
+    /// with [read_nth_shape()](struct.Reader.html#method.read_nth_shape).
+    pub fn iter_geometries<P: FeatureProcessor>(
+        mut self,
+        processor: &mut P,
+    ) -> ShapeIterator<'_, P, T> {
+        let seek_error = self.rewind().err();
+        self.shape_iter_at(processor, header::HEADER_SIZE as u64, 0, seek_error)
+    }
+
+    /// Returns an iterator over the shapes, continuing at the byte `offset` of a record
+    ///
+    /// The offset is usually a position saved with [`ShapeIterator::position`]
+    /// during a previous iteration, which allows continuing an interrupted
+    /// iteration without reading the preceding shapes. Without .shx index, the
+    /// headers of the preceding records are read to validate the offset.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::InvalidResumePosition` if `offset` is neither the start of a
+    /// shape record nor the end of the records.
+    pub fn iter_geometries_from<P: FeatureProcessor>(
+        mut self,
+        processor: &mut P,
+        offset: u64,
+    ) -> Result<ShapeIterator<'_, P, T>, Error> {
+        let skipped = self.records_before(offset)?;
+        self.source.seek(SeekFrom::Start(offset))?;
+        Ok(self.shape_iter_at(processor, offset, skipped.unwrap_or(0), None))
+    }
+
+    /// Shape iterator starting at `offset`, after `skipped` records
+    fn shape_iter_at<P: FeatureProcessor>(
+        self,
+        processor: &mut P,
+        offset: u64,
+        skipped: usize,
+        seek_error: Option<Error>,
+    ) -> ShapeIterator<'_, P, T> {
+        let total = self
+            .feature_count()
+            .map(|count| count.saturating_sub(skipped) as u64);
         ShapeIterator {
             processor,
+            current_pos: offset,
+            file_length: self.records_end(),
+            source: self.source,
+            bbox: None,
+            lenient: false,
+            m_nodata: self.m_nodata,
+            total,
+            progress: None,
+            seek_error,
+        }
+    }
+
+    /// Returns an iterator over the shapes, borrowing the reader
+    ///
+    /// Unlike [iter_geometries()](struct.Reader.html#method.iter_geometries), the reader
+    /// can be used after the iteration, e.g. for random access with
+    /// [read_nth_shape()](struct.Reader.html#method.read_nth_shape) or another iteration.
+    pub fn iter_geometries_by_ref<'a, P: FeatureProcessor>(
+        &'a mut self,
+        processor: &'a mut P,
+    ) -> Result<ShapeIterator<'a, P, &'a mut T>, Error> {
+        self.rewind()?;
+        let total = self.feature_count().map(|count| count as u64);
+        let file_length = self.records_end();
+        Ok(ShapeIterator {
+            processor,
+            source: &mut self.source,
+            current_pos: header::HEADER_SIZE as u64,
+            file_length,
+            bbox: None,
+            lenient: false,
+            m_nodata: self.m_nodata,
+            total,
+            progress: None,
+            seek_error: None,
+        })
+    }
+
+    /// Returns an iterator over the undecoded shape records
+    ///
+    /// The geometries are not decoded, which allows storing them verbatim
+    /// or decoding them later, e.g. in parallel.
+    pub fn iter_raw_shapes(mut self) -> RawShapeIterator<T> {
+        let seek_error = self.rewind().err();
+        RawShapeIterator {
+            current_pos: header::HEADER_SIZE as u64,
+            file_length: self.records_end(),
             source: self.source,
-            current_pos: header::HEADER_SIZE as usize,
-            file_length: (self.header.file_length * 2) as usize,
+            seek_error,
         }
     }
 
+    /// Returns an iterator over the undecoded shape records, borrowing the reader
+    ///
+    /// See [iter_geometries_by_ref()](struct.Reader.html#method.iter_geometries_by_ref).
+    pub fn iter_raw_shapes_by_ref(&mut self) -> Result<RawShapeIterator<&mut T>, Error> {
+        self.rewind()?;
+        let file_length = self.records_end();
+        Ok(RawShapeIterator {
+            source: &mut self.source,
+            current_pos: header::HEADER_SIZE as u64,
+            file_length,
+            seek_error: None,
+        })
+    }
+
     /// Returns an iterator over the Shapes and their Records
     ///
     /// # Errors
     ///
     /// The `Result` will be an error if the .dbf wasn't found
     pub fn iter_features<P: FeatureProcessor>(
+        self,
+        processor: &mut P,
+    ) -> Result<ShapeRecordIterator<'_, P, T>, Error> {
+        self.iter_features_with_field_selection(processor, None)
+    }
+
+    /// Returns an iterator over the Shapes and their Records, skipping invalid shapes
+    ///
+    /// Records with a shape which can't be decoded are returned as
+    /// [`Error::ShapeDecode`] and iteration continues with the next record
+    /// (see [`ShapeIterator::with_lenient`]). Skipped records are not passed
+    /// to the processor and don't get a feature number.
+    ///
+    /// # Errors
+    ///
+    /// The `Result` will be an error if the .dbf wasn't found
+    pub fn iter_features_lenient<P: FeatureProcessor>(
+        self,
+        processor: &mut P,
+    ) -> Result<ShapeRecordIterator<'_, P, T>, Error> {
+        let mut iter = self.iter_features_with_field_selection(processor, None)?;
+        iter.shape_iter.lenient = true;
+        Ok(iter)
+    }
+
+    /// Returns an iterator over the Shapes and their Records with the given fields only
+    ///
+    /// # Errors
+    ///
+    /// The `Result` will be an error if the .dbf wasn't found or
+    /// if a field name is not contained in the .dbf file
+    pub fn iter_features_with_fields<'a, P: FeatureProcessor>(
+        self,
+        processor: &'a mut P,
+        fields: &[&str],
+    ) -> Result<ShapeRecordIterator<'a, P, T>, Error> {
+        let dbf_fields = self.dbf_fields()?;
+        if let Some(unknown) = fields
+            .iter()
+            .find(|name| !dbf_fields.iter().any(|f| f.name() == **name))
+        {
+            return Err(Error::UnknownField(unknown.to_string()));
+        }
+        let fields = fields.iter().map(|name| name.to_string()).collect();
+        self.iter_features_with_field_selection(processor, Some(fields))
+    }
+
+    /// Returns an iterator over the Shapes and their Records, continuing at the byte
+    /// `offset` of a shape record
+    ///
+    /// The offset is usually a position saved with [`ShapeRecordIterator::position`].
+    /// The .dbf records are read starting with the record matching the shape record
+    /// at `offset`. Feature numbers passed to the processor start with 0 again.
+    /// Without .shx index, the headers of the preceding shape records are read.
+    ///
+    /// # Errors
+    ///
+    /// The `Result` will be an error if the .dbf wasn't found.
+    /// Returns `Error::InvalidResumePosition` if `offset` is neither the start of a
+    /// shape record nor the end of the records.
+    pub fn iter_features_from<P: FeatureProcessor>(
+        mut self,
+        processor: &mut P,
+        offset: u64,
+    ) -> Result<ShapeRecordIterator<'_, P, T>, Error> {
+        if self.dbf_reader.is_none() {
+            return Err(Error::MissingDbf);
+        }
+        let skipped = self.records_before(offset)?;
+        if let (Some(dbf_reader), Some(skipped)) = (&mut self.dbf_reader, skipped) {
+            dbf_reader.seek(skipped)?;
+        }
+        self.source.seek(SeekFrom::Start(offset))?;
+        self.iter_features_at(processor, None, offset, skipped.unwrap_or(0), None)
+    }
+
+    fn iter_features_with_field_selection<P: FeatureProcessor>(
         mut self,
         processor: &mut P,
+        fields: Option<Vec<String>>,
     ) -> Result<ShapeRecordIterator<P, T>, Error> {
+        let seek_error = self.rewind().err();
+        self.iter_features_at(processor, fields, header::HEADER_SIZE as u64, 0, seek_error)
+    }
+
+    /// Feature iterator starting at `offset`, after `skipped` records
+    fn iter_features_at<P: FeatureProcessor>(
+        mut self,
+        processor: &mut P,
+        fields: Option<Vec<String>>,
+        offset: u64,
+        skipped: usize,
+        seek_error: Option<Error>,
+    ) -> Result<ShapeRecordIterator<'_, P, T>, Error> {
         let maybe_dbf_reader = self.dbf_reader.take();
         if let Some(dbf_reader) = maybe_dbf_reader {
-            let shape_iter = self.iter_geometries(processor);
+            let crs = self.crs.take();
+            let shape_iter = self.shape_iter_at(processor, offset, skipped, seek_error);
             Ok(ShapeRecordIterator {
                 shape_iter,
                 dbf_reader,
+                crs,
+                fields,
                 featno: 0,
+                started: false,
+                finished: false,
             })
         } else {
             Err(Error::MissingDbf)
@@ -186,8 +1028,27 @@ impl<T: Read + Seek> Reader<T> {
 
     /// Adds the `source` as the source where the dbf record will be read from
     pub fn add_dbf_source(&mut self, source: T) -> Result<(), Error> {
-        let dbf_reader = dbase::Reader::new(source)?;
+        self.add_dbf_source_with_encoding(source, DbaseEncoding::default())
+    }
+
+    /// Adds the `source` as the source where the dbf record will be read from,
+    /// decoding character values with the given `encoding`
+    pub fn add_dbf_source_with_encoding(
+        &mut self,
+        source: T,
+        encoding: DbaseEncoding,
+    ) -> Result<(), Error> {
+        let dbf_reader = encoding.dbf_reader(source)?;
         self.dbf_reader = Some(dbf_reader);
+        self.dbf_encoding = encoding;
+        Ok(())
+    }
+
+    /// Reads the projection file (WKT) from the source
+    pub fn add_prj_source(&mut self, mut source: T) -> Result<(), Error> {
+        let mut wkt = String::new();
+        source.read_to_string(&mut wkt)?;
+        self.crs = Some(wkt.trim().to_string());
         Ok(())
     }
 }
@@ -195,16 +1056,53 @@ impl<T: Read + Seek> Reader<T> {
 impl Reader<BufReader<File>> {
     /// Creates a reader from a path to a file
     ///
-    /// Will attempt to read both the .shx and .dbf associated with the file,
+    /// Will attempt to read the .shx, .dbf and .prj associated with the file,
     /// if they do not exists the function will not fail, and you will get an error later
     /// if you try to use a function that requires the file to be present.
+    ///
+    /// The encoding of the .dbf file is detected from the code page declared in its header.
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_path_with_encoding(path, DbaseEncoding::default())
+    }
+
+    /// Creates a reader from a path to a file
+    ///
+    /// Like [from_path()](struct.Reader.html#method.from_path), but using `default_encoding`
+    /// for the .dbf file, if no known code page is declared in its header.
+    pub fn from_path_with_encoding<P: AsRef<Path>>(
+        path: P,
+        default_encoding: DbaseEncoding,
+    ) -> Result<Self, Error> {
+        Self::open(path, default_encoding, DEFAULT_BUF_CAPACITY)
+    }
+
+    /// Creates a reader from a path to a file, reading with buffers of `capacity` bytes
+    ///
+    /// Like [from_path()](struct.Reader.html#method.from_path), but with a buffer capacity
+    /// other than [`DEFAULT_BUF_CAPACITY`] for the .shp and .dbf file. Larger buffers reduce
+    /// the number of read calls of sequential scans, which matters for files on spinning disks
+    /// or network filesystems. A capacity of 256 KiB to 1 MiB is a good start for these.
+    pub fn from_path_with_capacity<P: AsRef<Path>>(
+        path: P,
+        capacity: usize,
+    ) -> Result<Self, Error> {
+        Self::open(path, DbaseEncoding::default(), capacity)
+    }
+
+    fn open<P: AsRef<Path>>(
+        path: P,
+        default_encoding: DbaseEncoding,
+        capacity: usize,
+    ) -> Result<Self, Error> {
         let shape_path = path.as_ref().to_path_buf();
         let shx_path = shape_path.with_extension("shx");
         let dbf_path = shape_path.with_extension("dbf");
+        let prj_path = shape_path.with_extension("prj");
 
-        let source = BufReader::new(File::open(shape_path)?);
+        let source = BufReader::with_capacity(capacity, File::open(&shape_path)?);
         let mut reader = Self::new(source)?;
+        reader.shp_path = Some(shape_path);
+        reader.buf_capacity = capacity;
 
         if shx_path.exists() {
             let index_source = BufReader::new(File::open(shx_path)?);
@@ -212,20 +1110,275 @@ impl Reader<BufReader<File>> {
         }
 
         if dbf_path.exists() {
-            let dbf_source = BufReader::new(File::open(dbf_path)?);
-            reader.add_dbf_source(dbf_source)?;
+            let mut dbf_source = BufReader::with_capacity(capacity, File::open(&dbf_path)?);
+            let mut dbf_header = [0; CODE_PAGE_MARK_OFFSET + 1];
+            dbf_source.read_exact(&mut dbf_header)?;
+            dbf_source.seek(SeekFrom::Start(0))?;
+            let encoding = DbaseEncoding::from_code_page_mark(dbf_header[CODE_PAGE_MARK_OFFSET])
+                .unwrap_or(default_encoding);
+            reader.add_dbf_source_with_encoding(dbf_source, encoding)?;
+            reader.dbf_path = Some(dbf_path);
+        }
+
+        if prj_path.exists() {
+            let prj_source = BufReader::new(File::open(prj_path)?);
+            reader.add_prj_source(prj_source)?;
         }
         Ok(reader)
     }
 }
 
-// Does not work, because iter_features requires P instead of &mut P
-// impl<T: Read> GeozeroDatasource for Reader<T> {
-//     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> geozero::error::Result<()> {
-//         self.iter_features(*processor).unwrap().all();
-//         Ok(())
-//     }
-// }
+impl Reader<Cursor<Vec<u8>>> {
+    /// Creates a reader from the in-memory content of the .shp file and its optional
+    /// .shx, .dbf and .prj files
+    ///
+    /// The encoding of the .dbf file is detected from the code page declared in its header.
+    pub fn from_parts(
+        shp: Vec<u8>,
+        shx: Option<Vec<u8>>,
+        dbf: Option<Vec<u8>>,
+        prj: Option<String>,
+    ) -> Result<Self, Error> {
+        let mut reader = Self::new(Cursor::new(shp))?;
+        if let Some(shx) = shx {
+            reader.add_index_source(Cursor::new(shx))?;
+        }
+        if let Some(dbf) = dbf {
+            let encoding = dbf
+                .get(CODE_PAGE_MARK_OFFSET)
+                .and_then(|mark| DbaseEncoding::from_code_page_mark(*mark))
+                .unwrap_or_default();
+            reader.add_dbf_source_with_encoding(Cursor::new(dbf), encoding)?;
+        }
+        if let Some(prj) = prj {
+            reader.crs = Some(prj.trim().to_string());
+        }
+        Ok(reader)
+    }
+}
+
+#[cfg(feature = "zip")]
+impl Reader<Cursor<Vec<u8>>> {
+    /// Creates a reader from a ZIP archive containing a single shapefile
+    ///
+    /// The .shp file and its .shx, .dbf and .prj files are located by extension,
+    /// also within subdirectories, and read into memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [Error::AmbiguousShapefile] if the archive contains more than one .shp file.
+    /// Use [from_zip_with_name()](struct.Reader.html#method.from_zip_with_name) in this case.
+    pub fn from_zip<R: Read + Seek>(archive: R) -> Result<Self, Error> {
+        Self::read_zip(archive, None)
+    }
+
+    /// Creates a reader for the shapefile `name` from a ZIP archive
+    ///
+    /// `name` is the file name without extension (e.g. `roads`), optionally with
+    /// its directory within the archive (e.g. `data/roads`).
+    pub fn from_zip_with_name<R: Read + Seek>(archive: R, name: &str) -> Result<Self, Error> {
+        Self::read_zip(archive, Some(name))
+    }
+
+    fn read_zip<R: Read + Seek>(archive: R, name: Option<&str>) -> Result<Self, Error> {
+        let mut archive = zip::ZipArchive::new(archive)?;
+        // Archive paths of the shapefiles without extension
+        let mut candidates: Vec<String> = archive
+            .file_names()
+            .filter(|path| !path.starts_with("__MACOSX/"))
+            .filter_map(|path| split_extension(path))
+            .filter(|(_, ext)| ext.eq_ignore_ascii_case("shp"))
+            .map(|(base, _)| base.to_string())
+            .filter(|base| match name {
+                None => true,
+                Some(name) => base == name || base.rsplit('/').next() == Some(name),
+            })
+            .collect();
+        candidates.sort();
+        let base = match candidates.len() {
+            0 => return Err(Error::MissingShapefile),
+            1 => candidates.remove(0),
+            _ => return Err(Error::AmbiguousShapefile(candidates)),
+        };
+
+        let mut read_part = |ext: &str| -> Result<Option<Vec<u8>>, Error> {
+            let path = archive
+                .file_names()
+                .find(|path| {
+                    split_extension(path).is_some_and(|(path_base, path_ext)| {
+                        path_base == base && path_ext.eq_ignore_ascii_case(ext)
+                    })
+                })
+                .map(str::to_string);
+            let Some(path) = path else {
+                return Ok(None);
+            };
+            let mut file = archive.by_name(&path)?;
+            let mut content = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut content)?;
+            Ok(Some(content))
+        };
+        let shp = read_part("shp")?.ok_or(Error::MissingShapefile)?;
+        let shx = read_part("shx")?;
+        let dbf = read_part("dbf")?;
+        let prj = read_part("prj")?.map(|prj| String::from_utf8_lossy(&prj).into_owned());
+        Self::from_parts(shp, shx, dbf, prj)
+    }
+}
+
+/// Split an archive path into base path and extension
+#[cfg(feature = "zip")]
+fn split_extension(path: &str) -> Option<(&str, &str)> {
+    let (base, ext) = path.rsplit_once('.')?;
+    (!ext.contains('/')).then_some((base, ext))
+}
+
+#[cfg(feature = "rayon")]
+impl Reader<BufReader<File>> {
+    /// Processes all features in parallel
+    ///
+    /// The records are split into one chunk per thread using the offsets of the index file.
+    /// Each chunk is processed by a clone of `processor` with its own readers of the
+    /// .shp and .dbf file. The processors are returned in record order, i.e. the first
+    /// processor has processed the first chunk of features.
+    ///
+    /// Feature numbers are the same as in sequential processing.
+    /// `dataset_begin` and `dataset_end` are not emitted.
+    ///
+    /// # Errors
+    ///
+    /// Requires a reader created with [from_path()](struct.Reader.html#method.from_path)
+    /// and an index file.
+    ///
+    /// Returns [Error::MissingDbfRecord] if the .dbf file has fewer records than the index.
+    pub fn par_process<P: FeatureProcessor + Clone + Send>(
+        self,
+        processor: P,
+    ) -> Result<Vec<P>, Error> {
+        use rayon::prelude::*;
+
+        let shp_path = self.shp_path.ok_or(Error::MissingPath)?;
+        let shapes_index = self.shapes_index.ok_or(Error::MissingIndexFile)?;
+        let dbf = self.dbf_path.map(|path| (path, self.dbf_encoding));
+        if shapes_index.is_empty() {
+            return Ok(vec![processor]);
+        }
+        let num_chunks = rayon::current_num_threads().clamp(1, shapes_index.len());
+        let chunk_size = shapes_index.len().div_ceil(num_chunks);
+        let processors = vec![processor; num_chunks];
+        shapes_index
+            .par_chunks(chunk_size)
+            .zip(processors)
+            .enumerate()
+            .map(|(chunk_no, (chunk, processor))| {
+                process_chunk(
+                    processor,
+                    &shp_path,
+                    dbf.as_ref(),
+                    chunk_no * chunk_size,
+                    chunk,
+                    self.m_nodata,
+                    self.buf_capacity,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Process the features of `chunk`, starting with feature number `first_featno`
+#[cfg(feature = "rayon")]
+fn process_chunk<P: FeatureProcessor>(
+    mut processor: P,
+    shp_path: &Path,
+    dbf: Option<&(PathBuf, DbaseEncoding)>,
+    first_featno: usize,
+    chunk: &[ShapeIndex],
+    m_nodata: NodataPolicy,
+    buf_capacity: usize,
+) -> Result<P, Error> {
+    let Some(first_index) = chunk.first() else {
+        return Ok(processor);
+    };
+    let mut source = BufReader::with_capacity(buf_capacity, File::open(shp_path)?);
+    // Records are stored contiguously
+    source.seek(SeekFrom::Start(first_index.byte_offset()))?;
+    let mut dbf_reader = match dbf {
+        Some((path, encoding)) => {
+            let mut dbf_reader =
+                encoding.dbf_reader(BufReader::with_capacity(buf_capacity, File::open(path)?))?;
+            dbf_reader.seek(first_featno)?;
+            Some(dbf_reader)
+        }
+        None => None,
+    };
+
+    for (idx, index) in chunk.iter().enumerate() {
+        let featno = (first_featno + idx) as u64;
+        let record = match &mut dbf_reader {
+            Some(dbf_reader) => match dbf_reader.iter_records().next() {
+                None => return Err(Error::MissingDbfRecord(featno)),
+                Some(record) => Some(record?),
+            },
+            None => None,
+        };
+        let offset = index.byte_offset();
+        let (hdr, content) = read_record(&mut source, offset)?;
+        let shape = ValidRecord {
+            record_number: hdr.record_number,
+            offset,
+            content,
+        };
+        process_feature(&mut processor, record.as_ref(), shape, m_nodata, featno)?;
+    }
+    Ok(processor)
+}
+
+impl<T: Read + Seek> GeozeroDatasource for Reader<T> {
+    /// Process all features of the shapefile
+    ///
+    /// Features have no properties, if no .dbf source was added.
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> geozero::error::Result<()> {
+        self.rewind()?;
+        let file_length = self.records_end();
+        let mut shape_iter = ShapeIterator {
+            processor,
+            source: &mut self.source,
+            current_pos: header::HEADER_SIZE as u64,
+            file_length,
+            bbox: None,
+            lenient: false,
+            m_nodata: self.m_nodata,
+            total: None,
+            progress: None,
+            seek_error: None,
+        };
+        if let Some(crs) = &self.crs {
+            shape_iter.processor.crs(crs)?;
+        }
+        shape_iter.processor.dataset_begin(None)?;
+        let mut featno = 0;
+        if let Some(dbf_reader) = &mut self.dbf_reader {
+            dbf_reader.seek(0).map_err(Error::from)?;
+            while let Some(result) = process_next_feature(&mut shape_iter, dbf_reader, None, featno)
+            {
+                result?;
+                featno += 1;
+            }
+        } else {
+            while shape_iter.current_pos < shape_iter.file_length {
+                shape_iter.processor.feature_begin(featno)?;
+                shape_iter.processor.geometry_begin()?;
+                if let Some(result) = shape_iter.next() {
+                    result?;
+                }
+                shape_iter.processor.geometry_end()?;
+                shape_iter.processor.feature_end(featno)?;
+                featno += 1;
+            }
+        }
+        shape_iter.processor.dataset_end()
+    }
+}
 
 // Does not work, because &mut self is required
 // impl<P: GeomProcessor, T: Read> GeozeroGeometry for ShapeIterator<P, T> {
@@ -1,19 +1,46 @@
 use crate::{Error, ShapeType};
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
-use geozero::GeomProcessor;
-use std::io::Read;
+use geozero::{CoordDimensions, GeomProcessor};
+use std::io::{Read, Seek, SeekFrom};
 use std::mem::size_of;
 
-/// Value inferior to this are considered as NO_DATA
+/// Value used for measures without data
 pub const NO_DATA: f64 = -10e38;
 
+/// Measures inferior to this value are considered as NO_DATA
+const NO_DATA_LIMIT: f64 = -1e38;
+
+/// Size of the shape type and bbox at the start of a shape record
+const BBOX_PREFIX_SIZE: usize = size_of::<i32>() + 4 * size_of::<f64>();
+
 pub(crate) fn is_no_data(val: f64) -> bool {
-    val <= NO_DATA
+    val < NO_DATA_LIMIT
+}
+
+/// Handling of measures with no-data values (below -10^38) when reading shapes
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NodataPolicy {
+    /// Pass no-data measures as `None` (default)
+    #[default]
+    AsNone,
+    /// Substitute no-data measures with the given value, e.g. `0.0` or `f64::NAN`
+    AsValue(f64),
+}
+
+impl NodataPolicy {
+    pub(crate) fn apply(self, m: f64) -> Option<f64> {
+        if !is_no_data(m) {
+            return Some(m);
+        }
+        match self {
+            NodataPolicy::AsNone => None,
+            NodataPolicy::AsValue(value) => Some(value),
+        }
+    }
 }
 
 /// Header of a shape record, present before any shape record
 pub(crate) struct RecordHeader {
-    #[allow(dead_code)]
     pub record_number: i32,
     pub record_size: i32,
 }
@@ -29,49 +56,179 @@ impl RecordHeader {
             record_size,
         })
     }
+
+    /// Size of the record content in bytes
+    pub(crate) fn content_len(&self) -> Result<usize, Error> {
+        if self.record_size < 0 {
+            return Err(Error::InvalidShapeRecordSize);
+        }
+        Ok(self.record_size as usize * 2)
+    }
+
+    /// Offset of the end of the record starting at `offset`, checked against `file_length`
+    pub(crate) fn record_end(&self, offset: u64, file_length: u64) -> Result<u64, Error> {
+        let end = offset + Self::SIZE as u64 + self.content_len()? as u64;
+        if end > file_length {
+            return Err(Error::RecordOutOfBounds {
+                record_number: self.record_number,
+                offset,
+                file_length,
+            });
+        }
+        Ok(end)
+    }
+
+    /// Wrap an error raised while decoding the record content at `offset`
+    pub(crate) fn decode_error(&self, offset: u64, error: Error) -> Error {
+        Error::ShapeDecode {
+            record_number: self.record_number,
+            offset,
+            source: Box::new(error),
+        }
+    }
 }
 
-/// Read and process one shape record
+/// Read and process one shape record starting at file position `offset`
 pub(crate) fn read_shape<'a, P: GeomProcessor + 'a, T: Read>(
     processor: &'a mut P,
     mut source: &mut T,
+    offset: u64,
+    m_nodata: NodataPolicy,
 ) -> Result<RecordHeader, Error> {
     let hdr = RecordHeader::read_from(&mut source)?;
-    let record_size = hdr.record_size * 2;
-    read_shape_rec(processor, &mut source, record_size as usize)?;
+    let record_size = hdr.content_len()?;
+    read_shape_rec(processor, &mut source, record_size, m_nodata)
+        .map_err(|e| hdr.decode_error(offset, e))?;
     Ok(hdr)
 }
 
-fn read_shape_rec<P: GeomProcessor, T: Read>(
+/// Bounding box for filtering shape records
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BBoxFilter {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl BBoxFilter {
+    fn intersects(&self, bbox: &[f64; 4]) -> bool {
+        let [min_x, min_y, max_x, max_y] = *bbox;
+        min_x <= self.max_x && max_x >= self.min_x && min_y <= self.max_y && max_y >= self.min_y
+    }
+
+    /// Check whether the record `content` intersects the bbox
+    pub(crate) fn intersects_record(&self, mut content: &[u8]) -> Result<bool, Error> {
+        let intersects = match read_record_bbox(&mut content)? {
+            Some(bbox) => self.intersects(&bbox),
+            None => false,
+        };
+        Ok(intersects)
+    }
+}
+
+/// Read the header and the content of one shape record starting at file position `offset`
+pub(crate) fn read_record<T: Read>(
+    mut source: &mut T,
+    offset: u64,
+) -> Result<(RecordHeader, Vec<u8>), Error> {
+    let hdr = RecordHeader::read_from(&mut source)?;
+    let mut content = vec![0; hdr.content_len()?];
+    source
+        .read_exact(&mut content)
+        .map_err(|e| hdr.decode_error(offset, e.into()))?;
+    Ok((hdr, content))
+}
+
+/// Read one shape record starting at file position `offset` and process it,
+/// if it intersects the `filter` bbox
+///
+/// Returns whether the shape was processed. Only the shape type and bbox are read
+/// from records not intersecting the bbox, the rest of the record is skipped.
+pub(crate) fn read_shape_filtered<'a, P: GeomProcessor + 'a, T: Read + Seek>(
+    processor: &'a mut P,
+    mut source: &mut T,
+    offset: u64,
+    filter: &BBoxFilter,
+    m_nodata: NodataPolicy,
+) -> Result<(RecordHeader, bool), Error> {
+    let hdr = RecordHeader::read_from(&mut source)?;
+    let record_size = hdr.content_len()?;
+    let mut prefix = [0; BBOX_PREFIX_SIZE];
+    let prefix = &mut prefix[..record_size.min(BBOX_PREFIX_SIZE)];
+    source
+        .read_exact(prefix)
+        .map_err(|e| hdr.decode_error(offset, e.into()))?;
+    let intersects = filter
+        .intersects_record(prefix)
+        .map_err(|e| hdr.decode_error(offset, e))?;
+    let rest = (record_size - prefix.len()) as u64;
+    if intersects {
+        let mut record = (&prefix[..]).chain(source.by_ref().take(rest));
+        read_shape_rec(processor, &mut record, record_size, m_nodata)
+            .map_err(|e| hdr.decode_error(offset, e))?;
+    } else {
+        source.seek(SeekFrom::Current(rest as i64))?;
+    }
+    Ok((hdr, intersects))
+}
+
+/// Read the bbox at the start of a shape record
+///
+/// Points have no bbox, their coordinate is returned as bbox instead.
+fn read_record_bbox<T: Read>(mut source: &mut T) -> Result<Option<[f64; 4]>, Error> {
+    let shape_type = ShapeType::read_from(&mut source)?;
+    let bbox = match shape_type {
+        ShapeType::NullShape => None,
+        ShapeType::Point | ShapeType::PointM | ShapeType::PointZ => {
+            let x = source.read_f64::<LittleEndian>()?;
+            let y = source.read_f64::<LittleEndian>()?;
+            Some([x, y, x, y])
+        }
+        _ => {
+            let bbox = read_bbox(source, 2)?;
+            Some([bbox[0], bbox[1], bbox[2], bbox[3]])
+        }
+    };
+    Ok(bbox)
+}
+
+pub(crate) fn read_shape_rec<P: GeomProcessor, T: Read>(
     processor: &mut P,
     mut source: &mut T,
     record_size: usize,
+    m_nodata: NodataPolicy,
 ) -> Result<(), Error> {
     let shape_type = ShapeType::read_from(&mut source)?;
     let record_size = record_size - size_of::<i32>();
+    if shape_type != ShapeType::NullShape {
+        // Measures are optional in Z shapes and only announced for M shapes
+        processor.input_dimensions(CoordDimensions {
+            z: shape_type.has_z() || shape_type == ShapeType::Multipatch,
+            m: shape_type.has_m() && !shape_type.has_z(),
+            ..CoordDimensions::xy()
+        })?;
+    }
     match shape_type {
-        ShapeType::Point => read_point(processor, &mut source, record_size, shape_type)?,
-        ShapeType::PointM => read_point(processor, &mut source, record_size, shape_type)?,
-        ShapeType::PointZ => read_point(processor, &mut source, record_size, shape_type)?,
-        ShapeType::Multipoint => {
-            read_multipoint(processor, &mut source, record_size, ShapeType::Point)?
+        ShapeType::Point | ShapeType::PointM | ShapeType::PointZ => {
+            read_point(processor, &mut source, record_size, shape_type, m_nodata)?
+        }
+        ShapeType::Multipoint | ShapeType::MultipointM | ShapeType::MultipointZ => {
+            read_multipoint(processor, &mut source, record_size, shape_type, m_nodata)?
         }
-        ShapeType::MultipointM => {
-            read_multipoint(processor, &mut source, record_size, ShapeType::PointM)?
+        ShapeType::Polyline | ShapeType::PolylineM | ShapeType::PolylineZ => {
+            read_polyline(processor, &mut source, record_size, shape_type, m_nodata)?
         }
-        ShapeType::MultipointZ => {
-            read_multipoint(processor, &mut source, record_size, ShapeType::PointZ)?
+        ShapeType::Polygon | ShapeType::PolygonM | ShapeType::PolygonZ => {
+            read_polygon(processor, &mut source, record_size, shape_type, m_nodata)?
         }
-        ShapeType::Polyline => read_polyline(processor, &mut source, record_size, false)?,
-        ShapeType::PolylineM => read_polyline(processor, &mut source, record_size, false)?,
-        ShapeType::PolylineZ => read_polyline(processor, &mut source, record_size, true)?,
-        ShapeType::Polygon => read_polygon(processor, &mut source, record_size, false)?,
-        ShapeType::PolygonM => read_polygon(processor, &mut source, record_size, false)?,
-        ShapeType::PolygonZ => read_polygon(processor, &mut source, record_size, true)?,
         ShapeType::Multipatch => {
-            read_multipatch_shape_content(processor, &mut source, record_size)?
+            read_multipatch_shape_content(processor, &mut source, record_size, m_nodata)?
+        }
+        ShapeType::NullShape => {
+            // Skip any content following the shape type
+            std::io::copy(&mut source.take(record_size as u64), &mut std::io::sink())?;
         }
-        ShapeType::NullShape => {}
     };
     Ok(())
 }
@@ -81,10 +238,12 @@ fn read_point<P: GeomProcessor, T: Read>(
     source: &mut T,
     record_size: usize,
     point_type: ShapeType,
+    m_nodata: NodataPolicy,
 ) -> Result<(), Error> {
-    let has_z = point_type == ShapeType::PointZ;
+    let has_z = point_type.has_z();
+    // M is optional for PointZ
     let has_m = point_type == ShapeType::PointM
-        || point_type == ShapeType::PointZ && record_size == 4 * size_of::<f64>();
+        || point_type.has_m() && record_size == 4 * size_of::<f64>();
     let mut dims = 2;
     if has_z {
         dims += 1;
@@ -103,7 +262,7 @@ fn read_point<P: GeomProcessor, T: Read>(
         None
     };
     let m = if has_m {
-        Some(source.read_f64::<LittleEndian>()?)
+        m_nodata.apply(source.read_f64::<LittleEndian>()?)
     } else {
         None
     };
@@ -126,6 +285,7 @@ fn read_multipoint<P: GeomProcessor, T: Read>(
     source: &mut T,
     record_size: usize,
     point_type: ShapeType,
+    m_nodata: NodataPolicy,
 ) -> Result<(), Error> {
     let _bbox = read_bbox(source, 2)?;
     let num_points = source.read_i32::<LittleEndian>()? as usize;
@@ -133,11 +293,12 @@ fn read_multipoint<P: GeomProcessor, T: Read>(
     let mut size = 4 * size_of::<f64>() // BBOX
     + size_of::<i32>() // num points
     + size_of::<f64>() * 2 * num_points;
-    let has_z = point_type == ShapeType::PointZ;
+    let has_z = point_type.has_z();
     if has_z {
         size += multipart_dim_value_size(num_points);
     }
-    let has_m = record_size == size + multipart_dim_value_size(num_points);
+    let has_m =
+        point_type.has_m() && record_size == size + multipart_dim_value_size(num_points);
     if has_m {
         size += multipart_dim_value_size(num_points);
     }
@@ -153,7 +314,7 @@ fn read_multipoint<P: GeomProcessor, T: Read>(
         Vec::new()
     };
     let m_values = if has_m {
-        read_dim_values(source, num_points)?
+        read_m_values(source, num_points, m_nodata)?
     } else {
         Vec::new()
     };
@@ -170,7 +331,7 @@ fn read_multipoint<P: GeomProcessor, T: Read>(
             processor.xy(coord.x, coord.y, idx)?;
         } else {
             let z = if get_z { Some(z_values[idx]) } else { None };
-            let m = if get_m { Some(m_values[idx]) } else { None };
+            let m = if get_m { m_values[idx] } else { None };
             processor.coordinate(coord.x, coord.y, z, m, None, None, idx)?;
         }
     }
@@ -182,9 +343,10 @@ fn read_polyline<P: GeomProcessor, T: Read>(
     processor: &mut P,
     source: &mut T,
     record_size: usize,
-    has_z: bool,
+    shape_type: ShapeType,
+    m_nodata: NodataPolicy,
 ) -> Result<(), Error> {
-    let multipart = MultiPartShape::read(source, record_size, has_z)?;
+    let multipart = MultiPartShape::read(source, record_size, shape_type, m_nodata)?;
     multipart.process(processor, false)?;
     Ok(())
 }
@@ -193,31 +355,190 @@ fn read_polygon<P: GeomProcessor, T: Read>(
     processor: &mut P,
     source: &mut T,
     record_size: usize,
-    has_z: bool,
+    shape_type: ShapeType,
+    m_nodata: NodataPolicy,
 ) -> Result<(), Error> {
-    let multipart = MultiPartShape::read(source, record_size, has_z)?;
+    let multipart = MultiPartShape::read(source, record_size, shape_type, m_nodata)?;
     multipart.process(processor, true)?;
     Ok(())
 }
 
 fn read_multipatch_shape_content<P: GeomProcessor, T: Read>(
-    _processor: &mut P,
+    processor: &mut P,
     source: &mut T,
     record_size: usize,
+    m_nodata: NodataPolicy,
 ) -> Result<(), Error> {
-    // TODO
-    let mut buffer = vec![0; record_size];
-    source.read_exact(&mut buffer)?;
+    let multipatch = MultiPatchShape::read(source, record_size, m_nodata)?;
+    multipatch.process(processor)?;
     Ok(())
 }
 
+// --- multipatch reader ---
+
+/// Type of a MultiPatch part
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum PatchType {
+    TriangleStrip,
+    TriangleFan,
+    OuterRing,
+    InnerRing,
+    FirstRing,
+    Ring,
+}
+
+impl PatchType {
+    fn from(code: i32) -> Option<PatchType> {
+        match code {
+            0 => Some(PatchType::TriangleStrip),
+            1 => Some(PatchType::TriangleFan),
+            2 => Some(PatchType::OuterRing),
+            3 => Some(PatchType::InnerRing),
+            4 => Some(PatchType::FirstRing),
+            5 => Some(PatchType::Ring),
+            _ => None,
+        }
+    }
+}
+
+struct MultiPatchShape {
+    shape: MultiPartShape,
+    part_types: Vec<PatchType>,
+}
+
+impl MultiPatchShape {
+    fn read<R: Read>(
+        source: &mut R,
+        record_size: usize,
+        m_nodata: NodataPolicy,
+    ) -> Result<MultiPatchShape, Error> {
+        let _bbox = read_bbox(source, 2)?;
+        let num_parts = source.read_i32::<LittleEndian>()? as usize;
+        let num_points = source.read_i32::<LittleEndian>()? as usize;
+        let rec_size = multipart_record_size(num_points, num_parts)
+            + size_of::<i32>() * num_parts // part types
+            + multipart_dim_value_size(num_points); // Z values
+        let has_m = record_size == rec_size + multipart_dim_value_size(num_points);
+        if record_size != rec_size && !has_m {
+            return Err(Error::InvalidShapeRecordSize);
+        }
+
+        let parts_index = read_parts_index(source, num_parts, num_points)?;
+        let mut part_types = Vec::with_capacity(num_parts);
+        for _ in 0..num_parts {
+            let code = source.read_i32::<LittleEndian>()?;
+            part_types.push(PatchType::from(code).ok_or(Error::UnsupportedPatchType(code))?);
+        }
+
+        let mut shape = MultiPartShape {
+            parts_index,
+            coords: Vec::new(),
+            z_values: Vec::new(),
+            m_values: Vec::new(),
+        };
+        shape.coords = read_xy(source, num_points)?;
+        shape.z_values = read_dim_values(source, num_points)?;
+        if has_m {
+            shape.m_values = read_m_values(source, num_points, m_nodata)?;
+        }
+
+        Ok(MultiPatchShape { shape, part_types })
+    }
+
+    /// Polygons as list of rings with coordinate indices
+    fn polygons(&self) -> Vec<Vec<Vec<usize>>> {
+        let mut polys: Vec<Vec<Vec<usize>>> = Vec::new();
+        // Rings of type `Ring` following a `FirstRing` belong to the same polygon
+        let mut in_first_ring_poly = false;
+        for (part_type, start_end) in self
+            .part_types
+            .iter()
+            .zip(self.shape.parts_index.windows(2))
+        {
+            let (start, end) = (start_end[0], start_end[1]);
+            match part_type {
+                PatchType::TriangleStrip => {
+                    for idx in start..end.saturating_sub(2) {
+                        polys.push(vec![vec![idx, idx + 1, idx + 2, idx]]);
+                    }
+                    in_first_ring_poly = false;
+                }
+                PatchType::TriangleFan => {
+                    for idx in start + 1..end.saturating_sub(1) {
+                        polys.push(vec![vec![start, idx, idx + 1, start]]);
+                    }
+                    in_first_ring_poly = false;
+                }
+                PatchType::OuterRing | PatchType::FirstRing => {
+                    polys.push(vec![(start..end).collect()]);
+                    in_first_ring_poly = *part_type == PatchType::FirstRing;
+                }
+                PatchType::InnerRing => match polys.last_mut() {
+                    Some(poly) => poly.push((start..end).collect()),
+                    None => polys.push(vec![(start..end).collect()]),
+                },
+                PatchType::Ring => {
+                    if in_first_ring_poly {
+                        if let Some(poly) = polys.last_mut() {
+                            poly.push((start..end).collect());
+                        }
+                    } else {
+                        polys.push(vec![(start..end).collect()]);
+                    }
+                }
+            }
+        }
+        polys
+    }
+
+    /// Process patches as MultiPolygon, triangles are emitted as polygons
+    fn process<P: GeomProcessor>(&self, processor: &mut P) -> Result<(), Error> {
+        let tagged = false;
+        let multi_dim = processor.multi_dim();
+        let dimensions = processor.dimensions();
+        let get_m = dimensions.m && !self.shape.m_values.is_empty();
+
+        let polys = self.polygons();
+        processor.multipolygon_begin(polys.len(), 0)?;
+        for (poly_idx, rings) in polys.iter().enumerate() {
+            processor.polygon_begin(tagged, rings.len(), poly_idx)?;
+            for (ring_idx, ring) in rings.iter().enumerate() {
+                processor.linestring_begin(tagged, ring.len(), ring_idx)?;
+                for (coord_idx, ofs) in ring.iter().enumerate() {
+                    let coord = &self.shape.coords[*ofs];
+                    if !multi_dim {
+                        processor.xy(coord.x, coord.y, coord_idx)?;
+                    } else {
+                        let z = if dimensions.z {
+                            Some(self.shape.z_values[*ofs])
+                        } else {
+                            None
+                        };
+                        let m = if get_m {
+                            self.shape.m_values[*ofs]
+                        } else {
+                            None
+                        };
+                        processor.coordinate(coord.x, coord.y, z, m, None, None, coord_idx)?;
+                    }
+                }
+                processor.linestring_end(tagged, ring_idx)?;
+            }
+            processor.polygon_end(tagged, poly_idx)?;
+        }
+        processor.multipolygon_end(0)?;
+        Ok(())
+    }
+}
+
 // --- multipart line reader ---
 
 struct MultiPartShape {
     parts_index: Vec<usize>,
     coords: Vec<Coord>,
     z_values: Vec<f64>,
-    m_values: Vec<f64>,
+    /// Measures with no-data policy applied
+    m_values: Vec<Option<f64>>,
 }
 
 struct Coord {
@@ -229,25 +550,25 @@ impl MultiPartShape {
     fn read<R: Read>(
         source: &mut R,
         record_size: usize,
-        has_z: bool,
+        shape_type: ShapeType,
+        m_nodata: NodataPolicy,
     ) -> Result<MultiPartShape, Error> {
         let _bbox = read_bbox(source, 2)?;
         let num_parts = source.read_i32::<LittleEndian>()? as usize;
         let num_points = source.read_i32::<LittleEndian>()? as usize;
+        let has_z = shape_type.has_z();
         let mut rec_size = multipart_record_size(num_points, num_parts);
         if has_z {
             rec_size += multipart_dim_value_size(num_points);
         }
-        let has_m = record_size == rec_size + multipart_dim_value_size(num_points);
+        // M values are optional, even for M and Z types
+        let has_m = shape_type.has_m()
+            && record_size == rec_size + multipart_dim_value_size(num_points);
         if record_size != rec_size && !has_m {
             return Err(Error::InvalidShapeRecordSize);
         }
 
-        let mut parts_index = Vec::with_capacity(num_parts + 1);
-        for _ in 0..num_parts {
-            parts_index.push(source.read_i32::<LittleEndian>()? as usize);
-        }
-        parts_index.push(num_points); // add last index to simplify iteration
+        let parts_index = read_parts_index(source, num_parts, num_points)?;
 
         let mut multipart = MultiPartShape {
             parts_index,
@@ -260,7 +581,7 @@ impl MultiPartShape {
             multipart.z_values = read_dim_values(source, num_points)?;
         }
         if has_m {
-            multipart.m_values = read_dim_values(source, num_points)?;
+            multipart.m_values = read_m_values(source, num_points, m_nodata)?;
         }
 
         Ok(multipart)
@@ -270,18 +591,39 @@ impl MultiPartShape {
         self.parts_index.len() - 1
     }
 
-    fn detect_polys(&self) -> Vec<usize> {
-        let mut polys = Vec::with_capacity(self.parts_index.len());
-        for idx in 0..self.parts_index.len() - 1 {
-            let (start_index, end_index) = (self.parts_index[idx], self.parts_index[idx + 1]);
-            if idx == 0
-                || ring_type_from_points_ordering(&self.coords[start_index..end_index])
-                    == RingType::OuterRing
-            {
-                polys.push(idx);
+    /// Coordinates of the part with index `idx`
+    fn part(&self, idx: usize) -> &[Coord] {
+        &self.coords[self.parts_index[idx]..self.parts_index[idx + 1]]
+    }
+
+    /// Group rings into polygons, each with its outer ring first.
+    ///
+    /// Inner rings are assigned to the smallest outer ring containing them, independent of
+    /// the ring order. Inner rings outside of all outer rings are assigned to the preceding outer ring.
+    fn detect_polys(&self) -> Vec<Vec<usize>> {
+        let mut polys: Vec<Vec<usize>> = Vec::new();
+        let mut holes = Vec::new();
+        for idx in 0..self.num_parts() {
+            if idx == 0 || ring_type_from_points_ordering(self.part(idx)) == RingType::OuterRing {
+                polys.push(vec![idx]);
+            } else {
+                holes.push((idx, polys.len() - 1));
             }
         }
-        polys.push(self.parts_index.len() - 1);
+        for (idx, preceding) in holes {
+            let shell = self.part(idx).first().and_then(|first| {
+                polys
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, rings)| ring_contains(self.part(rings[0]), first))
+                    .min_by(|(_, a), (_, b)| {
+                        let area = |rings: &[usize]| ring_area(self.part(rings[0])).abs();
+                        area(a).total_cmp(&area(b))
+                    })
+                    .map(|(poly_idx, _)| poly_idx)
+            });
+            polys[shell.unwrap_or(preceding)].push(idx);
+        }
         polys
     }
 
@@ -292,28 +634,24 @@ impl MultiPartShape {
         let get_z = dimensions.z && !self.z_values.is_empty();
         let get_m = dimensions.m && !self.m_values.is_empty();
 
-        let geom_parts_indices = if as_poly {
+        let geom_parts = if as_poly {
             self.detect_polys()
         } else {
-            vec![0, self.parts_index.len() - 1]
+            vec![(0..self.num_parts()).collect()]
         };
 
         if as_poly {
-            processor.multipolygon_begin(geom_parts_indices.len() - 1, 0)?;
+            processor.multipolygon_begin(geom_parts.len(), 0)?;
         } else {
             processor.multilinestring_begin(self.num_parts(), 0)?;
         }
-        for (geom_idx, geom_start_end) in geom_parts_indices.windows(2).enumerate() {
-            let (geom_start, geom_end) = (geom_start_end[0], geom_start_end[1]);
-            let num_rings = geom_end - geom_start;
+        for (geom_idx, parts) in geom_parts.iter().enumerate() {
             if as_poly {
-                processor.polygon_begin(tagged, num_rings, geom_idx)?;
+                processor.polygon_begin(tagged, parts.len(), geom_idx)?;
             }
-            for (ring_idx, start_end) in self.parts_index[geom_start..=geom_end]
-                .windows(2)
-                .enumerate()
-            {
-                let (start_index, end_index) = (start_end[0], start_end[1]);
+            for (ring_idx, part) in parts.iter().enumerate() {
+                let (start_index, end_index) =
+                    (self.parts_index[*part], self.parts_index[part + 1]);
                 let num_points_in_part = end_index - start_index;
                 processor.linestring_begin(tagged, num_points_in_part, ring_idx)?;
                 for ofs in start_index..end_index {
@@ -327,11 +665,7 @@ impl MultiPartShape {
                         } else {
                             None
                         };
-                        let m = if get_m {
-                            Some(self.m_values[ofs])
-                        } else {
-                            None
-                        };
+                        let m = if get_m { self.m_values[ofs] } else { None };
                         processor.coordinate(coord.x, coord.y, z, m, None, None, coord_idx)?;
                     }
                 }
@@ -366,6 +700,33 @@ fn multipart_dim_value_size(num_points: usize) -> usize {
      + num_points * size_of::<f64>() // values
 }
 
+/// Read the start indices of the parts, followed by `num_points` to simplify iteration
+///
+/// The indices must start at 0 and be in ascending order, so that every point
+/// belongs to exactly one part.
+fn read_parts_index<R: Read>(
+    source: &mut R,
+    num_parts: usize,
+    num_points: usize,
+) -> Result<Vec<usize>, Error> {
+    let mut parts_index = Vec::with_capacity(num_parts + 1);
+    for part in 0..num_parts {
+        let start = source.read_i32::<LittleEndian>()?;
+        let prev = parts_index.last().copied().unwrap_or(0);
+        let valid = if part == 0 {
+            start == 0
+        } else {
+            start >= 0 && (prev..=num_points).contains(&(start as usize))
+        };
+        if !valid {
+            return Err(Error::InvalidPartsIndex(start));
+        }
+        parts_index.push(start as usize);
+    }
+    parts_index.push(num_points);
+    Ok(parts_index)
+}
+
 fn read_bbox<R: Read>(source: &mut R, dims: usize) -> Result<Vec<f64>, Error> {
     let mut bbox = Vec::with_capacity(2 * dims);
     for _ in 0..bbox.capacity() {
@@ -393,6 +754,15 @@ fn read_dim_values<R: Read>(source: &mut R, num_points: usize) -> Result<Vec<f64
     Ok(values)
 }
 
+fn read_m_values<R: Read>(
+    source: &mut R,
+    num_points: usize,
+    m_nodata: NodataPolicy,
+) -> Result<Vec<Option<f64>>, Error> {
+    let values = read_dim_values(source, num_points)?;
+    Ok(values.into_iter().map(|m| m_nodata.apply(m)).collect())
+}
+
 #[derive(Eq, PartialEq, Debug)]
 enum RingType {
     OuterRing,
@@ -416,15 +786,32 @@ enum RingType {
 ///
 /// https://stackoverflow.com/questions/1165647/how-to-determine-if-a-list-of-polygon-points-are-in-clockwise-order/1180256#1180256
 fn ring_type_from_points_ordering(points: &[Coord]) -> RingType {
-    let area = points
+    if ring_area(points) < 0.0 {
+        RingType::InnerRing
+    } else {
+        RingType::OuterRing
+    }
+}
+
+/// Signed ring area, positive for clockwise rings
+fn ring_area(points: &[Coord]) -> f64 {
+    points
         .windows(2)
         .map(|pts| (pts[1].x - pts[0].x) * (pts[1].y + pts[0].y))
         .sum::<f64>()
-        / 2.0f64;
+        / 2.0f64
+}
 
-    if area < 0.0 {
-        RingType::InnerRing
-    } else {
-        RingType::OuterRing
+/// Check if `point` is inside of the ring (ray casting)
+fn ring_contains(ring: &[Coord], point: &Coord) -> bool {
+    let mut inside = false;
+    for pts in ring.windows(2) {
+        let (a, b) = (&pts[0], &pts[1]);
+        if (a.y > point.y) != (b.y > point.y)
+            && point.x < (b.x - a.x) * (point.y - a.y) / (b.y - a.y) + a.x
+        {
+            inside = !inside;
+        }
     }
+    inside
 }
@@ -0,0 +1,690 @@
+use crate::header::{self, Header};
+use crate::point_z::BBoxZ;
+use crate::shp_reader::{is_no_data, RecordHeader, NO_DATA};
+use crate::{Error, ShapeType};
+use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
+use dbase::{FieldName, FieldValue, TableWriterBuilder};
+use geozero::error::{GeozeroError, Result};
+use geozero::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::mem::size_of;
+use std::path::Path;
+
+/// Shapefile writer
+///
+/// Geometries are written as records of the .shp file, the file header
+/// is written when finishing (see [finish()](struct.ShapeWriter.html#method.finish)).
+/// The shape type of the file is taken from the first geometry and the requested dimensions.
+///
+/// If a .dbf destination is added, feature properties are collected and written
+/// when finishing. The field types are derived from the property values.
+pub struct ShapeWriter<W: Write + Seek> {
+    dest: W,
+    shx_dest: Option<W>,
+    dbf_dest: Option<W>,
+    dims: CoordDimensions,
+    header: Header,
+    /// Extent of all written shapes
+    bbox: Option<BBoxZ>,
+    /// Number of records written
+    num_records: i32,
+    /// Current geometry
+    shape: ShapeBuffer,
+    /// Geometry of current feature written
+    shape_written: bool,
+    /// Properties of current feature
+    properties: HashMap<String, FieldValue>,
+    records: Vec<HashMap<String, FieldValue>>,
+    /// Field names in order of appearance
+    field_names: Vec<String>,
+}
+
+impl<W: Write + Seek> ShapeWriter<W> {
+    /// Creates a writer for 2D shapes
+    pub fn new(dest: W) -> Self {
+        Self::with_dims(dest, CoordDimensions::xy())
+    }
+
+    /// Creates a writer with Z and/or M dimensions
+    pub fn with_dims(dest: W, dims: CoordDimensions) -> Self {
+        ShapeWriter {
+            dest,
+            shx_dest: None,
+            dbf_dest: None,
+            dims,
+            header: Header::default(),
+            bbox: None,
+            num_records: 0,
+            shape: ShapeBuffer::default(),
+            shape_written: false,
+            properties: HashMap::new(),
+            records: Vec::new(),
+            field_names: Vec::new(),
+        }
+    }
+
+    /// Adds the `dest` as the destination where the index file will be written to
+    pub fn add_index_dest(&mut self, dest: W) {
+        self.shx_dest = Some(dest);
+    }
+
+    /// Adds the `dest` as the destination where the dbf records will be written to
+    pub fn add_dbf_dest(&mut self, dest: W) {
+        self.dbf_dest = Some(dest);
+    }
+
+    /// Returns the shape type of the written shapes
+    ///
+    /// `NullShape` until the first geometry has been written.
+    pub fn shape_type(&self) -> ShapeType {
+        self.header.shape_type
+    }
+
+    /// Writes the header of the .shp and .shx file and the .dbf file
+    ///
+    /// Called at the end of dataset processing.
+    pub fn finish(&mut self) -> std::result::Result<(), Error> {
+        if let Some(bbox) = self.bbox {
+            self.header.bbox = bbox;
+        }
+        // Unused dimensions have a range of 0
+        if !self.header.shape_type.has_z() {
+            self.header.bbox.min.z = 0.0;
+            self.header.bbox.max.z = 0.0;
+        }
+        if !self.header.shape_type.has_m() {
+            self.header.bbox.min.m = 0.0;
+            self.header.bbox.max.m = 0.0;
+        }
+        if self.num_records == 0 {
+            // Header was not written yet
+            self.header.write_to(&mut self.dest)?;
+        }
+        self.dest.seek(SeekFrom::Start(0))?;
+        self.header.write_to(&mut self.dest)?;
+        self.dest.seek(SeekFrom::End(0))?;
+        self.dest.flush()?;
+
+        if let Some(shx_dest) = &mut self.shx_dest {
+            let mut shx_header = self.header;
+            shx_header.file_length = (header::HEADER_SIZE
+                + self.num_records * INDEX_RECORD_SIZE as i32)
+                / 2;
+            shx_dest.seek(SeekFrom::Start(0))?;
+            shx_header.write_to(shx_dest)?;
+            shx_dest.seek(SeekFrom::End(0))?;
+            shx_dest.flush()?;
+        }
+
+        if let Some(dbf_dest) = self.dbf_dest.take() {
+            self.write_dbf(dbf_dest)?;
+        }
+        Ok(())
+    }
+
+    fn write_dbf(&mut self, mut dest: W) -> std::result::Result<(), Error> {
+        let mut builder = TableWriterBuilder::new();
+        let mut fields = Vec::with_capacity(self.field_names.len());
+        for name in &self.field_names {
+            let field_type = DbfFieldType::from_values(
+                self.records.iter().filter_map(|record| record.get(name)),
+            );
+            let field_name = dbf_field_name(name)?;
+            builder = match field_type {
+                DbfFieldType::Character(len) => builder.add_character_field(field_name, len),
+                DbfFieldType::Integer => builder.add_numeric_field(field_name, 18, 0),
+                DbfFieldType::Numeric => builder.add_numeric_field(field_name, 24, 15),
+                DbfFieldType::Logical => builder.add_logical_field(field_name),
+            };
+            fields.push((name, field_type));
+        }
+        let records = self
+            .records
+            .drain(..)
+            .map(|mut values| {
+                let record = fields
+                    .iter()
+                    .map(|(name, field_type)| {
+                        let value = field_type.convert(values.remove(*name));
+                        (dbf_field_name_str(name).to_string(), value)
+                    })
+                    .collect::<HashMap<_, _>>();
+                dbase::Record::from(record)
+            })
+            .collect::<Vec<_>>();
+        // The table writer is closed when dropped, before flushing the destination
+        builder.build_with_dest(&mut dest).write_records(&records)?;
+        dest.flush()?;
+        Ok(())
+    }
+
+    fn begin_shape(&mut self, base_type: ShapeType) -> Result<()> {
+        if self.shape.base_type.is_some() {
+            return Err(GeozeroError::Geometry(
+                "Nested geometries are not supported by shapefiles".to_string(),
+            ));
+        }
+        self.shape.base_type = Some(base_type);
+        Ok(())
+    }
+
+    fn end_shape(&mut self) -> Result<()> {
+        let shape = std::mem::take(&mut self.shape);
+        let result = self.write_shape(&shape);
+        // Reuse allocated buffers
+        self.shape = shape;
+        self.shape.clear();
+        result.map_err(|e| GeozeroError::Geometry(e.to_string()))?;
+        self.shape_written = true;
+        Ok(())
+    }
+
+    fn write_shape(&mut self, shape: &ShapeBuffer) -> std::result::Result<(), Error> {
+        let shape_type = shape.shape_type(self.dims);
+        if self.header.shape_type == ShapeType::NullShape {
+            self.header.shape_type = shape_type;
+        } else if self.header.shape_type != shape_type {
+            return Err(Error::MixedShapeTypes {
+                expected: self.header.shape_type,
+                found: shape_type,
+            });
+        }
+        let mut content = Vec::new();
+        shape.write_to(&mut content, shape_type)?;
+        self.update_bbox(shape);
+        self.write_record(&content)
+    }
+
+    fn write_null_shape(&mut self) -> std::result::Result<(), Error> {
+        let mut content = Vec::new();
+        content.write_i32::<LittleEndian>(ShapeType::NullShape as i32)?;
+        self.write_record(&content)
+    }
+
+    fn write_record(&mut self, content: &[u8]) -> std::result::Result<(), Error> {
+        if self.num_records == 0 {
+            // Placeholder header
+            self.header.write_to(&mut self.dest)?;
+            if let Some(shx_dest) = &mut self.shx_dest {
+                self.header.write_to(shx_dest)?;
+            }
+        }
+        self.num_records += 1;
+        let record_size = (content.len() / 2) as i32;
+        if let Some(shx_dest) = &mut self.shx_dest {
+            shx_dest.write_i32::<BigEndian>(self.header.file_length)?;
+            shx_dest.write_i32::<BigEndian>(record_size)?;
+        }
+        self.dest.write_i32::<BigEndian>(self.num_records)?;
+        self.dest.write_i32::<BigEndian>(record_size)?;
+        self.dest.write_all(content)?;
+        self.header.file_length += (RecordHeader::SIZE / 2) as i32 + record_size;
+        Ok(())
+    }
+
+    fn update_bbox(&mut self, shape: &ShapeBuffer) {
+        let Some(bbox) = shape.bbox() else {
+            return;
+        };
+        let Some(hdr_bbox) = &mut self.bbox else {
+            self.bbox = Some(bbox);
+            return;
+        };
+        hdr_bbox.min.x = hdr_bbox.min.x.min(bbox.min.x);
+        hdr_bbox.min.y = hdr_bbox.min.y.min(bbox.min.y);
+        hdr_bbox.min.z = hdr_bbox.min.z.min(bbox.min.z);
+        hdr_bbox.max.x = hdr_bbox.max.x.max(bbox.max.x);
+        hdr_bbox.max.y = hdr_bbox.max.y.max(bbox.max.y);
+        hdr_bbox.max.z = hdr_bbox.max.z.max(bbox.max.z);
+        if is_no_data(hdr_bbox.min.m) {
+            hdr_bbox.min.m = bbox.min.m;
+            hdr_bbox.max.m = bbox.max.m;
+        } else if !is_no_data(bbox.min.m) {
+            hdr_bbox.min.m = hdr_bbox.min.m.min(bbox.min.m);
+            hdr_bbox.max.m = hdr_bbox.max.m.max(bbox.max.m);
+        }
+    }
+}
+
+impl ShapeWriter<BufWriter<File>> {
+    /// Creates a writer for the .shp file at `path` and the associated .shx and .dbf files
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        dims: CoordDimensions,
+    ) -> std::result::Result<Self, Error> {
+        let shape_path = path.as_ref().to_path_buf();
+        let shx_path = shape_path.with_extension("shx");
+        let dbf_path = shape_path.with_extension("dbf");
+
+        let mut writer = Self::with_dims(BufWriter::new(File::create(shape_path)?), dims);
+        writer.add_index_dest(BufWriter::new(File::create(shx_path)?));
+        writer.add_dbf_dest(BufWriter::new(File::create(dbf_path)?));
+        Ok(writer)
+    }
+}
+
+const INDEX_RECORD_SIZE: usize = 2 * size_of::<i32>();
+
+/// Maximal length of a DBF field name
+const DBF_FIELD_NAME_LEN: usize = 10;
+
+/// Maximal length of a DBF character field
+const DBF_CHARACTER_LEN: usize = 254;
+
+fn dbf_field_name_str(name: &str) -> &str {
+    match name.char_indices().nth(DBF_FIELD_NAME_LEN) {
+        Some((pos, _)) => &name[..pos],
+        None => name,
+    }
+}
+
+fn dbf_field_name(name: &str) -> std::result::Result<FieldName, Error> {
+    FieldName::try_from(dbf_field_name_str(name))
+        .map_err(|_| Error::InvalidFieldName(name.to_string()))
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum DbfFieldType {
+    Character(u8),
+    Integer,
+    Numeric,
+    Logical,
+}
+
+impl DbfFieldType {
+    /// Field type able to store all values
+    fn from_values<'a>(values: impl Iterator<Item = &'a FieldValue>) -> Self {
+        let mut field_type = None;
+        let mut max_len = 1;
+        for value in values {
+            let value_type = match value {
+                FieldValue::Numeric(Some(v)) if v.fract() == 0.0 => DbfFieldType::Integer,
+                FieldValue::Numeric(_) => DbfFieldType::Numeric,
+                FieldValue::Logical(_) => DbfFieldType::Logical,
+                _ => DbfFieldType::Character(0),
+            };
+            let len = match value {
+                FieldValue::Character(Some(s)) => s.len(),
+                _ => value.to_string().len(),
+            };
+            max_len = max_len.max(len);
+            field_type = match (field_type, value_type) {
+                (None, t) => Some(t),
+                (Some(t1), t2) if t1 == t2 => Some(t1),
+                (Some(DbfFieldType::Integer), DbfFieldType::Numeric)
+                | (Some(DbfFieldType::Numeric), DbfFieldType::Integer) => {
+                    Some(DbfFieldType::Numeric)
+                }
+                _ => Some(DbfFieldType::Character(0)),
+            };
+        }
+        match field_type {
+            Some(DbfFieldType::Character(_)) | None => {
+                DbfFieldType::Character(max_len.min(DBF_CHARACTER_LEN) as u8)
+            }
+            Some(t) => t,
+        }
+    }
+
+    fn convert(&self, value: Option<FieldValue>) -> FieldValue {
+        match (self, value) {
+            (DbfFieldType::Character(_), Some(v @ FieldValue::Character(_))) => v,
+            (DbfFieldType::Character(_), Some(v)) => FieldValue::Character(Some(v.to_string())),
+            (DbfFieldType::Character(_), None) => FieldValue::Character(None),
+            (DbfFieldType::Integer | DbfFieldType::Numeric, Some(v @ FieldValue::Numeric(_))) => v,
+            (DbfFieldType::Integer | DbfFieldType::Numeric, _) => FieldValue::Numeric(None),
+            (DbfFieldType::Logical, Some(v @ FieldValue::Logical(_))) => v,
+            (DbfFieldType::Logical, _) => FieldValue::Logical(None),
+        }
+    }
+}
+
+// --- shape buffer ---
+
+/// Coordinates and parts of the shape in progress
+#[derive(Default)]
+struct ShapeBuffer {
+    /// Point, Multipoint, Polyline or Polygon
+    base_type: Option<ShapeType>,
+    /// Start index of parts
+    parts_index: Vec<usize>,
+    /// Part is the exterior ring of a polygon
+    exterior: Vec<bool>,
+    xy: Vec<(f64, f64)>,
+    z_values: Vec<f64>,
+    m_values: Vec<f64>,
+}
+
+impl ShapeBuffer {
+    fn clear(&mut self) {
+        self.base_type = None;
+        self.parts_index.clear();
+        self.exterior.clear();
+        self.xy.clear();
+        self.z_values.clear();
+        self.m_values.clear();
+    }
+
+    fn add_coord(&mut self, x: f64, y: f64, z: Option<f64>, m: Option<f64>) {
+        self.xy.push((x, y));
+        self.z_values.push(z.unwrap_or(0.0));
+        self.m_values.push(m.unwrap_or(NO_DATA));
+    }
+
+    fn add_part(&mut self, exterior: bool) {
+        self.parts_index.push(self.xy.len());
+        self.exterior.push(exterior);
+    }
+
+    fn shape_type(&self, dims: CoordDimensions) -> ShapeType {
+        match (self.base_type, dims.z, dims.m) {
+            (Some(ShapeType::Point), false, false) => ShapeType::Point,
+            (Some(ShapeType::Point), false, true) => ShapeType::PointM,
+            (Some(ShapeType::Point), true, _) => ShapeType::PointZ,
+            (Some(ShapeType::Multipoint), false, false) => ShapeType::Multipoint,
+            (Some(ShapeType::Multipoint), false, true) => ShapeType::MultipointM,
+            (Some(ShapeType::Multipoint), true, _) => ShapeType::MultipointZ,
+            (Some(ShapeType::Polyline), false, false) => ShapeType::Polyline,
+            (Some(ShapeType::Polyline), false, true) => ShapeType::PolylineM,
+            (Some(ShapeType::Polyline), true, _) => ShapeType::PolylineZ,
+            (Some(ShapeType::Polygon), false, false) => ShapeType::Polygon,
+            (Some(ShapeType::Polygon), false, true) => ShapeType::PolygonM,
+            (Some(ShapeType::Polygon), true, _) => ShapeType::PolygonZ,
+            _ => ShapeType::NullShape,
+        }
+    }
+
+    fn bbox(&self) -> Option<BBoxZ> {
+        let (x, y) = *self.xy.first()?;
+        let mut bbox = BBoxZ::default();
+        bbox.min.x = x;
+        bbox.min.y = y;
+        bbox.max.x = x;
+        bbox.max.y = y;
+        for (x, y) in &self.xy {
+            bbox.min.x = bbox.min.x.min(*x);
+            bbox.min.y = bbox.min.y.min(*y);
+            bbox.max.x = bbox.max.x.max(*x);
+            bbox.max.y = bbox.max.y.max(*y);
+        }
+        [bbox.min.z, bbox.max.z] = value_range(&self.z_values);
+        [bbox.min.m, bbox.max.m] = measure_range(&self.m_values);
+        Some(bbox)
+    }
+
+    /// Ensure clockwise exterior rings and counterclockwise interior rings
+    fn orient_rings(&mut self) {
+        let mut parts_index = self.parts_index.clone();
+        parts_index.push(self.xy.len());
+        for (part, start_end) in parts_index.windows(2).enumerate() {
+            let (start, end) = (start_end[0], start_end[1]);
+            let clockwise = ring_area(&self.xy[start..end]) >= 0.0;
+            if clockwise != self.exterior[part] {
+                self.xy[start..end].reverse();
+                self.z_values[start..end].reverse();
+                self.m_values[start..end].reverse();
+            }
+        }
+    }
+
+    fn write_to<T: Write>(&self, dest: &mut T, shape_type: ShapeType) -> std::io::Result<()> {
+        dest.write_i32::<LittleEndian>(shape_type as i32)?;
+        match self.base_type {
+            Some(ShapeType::Point) => {
+                let (x, y) = self.xy.first().copied().unwrap_or((f64::NAN, f64::NAN));
+                dest.write_f64::<LittleEndian>(x)?;
+                dest.write_f64::<LittleEndian>(y)?;
+                if shape_type.has_z() {
+                    dest.write_f64::<LittleEndian>(self.z_values.first().copied().unwrap_or(0.0))?;
+                }
+                if shape_type.has_m() {
+                    dest.write_f64::<LittleEndian>(
+                        self.m_values.first().copied().unwrap_or(NO_DATA),
+                    )?;
+                }
+                return Ok(());
+            }
+            Some(ShapeType::Multipoint) => {
+                self.write_bbox(dest)?;
+                dest.write_i32::<LittleEndian>(self.xy.len() as i32)?;
+            }
+            _ => {
+                self.write_bbox(dest)?;
+                dest.write_i32::<LittleEndian>(self.parts_index.len() as i32)?;
+                dest.write_i32::<LittleEndian>(self.xy.len() as i32)?;
+                for idx in &self.parts_index {
+                    dest.write_i32::<LittleEndian>(*idx as i32)?;
+                }
+            }
+        }
+        for (x, y) in &self.xy {
+            dest.write_f64::<LittleEndian>(*x)?;
+            dest.write_f64::<LittleEndian>(*y)?;
+        }
+        if shape_type.has_z() {
+            write_dim_values(dest, value_range(&self.z_values), &self.z_values)?;
+        }
+        if shape_type.has_m() {
+            write_dim_values(dest, measure_range(&self.m_values), &self.m_values)?;
+        }
+        Ok(())
+    }
+
+    fn write_bbox<T: Write>(&self, dest: &mut T) -> std::io::Result<()> {
+        let bbox = self.bbox().unwrap_or_default();
+        dest.write_f64::<LittleEndian>(bbox.min.x)?;
+        dest.write_f64::<LittleEndian>(bbox.min.y)?;
+        dest.write_f64::<LittleEndian>(bbox.max.x)?;
+        dest.write_f64::<LittleEndian>(bbox.max.y)?;
+        Ok(())
+    }
+}
+
+fn write_dim_values<T: Write>(dest: &mut T, range: [f64; 2], values: &[f64]) -> std::io::Result<()> {
+    dest.write_f64::<LittleEndian>(range[0])?;
+    dest.write_f64::<LittleEndian>(range[1])?;
+    for value in values {
+        dest.write_f64::<LittleEndian>(*value)?;
+    }
+    Ok(())
+}
+
+fn value_range(values: &[f64]) -> [f64; 2] {
+    values
+        .iter()
+        .fold(None, |range: Option<[f64; 2]>, v| match range {
+            None => Some([*v, *v]),
+            Some([min, max]) => Some([min.min(*v), max.max(*v)]),
+        })
+        .unwrap_or([0.0, 0.0])
+}
+
+/// Range of measures without NO_DATA values
+fn measure_range(values: &[f64]) -> [f64; 2] {
+    let values = values
+        .iter()
+        .copied()
+        .filter(|m| !is_no_data(*m))
+        .collect::<Vec<_>>();
+    if values.is_empty() {
+        [NO_DATA, NO_DATA]
+    } else {
+        value_range(&values)
+    }
+}
+
+/// Twice the signed area, positive for clockwise rings
+fn ring_area(points: &[(f64, f64)]) -> f64 {
+    points
+        .windows(2)
+        .map(|pts| (pts[1].0 - pts[0].0) * (pts[1].1 + pts[0].1))
+        .sum::<f64>()
+}
+
+fn unsupported(geometry_type: &str) -> Result<()> {
+    Err(GeozeroError::Geometry(format!(
+        "{geometry_type} is not supported by shapefiles"
+    )))
+}
+
+impl<W: Write + Seek> GeomProcessor for ShapeWriter<W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.shape.add_coord(x, y, None, None);
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.shape.add_coord(x, y, z, m);
+        Ok(())
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        if self.shape.base_type == Some(ShapeType::Multipoint) {
+            return Ok(());
+        }
+        self.begin_shape(ShapeType::Point)
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        if self.shape.base_type == Some(ShapeType::Point) {
+            self.end_shape()?;
+        }
+        Ok(())
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin_shape(ShapeType::Multipoint)
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_shape()
+    }
+    fn linestring_begin(&mut self, tagged: bool, _size: usize, idx: usize) -> Result<()> {
+        if tagged {
+            self.begin_shape(ShapeType::Polyline)?;
+        }
+        self.shape.add_part(idx == 0);
+        Ok(())
+    }
+    fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        if tagged {
+            self.end_shape()?;
+        }
+        Ok(())
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin_shape(ShapeType::Polyline)
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end_shape()
+    }
+    fn polygon_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        if tagged {
+            self.begin_shape(ShapeType::Polygon)?;
+        }
+        Ok(())
+    }
+    fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        if tagged {
+            self.shape.orient_rings();
+            self.end_shape()?;
+        }
+        Ok(())
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin_shape(ShapeType::Polygon)
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.shape.orient_rings();
+        self.end_shape()
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("GeometryCollection")
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("CircularString")
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("CompoundCurve")
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("CurvePolygon")
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("MultiCurve")
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("MultiSurface")
+    }
+    fn triangle_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("Triangle")
+    }
+    fn polyhedralsurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("PolyhedralSurface")
+    }
+    fn tin_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("TIN")
+    }
+}
+
+impl<W: Write + Seek> PropertyProcessor for ShapeWriter<W> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.dbf_dest.is_none() {
+            return Ok(false);
+        }
+        let value = match value {
+            ColumnValue::Bool(v) => FieldValue::Logical(Some(*v)),
+            ColumnValue::Byte(v) => FieldValue::Numeric(Some(*v as f64)),
+            ColumnValue::UByte(v) => FieldValue::Numeric(Some(*v as f64)),
+            ColumnValue::Short(v) => FieldValue::Numeric(Some(*v as f64)),
+            ColumnValue::UShort(v) => FieldValue::Numeric(Some(*v as f64)),
+            ColumnValue::Int(v) => FieldValue::Numeric(Some(*v as f64)),
+            ColumnValue::UInt(v) => FieldValue::Numeric(Some(*v as f64)),
+            ColumnValue::Long(v) => FieldValue::Numeric(Some(*v as f64)),
+            ColumnValue::ULong(v) => FieldValue::Numeric(Some(*v as f64)),
+            ColumnValue::Float(v) => FieldValue::Numeric(Some(*v as f64)),
+            ColumnValue::Double(v) => FieldValue::Numeric(Some(*v)),
+            ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) => {
+                FieldValue::Character(Some(v.to_string()))
+            }
+            ColumnValue::Binary(_) => return Ok(false), // not supported
+        };
+        if !self.field_names.iter().any(|n| n == name) {
+            self.field_names.push(name.to_string());
+        }
+        self.properties.insert(name.to_string(), value);
+        Ok(false)
+    }
+}
+
+impl<W: Write + Seek> FeatureProcessor for ShapeWriter<W> {
+    fn dataset_end(&mut self) -> Result<()> {
+        self.finish()
+            .map_err(|e| GeozeroError::Dataset(e.to_string()))
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.shape_written = false;
+        self.properties.clear();
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        if !self.shape_written {
+            // Keep .shp and .dbf records in sync
+            self.write_null_shape()
+                .map_err(|e| GeozeroError::Feature(e.to_string()))?;
+        }
+        if self.dbf_dest.is_some() {
+            self.records.push(std::mem::take(&mut self.properties));
+        }
+        Ok(())
+    }
+}
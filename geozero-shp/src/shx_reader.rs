@@ -1,3 +1,4 @@
+use crate::shp_reader::RecordHeader;
 use crate::{header, Error};
 use byteorder::{BigEndian, ReadBytesExt};
 use std::io::Read;
@@ -5,17 +6,31 @@ use std::io::Read;
 const INDEX_RECORD_SIZE: usize = 2 * std::mem::size_of::<i32>();
 
 pub(crate) struct ShapeIndex {
-    #[allow(dead_code)]
+    /// Offset of the record header in 16bit words
     pub offset: i32,
-    #[allow(dead_code)]
     pub record_size: i32,
 }
 
+impl ShapeIndex {
+    /// Offset of the record header in bytes
+    pub fn byte_offset(&self) -> u64 {
+        u64::from(self.offset as u32) * 2
+    }
+
+    /// Offset of the end of the record in bytes
+    pub fn record_end(&self) -> u64 {
+        self.byte_offset() + RecordHeader::SIZE as u64 + u64::from(self.record_size as u32) * 2
+    }
+}
+
 /// Read the content of a .shx file
 pub(crate) fn read_index_file<T: Read>(mut source: T) -> Result<Vec<ShapeIndex>, Error> {
     let header = header::Header::read_from(&mut source)?;
 
-    let num_shapes = ((header.file_length * 2) - header::HEADER_SIZE) / INDEX_RECORD_SIZE as i32;
+    let num_shapes = header
+        .file_length_bytes()
+        .saturating_sub(header::HEADER_SIZE as u64)
+        / INDEX_RECORD_SIZE as u64;
     let mut shapes_index = Vec::<ShapeIndex>::with_capacity(num_shapes as usize);
     for _ in 0..num_shapes {
         let offset = source.read_i32::<BigEndian>()?;
@@ -1,7 +1,11 @@
 use dbase::FieldValue;
 use geozero::geojson::GeoJsonWriter;
-use geozero::wkt::WktWriter;
-use geozero::{CoordDimensions, FeatureProperties, ProcessorSink};
+use geozero::wkt::{WktDialect, WktStr, WktWriter};
+use geozero::{
+    CoordDimensions, FeatureProcessor, FeatureProperties, GeomProcessor, GeozeroDatasource,
+    GeozeroGeometry, ProcessorSink, PropertyFilter, PropertyProcessor, ToWkt,
+};
+use geozero_shp::NodataPolicy;
 use std::fs::File;
 use std::io::BufReader;
 use std::str::from_utf8;
@@ -15,6 +19,24 @@ fn read_header() {
     assert_eq!(header.bbox.x_range(), [1.0, 5.0]);
 }
 
+#[test]
+fn header_metadata() {
+    let reader = geozero_shp::Reader::from_path("./tests/data/line.shp").unwrap();
+    assert_eq!(reader.shape_type(), geozero_shp::ShapeType::Polyline);
+    assert_eq!(reader.bbox(), [1.0, 1.0, 5.0, 6.0]);
+    assert_eq!(reader.z_range(), None);
+    assert_eq!(reader.m_range(), None);
+    assert_eq!(reader.feature_count(), Some(1));
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/linez.shp").unwrap();
+    assert_eq!(reader.shape_type(), geozero_shp::ShapeType::PolylineZ);
+    assert_eq!(reader.bbox(), [1.0, 1.0, 5.0, 9.0]);
+    assert_eq!(reader.z_range(), Some([0.0, 22.0]));
+    assert_eq!(reader.m_range(), Some([0.0, 3.0]));
+    // No .shx index
+    assert_eq!(reader.feature_count(), None);
+}
+
 #[test]
 fn iterate() -> Result<(), geozero_shp::Error> {
     let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
@@ -45,6 +67,42 @@ fn iterate() -> Result<(), geozero_shp::Error> {
     Ok(())
 }
 
+#[test]
+fn progress() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut calls = Vec::new();
+    let cnt = reader
+        .iter_geometries(&mut ProcessorSink::new())
+        .with_progress(3, |processed, total| calls.push((processed, total)))
+        .count();
+    assert_eq!(cnt, 10);
+    assert_eq!(
+        calls,
+        vec![(3, Some(10)), (6, Some(10)), (9, Some(10)), (10, Some(10))]
+    );
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut calls = Vec::new();
+    let cnt = reader
+        .iter_features(&mut ProcessorSink::new())?
+        .with_progress(5, |processed, total| calls.push((processed, total)))
+        .count();
+    assert_eq!(cnt, 10);
+    assert_eq!(calls, vec![(5, Some(10)), (10, Some(10))]);
+
+    let source = BufReader::new(File::open("./tests/data/poly.shp")?);
+    let reader = geozero_shp::Reader::new(source)?;
+    let mut calls = Vec::new();
+    let cnt = reader
+        .iter_geometries(&mut ProcessorSink::new())
+        .with_progress(4, |processed, total| calls.push((processed, total)))
+        .count();
+    assert_eq!(cnt, 10);
+    assert_eq!(calls, vec![(4, None), (8, None), (10, None)]);
+
+    Ok(())
+}
+
 #[test]
 fn shp_to_json() -> Result<(), geozero_shp::Error> {
     let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
@@ -66,6 +124,25 @@ fn shp_to_json() -> Result<(), geozero_shp::Error> {
     Ok(())
 }
 
+#[test]
+fn buffer_capacity() -> Result<(), geozero_shp::Error> {
+    let to_json = |reader: geozero_shp::Reader<_>| -> Result<Vec<u8>, geozero_shp::Error> {
+        let mut json: Vec<u8> = Vec::new();
+        let cnt = reader
+            .iter_features(&mut GeoJsonWriter::new(&mut json))?
+            .count();
+        assert_eq!(cnt, 10);
+        Ok(json)
+    };
+    let expected = to_json(geozero_shp::Reader::from_path("./tests/data/poly.shp")?)?;
+    for capacity in [16, 1024 * 1024] {
+        let reader =
+            geozero_shp::Reader::from_path_with_capacity("./tests/data/poly.shp", capacity)?;
+        assert_eq!(to_json(reader)?, expected);
+    }
+    Ok(())
+}
+
 #[test]
 fn shp_to_geo() -> Result<(), geozero_shp::Error> {
     use geo_types::Geometry;
@@ -103,6 +180,62 @@ fn property_filter() -> Result<(), geozero_shp::Error> {
     Ok(())
 }
 
+#[test]
+fn property_filter_processor() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut json: Vec<u8> = Vec::new();
+    let mut filter = PropertyFilter::new(GeoJsonWriter::new(&mut json), |props| {
+        props.number("AREA").is_some_and(|area| area > 260000.0)
+    });
+    let cnt = reader.iter_features(&mut filter)?.count();
+    // All records are read, but only matching features are written
+    assert_eq!(cnt, 10);
+    drop(filter);
+    let json = from_utf8(&json).unwrap();
+    assert_eq!(json.matches(r#""type": "Feature""#).count(), 5);
+    assert!(!json.contains(r#""AREA": 5268.813"#));
+    Ok(())
+}
+
+/// Counts geometry callbacks and requests skipping every geometry
+#[derive(Clone, Default)]
+struct SkipAll {
+    geometries: usize,
+    coords: usize,
+}
+
+impl GeomProcessor for SkipAll {
+    fn xy(&mut self, _x: f64, _y: f64, _idx: usize) -> geozero::error::Result<()> {
+        self.coords += 1;
+        Ok(())
+    }
+}
+impl PropertyProcessor for SkipAll {}
+impl FeatureProcessor for SkipAll {
+    fn geometry_begin(&mut self) -> geozero::error::Result<()> {
+        self.geometries += 1;
+        Ok(())
+    }
+    fn skip_geometry(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn skip_geometry() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut processor = SkipAll::default();
+    let mut cnt = 0;
+    for feat in reader.iter_features(&mut processor)? {
+        assert!(feat?.property::<f64>("EAS_ID").unwrap() > 100.0);
+        cnt += 1;
+    }
+    assert_eq!(cnt, 10);
+    assert_eq!(processor.geometries, 0);
+    assert_eq!(processor.coords, 0);
+    Ok(())
+}
+
 #[test]
 fn property_access() -> Result<(), geozero_shp::Error> {
     let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
@@ -130,6 +263,26 @@ fn property_access() -> Result<(), geozero_shp::Error> {
     Ok(())
 }
 
+#[test]
+fn dbf_fields_before_iteration() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/null_shapes.shp")?;
+    let fields = reader
+        .dbf_fields()?
+        .iter()
+        .map(|f| (f.name().to_string(), f.length()))
+        .collect::<Vec<_>>();
+    assert_eq!(fields, [("name".to_string(), 10)]);
+
+    // Reading the schema doesn't consume any records
+    let mut processor = ProcessorSink::new();
+    let features = reader
+        .iter_features(&mut processor)?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(features.len(), 3);
+    assert_eq!(features[0].property::<String>("name").unwrap(), "a");
+    Ok(())
+}
+
 #[test]
 fn property_file() -> Result<(), geozero_shp::Error> {
     let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
@@ -182,7 +335,7 @@ fn pointzm() -> Result<(), geozero_shp::Error> {
     reader.iter_geometries(&mut writer).next();
     assert_eq!(
         from_utf8(&wkt_data).unwrap(),
-        "POINT(160477.9000324604 5403959.561417906 0)"
+        "POINT M (160477.9000324604 5403959.561417906 0)"
     );
 
     let reader = geozero_shp::Reader::from_path("./tests/data/pointz.shp")?;
@@ -191,11 +344,44 @@ fn pointzm() -> Result<(), geozero_shp::Error> {
     reader.iter_geometries(&mut writer).next();
     assert_eq!(
         from_utf8(&wkt_data).unwrap(),
-        "POINT(1422464.3681007193 4188962.3364355816 72.40956470558095)"
+        "POINT Z (1422464.3681007193 4188962.3364355816 72.40956470558095)"
     );
     Ok(())
 }
 
+#[test]
+fn pointm_no_data() -> Result<(), geozero_shp::Error> {
+    use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
+    use std::io::Cursor;
+
+    // PointM shapefile with a single point with NO_DATA measure
+    let mut shp = Cursor::new(Vec::new());
+    shp.write_i32::<BigEndian>(9994)?;
+    for _ in 0..5 {
+        shp.write_i32::<BigEndian>(0)?;
+    }
+    shp.write_i32::<BigEndian>(68)?;
+    shp.write_i32::<LittleEndian>(1000)?;
+    shp.write_i32::<LittleEndian>(21)?;
+    for _ in 0..8 {
+        shp.write_f64::<LittleEndian>(0.0)?;
+    }
+    shp.write_i32::<BigEndian>(1)?;
+    shp.write_i32::<BigEndian>(14)?;
+    shp.write_i32::<LittleEndian>(21)?;
+    shp.write_f64::<LittleEndian>(1.0)?;
+    shp.write_f64::<LittleEndian>(2.0)?;
+    shp.write_f64::<LittleEndian>(geozero_shp::NO_DATA)?;
+    shp.set_position(0);
+
+    let reader = geozero_shp::Reader::new(shp)?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    let mut writer = WktWriter::with_dims(&mut wkt_data, CoordDimensions::xym());
+    reader.iter_geometries(&mut writer).next().unwrap()?;
+    assert_eq!(from_utf8(&wkt_data).unwrap(), "POINT(1 2)");
+    Ok(())
+}
+
 #[test]
 fn multipoint() -> Result<(), geozero_shp::Error> {
     let reader = geozero_shp::Reader::from_path("./tests/data/multipoint.shp")?;
@@ -215,7 +401,7 @@ fn multipointzm() -> Result<(), geozero_shp::Error> {
     reader.iter_geometries(&mut writer).next();
     assert_eq!(
         from_utf8(&wkt_data).unwrap(),
-        "MULTIPOINT(1422671.7232666016 4188903.4295959473 72.00995635986328,1422672.1022949219 4188903.4295959473 72.0060806274414,1422671.9127807617 4188903.7578430176 72.00220489501953,1422671.9127807617 4188903.539001465 71.99445343017578)"
+        "MULTIPOINT Z (1422671.7232666016 4188903.4295959473 72.00995635986328,1422672.1022949219 4188903.4295959473 72.0060806274414,1422671.9127807617 4188903.7578430176 72.00220489501953,1422671.9127807617 4188903.539001465 71.99445343017578)"
     );
     Ok(())
 }
@@ -236,13 +422,15 @@ fn line() -> Result<(), geozero_shp::Error> {
 
 #[test]
 fn linezm() -> Result<(), geozero_shp::Error> {
-    let reader = geozero_shp::Reader::from_path("./tests/data/linez.shp")?;
+    // Substitute missing measures, which would be omitted otherwise
+    let reader = geozero_shp::Reader::from_path("./tests/data/linez.shp")?
+        .with_m_nodata_policy(NodataPolicy::AsValue(0.0));
     let mut wkt_data: Vec<u8> = Vec::new();
     let mut writer = WktWriter::with_dims(&mut wkt_data, CoordDimensions::xyzm());
     reader.iter_geometries(&mut writer).next();
     assert_eq!(
         from_utf8(&wkt_data).unwrap(),
-        "MULTILINESTRING((1 5 18 -1000000000000000000000000000000000000000,5 5 20 -1000000000000000000000000000000000000000,5 1 22 -1000000000000000000000000000000000000000,3 3 0 -1000000000000000000000000000000000000000,1 1 0 -1000000000000000000000000000000000000000),(3 2 0 -1000000000000000000000000000000000000000,2 6 0 -1000000000000000000000000000000000000000),(3 2 15 0,2 6 13 3,1 9 14 2))"
+        "MULTILINESTRING ZM ((1 5 18 0,5 5 20 0,5 1 22 0,3 3 0 0,1 1 0 0),(3 2 0 0,2 6 0 0),(3 2 15 0,2 6 13 3,1 9 14 2))"
     );
 
     let reader = geozero_shp::Reader::from_path("./tests/data/linez.shp")?;
@@ -251,7 +439,7 @@ fn linezm() -> Result<(), geozero_shp::Error> {
     reader.iter_geometries(&mut writer).next();
     assert_eq!(
         from_utf8(&wkt_data).unwrap(),
-        "MULTILINESTRING((1 5 18,5 5 20,5 1 22,3 3 0,1 1 0),(3 2 0,2 6 0),(3 2 15,2 6 13,1 9 14))"
+        "MULTILINESTRING Z ((1 5 18,5 5 20,5 1 22,3 3 0,1 1 0),(3 2 0,2 6 0),(3 2 15,2 6 13,1 9 14))"
     );
 
     let reader = geozero_shp::Reader::from_path("./tests/data/linez.shp")?;
@@ -264,18 +452,107 @@ fn linezm() -> Result<(), geozero_shp::Error> {
         "MULTILINESTRING((1 5,5 5,5 1,3 3,1 1),(3 2,2 6),(3 2,2 6,1 9))"
     );
 
-    let reader = geozero_shp::Reader::from_path("./tests/data/linem.shp")?;
+    let reader = geozero_shp::Reader::from_path("./tests/data/linem.shp")?
+        .with_m_nodata_policy(NodataPolicy::AsValue(0.0));
     let mut wkt_data: Vec<u8> = Vec::new();
     let mut writer = WktWriter::with_dims(&mut wkt_data, CoordDimensions::xym());
     reader.iter_geometries(&mut writer).next();
     assert_eq!(
         from_utf8(&wkt_data).unwrap(),
-        "MULTILINESTRING((1 5 0,5 5 -1000000000000000000000000000000000000000,5 1 3,3 3 -1000000000000000000000000000000000000000,1 1 0),(3 2 -1000000000000000000000000000000000000000,2 6 -1000000000000000000000000000000000000000))"
+        "MULTILINESTRING M ((1 5 0,5 5 0,5 1 3,3 3 0,1 1 0),(3 2 0,2 6 0))"
     );
 
     Ok(())
 }
 
+/// Collects the measures of all coordinates
+struct MeasureCollector(Vec<Option<f64>>);
+
+impl GeomProcessor for MeasureCollector {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xym()
+    }
+    fn coordinate(
+        &mut self,
+        _x: f64,
+        _y: f64,
+        _z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> geozero::error::Result<()> {
+        self.0.push(m);
+        Ok(())
+    }
+}
+impl PropertyProcessor for MeasureCollector {}
+impl FeatureProcessor for MeasureCollector {}
+
+#[test]
+fn m_nodata_policy() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/linem.shp")?;
+    let mut measures = MeasureCollector(Vec::new());
+    reader.iter_geometries(&mut measures).next().unwrap()?;
+    assert_eq!(
+        measures.0,
+        [Some(0.0), None, Some(3.0), None, Some(0.0), None, None]
+    );
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/linem.shp")?
+        .with_m_nodata_policy(NodataPolicy::AsValue(-1.0));
+    let mut measures = MeasureCollector(Vec::new());
+    reader.iter_geometries(&mut measures).next().unwrap()?;
+    assert_eq!(
+        measures.0,
+        [
+            Some(0.0),
+            Some(-1.0),
+            Some(3.0),
+            Some(-1.0),
+            Some(0.0),
+            Some(-1.0),
+            Some(-1.0)
+        ]
+    );
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/pointm.shp")?
+        .with_m_nodata_policy(NodataPolicy::AsValue(-1.0));
+    let mut measures = MeasureCollector(Vec::new());
+    reader.iter_geometries(&mut measures).next().unwrap()?;
+    assert_eq!(measures.0, [Some(0.0)]);
+
+    Ok(())
+}
+
+/// Collects the input dimensions announced before each geometry
+struct InputDimensions(Vec<CoordDimensions>);
+
+impl GeomProcessor for InputDimensions {
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> geozero::error::Result<()> {
+        self.0.push(dimensions);
+        Ok(())
+    }
+}
+impl PropertyProcessor for InputDimensions {}
+impl FeatureProcessor for InputDimensions {}
+
+#[test]
+fn input_dimensions() -> Result<(), geozero_shp::Error> {
+    for (path, expected) in [
+        ("./tests/data/point.shp", CoordDimensions::xy()),
+        ("./tests/data/pointm.shp", CoordDimensions::xym()),
+        ("./tests/data/pointz.shp", CoordDimensions::xyz()),
+        ("./tests/data/multipatch.shp", CoordDimensions::xyz()),
+    ] {
+        let reader = geozero_shp::Reader::from_path(path)?;
+        let mut dimensions = InputDimensions(Vec::new());
+        reader.iter_geometries(&mut dimensions).next().unwrap()?;
+        assert_eq!(dimensions.0, [expected], "{path}");
+    }
+    Ok(())
+}
+
 #[test]
 fn polygon() -> Result<(), geozero_shp::Error> {
     let reader = geozero_shp::Reader::from_path("./tests/data/polygon.shp")?;
@@ -299,6 +576,17 @@ fn polygon() -> Result<(), geozero_shp::Error> {
         "MULTIPOLYGON(((-120 60,120 60,120 -60,-120 -60,-120 60),(-60 30,-60 -30,60 -30,60 30,-60 30)))"
     );
 
+    // Holes after both outer rings are assigned by containment
+    let reader = geozero_shp::Reader::from_path("./tests/data/polygon_holes_unordered.shp")?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader
+        .iter_geometries(&mut WktWriter::new(&mut wkt_data))
+        .next();
+    assert_eq!(
+        from_utf8(&wkt_data).unwrap(),
+        "MULTIPOLYGON(((0 0,0 10,10 10,10 0,0 0),(2 2,4 2,4 4,2 4,2 2)),((20 0,20 10,30 10,30 0,20 0),(22 2,24 2,24 4,22 4,22 2)))"
+    );
+
     let reader = geozero_shp::Reader::from_path("./tests/data/multi_polygon.shp")?;
     let mut wkt_data: Vec<u8> = Vec::new();
     reader
@@ -323,17 +611,865 @@ fn polygonzm() -> Result<(), geozero_shp::Error> {
     reader.iter_geometries(&mut writer).next();
     assert_eq!(
         from_utf8(&wkt_data).unwrap(),
-        "MULTIPOLYGON(((1422692.1644789441 4188837.794210903 72.46632654472523 0,1422692.1625749937 4188837.75060327 72.46632654472523 1,1422692.156877633 4188837.7073275167 72.46632654472523 2,1422692.1474302218 4188837.664712999 72.46632654472523 3,1422692.1343046608 4188837.6230840385 72.46632654472523 4,1422692.1176008438 4188837.582757457 72.46632654472523 5,1422692.0974458966 4188837.5440401635 72.46632654472523 6,1422692.0739932107 4188837.5072268206 72.46632654472523 7,1422692.047421275 4188837.4725976 72.46632654472523 8,1422692.017932318 4188837.4404160506 72.46632654472523 9,1422691.9857507686 4188837.4109270936 72.46632654472523 10,1422691.951121548 4188837.384355158 72.46632654472523 11,1422691.914308205 4188837.360902472 72.46632654472523 12,1422691.8755909116 4188837.3407475245 72.46632654472523 13,1422691.8352643298 4188837.3240437075 72.46632654472523 14,1422691.7936353693 4188837.3109181467 72.46632654472523 15,1422691.7510208515 4188837.3014707356 72.46632654472523 16,1422691.7077450987 4188837.295773375 72.46632654472523 17,1422691.6641374656 4188837.293869424 72.46632654472523 18,1422691.6205298326 4188837.295773375 72.46632654472523 19,1422691.5772540797 4188837.3014707356 72.46632654472523 20,1422691.534639562 4188837.3109181467 72.46632654472523 21,1422691.4930106015 4188837.3240437075 72.46632654472523 22,1422691.4526840197 4188837.3407475245 72.46632654472523 23,1422691.4139667263 4188837.360902472 72.46632654472523 24,1422691.3771533833 4188837.384355158 72.46632654472523 25,1422691.3425241627 4188837.4109270936 72.46632654472523 26,1422691.3103426134 4188837.4404160506 72.46632654472523 27,1422691.2808536564 4188837.4725976 72.46632654472523 28,1422691.2542817206 4188837.5072268206 72.46632654472523 29,1422691.2308290347 4188837.5440401635 72.46632654472523 30,1422691.2106740875 4188837.582757457 72.46632654472523 31,1422691.1939702705 4188837.6230840385 72.46632654472523 32,1422691.1808447095 4188837.664712999 72.46632654472523 33,1422691.1713972983 4188837.7073275167 72.46632654472523 34,1422691.1656999376 4188837.75060327 72.46632654472523 35,1422691.1637959871 4188837.794210903 72.46632654472523 36,1422691.1656999376 4188837.837818536 72.46632654472523 37,1422691.1713972983 4188837.881094289 72.46632654472523 38,1422691.1808447095 4188837.9237088067 72.46632654472523 39,1422691.1939702705 4188837.9653377673 72.46632654472523 40,1422691.2106740875 4188838.0056643486 72.46632654472523 41,1422691.2308290347 4188838.0443816422 72.46632654472523 42,1422691.2542817206 4188838.081194985 72.46632654472523 43,1422691.2808536564 4188838.115824206 72.46632654472523 44,1422691.3103426134 4188838.148005755 72.46632654472523 45,1422691.3425241627 4188838.177494712 72.46632654472523 46,1422691.3771533833 4188838.2040666477 72.46632654472523 47,1422691.4139667263 4188838.227519334 72.46632654472523 48,1422691.4526840197 4188838.2476742812 72.46632654472523 49,1422691.4930106015 4188838.2643780983 72.46632654472523 50,1422691.534639562 4188838.277503659 72.46632654472523 51,1422691.5772540797 4188838.28695107 72.46632654472523 52,1422691.6205298326 4188838.292648431 72.46632654472523 53,1422691.6641374656 4188838.2945523816 72.46632654472523 54,1422691.7077450987 4188838.292648431 72.46632654472523 55,1422691.7510208515 4188838.28695107 72.46632654472523 56,1422691.7936353693 4188838.277503659 72.46632654472523 57,1422691.8352643298 4188838.2643780983 72.46632654472523 58,1422691.8755909116 4188838.2476742812 72.46632654472523 59,1422691.914308205 4188838.227519334 72.46632654472523 60,1422691.951121548 4188838.2040666477 72.46632654472523 61,1422691.9857507686 4188838.177494712 72.46632654472523 62,1422692.017932318 4188838.148005755 72.46632654472523 63,1422692.047421275 4188838.115824206 72.46632654472523 64,1422692.0739932107 4188838.081194985 72.46632654472523 65,1422692.0974458966 4188838.0443816422 72.46632654472523 66,1422692.1176008438 4188838.0056643486 72.46632654472523 67,1422692.1343046608 4188837.9653377673 72.46632654472523 68,1422692.1474302218 4188837.9237088067 72.46632654472523 69,1422692.156877633 4188837.881094289 72.46632654472523 70,1422692.1625749937 4188837.837818536 72.46632654472523 71,1422692.1644789441 4188837.794210903 72.46632654472523 72)))"
+        "MULTIPOLYGON ZM (((1422692.1644789441 4188837.794210903 72.46632654472523 0,1422692.1625749937 4188837.75060327 72.46632654472523 1,1422692.156877633 4188837.7073275167 72.46632654472523 2,1422692.1474302218 4188837.664712999 72.46632654472523 3,1422692.1343046608 4188837.6230840385 72.46632654472523 4,1422692.1176008438 4188837.582757457 72.46632654472523 5,1422692.0974458966 4188837.5440401635 72.46632654472523 6,1422692.0739932107 4188837.5072268206 72.46632654472523 7,1422692.047421275 4188837.4725976 72.46632654472523 8,1422692.017932318 4188837.4404160506 72.46632654472523 9,1422691.9857507686 4188837.4109270936 72.46632654472523 10,1422691.951121548 4188837.384355158 72.46632654472523 11,1422691.914308205 4188837.360902472 72.46632654472523 12,1422691.8755909116 4188837.3407475245 72.46632654472523 13,1422691.8352643298 4188837.3240437075 72.46632654472523 14,1422691.7936353693 4188837.3109181467 72.46632654472523 15,1422691.7510208515 4188837.3014707356 72.46632654472523 16,1422691.7077450987 4188837.295773375 72.46632654472523 17,1422691.6641374656 4188837.293869424 72.46632654472523 18,1422691.6205298326 4188837.295773375 72.46632654472523 19,1422691.5772540797 4188837.3014707356 72.46632654472523 20,1422691.534639562 4188837.3109181467 72.46632654472523 21,1422691.4930106015 4188837.3240437075 72.46632654472523 22,1422691.4526840197 4188837.3407475245 72.46632654472523 23,1422691.4139667263 4188837.360902472 72.46632654472523 24,1422691.3771533833 4188837.384355158 72.46632654472523 25,1422691.3425241627 4188837.4109270936 72.46632654472523 26,1422691.3103426134 4188837.4404160506 72.46632654472523 27,1422691.2808536564 4188837.4725976 72.46632654472523 28,1422691.2542817206 4188837.5072268206 72.46632654472523 29,1422691.2308290347 4188837.5440401635 72.46632654472523 30,1422691.2106740875 4188837.582757457 72.46632654472523 31,1422691.1939702705 4188837.6230840385 72.46632654472523 32,1422691.1808447095 4188837.664712999 72.46632654472523 33,1422691.1713972983 4188837.7073275167 72.46632654472523 34,1422691.1656999376 4188837.75060327 72.46632654472523 35,1422691.1637959871 4188837.794210903 72.46632654472523 36,1422691.1656999376 4188837.837818536 72.46632654472523 37,1422691.1713972983 4188837.881094289 72.46632654472523 38,1422691.1808447095 4188837.9237088067 72.46632654472523 39,1422691.1939702705 4188837.9653377673 72.46632654472523 40,1422691.2106740875 4188838.0056643486 72.46632654472523 41,1422691.2308290347 4188838.0443816422 72.46632654472523 42,1422691.2542817206 4188838.081194985 72.46632654472523 43,1422691.2808536564 4188838.115824206 72.46632654472523 44,1422691.3103426134 4188838.148005755 72.46632654472523 45,1422691.3425241627 4188838.177494712 72.46632654472523 46,1422691.3771533833 4188838.2040666477 72.46632654472523 47,1422691.4139667263 4188838.227519334 72.46632654472523 48,1422691.4526840197 4188838.2476742812 72.46632654472523 49,1422691.4930106015 4188838.2643780983 72.46632654472523 50,1422691.534639562 4188838.277503659 72.46632654472523 51,1422691.5772540797 4188838.28695107 72.46632654472523 52,1422691.6205298326 4188838.292648431 72.46632654472523 53,1422691.6641374656 4188838.2945523816 72.46632654472523 54,1422691.7077450987 4188838.292648431 72.46632654472523 55,1422691.7510208515 4188838.28695107 72.46632654472523 56,1422691.7936353693 4188838.277503659 72.46632654472523 57,1422691.8352643298 4188838.2643780983 72.46632654472523 58,1422691.8755909116 4188838.2476742812 72.46632654472523 59,1422691.914308205 4188838.227519334 72.46632654472523 60,1422691.951121548 4188838.2040666477 72.46632654472523 61,1422691.9857507686 4188838.177494712 72.46632654472523 62,1422692.017932318 4188838.148005755 72.46632654472523 63,1422692.047421275 4188838.115824206 72.46632654472523 64,1422692.0739932107 4188838.081194985 72.46632654472523 65,1422692.0974458966 4188838.0443816422 72.46632654472523 66,1422692.1176008438 4188838.0056643486 72.46632654472523 67,1422692.1343046608 4188837.9653377673 72.46632654472523 68,1422692.1474302218 4188837.9237088067 72.46632654472523 69,1422692.156877633 4188837.881094289 72.46632654472523 70,1422692.1625749937 4188837.837818536 72.46632654472523 71,1422692.1644789441 4188837.794210903 72.46632654472523 72)))"
     );
 
+    let reader = geozero_shp::Reader::from_path("./tests/data/polygonz.shp")?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    let mut writer = WktWriter::with_opts(
+        &mut wkt_data,
+        WktDialect::Ewkt,
+        CoordDimensions::xyz(),
+        Some(2056),
+    );
+    reader.iter_geometries(&mut writer).next();
+    let ewkt = from_utf8(&wkt_data).unwrap();
+    assert!(ewkt.starts_with(
+        "SRID=2056;MULTIPOLYGON(((1422692.1644789441 4188837.794210903 72.46632654472523,1422692.1625749937 4188837.75060327 72.46632654472523,"
+    ));
+    assert!(ewkt.ends_with(
+        ",1422692.1644789441 4188837.794210903 72.46632654472523)))"
+    ));
+
     let reader = geozero_shp::Reader::from_path("./tests/data/polygonm.shp")?;
     let mut wkt_data: Vec<u8> = Vec::new();
     let mut writer = WktWriter::with_dims(&mut wkt_data, CoordDimensions::xym());
     reader.iter_geometries(&mut writer).next();
     assert_eq!(
         from_utf8(&wkt_data).unwrap(),
-        "MULTIPOLYGON(((159814.75390576152 5404314.139043656 0,160420.36722814097 5403703.520652497 0,159374.30785312195 5403473.287488617 0,159814.75390576152 5404314.139043656 0)))"
+        "MULTIPOLYGON M (((159814.75390576152 5404314.139043656 0,160420.36722814097 5403703.520652497 0,159374.30785312195 5403473.287488617 0,159814.75390576152 5404314.139043656 0)))"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn read_nth_shape() -> Result<(), geozero_shp::Error> {
+    let mut reader = geozero_shp::Reader::from_path("./tests/data/polygon_hole.shp")?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader.read_nth_shape(0, &mut WktWriter::new(&mut wkt_data))?;
+    assert_eq!(
+        from_utf8(&wkt_data).unwrap(),
+        "MULTIPOLYGON(((-120 60,120 60,120 -60,-120 -60,-120 60),(-60 30,-60 -30,60 -30,60 30,-60 30)))"
     );
 
+    let mut reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader.read_nth_shape(9, &mut WktWriter::new(&mut wkt_data))?;
+    assert!(from_utf8(&wkt_data)
+        .unwrap()
+        .ends_with("479735.90625 4764752,479750.6875 4764702)))"));
+    // random access in any order
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader.read_nth_shape(0, &mut WktWriter::new(&mut wkt_data))?;
+    assert!(from_utf8(&wkt_data).unwrap().starts_with("MULTIPOLYGON((("));
+
+    assert!(matches!(
+        reader.read_nth_shape(10, &mut ProcessorSink::new()),
+        Err(geozero_shp::Error::IndexOutOfBounds { index: 10, len: 10 })
+    ));
+
+    let mut reader = geozero_shp::Reader::from_path("./tests/data/polygon.shp")?;
+    assert!(matches!(
+        reader.read_nth_shape(0, &mut ProcessorSink::new()),
+        Err(geozero_shp::Error::MissingIndexFile)
+    ));
+    Ok(())
+}
+
+#[test]
+fn mixed_access() -> Result<(), geozero_shp::Error> {
+    let mut reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let shape = reader.read_nth_raw_shape(9)?;
+    assert_eq!(shape.record_number, 10);
+    assert_eq!(shape.shape_type, geozero_shp::ShapeType::Polygon);
+
+    // Sequential iteration starts with the first shape after random access
+    let mut sink = ProcessorSink::new();
+    let count = reader
+        .iter_geometries_by_ref(&mut sink)?
+        .collect::<Result<Vec<_>, _>>()?
+        .len();
+    assert_eq!(count, 10);
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader.read_nth_shape(0, &mut WktWriter::new(&mut wkt_data))?;
+    assert!(from_utf8(&wkt_data).unwrap().starts_with("MULTIPOLYGON((("));
+    let record_numbers = reader
+        .iter_raw_shapes_by_ref()?
+        .map(|shape| shape.map(|shape| shape.record_number))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(record_numbers, (1..=10).collect::<Vec<_>>());
+
+    reader.read_nth_shape(5, &mut ProcessorSink::new())?;
+    assert_eq!(reader.iter_raw_shapes().count(), 10);
+
+    let mut reader = geozero_shp::Reader::from_path("./tests/data/polygon.shp")?;
+    assert!(matches!(
+        reader.read_nth_raw_shape(0),
+        Err(geozero_shp::Error::MissingIndexFile)
+    ));
+    Ok(())
+}
+
+#[test]
+fn raw_shapes() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let shapes = reader.iter_raw_shapes().collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(shapes.len(), 10);
+    assert_eq!(shapes[0].record_number, 1);
+    assert_eq!(shapes[0].shape_type, geozero_shp::ShapeType::Polygon);
+
+    // Lazy decoding matches the eagerly processed geometries
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut expected: Vec<u8> = Vec::new();
+    let mut writer = WktWriter::new(&mut expected);
+    for result in reader.iter_geometries(&mut writer) {
+        result?;
+    }
+    let mut wkt_data: Vec<u8> = Vec::new();
+    let mut writer = WktWriter::new(&mut wkt_data);
+    for shape in &shapes {
+        shape.process_geom(&mut writer)?;
+    }
+    assert_eq!(wkt_data, expected);
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/line.shp")?;
+    let shape = reader.iter_raw_shapes().next().unwrap()?;
+    assert_eq!(
+        shape.to_wkt().unwrap(),
+        "MULTILINESTRING((1 5,5 5,5 1,3 3,1 1),(3 2,2 6))"
+    );
+    Ok(())
+}
+
+#[test]
+fn null_shapes() -> Result<(), geozero_shp::Error> {
+    // Null record between two points
+    let reader = geozero_shp::Reader::from_path("./tests/data/null_shapes.shp")?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    let mut cnt = 0;
+    for geom in reader.iter_geometries(&mut WktWriter::new(&mut wkt_data)) {
+        geom?;
+        cnt += 1;
+    }
+    assert_eq!(cnt, 3);
+    assert_eq!(from_utf8(&wkt_data).unwrap(), "POINT(1 1)POINT(3 3)");
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/null_shapes.shp")?;
+    let mut json_data: Vec<u8> = Vec::new();
+    let mut writer = GeoJsonWriter::new(&mut json_data);
+    let features = reader
+        .iter_features(&mut writer)?
+        .collect::<Result<Vec<_>, _>>()?;
+    // Feature count matches the DBF row count
+    assert_eq!(features.len(), 3);
+    let names = features
+        .iter()
+        .map(|feat| feat.property::<String>("name").unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["a", "b", "c"]);
+    assert_eq!(features[2].record_number, 3);
+    assert_eq!(
+        from_utf8(&json_data).unwrap(),
+        r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1,1]}},
+{"type": "Feature", "properties": {"name": "b"}},
+{"type": "Feature", "properties": {"name": "c"}, "geometry": {"type": "Point", "coordinates": [3,3]}}]}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn record_positions() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut positions = Vec::new();
+    for feat in reader.iter_features(&mut ProcessorSink::new())? {
+        let feat = feat?;
+        positions.push((feat.record_number, feat.file_offset));
+    }
+    assert_eq!(positions.len(), 10);
+    assert_eq!(positions[0], (1, 100));
+    assert_eq!(positions[9].0, 10);
+    assert!(positions.windows(2).all(|w| w[0].1 < w[1].1));
+
+    // Truncated last record
+    let mut data = std::fs::read("./tests/data/poly.shp")?;
+    data.truncate(data.len() - 16);
+    let reader = geozero_shp::Reader::new(std::io::Cursor::new(data))?;
+    let mut processor = ProcessorSink::new();
+    let results: Vec<_> = reader.iter_geometries(&mut processor).take(10).collect();
+    assert!(results[..9].iter().all(|result| result.is_ok()));
+    match &results[9] {
+        Err(
+            err @ geozero_shp::Error::ShapeDecode {
+                record_number,
+                offset,
+                source,
+            },
+        ) => {
+            assert_eq!((*record_number, *offset), positions[9]);
+            assert!(matches!(**source, geozero_shp::Error::IoError(_)));
+            assert_eq!(
+                err.to_string(),
+                format!("Shape record 10 at offset {offset} could not be decoded")
+            );
+        }
+        other => panic!("unexpected result {other:?}"),
+    }
+    Ok(())
+}
+
+#[test]
+fn file_length_limits() -> Result<(), geozero_shp::Error> {
+    use std::io::Cursor;
+
+    // File length in 16bit words of oversized files exceeds i32::MAX
+    let mut data = std::fs::read("./tests/data/poly.shp")?;
+    data[24..28].copy_from_slice(&(-2i32).to_be_bytes());
+    let reader = geozero_shp::Reader::new(Cursor::new(data.clone()))?;
+    assert_eq!(
+        reader.header().file_length_bytes(),
+        (u64::from(u32::MAX) - 1) * 2
+    );
+    // Iteration ends at the end of the data
+    let mut processor = ProcessorSink::new();
+    let results: Vec<_> = reader.iter_geometries(&mut processor).collect();
+    assert_eq!(results.len(), 11);
+    assert!(results[..10].iter().all(|result| result.is_ok()));
+    assert!(matches!(results[10], Err(geozero_shp::Error::IoError(_))));
+
+    // The index determines the end of the records
+    let mut reader = geozero_shp::Reader::new(Cursor::new(data))?;
+    reader.add_index_source(Cursor::new(std::fs::read("./tests/data/poly.shx")?))?;
+    let results: Vec<_> = reader.iter_geometries(&mut processor).collect();
+    assert_eq!(results.len(), 10);
+    assert!(results.iter().all(|result| result.is_ok()));
+
+    // Last record exceeds the file length of the header
+    let mut data = std::fs::read("./tests/data/poly.shp")?;
+    let file_length = data.len() as u64 - 20;
+    data[24..28].copy_from_slice(&(file_length as i32 / 2).to_be_bytes());
+    let reader = geozero_shp::Reader::new(Cursor::new(data))?;
+    let results: Vec<_> = reader.iter_raw_shapes().collect();
+    assert_eq!(results.len(), 10);
+    match &results[9] {
+        Err(
+            err @ geozero_shp::Error::RecordOutOfBounds {
+                record_number: 10,
+                offset,
+                ..
+            },
+        ) => assert_eq!(
+            err.to_string(),
+            format!(
+                "Shape record 10 at offset {offset} exceeds the file length of {file_length} bytes"
+            )
+        ),
+        other => panic!("unexpected result {other:?}"),
+    }
+    Ok(())
+}
+
+#[test]
+fn lenient_iteration() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let offsets: Vec<u64> = reader
+        .iter_features(&mut ProcessorSink::new())?
+        .map(|feat| feat.map(|feat| feat.file_offset))
+        .collect::<Result<_, _>>()?;
+
+    // Invalid shape type in record 3
+    let mut data = std::fs::read("./tests/data/poly.shp")?;
+    let pos = offsets[2] as usize + 8;
+    data[pos..pos + 4].copy_from_slice(&99i32.to_le_bytes());
+    let open = |data: &[u8]| -> Result<_, geozero_shp::Error> {
+        let mut reader = geozero_shp::Reader::new(std::io::Cursor::new(data.to_vec()))?;
+        reader.add_dbf_source(std::io::Cursor::new(std::fs::read(
+            "./tests/data/poly.dbf",
+        )?))?;
+        Ok(reader)
+    };
+
+    let mut json_data: Vec<u8> = Vec::new();
+    let results: Vec<_> = open(&data)?
+        .iter_features_lenient(&mut GeoJsonWriter::new(&mut json_data))?
+        .collect();
+    assert_eq!(results.len(), 10);
+    assert!(matches!(
+        results[2],
+        Err(geozero_shp::Error::ShapeDecode {
+            record_number: 3,
+            ..
+        })
+    ));
+    assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 9);
+    let json = from_utf8(&json_data).unwrap();
+    assert_eq!(json.matches(r#""type": "Feature""#).count(), 9);
+    assert!(json.ends_with("]}"));
+
+    let mut processor = ProcessorSink::new();
+    let results: Vec<_> = open(&data)?
+        .iter_geometries(&mut processor)
+        .with_lenient(true)
+        .collect();
+    assert_eq!(results.len(), 10);
+    assert!(results[2].is_err());
+
+    // Iteration ends with a truncated record
+    data.truncate(offsets[5] as usize + 20);
+    let mut processor = ProcessorSink::new();
+    let results: Vec<_> = open(&data)?
+        .iter_geometries(&mut processor)
+        .with_lenient(true)
+        .collect();
+    assert_eq!(results.len(), 6);
+    assert!(results[5].is_err());
+    Ok(())
+}
+
+#[test]
+fn lenient_dataset_callbacks() -> Result<(), geozero_shp::Error> {
+    #[derive(Default)]
+    struct DatasetCounter {
+        begin: usize,
+        end: usize,
+    }
+    impl GeomProcessor for DatasetCounter {}
+    impl PropertyProcessor for DatasetCounter {}
+    impl FeatureProcessor for DatasetCounter {
+        fn dataset_begin(&mut self, _name: Option<&str>) -> geozero::error::Result<()> {
+            self.begin += 1;
+            Ok(())
+        }
+        fn dataset_end(&mut self) -> geozero::error::Result<()> {
+            self.end += 1;
+            Ok(())
+        }
+    }
+
+    // Invalid shape type in the first two records
+    let mut data = std::fs::read("./tests/data/poly.shp")?;
+    let offsets: Vec<u64> = geozero_shp::Reader::from_path("./tests/data/poly.shp")?
+        .iter_features(&mut ProcessorSink::new())?
+        .map(|feat| feat.map(|feat| feat.file_offset))
+        .collect::<Result<_, _>>()?;
+    for offset in &offsets[0..2] {
+        let pos = *offset as usize + 8;
+        data[pos..pos + 4].copy_from_slice(&99i32.to_le_bytes());
+    }
+    let mut reader = geozero_shp::Reader::new(std::io::Cursor::new(data))?;
+    reader.add_dbf_source(std::io::Cursor::new(std::fs::read(
+        "./tests/data/poly.dbf",
+    )?))?;
+
+    let mut counter = DatasetCounter::default();
+    let mut features = reader.iter_features_lenient(&mut counter)?;
+    let results: Vec<_> = features.by_ref().collect();
+    assert_eq!(results.iter().filter(|result| result.is_err()).count(), 2);
+    assert!(features.next().is_none());
+    drop(features);
+    assert_eq!(counter.begin, 1);
+    assert_eq!(counter.end, 1);
+    Ok(())
+}
+
+#[test]
+fn bbox_filter() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    let mut writer = WktWriter::new(&mut wkt_data);
+    let cnt = reader
+        .iter_geometries(&mut writer)
+        .with_bbox(478000.0, 4764000.0, 479400.0, 4765200.0)
+        .count();
+    assert_eq!(cnt, 5);
+    assert_eq!(from_utf8(&wkt_data).unwrap().matches("MULTIPOLYGON").count(), 5);
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/point.shp")?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    let cnt = reader
+        .iter_geometries(&mut WktWriter::new(&mut wkt_data))
+        .with_bbox(120.0, 30.0, 130.0, 40.0)
+        .count();
+    assert_eq!(cnt, 1);
+    assert_eq!(from_utf8(&wkt_data).unwrap(), "POINT(122 37)");
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/point.shp")?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    let cnt = reader
+        .iter_geometries(&mut WktWriter::new(&mut wkt_data))
+        .with_bbox(0.0, 0.0, 10.0, 10.0)
+        .count();
+    assert_eq!(cnt, 0);
+    assert!(wkt_data.is_empty());
+    Ok(())
+}
+
+#[test]
+fn prj_crs() -> Result<(), geozero_shp::Error> {
+    let mut reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    reader.add_prj_source(BufReader::new(File::open("./tests/data/poly.PRJ")?))?;
+    assert!(reader
+        .crs()
+        .unwrap()
+        .starts_with(r#"PROJCS["OSGB 1936 / British National Grid""#));
+
+    let mut json: Vec<u8> = Vec::new();
+    let cnt = reader
+        .iter_features(&mut GeoJsonWriter::new(&mut json))?
+        .count();
+    assert_eq!(cnt, 10);
+    assert!(from_utf8(&json).unwrap().starts_with(
+        r#"{
+"type": "FeatureCollection",
+"crs": {"type": "name", "properties": {"name": "PROJCS[\"OSGB 1936 / British National Grid\","#
+    ));
+    Ok(())
+}
+
+#[test]
+fn field_selection() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut json: Vec<u8> = Vec::new();
+    let mut cnt = 0;
+    for feat in reader.iter_features_with_fields(&mut GeoJsonWriter::new(&mut json), &["EAS_ID"])? {
+        let feat = feat?;
+        assert!(feat.property::<f64>("EAS_ID").unwrap() > 100.0);
+        assert!(feat.property::<f64>("AREA").is_err());
+        assert_eq!(feat.properties()?.len(), 1);
+        cnt += 1;
+    }
+    assert_eq!(cnt, 10);
+    let json = from_utf8(&json).unwrap();
+    assert!(json.contains(r#""properties": {"EAS_ID": 168}"#));
+    assert!(!json.contains("PRFEDEA"));
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    assert!(matches!(
+        reader.iter_features_with_fields(&mut ProcessorSink::new(), &["EAS_ID", "NAME"]),
+        Err(geozero_shp::Error::UnknownField(name)) if name == "NAME"
+    ));
+    Ok(())
+}
+
+#[test]
+fn records_iter() -> Result<(), geozero_shp::Error> {
+    let records = geozero_shp::Reader::from_path("./tests/data/poly.shp")?.read_records()?;
+    let mut reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut iter = reader.records_iter()?;
+    assert_eq!(iter.next().transpose()?.as_ref(), records.first());
+    assert_eq!(iter.count(), 9);
+
+    let mut reader =
+        geozero_shp::Reader::new(BufReader::new(File::open("./tests/data/poly.shp")?))?;
+    assert!(matches!(
+        reader.records_iter().err(),
+        Some(geozero_shp::Error::MissingDbf)
+    ));
+    Ok(())
+}
+
+#[test]
+fn resume_iteration() -> Result<(), geozero_shp::Error> {
+    let records = geozero_shp::Reader::from_path("./tests/data/poly.shp")?.read_records()?;
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut processor = ProcessorSink::new();
+    let mut iter = reader.iter_features(&mut processor)?;
+    for _ in 0..4 {
+        iter.next().transpose()?;
+    }
+    let position = iter.position();
+    let next = iter.next().transpose()?.unwrap();
+    assert_eq!(next.file_offset, position);
+    drop(iter);
+
+    // Resume with index
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let resumed = reader
+        .iter_features_from(&mut processor, position)?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(resumed.len(), 6);
+    assert_eq!(resumed[0].record_number, 5);
+    assert_eq!(resumed[0].record, records[4]);
+
+    // Resume without index
+    let mut reader =
+        geozero_shp::Reader::new(BufReader::new(File::open("./tests/data/poly.shp")?))?;
+    reader.add_dbf_source(BufReader::new(File::open("./tests/data/poly.dbf")?))?;
+    let resumed = reader
+        .iter_features_from(&mut processor, position)?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(resumed.len(), 6);
+    assert_eq!(resumed[0].record, records[4]);
+
+    // Geometries
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut iter = reader.iter_geometries_from(&mut processor, position)?;
+    assert_eq!(iter.position(), position);
+    assert_eq!(iter.by_ref().count(), 6);
+    let end = iter.position();
+    drop(iter);
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    assert_eq!(reader.iter_geometries_from(&mut processor, end)?.count(), 0);
+
+    // Offsets within a record
+    for invalid in [position + 8, position + 12] {
+        for reader in [
+            geozero_shp::Reader::from_path("./tests/data/poly.shp")?,
+            geozero_shp::Reader::new(BufReader::new(File::open("./tests/data/poly.shp")?))?,
+        ] {
+            assert!(matches!(
+                reader.iter_geometries_from(&mut processor, invalid).err(),
+                Some(geozero_shp::Error::InvalidResumePosition(offset)) if offset == invalid
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn dbf_encoding() -> Result<(), geozero_shp::Error> {
+    use geozero_shp::DbaseEncoding;
+
+    // Code page detected from .dbf header
+    let reader = geozero_shp::Reader::from_path("./tests/data/cyrillic.shp")?;
+    let mut json: Vec<u8> = Vec::new();
+    for feat in reader.iter_features(&mut GeoJsonWriter::new(&mut json))? {
+        assert_eq!(feat?.property::<String>("NAME").unwrap(), "Москва");
+    }
+    assert!(from_utf8(&json)
+        .unwrap()
+        .contains(r#""properties": {"NAME": "Москва"}"#));
+
+    // Explicit encoding
+    let mut reader =
+        geozero_shp::Reader::new(BufReader::new(File::open("./tests/data/cyrillic.shp")?))?;
+    reader.add_dbf_source_with_encoding(
+        BufReader::new(File::open("./tests/data/cyrillic.dbf")?),
+        DbaseEncoding::Windows1251,
+    )?;
+    let records = reader.read_records()?;
+    assert_eq!(
+        records[0].get("NAME"),
+        Some(&FieldValue::Character(Some("Москва".to_string())))
+    );
+    Ok(())
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_process() -> Result<(), geozero_shp::Error> {
+    use geozero::{ColumnValue, FeatureProcessor, GeomProcessor, PropertyProcessor};
+
+    #[derive(Clone, Default)]
+    struct FeatureCollector {
+        featnos: Vec<u64>,
+        eas_ids: Vec<f64>,
+        geometries: usize,
+    }
+    impl FeatureProcessor for FeatureCollector {
+        fn feature_begin(&mut self, idx: u64) -> geozero::error::Result<()> {
+            self.featnos.push(idx);
+            Ok(())
+        }
+        fn geometry_end(&mut self) -> geozero::error::Result<()> {
+            self.geometries += 1;
+            Ok(())
+        }
+    }
+    impl GeomProcessor for FeatureCollector {}
+    impl PropertyProcessor for FeatureCollector {
+        fn property(
+            &mut self,
+            _idx: usize,
+            name: &str,
+            value: &ColumnValue,
+        ) -> geozero::error::Result<bool> {
+            if let (ColumnValue::Double(v), "EAS_ID") = (value, name) {
+                self.eas_ids.push(*v);
+            }
+            Ok(false)
+        }
+    }
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut sequential = FeatureCollector::default();
+    for feat in reader.iter_features(&mut sequential)? {
+        feat?;
+    }
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let processors = reader.par_process(FeatureCollector::default())?;
+    let featnos: Vec<u64> = processors.iter().flat_map(|p| p.featnos.clone()).collect();
+    let eas_ids: Vec<f64> = processors.iter().flat_map(|p| p.eas_ids.clone()).collect();
+    let geometries: usize = processors.iter().map(|p| p.geometries).sum();
+    assert_eq!(featnos, (0..10).collect::<Vec<u64>>());
+    assert_eq!(eas_ids, sequential.eas_ids);
+    assert_eq!(geometries, 10);
+
+    let reader = geozero_shp::Reader::new(BufReader::new(File::open("./tests/data/poly.shp")?))?;
+    assert!(matches!(
+        reader.par_process(FeatureCollector::default()),
+        Err(geozero_shp::Error::MissingPath)
+    ));
+    Ok(())
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_process_skip_geometry() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let processors = reader.par_process(SkipAll::default())?;
+    assert_eq!(processors.iter().map(|p| p.geometries).sum::<usize>(), 0);
+    assert_eq!(processors.iter().map(|p| p.coords).sum::<usize>(), 0);
+    Ok(())
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_process_missing_dbf_record() -> Result<(), geozero_shp::Error> {
+    // 10 shapes, but only 3 dbf records
+    let dir = std::env::temp_dir().join("geozero_shp_missing_dbf_record");
+    std::fs::create_dir_all(&dir)?;
+    std::fs::copy("./tests/data/poly.shp", dir.join("poly.shp"))?;
+    std::fs::copy("./tests/data/poly.shx", dir.join("poly.shx"))?;
+    std::fs::copy("./tests/data/null_shapes.dbf", dir.join("poly.dbf"))?;
+    let reader = geozero_shp::Reader::from_path(dir.join("poly.shp"))?;
+    assert!(reader.par_process(SkipAll::default()).is_err());
+    Ok(())
+}
+
+#[test]
+fn datasource() -> Result<(), geozero_shp::Error> {
+    use geozero::ProcessToJson;
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut expected: Vec<u8> = Vec::new();
+    let cnt = reader
+        .iter_features(&mut GeoJsonWriter::new(&mut expected))?
+        .count();
+    assert_eq!(cnt, 10);
+
+    let mut reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    let mut json: Vec<u8> = Vec::new();
+    reader.process(&mut GeoJsonWriter::new(&mut json))?;
+    assert_eq!(from_utf8(&json).unwrap(), from_utf8(&expected).unwrap());
+    // Processing again starts from the beginning
+    assert_eq!(reader.to_json()?, from_utf8(&expected).unwrap());
+
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader.process_geom(&mut WktWriter::new(&mut wkt_data))?;
+    assert_eq!(
+        from_utf8(&wkt_data)
+            .unwrap()
+            .matches("MULTIPOLYGON")
+            .count(),
+        10
+    );
+
+    // Without .dbf
+    let mut reader = geozero_shp::Reader::from_path("./tests/data/point.shp")?;
+    assert_eq!(
+        reader.to_json()?,
+        r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "geometry": {"type": "Point", "coordinates": [122,37]}}]}"#
+    );
+    Ok(())
+}
+
+#[test]
+fn multipatch() -> Result<(), geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_path("./tests/data/multipatch.shp")?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    let mut writer = WktWriter::with_dims(&mut wkt_data, CoordDimensions::xyz());
+    reader.iter_geometries(&mut writer).next().unwrap()?;
+    let wkt = from_utf8(&wkt_data).unwrap();
+    // triangle strip with 10 points and triangle fan with 6 points
+    assert_eq!(wkt.matches("((").count(), 8 + 4);
+    assert!(
+        wkt.starts_with("MULTIPOLYGON Z (((0 0 0,0 0 3,5 0 0,0 0 0)),((0 0 3,5 0 0,5 0 3,0 0 3)),")
+    );
+    assert!(wkt.ends_with(
+        "((2.5 2.5 5,0 0 3,5 0 3,2.5 2.5 5)),((2.5 2.5 5,5 0 3,5 5 3,2.5 2.5 5)),((2.5 2.5 5,5 5 3,0 5 3,2.5 2.5 5)),((2.5 2.5 5,0 5 3,0 0 3,2.5 2.5 5)))"
+    ));
+
+    let reader = geozero_shp::Reader::from_path("./tests/data/multipatch.shp")?;
+    let mut cnt = 0;
+    for feat in reader.iter_features(&mut ProcessorSink::new())? {
+        feat?;
+        cnt += 1;
+    }
+    assert_eq!(cnt, 1);
+    Ok(())
+}
+
+#[test]
+fn from_parts() -> Result<(), geozero_shp::Error> {
+    let mut reader = geozero_shp::Reader::from_parts(
+        std::fs::read("./tests/data/poly.shp")?,
+        Some(std::fs::read("./tests/data/poly.shx")?),
+        Some(std::fs::read("./tests/data/poly.dbf")?),
+        Some(std::fs::read_to_string("./tests/data/poly.PRJ")?),
+    )?;
+    assert_eq!(reader.feature_count(), Some(10));
+    assert!(reader
+        .crs()
+        .unwrap()
+        .starts_with(r#"PROJCS["OSGB 1936 / British National Grid""#));
+
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader.read_nth_shape(9, &mut WktWriter::new(&mut wkt_data))?;
+    assert!(from_utf8(&wkt_data).unwrap().starts_with("MULTIPOLYGON((("));
+
+    let mut json: Vec<u8> = Vec::new();
+    let cnt = reader
+        .iter_features(&mut GeoJsonWriter::new(&mut json))?
+        .count();
+    assert_eq!(cnt, 10);
+
+    let mut expected: Vec<u8> = Vec::new();
+    let mut reader = geozero_shp::Reader::from_path("./tests/data/poly.shp")?;
+    reader.add_prj_source(BufReader::new(File::open("./tests/data/poly.PRJ")?))?;
+    let cnt = reader
+        .iter_features(&mut GeoJsonWriter::new(&mut expected))?
+        .count();
+    assert_eq!(cnt, 10);
+    assert_eq!(from_utf8(&json).unwrap(), from_utf8(&expected).unwrap());
+
+    // Without optional parts
+    let reader =
+        geozero_shp::Reader::from_parts(std::fs::read("./tests/data/poly.shp")?, None, None, None)?;
+    assert_eq!(reader.feature_count(), None);
+    assert!(reader.crs().is_none());
+    assert_eq!(
+        reader.iter_geometries(&mut ProcessorSink::new()).count(),
+        10
+    );
+    Ok(())
+}
+
+#[cfg(feature = "zip")]
+#[test]
+fn from_zip() -> Result<(), geozero_shp::Error> {
+    // Archive with data/poly.{shp,shx,dbf,prj} and data/point.{shp,shx}
+    let archive = || File::open("./tests/data/shapefiles.zip");
+
+    assert!(matches!(
+        geozero_shp::Reader::from_zip(archive()?),
+        Err(geozero_shp::Error::AmbiguousShapefile(names)) if names == ["data/point", "data/poly"]
+    ));
+    assert!(matches!(
+        geozero_shp::Reader::from_zip_with_name(archive()?, "missing"),
+        Err(geozero_shp::Error::MissingShapefile)
+    ));
+
+    let reader = geozero_shp::Reader::from_zip_with_name(archive()?, "poly")?;
+    assert_eq!(reader.feature_count(), Some(10));
+    assert!(reader
+        .crs()
+        .unwrap()
+        .starts_with(r#"PROJCS["OSGB 1936 / British National Grid""#));
+    let cnt = reader
+        .iter_features(&mut ProcessorSink::new())?
+        .filter(|feat| {
+            feat.as_ref()
+                .is_ok_and(|feat| feat.property::<f64>("EAS_ID").unwrap() > 100.0)
+        })
+        .count();
+    assert_eq!(cnt, 10);
+
+    let reader = geozero_shp::Reader::from_zip_with_name(archive()?, "data/point")?;
+    assert_eq!(reader.feature_count(), Some(1));
+    assert!(reader.iter_features(&mut ProcessorSink::new()).is_err());
+    Ok(())
+}
+
+/// Collects the sizes of the begin callbacks of multipart shapes
+#[derive(Default)]
+struct PartCollector {
+    /// Number of LineStrings of each MultiLineString
+    multilinestrings: Vec<usize>,
+    /// Number of rings of each Polygon
+    polygons: Vec<usize>,
+    /// Number of points of each LineString or ring
+    linestrings: Vec<usize>,
+}
+
+impl GeomProcessor for PartCollector {
+    fn multilinestring_begin(&mut self, size: usize, _idx: usize) -> geozero::error::Result<()> {
+        self.multilinestrings.push(size);
+        Ok(())
+    }
+    fn polygon_begin(
+        &mut self,
+        _tagged: bool,
+        size: usize,
+        _idx: usize,
+    ) -> geozero::error::Result<()> {
+        self.polygons.push(size);
+        Ok(())
+    }
+    fn linestring_begin(
+        &mut self,
+        _tagged: bool,
+        size: usize,
+        _idx: usize,
+    ) -> geozero::error::Result<()> {
+        self.linestrings.push(size);
+        Ok(())
+    }
+}
+impl PropertyProcessor for PartCollector {}
+impl FeatureProcessor for PartCollector {}
+
+fn write_shp(wkt: &str) -> Result<Vec<u8>, geozero_shp::Error> {
+    let mut shp_data: Vec<u8> = Vec::new();
+    let mut writer = geozero_shp::ShapeWriter::new(std::io::Cursor::new(&mut shp_data));
+    WktStr(wkt).process_geom(&mut writer)?;
+    writer.finish()?;
+    drop(writer);
+    Ok(shp_data)
+}
+
+fn collect_parts(shp_data: Vec<u8>) -> Result<PartCollector, geozero_shp::Error> {
+    let reader = geozero_shp::Reader::from_parts(shp_data, None, None, None)?;
+    let mut parts = PartCollector::default();
+    for geom in reader.iter_geometries(&mut parts) {
+        geom?;
+    }
+    Ok(parts)
+}
+
+#[test]
+fn multipart_boundaries() -> Result<(), geozero_shp::Error> {
+    let shp_data = write_shp("MULTILINESTRING((0 0,1 1),(2 2,3 3,4 4),(5 5,6 6,7 7,8 8))")?;
+    let parts = collect_parts(shp_data)?;
+    assert_eq!(parts.multilinestrings, vec![3]);
+    assert_eq!(parts.linestrings, vec![2, 3, 4]);
+    assert!(parts.polygons.is_empty());
+
+    let shp_data = write_shp("POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,2 4,4 4,2 2))")?;
+    let parts = collect_parts(shp_data)?;
+    assert_eq!(parts.polygons, vec![2]);
+    assert_eq!(parts.linestrings, vec![5, 4]);
+    Ok(())
+}
+
+#[test]
+fn invalid_parts_index() -> Result<(), geozero_shp::Error> {
+    let shp_data = write_shp("MULTILINESTRING((0 0,1 1),(2 2,3 3,4 4),(5 5,6 6,7 7,8 8))")?;
+    // File header, record header, shape type, bbox, number of parts and points
+    let parts_offset = 100 + 8 + 4 + 32 + 4 + 4;
+    for (part, start) in [(0, 1), (1, 10), (2, 1), (1, -1)] {
+        let mut shp_data = shp_data.clone();
+        let pos = parts_offset + 4 * part;
+        shp_data[pos..pos + 4].copy_from_slice(&i32::to_le_bytes(start));
+        let err = collect_parts(shp_data).err().unwrap();
+        let source = std::error::Error::source(&err).unwrap().to_string();
+        assert_eq!(source, format!("Invalid part start index `{start}`"));
+    }
     Ok(())
 }
@@ -0,0 +1,112 @@
+use geozero::wkt::{WktStr, WktWriter};
+use geozero::{CoordDimensions, FeatureProperties, GeozeroGeometry, ProcessorSink};
+use geozero_shp::{Reader, ShapeType, ShapeWriter};
+use std::io::Cursor;
+use std::str::from_utf8;
+
+fn read_wkt(shp: &[u8], dims: CoordDimensions) -> Result<String, geozero_shp::Error> {
+    let reader = Reader::new(Cursor::new(shp))?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    let mut writer = WktWriter::with_dims(&mut wkt_data, dims);
+    for geom in reader.iter_geometries(&mut writer) {
+        geom?;
+    }
+    Ok(from_utf8(&wkt_data).unwrap().to_string())
+}
+
+#[test]
+fn write_features() -> Result<(), geozero_shp::Error> {
+    let mut shp_data: Vec<u8> = Vec::new();
+    let mut shx_data: Vec<u8> = Vec::new();
+    let mut dbf_data: Vec<u8> = Vec::new();
+    {
+        let mut writer = ShapeWriter::new(Cursor::new(&mut shp_data));
+        writer.add_index_dest(Cursor::new(&mut shx_data));
+        writer.add_dbf_dest(Cursor::new(&mut dbf_data));
+        let reader = Reader::from_path("./tests/data/poly.shp")?;
+        for feat in reader.iter_features(&mut writer)? {
+            feat?;
+        }
+        assert_eq!(writer.shape_type(), ShapeType::Polygon);
+    }
+
+    let reader = Reader::from_path("./tests/data/poly.shp")?;
+    let orig_header = *reader.header();
+    let mut wkt_data: Vec<u8> = Vec::new();
+    for geom in reader.iter_geometries(&mut WktWriter::new(&mut wkt_data)) {
+        geom?;
+    }
+    assert_eq!(
+        read_wkt(&shp_data, CoordDimensions::xy())?,
+        from_utf8(&wkt_data).unwrap()
+    );
+
+    let mut reader = Reader::new(Cursor::new(shp_data.as_slice()))?;
+    assert!(*reader.header() == orig_header);
+    reader.add_index_source(Cursor::new(shx_data.as_slice()))?;
+    reader.add_dbf_source(Cursor::new(dbf_data.as_slice()))?;
+    let mut wkt_data: Vec<u8> = Vec::new();
+    reader.read_nth_shape(9, &mut WktWriter::new(&mut wkt_data))?;
+    assert!(from_utf8(&wkt_data)
+        .unwrap()
+        .ends_with("479735.90625 4764752,479750.6875 4764702)))"));
+
+    let mut cnt = 0;
+    for feat in reader.iter_features(&mut ProcessorSink::new())? {
+        let feat = feat?;
+        assert!(feat.property::<f64>("EAS_ID").unwrap() > 100.0);
+        assert!(feat.property::<String>("PRFEDEA").unwrap().starts_with("350"));
+        cnt += 1;
+    }
+    assert_eq!(cnt, 10);
+    Ok(())
+}
+
+#[test]
+fn write_geometries() -> Result<(), geozero_shp::Error> {
+    let mut shp_data: Vec<u8> = Vec::new();
+    {
+        let mut writer =
+            ShapeWriter::with_dims(Cursor::new(&mut shp_data), CoordDimensions::xyz());
+        WktStr("LINESTRING(1 5 18,5 5 20,5 1 22)").process_geom(&mut writer)?;
+        WktStr("MULTILINESTRING((3 2 15,2 6 13),(1 9 14,1 10 1))").process_geom(&mut writer)?;
+        writer.finish()?;
+    }
+    let reader = Reader::new(Cursor::new(shp_data.as_slice()))?;
+    assert_eq!(reader.header().shape_type, ShapeType::PolylineZ);
+    assert_eq!(reader.header().bbox.x_range(), [1.0, 5.0]);
+    assert_eq!(reader.header().bbox.z_range(), [1.0, 22.0]);
+    assert_eq!(
+        read_wkt(&shp_data, CoordDimensions::xyz())?,
+        "MULTILINESTRING Z ((1 5 18,5 5 20,5 1 22))MULTILINESTRING Z ((3 2 15,2 6 13),(1 9 14,1 10 1))"
+    );
+
+    // Counterclockwise exterior ring is reversed
+    let mut shp_data: Vec<u8> = Vec::new();
+    {
+        let mut writer = ShapeWriter::new(Cursor::new(&mut shp_data));
+        WktStr("POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,2 4,4 4,4 2,2 2))")
+            .process_geom(&mut writer)?;
+        writer.finish()?;
+    }
+    assert_eq!(
+        read_wkt(&shp_data, CoordDimensions::xy())?,
+        "MULTIPOLYGON(((0 0,0 10,10 10,10 0,0 0),(2 2,4 2,4 4,2 4,2 2)))"
+    );
+    Ok(())
+}
+
+#[test]
+fn mixed_shape_types() -> Result<(), geozero_shp::Error> {
+    let mut shp_data: Vec<u8> = Vec::new();
+    let mut writer = ShapeWriter::new(Cursor::new(&mut shp_data));
+    WktStr("POINT(1 2)").process_geom(&mut writer)?;
+    let err = WktStr("LINESTRING(1 2,3 4)")
+        .process_geom(&mut writer)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "processing geometry `Shapes of type 'Polyline' can not be written to a file with shape type 'Point'`"
+    );
+    Ok(())
+}
@@ -0,0 +1,38 @@
+//! Convert a GeoJSON request body to WKB without blocking the runtime while reading.
+//!
+//! ```sh
+//! cargo run -p geozero --example axum_geojson --features with-tokio,with-wkb
+//! curl -d '{"type": "Point", "coordinates": [10, -20]}' http://127.0.0.1:3000/wkb
+//! ```
+use axum::body::{Body, Bytes};
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use futures_util::TryStreamExt;
+use geozero::geojson::GeoJsonAsyncReader;
+use geozero::wkb::{WkbDialect, WkbWriter};
+use tokio_util::io::StreamReader;
+
+const MAX_BODY_SIZE: u64 = 10 * 1024 * 1024;
+
+async fn to_wkb(body: Body) -> Result<Bytes, (StatusCode, String)> {
+    let stream = body.into_data_stream().map_err(std::io::Error::other);
+    let mut wkb: Vec<u8> = Vec::new();
+    let mut writer = WkbWriter::new(&mut wkb, WkbDialect::Wkb);
+    // Request bodies are buffered, so their size is limited
+    GeoJsonAsyncReader::new(StreamReader::new(stream))
+        .with_limit(MAX_BODY_SIZE)
+        .process(&mut writer)
+        .await
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    Ok(wkb.into())
+}
+
+#[tokio::main]
+async fn main() {
+    let app = Router::new().route("/wkb", post(to_wkb));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
+        .await
+        .unwrap();
+    axum::serve(listener, app).await.unwrap();
+}
@@ -0,0 +1,362 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+
+/// 2D affine transformation, with the parameters of PostGIS `ST_Affine`:
+///
+/// ```text
+/// x' = a * x + b * y + xoff
+/// y' = d * x + e * y + yoff
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AffineTransform {
+    pub a: f64,
+    pub b: f64,
+    pub d: f64,
+    pub e: f64,
+    pub xoff: f64,
+    pub yoff: f64,
+}
+
+impl AffineTransform {
+    /// Transformation from the matrix `[a, b, d, e, xoff, yoff]`.
+    pub fn new([a, b, d, e, xoff, yoff]: [f64; 6]) -> Self {
+        AffineTransform {
+            a,
+            b,
+            d,
+            e,
+            xoff,
+            yoff,
+        }
+    }
+    pub fn identity() -> Self {
+        Self::new([1.0, 0.0, 0.0, 1.0, 0.0, 0.0])
+    }
+    /// Translation by `dx`/`dy` (`ST_Translate`).
+    pub fn translate(dx: f64, dy: f64) -> Self {
+        Self::new([1.0, 0.0, 0.0, 1.0, dx, dy])
+    }
+    /// Scaling by `sx`/`sy` relative to the origin (`ST_Scale`).
+    pub fn scale(sx: f64, sy: f64) -> Self {
+        Self::new([sx, 0.0, 0.0, sy, 0.0, 0.0])
+    }
+    /// Counter-clockwise rotation by `radians` around `origin` (`ST_Rotate`).
+    pub fn rotate(radians: f64, origin: (f64, f64)) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        let (x0, y0) = origin;
+        Self::new([
+            cos,
+            -sin,
+            sin,
+            cos,
+            x0 - cos * x0 + sin * y0,
+            y0 - sin * x0 - cos * y0,
+        ])
+    }
+    /// Transformation applying `self` first, then `next`.
+    ///
+    /// `ST_Translate(ST_Scale(geom, sx, sy), dx, dy)` corresponds to
+    /// `AffineTransform::scale(sx, sy).then(AffineTransform::translate(dx, dy))`.
+    pub fn then(self, next: AffineTransform) -> Self {
+        AffineTransform {
+            a: next.a * self.a + next.b * self.d,
+            b: next.a * self.b + next.b * self.e,
+            d: next.d * self.a + next.e * self.d,
+            e: next.d * self.b + next.e * self.e,
+            xoff: next.a * self.xoff + next.b * self.yoff + next.xoff,
+            yoff: next.d * self.xoff + next.e * self.yoff + next.yoff,
+        }
+    }
+    /// Transform a coordinate.
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.a * x + self.b * y + self.xoff,
+            self.d * x + self.e * y + self.yoff,
+        )
+    }
+}
+
+impl Default for AffineTransform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Processor wrapper applying an [`AffineTransform`] to x/y coordinates before passing
+/// them to the inner processor.
+///
+/// Z and M values are passed unchanged.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, wkt::WktWriter, Affine, GeozeroGeometry};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = Affine::translate(WktWriter::new(&mut out), 10.0, -5.0);
+/// WktStr("LINESTRING(0 0,1 2)").process_geom(&mut processor).unwrap();
+/// drop(processor);
+/// assert_eq!(String::from_utf8(out).unwrap(), "LINESTRING(10 -5,11 -3)");
+/// # }
+/// ```
+pub struct Affine<P: GeomProcessor> {
+    inner: P,
+    transform: AffineTransform,
+}
+
+impl<P: GeomProcessor> Affine<P> {
+    pub fn new(inner: P, transform: AffineTransform) -> Self {
+        Affine { inner, transform }
+    }
+    /// Translate by `dx`/`dy`.
+    pub fn translate(inner: P, dx: f64, dy: f64) -> Self {
+        Self::new(inner, AffineTransform::translate(dx, dy))
+    }
+    /// Scale by `sx`/`sy` relative to the origin.
+    pub fn scale(inner: P, sx: f64, sy: f64) -> Self {
+        Self::new(inner, AffineTransform::scale(sx, sy))
+    }
+    /// Rotate counter-clockwise by `radians` around `origin`.
+    pub fn rotate(inner: P, radians: f64, origin: (f64, f64)) -> Self {
+        Self::new(inner, AffineTransform::rotate(radians, origin))
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for Affine<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.inner.input_dimensions(dimensions)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        let (x, y) = self.transform.apply(x, y);
+        self.inner.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        let (x, y) = self.transform.apply(x, y);
+        self.inner.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.inner.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.tin_end(idx)
+    }
+}
+
+impl<P: GeomProcessor + PropertyProcessor> PropertyProcessor for Affine<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.inner.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for Affine<P> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.inner.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+    fn skip_geometry(&self) -> bool {
+        self.inner.skip_geometry()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_close((x, y): (f64, f64), (ex, ey): (f64, f64)) {
+        assert!(
+            (x - ex).abs() < 1e-12 && (y - ey).abs() < 1e-12,
+            "({x} {y}) != ({ex} {ey})"
+        );
+    }
+
+    #[test]
+    fn transforms() {
+        let t = AffineTransform::new([1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(t.apply(1.0, 1.0), (8.0, 13.0));
+        assert_eq!(AffineTransform::identity().apply(3.0, 4.0), (3.0, 4.0));
+        assert_eq!(
+            AffineTransform::translate(1.0, -1.0).apply(3.0, 4.0),
+            (4.0, 3.0)
+        );
+        assert_eq!(
+            AffineTransform::scale(2.0, -1.0).apply(3.0, 4.0),
+            (6.0, -4.0)
+        );
+        let rotate = AffineTransform::rotate(std::f64::consts::FRAC_PI_2, (0.0, 0.0));
+        assert_close(rotate.apply(1.0, 0.0), (0.0, 1.0));
+        let rotate = AffineTransform::rotate(std::f64::consts::PI, (1.0, 1.0));
+        assert_close(rotate.apply(2.0, 1.0), (0.0, 1.0));
+    }
+
+    #[test]
+    fn composition() {
+        // ST_Translate(ST_Scale('POINT(1 2)', 2, 3), 10, 20) = POINT(12 26)
+        let t = AffineTransform::scale(2.0, 3.0).then(AffineTransform::translate(10.0, 20.0));
+        assert_eq!(t, AffineTransform::new([2.0, 0.0, 0.0, 3.0, 10.0, 20.0]));
+        assert_eq!(t.apply(1.0, 2.0), (12.0, 26.0));
+        // ST_Scale(ST_Translate('POINT(1 2)', 10, 20), 2, 3) = POINT(22 66)
+        let t = AffineTransform::translate(10.0, 20.0).then(AffineTransform::scale(2.0, 3.0));
+        assert_eq!(t.apply(1.0, 2.0), (22.0, 66.0));
+        // ST_Translate(ST_Rotate('POINT(1 0)', pi()/2), 1, 0) = POINT(1 1)
+        let t = AffineTransform::rotate(std::f64::consts::FRAC_PI_2, (0.0, 0.0))
+            .then(AffineTransform::translate(1.0, 0.0));
+        assert_close(t.apply(1.0, 0.0), (1.0, 1.0));
+    }
+
+    #[test]
+    #[cfg(feature = "with-wkt")]
+    fn processor() {
+        use crate::test_util::process_wkt;
+        use crate::wkt::WktWriter;
+
+        assert_eq!(
+            process_wkt("POLYGON ZM ((0 0 1 2,1 0 3 4,1 1 5 6,0 0 1 2))", |out| {
+                Affine::scale(
+                    WktWriter::with_dims(out, CoordDimensions::xyzm()),
+                    2.0,
+                    -1.0,
+                )
+            }),
+            "POLYGON ZM ((0 0 1 2,2 0 3 4,2 -1 5 6,0 0 1 2))"
+        );
+    }
+}
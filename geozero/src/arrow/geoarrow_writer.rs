@@ -0,0 +1,771 @@
+use crate::error::{GeozeroError, Result};
+use crate::wkb::{process_wkb_geom, WkbDialect, WkbWriter};
+use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use arrow2::array::{
+    Array, BinaryArray, BooleanArray, FixedSizeListArray, Float64Array, Int64Array, ListArray,
+    Utf8Array,
+};
+use arrow2::bitmap::Bitmap;
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Metadata, Schema};
+use arrow2::offset::Offsets;
+
+/// Encoding of the GeoArrow geometry column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GeoArrowEncoding {
+    /// WKB encoded binary column (`geoarrow.wkb`)
+    ///
+    /// Z and M values are written for geometries with input dimensions announced by the
+    /// reader, like WKB or Shapefile geometries. Other geometries are written as XY.
+    #[default]
+    Wkb,
+    /// GeoArrow native nested list encoding (`geoarrow.point`, `geoarrow.linestring`, ...)
+    ///
+    /// Only XY geometries of a single geometry type are supported.
+    Native,
+}
+
+/// GeoArrow writer.
+///
+/// Processed features are collected in memory and converted into an Arrow record batch
+/// with [`GeoArrowWriter::record_batch`]. The property columns are inferred from the first
+/// features. Properties with other names or types in later features are rejected, unless
+/// they are coerced to strings with [`GeoArrowWriter::with_coerce_to_string`].
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::arrow::GeoArrowWriter;
+/// use geozero::GeozeroDatasource;
+///
+/// # fn geojson_to_arrow() -> geozero::error::Result<()> {
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// let mut writer = GeoArrowWriter::new();
+/// let mut geojson = geozero::geojson::GeoJsonReader(std::fs::File::open("countries.geojson")?);
+/// geojson.process(&mut writer)?;
+/// let (schema, chunk) = writer.record_batch()?;
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub struct GeoArrowWriter {
+    geom_column: String,
+    encoding: GeoArrowEncoding,
+    /// Number of features used for schema inference
+    inference_rows: usize,
+    coerce_to_string: bool,
+    rows: Vec<FeatureRow>,
+    // Processing state
+    row: Option<FeatureRow>,
+    /// Input dimensions of the current geometry
+    input_dims: CoordDimensions,
+    wkb: Option<WkbWriter<Vec<u8>>>,
+}
+
+struct FeatureRow {
+    geometry: Option<Vec<u8>>,
+    values: Vec<(String, Value)>,
+}
+
+enum Value {
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+    String(String),
+    Binary(Vec<u8>),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Bool,
+    Int,
+    Double,
+    String,
+    Binary,
+}
+
+impl Value {
+    fn column_type(&self) -> ColumnType {
+        match self {
+            Value::Bool(_) => ColumnType::Bool,
+            Value::Int(_) => ColumnType::Int,
+            Value::Double(_) => ColumnType::Double,
+            Value::String(_) => ColumnType::String,
+            Value::Binary(_) => ColumnType::Binary,
+        }
+    }
+    fn to_string_value(&self) -> String {
+        match self {
+            Value::Bool(v) => v.to_string(),
+            Value::Int(v) => v.to_string(),
+            Value::Double(v) => v.to_string(),
+            Value::String(v) => v.clone(),
+            Value::Binary(v) => String::from_utf8_lossy(v).to_string(),
+        }
+    }
+}
+
+impl ColumnType {
+    /// Common type of a column with values of type `self` and `other`
+    fn merge(self, other: ColumnType) -> Option<ColumnType> {
+        match (self, other) {
+            (a, b) if a == b => Some(a),
+            (ColumnType::Int, ColumnType::Double) | (ColumnType::Double, ColumnType::Int) => {
+                Some(ColumnType::Double)
+            }
+            _ => None,
+        }
+    }
+    fn data_type(self) -> DataType {
+        match self {
+            ColumnType::Bool => DataType::Boolean,
+            ColumnType::Int => DataType::Int64,
+            ColumnType::Double => DataType::Float64,
+            ColumnType::String => DataType::Utf8,
+            ColumnType::Binary => DataType::Binary,
+        }
+    }
+}
+
+impl Default for GeoArrowWriter {
+    fn default() -> Self {
+        GeoArrowWriter {
+            geom_column: "geometry".to_string(),
+            encoding: GeoArrowEncoding::Wkb,
+            inference_rows: 100,
+            coerce_to_string: false,
+            rows: Vec::new(),
+            row: None,
+            input_dims: CoordDimensions::xy(),
+            wkb: None,
+        }
+    }
+}
+
+impl GeoArrowWriter {
+    /// Writer with WKB encoded geometry column `geometry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Set name of the geometry column.
+    pub fn with_geometry_column(mut self, name: &str) -> Self {
+        self.geom_column = name.to_string();
+        self
+    }
+    /// Set encoding of the geometry column.
+    pub fn with_encoding(mut self, encoding: GeoArrowEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+    /// Set number of features used for inferring the property columns (default: 100).
+    pub fn with_schema_inference_rows(mut self, rows: usize) -> Self {
+        self.inference_rows = rows.max(1);
+        self
+    }
+    /// Convert properties not matching the inferred schema to strings instead of failing.
+    pub fn with_coerce_to_string(mut self, coerce: bool) -> Self {
+        self.coerce_to_string = coerce;
+        self
+    }
+
+    /// Arrow schema and columns of the processed features.
+    pub fn record_batch(&self) -> Result<(Schema, Chunk<Box<dyn Array>>)> {
+        let columns = self.infer_columns()?;
+        let (geom_field, geom_array) = match self.encoding {
+            GeoArrowEncoding::Wkb => self.wkb_column(),
+            GeoArrowEncoding::Native => self.native_column()?,
+        };
+        let mut fields = vec![geom_field];
+        let mut arrays = vec![geom_array];
+        for (name, column_type) in &columns {
+            let values = self
+                .rows
+                .iter()
+                .map(|row| row.values.iter().find(|(n, _)| n == name).map(|(_, v)| v));
+            let array: Box<dyn Array> = match column_type {
+                ColumnType::Bool => Box::new(BooleanArray::from(
+                    &values
+                        .map(|v| match v {
+                            Some(Value::Bool(v)) => Some(*v),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>(),
+                )),
+                ColumnType::Int => Box::new(Int64Array::from(
+                    &values
+                        .map(|v| match v {
+                            Some(Value::Int(v)) => Some(*v),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>(),
+                )),
+                ColumnType::Double => Box::new(Float64Array::from(
+                    &values
+                        .map(|v| match v {
+                            Some(Value::Double(v)) => Some(*v),
+                            Some(Value::Int(v)) => Some(*v as f64),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>(),
+                )),
+                ColumnType::String => Box::new(Utf8Array::<i32>::from(
+                    values
+                        .map(|v| v.map(Value::to_string_value))
+                        .collect::<Vec<_>>(),
+                )),
+                ColumnType::Binary => Box::new(BinaryArray::<i32>::from(
+                    values
+                        .map(|v| match v {
+                            Some(Value::Binary(v)) => Some(v.as_slice()),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>(),
+                )),
+            };
+            fields.push(Field::new(name, column_type.data_type(), true));
+            arrays.push(array);
+        }
+        let chunk = Chunk::try_new(arrays).map_err(arrow_error)?;
+        Ok((Schema::from(fields), chunk))
+    }
+
+    /// Infer property columns from the first rows and check the remaining rows.
+    fn infer_columns(&self) -> Result<Vec<(String, ColumnType)>> {
+        let mut columns: Vec<(String, ColumnType)> = Vec::new();
+        for (idx, row) in self.rows.iter().enumerate() {
+            let inferring = idx < self.inference_rows;
+            for (name, value) in &row.values {
+                let value_type = value.column_type();
+                match columns.iter_mut().find(|(n, _)| n == name) {
+                    Some((_, column_type)) => {
+                        let merged = if inferring {
+                            column_type.merge(value_type)
+                        } else if *column_type == ColumnType::Double
+                            && value_type == ColumnType::Int
+                        {
+                            Some(ColumnType::Double)
+                        } else {
+                            Some(value_type).filter(|t| t == column_type)
+                        };
+                        match merged {
+                            Some(t) => *column_type = t,
+                            None if self.coerce_to_string => *column_type = ColumnType::String,
+                            None => {
+                                return Err(GeozeroError::Property(format!(
+                                    "type of property `{name}` in feature {idx} doesn't match inferred schema"
+                                )))
+                            }
+                        }
+                    }
+                    None if inferring => columns.push((name.clone(), value_type)),
+                    None if self.coerce_to_string => {
+                        columns.push((name.clone(), ColumnType::String))
+                    }
+                    None => {
+                        return Err(GeozeroError::Property(format!(
+                            "property `{name}` in feature {idx} missing in inferred schema"
+                        )))
+                    }
+                }
+            }
+        }
+        Ok(columns)
+    }
+
+    fn geom_field(&self, data_type: DataType, extension: &str) -> Field {
+        let metadata =
+            Metadata::from([("ARROW:extension:name".to_string(), extension.to_string())]);
+        Field::new(&self.geom_column, data_type, true).with_metadata(metadata)
+    }
+
+    fn wkb_column(&self) -> (Field, Box<dyn Array>) {
+        let array = BinaryArray::<i32>::from(
+            self.rows
+                .iter()
+                .map(|row| row.geometry.as_deref())
+                .collect::<Vec<_>>(),
+        );
+        let field = self.geom_field(DataType::Binary, "geoarrow.wkb");
+        (field, Box::new(array))
+    }
+
+    fn native_column(&self) -> Result<(Field, Box<dyn Array>)> {
+        // Geometry type of the first geometry
+        let mut geom_type = None;
+        if let Some(wkb) = self.rows.iter().find_map(|row| row.geometry.as_ref()) {
+            let mut builder = NativeBuilder::new();
+            process_wkb_geom(&mut wkb.as_slice(), &mut builder)?;
+            geom_type = builder.geom_type;
+        }
+        let geom_type = geom_type.unwrap_or(NativeType::Point);
+        let mut builder = NativeBuilder::new();
+        builder.geom_type = Some(geom_type);
+        for row in &self.rows {
+            match &row.geometry {
+                Some(wkb) => {
+                    process_wkb_geom(&mut wkb.as_slice(), &mut builder)?;
+                    builder.validity.push(true);
+                }
+                None => builder.push_null(),
+            }
+        }
+        builder.finish(self)
+    }
+
+    /// WKB writer for the current geometry
+    fn wkb(&mut self) -> &mut WkbWriter<Vec<u8>> {
+        let dims = self.input_dims;
+        self.wkb.get_or_insert_with(|| {
+            WkbWriter::with_opts(Vec::new(), WkbDialect::Wkb, dims, None, Vec::new())
+        })
+    }
+}
+
+fn arrow_error(e: arrow2::error::Error) -> GeozeroError {
+    GeozeroError::Dataset(e.to_string())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum NativeType {
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+}
+
+impl NativeType {
+    fn extension_name(self) -> &'static str {
+        match self {
+            NativeType::Point => "geoarrow.point",
+            NativeType::LineString => "geoarrow.linestring",
+            NativeType::Polygon => "geoarrow.polygon",
+            NativeType::MultiPoint => "geoarrow.multipoint",
+            NativeType::MultiLineString => "geoarrow.multilinestring",
+            NativeType::MultiPolygon => "geoarrow.multipolygon",
+        }
+    }
+}
+
+/// Builder for GeoArrow native encoded geometries.
+///
+/// Each `*_end` event appends the current length of the child level to the offsets of its level.
+struct NativeBuilder {
+    geom_type: Option<NativeType>,
+    /// Interleaved XY coordinates
+    coords: Vec<f64>,
+    /// Coordinate offsets of rings and linestrings within multilinestrings
+    ring_offsets: Vec<i32>,
+    /// Ring offsets of polygons within multipolygons
+    polygon_offsets: Vec<i32>,
+    geom_offsets: Vec<i32>,
+    validity: Vec<bool>,
+}
+
+impl NativeBuilder {
+    fn new() -> Self {
+        NativeBuilder {
+            geom_type: None,
+            coords: Vec::new(),
+            ring_offsets: vec![0],
+            polygon_offsets: vec![0],
+            geom_offsets: vec![0],
+            validity: Vec::new(),
+        }
+    }
+    fn set_type(&mut self, geom_type: NativeType) -> Result<()> {
+        match self.geom_type {
+            Some(t) if t != geom_type => Err(GeozeroError::Geometry(format!(
+                "{geom_type:?} in {t:?} column not supported by GeoArrow native encoding"
+            ))),
+            _ => {
+                self.geom_type = Some(geom_type);
+                Ok(())
+            }
+        }
+    }
+    fn num_coords(&self) -> i32 {
+        (self.coords.len() / 2) as i32
+    }
+    fn push_null(&mut self) {
+        if self.geom_type == Some(NativeType::Point) {
+            self.coords.extend([f64::NAN, f64::NAN]);
+        } else {
+            self.geom_offsets.push(*self.geom_offsets.last().unwrap());
+        }
+        self.validity.push(false);
+    }
+    fn finish(self, writer: &GeoArrowWriter) -> Result<(Field, Box<dyn Array>)> {
+        let geom_type = self.geom_type.unwrap_or(NativeType::Point);
+        let validity = if self.validity.iter().all(|valid| *valid) {
+            None
+        } else {
+            Some(self.validity.iter().copied().collect::<Bitmap>())
+        };
+        let coord_type =
+            DataType::FixedSizeList(Box::new(Field::new("xy", DataType::Float64, false)), 2);
+        let coords = |validity: Option<Bitmap>| -> Result<Box<dyn Array>> {
+            let values = Float64Array::from_vec(self.coords.clone()).boxed();
+            Ok(
+                FixedSizeListArray::try_new(coord_type.clone(), values, validity)
+                    .map_err(arrow_error)?
+                    .boxed(),
+            )
+        };
+        let list = |name: &str,
+                    offsets: &[i32],
+                    values: Box<dyn Array>,
+                    validity: Option<Bitmap>|
+         -> Result<Box<dyn Array>> {
+            let data_type = DataType::List(Box::new(Field::new(
+                name,
+                values.data_type().clone(),
+                false,
+            )));
+            let offsets = Offsets::try_from(offsets.to_vec()).map_err(arrow_error)?;
+            Ok(
+                ListArray::<i32>::try_new(data_type, offsets.into(), values, validity)
+                    .map_err(arrow_error)?
+                    .boxed(),
+            )
+        };
+        let array = match geom_type {
+            NativeType::Point => coords(validity)?,
+            NativeType::LineString => {
+                list("vertices", &self.geom_offsets, coords(None)?, validity)?
+            }
+            NativeType::MultiPoint => list("points", &self.geom_offsets, coords(None)?, validity)?,
+            NativeType::Polygon => {
+                let rings = list("vertices", &self.ring_offsets, coords(None)?, None)?;
+                list("rings", &self.geom_offsets, rings, validity)?
+            }
+            NativeType::MultiLineString => {
+                let lines = list("vertices", &self.ring_offsets, coords(None)?, None)?;
+                list("linestrings", &self.geom_offsets, lines, validity)?
+            }
+            NativeType::MultiPolygon => {
+                let rings = list("vertices", &self.ring_offsets, coords(None)?, None)?;
+                let polygons = list("rings", &self.polygon_offsets, rings, None)?;
+                list("polygons", &self.geom_offsets, polygons, validity)?
+            }
+        };
+        let field = writer.geom_field(array.data_type().clone(), geom_type.extension_name());
+        Ok((field, array))
+    }
+}
+
+impl GeomProcessor for NativeBuilder {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.coords.extend([x, y]);
+        Ok(())
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.set_type(NativeType::Point)
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.set_type(NativeType::MultiPoint)?;
+        self.geom_offsets.push(self.num_coords());
+        Ok(())
+    }
+    fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        if tagged {
+            self.set_type(NativeType::LineString)?;
+            self.geom_offsets.push(self.num_coords());
+        } else {
+            self.ring_offsets.push(self.num_coords());
+        }
+        Ok(())
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.set_type(NativeType::MultiLineString)?;
+        self.geom_offsets.push(self.ring_offsets.len() as i32 - 1);
+        Ok(())
+    }
+    fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        let num_rings = self.ring_offsets.len() as i32 - 1;
+        if tagged {
+            self.set_type(NativeType::Polygon)?;
+            self.geom_offsets.push(num_rings);
+        } else {
+            self.polygon_offsets.push(num_rings);
+        }
+        Ok(())
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.set_type(NativeType::MultiPolygon)?;
+        self.geom_offsets
+            .push(self.polygon_offsets.len() as i32 - 1);
+        Ok(())
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Err(GeozeroError::Geometry(
+            "GeometryCollection not supported by GeoArrow native encoding".to_string(),
+        ))
+    }
+}
+
+impl GeomProcessor for GeoArrowWriter {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyzm()
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        // Dimensions of the WKB writer are set at the start of the geometry
+        if self.wkb.is_none() {
+            self.input_dims = CoordDimensions {
+                z: dimensions.z,
+                m: dimensions.m,
+                ..CoordDimensions::xy()
+            };
+        }
+        Ok(())
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.wkb().xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.wkb().coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.wkb().empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.wkb().point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.wkb().linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.wkb().linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.wkb().polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.wkb().polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().geometrycollection_end(idx)
+    }
+}
+
+impl PropertyProcessor for GeoArrowWriter {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let Some(row) = self.row.as_mut() else {
+            return Ok(false);
+        };
+        let value = match value {
+            ColumnValue::Bool(v) => Value::Bool(*v),
+            ColumnValue::Byte(v) => Value::Int(*v as i64),
+            ColumnValue::UByte(v) => Value::Int(*v as i64),
+            ColumnValue::Short(v) => Value::Int(*v as i64),
+            ColumnValue::UShort(v) => Value::Int(*v as i64),
+            ColumnValue::Int(v) => Value::Int(*v as i64),
+            ColumnValue::UInt(v) => Value::Int(*v as i64),
+            ColumnValue::Long(v) => Value::Int(*v),
+            ColumnValue::ULong(v) => Value::Int(*v as i64),
+            ColumnValue::Float(v) => Value::Double(*v as f64),
+            ColumnValue::Double(v) => Value::Double(*v),
+            ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) => {
+                Value::String(v.to_string())
+            }
+            ColumnValue::Binary(v) => Value::Binary(v.to_vec()),
+        };
+        row.values.push((name.to_string(), value));
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for GeoArrowWriter {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.row = Some(FeatureRow {
+            geometry: None,
+            values: Vec::new(),
+        });
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        if let Some(row) = self.row.take() {
+            self.rows.push(row);
+        }
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.input_dims = CoordDimensions::xy();
+        self.wkb = None;
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        if let Some(wkb) = self.wkb.take() {
+            if let Some(row) = self.row.as_mut() {
+                row.geometry = Some(wkb.into_inner());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::GeozeroDatasource;
+
+    #[test]
+    fn wkb_column() -> Result<()> {
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {"name": "a", "pop": 1}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+                {"type": "Feature", "properties": {"name": "b", "pop": 2.5}, "geometry": null}
+            ]}"#,
+        );
+        let mut writer = GeoArrowWriter::new();
+        geojson.process(&mut writer)?;
+        let (schema, chunk) = writer.record_batch()?;
+        let names: Vec<_> = schema.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["geometry", "name", "pop"]);
+        assert_eq!(
+            schema.fields[0].metadata.get("ARROW:extension:name"),
+            Some(&"geoarrow.wkb".to_string())
+        );
+        assert_eq!(schema.fields[2].data_type, DataType::Float64);
+        assert_eq!(chunk.len(), 2);
+
+        let geoms = chunk.arrays()[0]
+            .as_any()
+            .downcast_ref::<BinaryArray<i32>>()
+            .unwrap();
+        assert!(geoms.is_valid(0));
+        assert!(geoms.is_null(1));
+        let pop = chunk.arrays()[2]
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(pop.value(0), 1.0);
+        assert_eq!(pop.value(1), 2.5);
+        Ok(())
+    }
+
+    #[test]
+    fn wkb_input_dimensions() -> Result<()> {
+        // POINT Z (1 2 3)
+        let wkb =
+            hex::decode("01e9030000000000000000f03f00000000000000400000000000000840").unwrap();
+        let mut writer = GeoArrowWriter::new();
+        writer.dataset_begin(None)?;
+        writer.feature_begin(0)?;
+        writer.geometry_begin()?;
+        process_wkb_geom(&mut wkb.as_slice(), &mut writer)?;
+        writer.geometry_end()?;
+        writer.feature_end(0)?;
+        writer.dataset_end()?;
+        let (_schema, chunk) = writer.record_batch()?;
+        let geoms = chunk.arrays()[0]
+            .as_any()
+            .downcast_ref::<BinaryArray<i32>>()
+            .unwrap();
+        assert_eq!(geoms.value(0), wkb.as_slice());
+        Ok(())
+    }
+
+    #[test]
+    fn native_polygons() -> Result<()> {
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 0]]]}},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [4, 0], [4, 4], [0, 0]], [[1, 1], [2, 1], [2, 2], [1, 1]]]}}
+            ]}"#,
+        );
+        let mut writer = GeoArrowWriter::new().with_encoding(GeoArrowEncoding::Native);
+        geojson.process(&mut writer)?;
+        let (schema, chunk) = writer.record_batch()?;
+        assert_eq!(
+            schema.fields[0].metadata.get("ARROW:extension:name"),
+            Some(&"geoarrow.polygon".to_string())
+        );
+        let polygons = chunk.arrays()[0]
+            .as_any()
+            .downcast_ref::<ListArray<i32>>()
+            .unwrap();
+        assert_eq!(polygons.offsets().as_slice(), &[0, 1, 3]);
+        let rings = polygons
+            .values()
+            .as_any()
+            .downcast_ref::<ListArray<i32>>()
+            .unwrap();
+        assert_eq!(rings.offsets().as_slice(), &[0, 4, 8, 12]);
+
+        // Mixed geometry types
+        let mut geojson = GeoJson(
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1, 2], [3, 4]]}}
+            ]}"#,
+        );
+        let mut writer = GeoArrowWriter::new().with_encoding(GeoArrowEncoding::Native);
+        geojson.process(&mut writer)?;
+        assert!(writer.record_batch().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn schema_drift() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"value": 1}, "geometry": null},
+            {"type": "Feature", "properties": {"value": "x", "other": true}, "geometry": null}
+        ]}"#;
+        let mut writer = GeoArrowWriter::new().with_schema_inference_rows(1);
+        GeoJson(geojson).process(&mut writer)?;
+        assert!(writer.record_batch().is_err());
+
+        let mut writer = GeoArrowWriter::new()
+            .with_schema_inference_rows(1)
+            .with_coerce_to_string(true);
+        GeoJson(geojson).process(&mut writer)?;
+        let (schema, chunk) = writer.record_batch()?;
+        assert_eq!(schema.fields[1].data_type, DataType::Utf8);
+        assert_eq!(schema.fields[2].data_type, DataType::Utf8);
+        let values = chunk.arrays()[1]
+            .as_any()
+            .downcast_ref::<Utf8Array<i32>>()
+            .unwrap();
+        assert_eq!(values.value(0), "1");
+        assert_eq!(values.value(1), "x");
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 //! GeoArrow conversions.
 //!
 pub(crate) mod geoarrow_reader;
+pub(crate) mod geoarrow_writer;
 
 pub use geoarrow_reader::*;
+pub use geoarrow_writer::*;
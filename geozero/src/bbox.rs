@@ -0,0 +1,171 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::PropertyProcessor;
+use crate::GeozeroGeometry;
+
+/// Processor computing the bounding box of processed geometries.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, GeometryBbox, GeozeroGeometry};
+///
+/// let mut bbox = GeometryBbox::new();
+/// WktStr("LINESTRING(1 5,3 2)").process_geom(&mut bbox).unwrap();
+/// assert_eq!(bbox.bounds(), Some([1.0, 2.0, 3.0, 5.0]));
+/// # }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct GeometryBbox {
+    /// minx, miny, maxx, maxy
+    xy: Option<[f64; 4]>,
+    /// minz, maxz
+    z: Option<[f64; 2]>,
+}
+
+impl GeometryBbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Bounds as `[minx, miny, maxx, maxy]`, `None` if no coordinates were processed.
+    pub fn bounds(&self) -> Option<[f64; 4]> {
+        self.xy
+    }
+    /// Bounds as `[minx, miny, minz, maxx, maxy, maxz]`, `None` without Z values.
+    pub fn bounds3d(&self) -> Option<[f64; 6]> {
+        match (self.xy, self.z) {
+            (Some([minx, miny, maxx, maxy]), Some([minz, maxz])) => {
+                Some([minx, miny, minz, maxx, maxy, maxz])
+            }
+            _ => None,
+        }
+    }
+    /// Forget all processed coordinates.
+    pub fn reset(&mut self) {
+        self.xy = None;
+        self.z = None;
+    }
+    fn extend_xy(&mut self, x: f64, y: f64) {
+        self.xy = Some(match self.xy {
+            Some([minx, miny, maxx, maxy]) => [minx.min(x), miny.min(y), maxx.max(x), maxy.max(y)],
+            None => [x, y, x, y],
+        });
+    }
+    fn extend_z(&mut self, z: f64) {
+        self.z = Some(match self.z {
+            Some([minz, maxz]) => [minz.min(z), maxz.max(z)],
+            None => [z, z],
+        });
+    }
+}
+
+/// Bounding box `[minx, miny, maxx, maxy]` of a geometry.
+///
+/// Returns `None` for empty geometries or if the geometry can't be processed.
+pub fn geometry_bbox(geom: &impl GeozeroGeometry) -> Option<[f64; 4]> {
+    let mut bbox = GeometryBbox::new();
+    geom.process_geom(&mut bbox).ok()?;
+    bbox.bounds()
+}
+
+impl GeomProcessor for GeometryBbox {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyz()
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.extend_xy(x, y);
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.extend_xy(x, y);
+        if let Some(z) = z {
+            self.extend_z(z);
+        }
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for GeometryBbox {}
+
+impl FeatureProcessor for GeometryBbox {}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::WktStr;
+    use crate::GeozeroDatasource;
+
+    #[test]
+    fn bounds() {
+        assert_eq!(
+            geometry_bbox(&WktStr("POLYGON((0 0,4 -1,2 3,0 0))")),
+            Some([0.0, -1.0, 4.0, 3.0])
+        );
+        assert_eq!(
+            geometry_bbox(&WktStr("POINT(1 2)")),
+            Some([1.0, 2.0, 1.0, 2.0])
+        );
+        assert_eq!(
+            geometry_bbox(&WktStr("CIRCULARSTRING(0 0,1 1,2 0)")),
+            Some([0.0, 0.0, 2.0, 1.0])
+        );
+        assert_eq!(geometry_bbox(&WktStr("POINT EMPTY")), None);
+        assert_eq!(geometry_bbox(&WktStr("GEOMETRYCOLLECTION EMPTY")), None);
+    }
+
+    #[test]
+    fn bounds3d() {
+        let mut bbox = GeometryBbox::new();
+        WktStr("LINESTRING Z (1 2 3,-1 5 0)")
+            .process_geom(&mut bbox)
+            .unwrap();
+        assert_eq!(bbox.bounds(), Some([-1.0, 2.0, 1.0, 5.0]));
+        assert_eq!(bbox.bounds3d(), Some([-1.0, 2.0, 0.0, 1.0, 5.0, 3.0]));
+
+        bbox.reset();
+        WktStr("LINESTRING(1 2,-1 5)")
+            .process_geom(&mut bbox)
+            .unwrap();
+        assert_eq!(bbox.bounds3d(), None);
+    }
+
+    #[test]
+    fn dataset_bounds() {
+        let mut bbox = GeometryBbox::new();
+        crate::wkt::WktReader("MULTIPOINT(1 1,5 2)".as_bytes())
+            .process(&mut bbox)
+            .unwrap();
+        assert_eq!(bbox.bounds(), Some([1.0, 1.0, 5.0, 2.0]));
+    }
+}
@@ -0,0 +1,289 @@
+use crate::error::Result;
+use crate::geom_event_processor::geom_event_processor;
+use crate::geom_type_filter::GeomEvent;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::GeozeroGeometry;
+
+/// Processor wrapper replacing each geometry with its centroid point.
+///
+/// Polygonal geometries are reduced to their area-weighted centroid, with interior
+/// rings subtracted. Geometries without area (points, lines, zero-area polygons)
+/// are reduced to the mean of their vertices. Empty geometries are passed as empty
+/// point. Curves are reduced to the mean of their vertices.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, wkt::WktWriter, Centroid, GeozeroGeometry};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = Centroid::new(WktWriter::new(&mut out));
+/// WktStr("POLYGON((0 0,4 0,4 2,0 2,0 0))").process_geom(&mut processor).unwrap();
+/// drop(processor);
+/// assert_eq!(String::from_utf8(out).unwrap(), "POINT(2 1)");
+/// # }
+/// ```
+pub struct Centroid<P: GeomProcessor> {
+    inner: P,
+    collector: CentroidCollector,
+    /// Nesting depth of the current geometry
+    depth: usize,
+    /// Index of the current top-level geometry
+    idx: usize,
+}
+
+impl<P: GeomProcessor> Centroid<P> {
+    pub fn new(inner: P) -> Self {
+        Centroid {
+            inner,
+            collector: CentroidCollector::default(),
+            depth: 0,
+            idx: 0,
+        }
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    fn begin(&mut self, idx: usize) {
+        if self.depth == 0 {
+            self.idx = idx;
+            self.collector = CentroidCollector::default();
+        }
+        self.depth += 1;
+    }
+    fn end(&mut self) -> Result<()> {
+        self.depth -= 1;
+        if self.depth > 0 {
+            return Ok(());
+        }
+        match self.collector.centroid() {
+            Some((x, y)) => {
+                self.inner.point_begin(self.idx)?;
+                self.inner.xy(x, y, 0)?;
+                self.inner.point_end(self.idx)
+            }
+            None => self.inner.empty_point(self.idx),
+        }
+    }
+}
+
+/// Centroid `(x, y)` of a geometry, see [`Centroid`].
+///
+/// Returns `None` for empty geometries or if the geometry can't be processed.
+pub fn centroid(geom: &impl GeozeroGeometry) -> Option<(f64, f64)> {
+    let mut collector = CentroidCollector::default();
+    geom.process_geom(&mut collector).ok()?;
+    collector.centroid()
+}
+
+#[derive(Default)]
+struct CentroidCollector {
+    /// Twice the polygon area
+    area2: f64,
+    /// Area-weighted coordinate sums (times 6)
+    area_x: f64,
+    area_y: f64,
+    vertex_count: usize,
+    vertex_x: f64,
+    vertex_y: f64,
+    /// Open polygons, whose linestrings are rings
+    polygon_depth: usize,
+    /// Current ring is the exterior ring
+    exterior: bool,
+    /// Coordinates of the current ring
+    ring: Option<Vec<[f64; 2]>>,
+}
+
+impl CentroidCollector {
+    fn centroid(&self) -> Option<(f64, f64)> {
+        if self.area2 != 0.0 {
+            Some((
+                self.area_x / (3.0 * self.area2),
+                self.area_y / (3.0 * self.area2),
+            ))
+        } else if self.vertex_count > 0 {
+            let n = self.vertex_count as f64;
+            Some((self.vertex_x / n, self.vertex_y / n))
+        } else {
+            None
+        }
+    }
+    fn add_vertex(&mut self, x: f64, y: f64) {
+        self.vertex_count += 1;
+        self.vertex_x += x;
+        self.vertex_y += y;
+    }
+    fn add_ring(&mut self, ring: &[[f64; 2]]) {
+        let (mut area2, mut area_x, mut area_y) = (0.0, 0.0, 0.0);
+        for pair in ring.windows(2) {
+            let [x0, y0] = pair[0];
+            let [x1, y1] = pair[1];
+            let cross = x0 * y1 - x1 * y0;
+            area2 += cross;
+            area_x += (x0 + x1) * cross;
+            area_y += (y0 + y1) * cross;
+        }
+        // Exterior rings add area, interior rings subtract area, regardless of orientation
+        let sign = if self.exterior == (area2 >= 0.0) {
+            1.0
+        } else {
+            -1.0
+        };
+        self.area2 += sign * area2;
+        self.area_x += sign * area_x;
+        self.area_y += sign * area_y;
+        // The closing vertex is not counted twice
+        let closed = ring.len() > 1 && ring.first() == ring.last();
+        for &[x, y] in &ring[..ring.len() - usize::from(closed)] {
+            self.add_vertex(x, y);
+        }
+    }
+}
+
+impl GeomProcessor for CentroidCollector {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        match self.ring.as_mut() {
+            Some(ring) => ring.push([x, y]),
+            None => self.add_vertex(x, y),
+        }
+        Ok(())
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn linestring_begin(&mut self, _tagged: bool, size: usize, idx: usize) -> Result<()> {
+        if self.polygon_depth > 0 {
+            // The first ring of a polygon is the exterior ring
+            self.exterior = idx == 0;
+            self.ring = Some(Vec::with_capacity(size));
+        }
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        if let Some(ring) = self.ring.take() {
+            self.add_ring(&ring);
+        }
+        Ok(())
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.polygon_depth += 1;
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.polygon_depth -= 1;
+        Ok(())
+    }
+    fn triangle_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.polygon_depth += 1;
+        Ok(())
+    }
+    fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.polygon_depth -= 1;
+        Ok(())
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<P: GeomProcessor> Centroid<P> {
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        match event {
+            GeomEvent::Srid(_) => event.replay(&mut self.inner),
+            GeomEvent::InputDimensions(_) => Ok(()),
+            GeomEvent::Xy(x, y, idx) | GeomEvent::Coordinate(x, y, .., idx) => {
+                self.collector.xy(x, y, idx)
+            }
+            GeomEvent::EmptyPoint(idx) if self.depth == 0 => self.inner.empty_point(idx),
+            GeomEvent::EmptyPoint(_) => Ok(()),
+            GeomEvent::Begin(.., idx) => {
+                self.begin(idx);
+                event.replay(&mut self.collector)
+            }
+            GeomEvent::End(..) => {
+                event.replay(&mut self.collector)?;
+                self.end()
+            }
+        }
+    }
+}
+
+geom_event_processor!(Centroid, self => CoordDimensions::xy());
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::test_util::process_wkt;
+    use crate::wkt::{WktStr, WktWriter};
+
+    fn to_centroid(wkt: &str) -> String {
+        process_wkt(wkt, |out| Centroid::new(WktWriter::new(out)))
+    }
+
+    #[test]
+    fn polygons() {
+        assert_eq!(to_centroid("POLYGON((0 0,4 0,4 4,0 4,0 0))"), "POINT(2 2)");
+        // Clockwise exterior ring
+        assert_eq!(to_centroid("POLYGON((0 0,0 2,2 2,2 0,0 0))"), "POINT(1 1)");
+        assert_eq!(
+            to_centroid("POLYGON((0 0,4 0,4 4,0 4,0 0),(0 0,0 4,2 4,2 0,0 0))"),
+            "POINT(3 2)"
+        );
+        assert_eq!(
+            to_centroid("MULTIPOLYGON(((0 0,2 0,2 2,0 2,0 0)),((4 0,6 0,6 2,4 2,4 0)))"),
+            "POINT(3 1)"
+        );
+        // Zero-area polygon
+        assert_eq!(to_centroid("POLYGON((0 0,1 1,2 2,0 0))"), "POINT(1 1)");
+    }
+
+    #[test]
+    fn vertex_mean() {
+        assert_eq!(to_centroid("POINT(1 2)"), "POINT(1 2)");
+        assert_eq!(to_centroid("MULTIPOINT(0 0,2 4)"), "POINT(1 2)");
+        assert_eq!(to_centroid("LINESTRING(0 0,2 0,4 3)"), "POINT(2 1)");
+        assert_eq!(to_centroid("POINT EMPTY"), "POINT EMPTY");
+        assert_eq!(to_centroid("GEOMETRYCOLLECTION EMPTY"), "POINT EMPTY");
+        // Polygons take precedence in collections
+        assert_eq!(
+            to_centroid("GEOMETRYCOLLECTION(POINT(10 10),POLYGON((0 0,2 0,2 2,0 2,0 0)))"),
+            "POINT(1 1)"
+        );
+    }
+
+    #[test]
+    fn standalone() {
+        assert_eq!(
+            centroid(&WktStr("POLYGON((0 0,4 0,4 2,0 2,0 0))")),
+            Some((2.0, 1.0))
+        );
+        assert_eq!(centroid(&WktStr("LINESTRING EMPTY")), None);
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn features() {
+        use crate::geojson::GeoJsonWriter;
+        use crate::test_util::process_geojson;
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [4, 0], [4, 4], [0, 4], [0, 0]]]}}]}"#;
+        let json = process_geojson(geojson, |out| Centroid::new(GeoJsonWriter::new(out)));
+        assert!(json.contains(r#""properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [2,2]}"#), "{json}");
+    }
+}
@@ -0,0 +1,674 @@
+use crate::error::{GeozeroError, Result};
+use crate::geom_event_processor::geom_event_processor;
+use crate::geom_type_filter::GeomEvent;
+use crate::geometry_processor::GeomProcessor;
+use crate::GeomType;
+use std::f64::consts::TAU;
+
+/// Processor wrapper clipping geometries to a mask polygon.
+///
+/// Geometries straddling the mask boundary are replaced by their intersection with the mask.
+/// Geometries entirely outside of the mask produce no geometry callbacks at all, so features
+/// are passed with an empty geometry. Points are kept if they are inside of the mask or on its
+/// boundary.
+///
+/// The built-in clipping of [`ClipMask::new`] is limited to convex masks: lines are cut at the
+/// mask boundary, where a line leaving and reentering the mask becomes a multilinestring, and
+/// polygon rings are clipped with the Sutherland–Hodgman algorithm. Parts of concave polygons
+/// outside of the mask can leave zero-width spikes along its boundary. Z and M values of new
+/// vertices are interpolated. Curves and surfaces are passed unchanged.
+/// Arbitrary masks, like the boundary of a country, are supported with GEOS
+/// (feature `with-geos`) by [`ClipMask::new_geos`].
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, wkt::WktWriter, ClipMask, GeozeroGeometry};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mask = [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0), (0.0, 0.0)];
+/// let mut processor = ClipMask::new(WktWriter::new(&mut out), &mask).unwrap();
+/// WktStr("LINESTRING(-5 5,15 5)").process_geom(&mut processor).unwrap();
+/// drop(processor);
+/// assert_eq!(String::from_utf8(out).unwrap(), "LINESTRING(0 5,5 5)");
+/// # }
+/// ```
+pub struct ClipMask<P: GeomProcessor> {
+    inner: P,
+    mask: Mask,
+    /// Buffered events of the current geometry
+    geometry: Vec<GeomEvent>,
+    /// Nesting depth of the current geometry
+    depth: usize,
+}
+
+enum Mask {
+    Convex(ConvexRegion),
+    #[cfg(feature = "with-geos")]
+    Geos(geos::Geometry<'static>),
+}
+
+impl<P: GeomProcessor> ClipMask<P> {
+    /// Clip to the convex polygon `mask`, given as ring in either orientation.
+    ///
+    /// Returns an error if the mask is not convex or has less than 3 distinct vertices.
+    pub fn new(inner: P, mask: &[(f64, f64)]) -> Result<Self> {
+        let region = ConvexRegion::polygon(mask)
+            .ok_or_else(|| GeozeroError::Geometry("Clip mask is not convex".to_string()))?;
+        Ok(ClipMask {
+            inner,
+            mask: Mask::Convex(region),
+            geometry: Vec::new(),
+            depth: 0,
+        })
+    }
+    /// Clip to an arbitrary `mask` geometry with GEOS.
+    #[cfg(feature = "with-geos")]
+    pub fn new_geos(inner: P, mask: geos::Geometry<'static>) -> Self {
+        ClipMask {
+            inner,
+            mask: Mask::Geos(mask),
+            geometry: Vec::new(),
+            depth: 0,
+        }
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        if self.depth == 0 && !matches!(event, GeomEvent::Begin(..) | GeomEvent::EmptyPoint(_)) {
+            return event.replay(&mut self.inner);
+        }
+        match event {
+            GeomEvent::Begin(..) => self.depth += 1,
+            GeomEvent::End(..) => self.depth -= 1,
+            _ => {}
+        }
+        self.geometry.push(event);
+        if self.depth == 0 {
+            let geometry = std::mem::take(&mut self.geometry);
+            self.clip(&geometry)?;
+        }
+        Ok(())
+    }
+    /// Pass the part of `geometry` inside of the mask to the inner processor
+    fn clip(&mut self, geometry: &[GeomEvent]) -> Result<()> {
+        match &self.mask {
+            Mask::Convex(region) => {
+                for event in region.clip(geometry).unwrap_or_default() {
+                    event.replay(&mut self.inner)?;
+                }
+            }
+            #[cfg(feature = "with-geos")]
+            Mask::Geos(mask) => {
+                use geos::Geom;
+                let mut writer = crate::geos::GeosWriter::new();
+                for event in geometry {
+                    event.replay(&mut writer)?;
+                }
+                let clipped = mask.intersection(writer.geometry())?;
+                if !clipped.is_empty()? {
+                    crate::geos::process_geom(&clipped, &mut self.inner)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Half-plane `a * x + b * y <= c`
+#[derive(Clone, Copy, Debug)]
+struct HalfPlane {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl HalfPlane {
+    /// Scaled signed distance of `(x, y)` to the boundary, positive outside
+    fn distance(&self, (x, y): (f64, f64)) -> f64 {
+        self.a * x + self.b * y - self.c
+    }
+    /// Move `point` exactly onto an axis-parallel boundary
+    fn snap(&self, point: &mut GeomEvent) {
+        if let Some((x, y)) = xy_mut(point) {
+            // Adding 0.0 normalizes -0.0
+            if self.b == 0.0 {
+                *x = self.c / self.a + 0.0;
+            } else if self.a == 0.0 {
+                *y = self.c / self.b + 0.0;
+            }
+        }
+    }
+}
+
+/// Convex clip region, the intersection of half-planes
+#[derive(Clone, Debug)]
+pub(crate) struct ConvexRegion {
+    planes: Vec<HalfPlane>,
+}
+
+impl ConvexRegion {
+    /// Region of a rectangle
+    pub(crate) fn rect(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Self {
+        let plane = |a, b, c| HalfPlane { a, b, c };
+        ConvexRegion {
+            planes: vec![
+                plane(-1.0, 0.0, -min_x),
+                plane(0.0, -1.0, -min_y),
+                plane(1.0, 0.0, max_x),
+                plane(0.0, 1.0, max_y),
+            ],
+        }
+    }
+    /// Region of a convex polygon ring, `None` if the ring is not convex
+    pub(crate) fn polygon(ring: &[(f64, f64)]) -> Option<Self> {
+        let mut points: Vec<(f64, f64)> = Vec::with_capacity(ring.len());
+        for &point in ring {
+            if points.last() != Some(&point) {
+                points.push(point);
+            }
+        }
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        let n = points.len();
+        if n < 3 {
+            return None;
+        }
+        let edge = |i: usize| {
+            let ((x0, y0), (x1, y1)) = (points[i], points[(i + 1) % n]);
+            (x1 - x0, y1 - y0)
+        };
+        // All turns go in the same direction and add up to one full turn
+        let mut sign = 0.0;
+        let mut turning = 0.0;
+        for i in 0..n {
+            let ((dx0, dy0), (dx1, dy1)) = (edge(i), edge((i + 1) % n));
+            let cross = dx0 * dy1 - dy0 * dx1;
+            if cross * sign < 0.0 {
+                return None;
+            }
+            if cross != 0.0 {
+                sign = cross.signum();
+            }
+            turning += cross.atan2(dx0 * dx1 + dy0 * dy1);
+        }
+        if sign == 0.0 || (turning.abs() - TAU).abs() > 1e-9 {
+            return None;
+        }
+        let planes = points
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, y))| {
+                // The interior is left of counterclockwise edges
+                let (dx, dy) = edge(i);
+                let (a, b) = (dy * sign, -dx * sign);
+                HalfPlane {
+                    a,
+                    b,
+                    c: a * x + b * y,
+                }
+            })
+            .collect();
+        Some(ConvexRegion { planes })
+    }
+    /// Whether `point` is inside of the region or on its boundary
+    fn contains(&self, point: &GeomEvent) -> bool {
+        point
+            .xy()
+            .is_some_and(|xy| self.planes.iter().all(|plane| plane.distance(xy) <= 0.0))
+    }
+    /// Geometry clipped to the region, or `None` if nothing is left
+    pub(crate) fn clip(&self, geometry: &[GeomEvent]) -> Option<Vec<GeomEvent>> {
+        let start = geometry
+            .iter()
+            .position(|event| matches!(event, GeomEvent::Begin(..) | GeomEvent::EmptyPoint(_)))?;
+        let idx = match geometry[start] {
+            GeomEvent::Begin(.., idx) => idx,
+            _ => 0,
+        };
+        let mut clipped = geometry[..start].to_vec();
+        let mut pos = start;
+        clip_member(geometry, &mut pos, idx, self, &mut clipped).then_some(clipped)
+    }
+}
+
+/// Position after the geometry starting at `events[start]`
+fn geometry_end(events: &[GeomEvent], start: usize) -> usize {
+    let mut depth = 0;
+    for (i, event) in events.iter().enumerate().skip(start) {
+        match event {
+            GeomEvent::Begin(..) => depth += 1,
+            GeomEvent::End(..) => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            GeomEvent::EmptyPoint(_) if depth == 0 => return i + 1,
+            _ => {}
+        }
+    }
+    events.len()
+}
+
+/// Coordinates of the geometry at `events[*pos]`, advancing `pos` past it
+fn coords(events: &[GeomEvent], pos: &mut usize) -> Vec<GeomEvent> {
+    let end = geometry_end(events, *pos);
+    let coords = events[*pos..end]
+        .iter()
+        .filter(|event| event.xy().is_some())
+        .cloned()
+        .collect();
+    *pos = end;
+    coords
+}
+
+fn is_end(event: &GeomEvent) -> bool {
+    matches!(event, GeomEvent::End(..))
+}
+
+fn push_part(
+    out: &mut Vec<GeomEvent>,
+    geom_type: GeomType,
+    tagged: bool,
+    idx: usize,
+    coords: &[GeomEvent],
+) {
+    out.push(GeomEvent::Begin(geom_type, tagged, coords.len(), idx));
+    out.extend(
+        coords
+            .iter()
+            .enumerate()
+            .map(|(i, coord)| coord.with_coord_idx(i)),
+    );
+    out.push(GeomEvent::End(geom_type, tagged, idx));
+}
+
+fn push_lines(out: &mut Vec<GeomEvent>, idx: usize, lines: &[Vec<GeomEvent>]) {
+    out.push(GeomEvent::Begin(
+        GeomType::MultiLineString,
+        true,
+        lines.len(),
+        idx,
+    ));
+    for (i, line) in lines.iter().enumerate() {
+        push_part(out, GeomType::LineString, false, i, line);
+    }
+    out.push(GeomEvent::End(GeomType::MultiLineString, true, idx));
+}
+
+fn push_polygon(out: &mut Vec<GeomEvent>, tagged: bool, idx: usize, rings: &[Vec<GeomEvent>]) {
+    out.push(GeomEvent::Begin(
+        GeomType::Polygon,
+        tagged,
+        rings.len(),
+        idx,
+    ));
+    for (i, ring) in rings.iter().enumerate() {
+        push_part(out, GeomType::LineString, false, i, ring);
+    }
+    out.push(GeomEvent::End(GeomType::Polygon, tagged, idx));
+}
+
+/// Clip the geometry at `events[*pos]`, append it with index `idx` to `out` and advance `pos`
+/// past it. Returns `false` if nothing is left.
+fn clip_member(
+    events: &[GeomEvent],
+    pos: &mut usize,
+    idx: usize,
+    region: &ConvexRegion,
+    out: &mut Vec<GeomEvent>,
+) -> bool {
+    let GeomEvent::Begin(geom_type, tagged, ..) = events[*pos] else {
+        // Empty point
+        *pos += 1;
+        return false;
+    };
+    match geom_type {
+        GeomType::Point | GeomType::MultiPoint => {
+            let points: Vec<_> = coords(events, pos)
+                .into_iter()
+                .filter(|point| region.contains(point))
+                .collect();
+            if points.is_empty() {
+                return false;
+            }
+            push_part(out, geom_type, tagged, idx, &points);
+        }
+        GeomType::LineString => {
+            let lines = clip_line(&coords(events, pos), region);
+            match lines.len() {
+                0 => return false,
+                1 => push_part(out, GeomType::LineString, tagged, idx, &lines[0]),
+                // A line leaving and reentering the region becomes a multilinestring
+                _ => push_lines(out, idx, &lines),
+            }
+        }
+        GeomType::MultiLineString => {
+            *pos += 1;
+            let mut lines = Vec::new();
+            while !is_end(&events[*pos]) {
+                lines.extend(clip_line(&coords(events, pos), region));
+            }
+            *pos += 1;
+            if lines.is_empty() {
+                return false;
+            }
+            push_lines(out, idx, &lines);
+        }
+        GeomType::Polygon => {
+            let Some(rings) = clip_polygon(events, pos, region) else {
+                return false;
+            };
+            push_polygon(out, tagged, idx, &rings);
+        }
+        GeomType::MultiPolygon => {
+            *pos += 1;
+            let mut polygons = Vec::new();
+            while !is_end(&events[*pos]) {
+                polygons.extend(clip_polygon(events, pos, region));
+            }
+            *pos += 1;
+            if polygons.is_empty() {
+                return false;
+            }
+            out.push(GeomEvent::Begin(
+                GeomType::MultiPolygon,
+                true,
+                polygons.len(),
+                idx,
+            ));
+            for (i, rings) in polygons.iter().enumerate() {
+                push_polygon(out, false, i, rings);
+            }
+            out.push(GeomEvent::End(GeomType::MultiPolygon, true, idx));
+        }
+        GeomType::GeometryCollection => {
+            *pos += 1;
+            let mut members = Vec::new();
+            let mut size = 0;
+            while !is_end(&events[*pos]) {
+                if clip_member(events, pos, size, region, &mut members) {
+                    size += 1;
+                }
+            }
+            *pos += 1;
+            if size == 0 {
+                return false;
+            }
+            out.push(GeomEvent::Begin(
+                GeomType::GeometryCollection,
+                true,
+                size,
+                idx,
+            ));
+            out.append(&mut members);
+            out.push(GeomEvent::End(GeomType::GeometryCollection, true, idx));
+        }
+        _ => {
+            // Curves and surfaces are passed unchanged
+            let end = geometry_end(events, *pos);
+            out.push(events[*pos].with_idx(idx));
+            out.extend_from_slice(&events[*pos + 1..end - 1]);
+            out.push(events[end - 1].with_idx(idx));
+            *pos = end;
+        }
+    }
+    true
+}
+
+/// Clipped rings of the polygon at `events[*pos]`, or `None` if the exterior ring vanishes
+fn clip_polygon(
+    events: &[GeomEvent],
+    pos: &mut usize,
+    region: &ConvexRegion,
+) -> Option<Vec<Vec<GeomEvent>>> {
+    *pos += 1;
+    let mut rings = Vec::new();
+    let mut exterior = true;
+    while !is_end(&events[*pos]) {
+        let ring = clip_ring(&coords(events, pos), region);
+        if ring.len() >= 4 {
+            rings.push(ring);
+        } else if rings.is_empty() {
+            exterior = false;
+        }
+    }
+    *pos += 1;
+    if exterior {
+        Some(rings)
+    } else {
+        None
+    }
+}
+
+fn xy_mut(event: &mut GeomEvent) -> Option<(&mut f64, &mut f64)> {
+    match event {
+        GeomEvent::Xy(x, y, _) | GeomEvent::Coordinate(x, y, ..) => Some((x, y)),
+        _ => None,
+    }
+}
+
+/// Coordinate at `t` between `a` and `b`
+fn interpolate(a: &GeomEvent, b: &GeomEvent, t: f64) -> GeomEvent {
+    let lerp = |a: f64, b: f64| a + t * (b - a);
+    let lerp_opt = |a: Option<f64>, b: Option<f64>| Some(lerp(a?, b?));
+    let (ax, ay) = a.xy().unwrap_or_default();
+    let (bx, by) = b.xy().unwrap_or_default();
+    match (a, b) {
+        (GeomEvent::Coordinate(_, _, az, am, ..), GeomEvent::Coordinate(_, _, bz, bm, ..)) => {
+            GeomEvent::Coordinate(
+                lerp(ax, bx),
+                lerp(ay, by),
+                lerp_opt(*az, *bz),
+                lerp_opt(*am, *bm),
+                None,
+                None,
+                0,
+            )
+        }
+        _ => GeomEvent::Xy(lerp(ax, bx), lerp(ay, by), 0),
+    }
+}
+
+/// Point at `t` between `a` and `b` on the boundary of `plane`
+fn boundary_point(a: &GeomEvent, b: &GeomEvent, t: f64, plane: &HalfPlane) -> GeomEvent {
+    let mut point = interpolate(a, b, t);
+    plane.snap(&mut point);
+    point
+}
+
+/// Part of the segment from `a` to `b` inside of `region` (Cyrus–Beck)
+fn clip_segment(
+    a: &GeomEvent,
+    b: &GeomEvent,
+    region: &ConvexRegion,
+) -> Option<(GeomEvent, GeomEvent)> {
+    let (start, end) = (a.xy()?, b.xy()?);
+    // Parameters of the clipped end points, with the boundary they are on
+    let (mut t0, mut plane0) = (0.0f64, None);
+    let (mut t1, mut plane1) = (1.0f64, None);
+    for plane in &region.planes {
+        let (from, to) = (plane.distance(start), plane.distance(end));
+        if from > 0.0 && to > 0.0 {
+            return None;
+        }
+        if from > 0.0 || to > 0.0 {
+            let t = from / (from - to);
+            if from > 0.0 && t > t0 {
+                (t0, plane0) = (t, Some(plane));
+            } else if to > 0.0 && t < t1 {
+                (t1, plane1) = (t, Some(plane));
+            }
+        }
+    }
+    if t0 >= t1 {
+        return None;
+    }
+    let start = match plane0 {
+        Some(plane) => boundary_point(a, b, t0, plane),
+        None => a.clone(),
+    };
+    let end = match plane1 {
+        Some(plane) => boundary_point(a, b, t1, plane),
+        None => b.clone(),
+    };
+    Some((start, end))
+}
+
+fn end_part(lines: &mut Vec<Vec<GeomEvent>>, line: &mut Vec<GeomEvent>) {
+    if line.len() >= 2 {
+        lines.push(std::mem::take(line));
+    } else {
+        line.clear();
+    }
+}
+
+/// Parts of `line` inside of `region`
+fn clip_line(line: &[GeomEvent], region: &ConvexRegion) -> Vec<Vec<GeomEvent>> {
+    let mut lines = Vec::new();
+    let mut current = Vec::new();
+    for segment in line.windows(2) {
+        let Some((start, end)) = clip_segment(&segment[0], &segment[1], region) else {
+            end_part(&mut lines, &mut current);
+            continue;
+        };
+        if current.last().and_then(GeomEvent::xy) != start.xy() {
+            end_part(&mut lines, &mut current);
+            current.push(start);
+        }
+        let leaves = end.xy() != segment[1].xy();
+        current.push(end);
+        if leaves {
+            end_part(&mut lines, &mut current);
+        }
+    }
+    end_part(&mut lines, &mut current);
+    lines
+}
+
+/// `ring` clipped to `region` (Sutherland–Hodgman), closed if not empty
+fn clip_ring(ring: &[GeomEvent], region: &ConvexRegion) -> Vec<GeomEvent> {
+    let mut points = ring.to_vec();
+    if points.len() > 1 && points[0].xy() == points[points.len() - 1].xy() {
+        points.pop();
+    }
+    for plane in &region.planes {
+        let distance = |point: &GeomEvent| plane.distance(point.xy().unwrap_or_default());
+        let input = std::mem::take(&mut points);
+        let mut push = |point: GeomEvent| {
+            if points.last().and_then(GeomEvent::xy) != point.xy() {
+                points.push(point);
+            }
+        };
+        for (i, current) in input.iter().enumerate() {
+            let previous = &input[(i + input.len() - 1) % input.len()];
+            let (from, to) = (distance(previous), distance(current));
+            if (from > 0.0) != (to > 0.0) {
+                push(boundary_point(previous, current, from / (from - to), plane));
+            }
+            if to <= 0.0 {
+                push(current.clone());
+            }
+        }
+        if points.len() > 1 && points[0].xy() == points[points.len() - 1].xy() {
+            points.pop();
+        }
+    }
+    if let Some(first) = points.first().cloned() {
+        points.push(first);
+    }
+    points
+}
+
+geom_event_processor!(ClipMask);
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::test_util::process_wkt;
+    use crate::wkt::WktWriter;
+
+    const TRIANGLE: [(f64, f64); 3] = [(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)];
+
+    fn clip(wkt: &str, mask: &[(f64, f64)]) -> String {
+        process_wkt(wkt, |out| ClipMask::new(WktWriter::new(out), mask).unwrap())
+    }
+
+    #[test]
+    fn points() {
+        // Points on the boundary are kept
+        assert_eq!(
+            clip("MULTIPOINT(2 2,8 8,5 5)", &TRIANGLE),
+            "MULTIPOINT(2 2,5 5)"
+        );
+        assert_eq!(clip("POINT(8 8)", &TRIANGLE), "");
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(
+            clip("LINESTRING(-5 5,15 5)", &TRIANGLE),
+            "LINESTRING(0 5,5 5)"
+        );
+        assert_eq!(clip("LINESTRING(20 20,30 30)", &TRIANGLE), "");
+        // Clockwise mask
+        let clockwise = [(0.0, 0.0), (0.0, 10.0), (10.0, 0.0), (0.0, 0.0)];
+        assert_eq!(
+            clip("LINESTRING(-5 5,15 5)", &clockwise),
+            "LINESTRING(0 5,5 5)"
+        );
+    }
+
+    #[test]
+    fn polygons() {
+        assert_eq!(
+            clip("POLYGON((0 0,10 0,10 10,0 10,0 0))", &TRIANGLE),
+            "POLYGON((0 0,10 0,0 10,0 0))"
+        );
+        assert_eq!(
+            clip(
+                "GEOMETRYCOLLECTION(POLYGON((20 20,30 20,30 30,20 20)),POINT(1 1))",
+                &TRIANGLE
+            ),
+            "GEOMETRYCOLLECTION(POINT(1 1))"
+        );
+    }
+
+    #[test]
+    fn concave_mask() {
+        let mask = [
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (5.0, 2.0),
+            (10.0, 10.0),
+            (0.0, 10.0),
+        ];
+        assert!(ClipMask::new(WktWriter::new(Vec::new()), &mask).is_err());
+        // Self-intersecting star
+        let star = [(0.0, 0.0), (2.0, 6.0), (4.0, 0.0), (-1.0, 4.0), (5.0, 4.0)];
+        assert!(ClipMask::new(WktWriter::new(Vec::new()), &star).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "with-geos")]
+    fn geos_mask() {
+        use crate::wkt::WktStr;
+        use crate::GeozeroGeometry;
+
+        let mask =
+            geos::Geometry::new_from_wkt("POLYGON((0 0,10 0,10 10,5 10,5 5,0 5,0 0))").unwrap();
+        let mut out: Vec<u8> = Vec::new();
+        let mut processor = ClipMask::new_geos(WktWriter::new(&mut out), mask);
+        WktStr("POINT(2 8)").process_geom(&mut processor).unwrap();
+        WktStr("POINT(2 2)").process_geom(&mut processor).unwrap();
+        drop(processor);
+        assert_eq!(String::from_utf8(out).unwrap(), "POINT(2 2)");
+    }
+}
@@ -0,0 +1,128 @@
+use crate::error::Result;
+use crate::geom_event_processor::{geom_event_processor, LineBuffer, LineEvent};
+use crate::geom_type_filter::GeomEvent;
+use crate::geometry_processor::GeomProcessor;
+
+/// Processor wrapper closing polygon rings before passing them to the inner processor.
+///
+/// Rings whose last coordinate doesn't equal the first one get the first coordinate appended.
+/// By default coordinates have to be exactly equal, [`CloseRings::with_tolerance`] allows
+/// small differences. Other geometries are passed unchanged.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, wkt::WktWriter, CloseRings, GeozeroGeometry};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = CloseRings::new(WktWriter::new(&mut out));
+/// WktStr("POLYGON((0 0,10 0,10 10))").process_geom(&mut processor).unwrap();
+/// drop(processor);
+/// assert_eq!(String::from_utf8(out).unwrap(), "POLYGON((0 0,10 0,10 10,0 0))");
+/// # }
+/// ```
+pub struct CloseRings<P: GeomProcessor> {
+    inner: P,
+    tolerance: f64,
+    /// Collected polygon rings
+    lines: LineBuffer,
+    closed_rings: usize,
+}
+
+impl<P: GeomProcessor> CloseRings<P> {
+    pub fn new(inner: P) -> Self {
+        CloseRings {
+            inner,
+            tolerance: 0.0,
+            lines: LineBuffer::new(true),
+            closed_rings: 0,
+        }
+    }
+    /// Consider rings as closed, when the x and y distance of the first and last coordinate
+    /// is within `tolerance` (default: exact comparison).
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+    /// Number of rings which were closed.
+    pub fn closed_rings(&self) -> usize {
+        self.closed_rings
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: GeomProcessor> CloseRings<P> {
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        match self.lines.push(event) {
+            LineEvent::Pass(event) => event.replay(&mut self.inner),
+            LineEvent::Buffered => Ok(()),
+            LineEvent::Line(mut ring) => {
+                if let (Some(first), Some(last)) = (ring.coords.first(), ring.coords.last()) {
+                    let closed = (first.x - last.x).abs() <= self.tolerance
+                        && (first.y - last.y).abs() <= self.tolerance;
+                    if !closed {
+                        ring.coords.push(*first);
+                        self.closed_rings += 1;
+                    }
+                }
+                ring.replay(&mut self.inner)
+            }
+        }
+    }
+}
+
+geom_event_processor!(CloseRings);
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::test_util::process_wkt_with;
+    use crate::wkt::WktWriter;
+
+    fn close(wkt: &str, tolerance: f64) -> (String, usize) {
+        process_wkt_with(
+            wkt,
+            |out| CloseRings::new(WktWriter::new(out)).with_tolerance(tolerance),
+            CloseRings::closed_rings,
+        )
+    }
+
+    #[test]
+    fn rings() {
+        assert_eq!(
+            close("POLYGON((0 0,10 0,10 10,0 10),(2 2,4 2,4 4,2 2))", 0.0),
+            (
+                "POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,4 2,4 4,2 2))".to_string(),
+                1
+            )
+        );
+        assert_eq!(
+            close("MULTIPOLYGON(((0 0,1 0,1 1)),((5 5,6 5,6 6)))", 0.0),
+            (
+                "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5)))".to_string(),
+                2
+            )
+        );
+        // Lines are not closed
+        assert_eq!(
+            close("LINESTRING(0 0,1 0,1 1)", 0.0),
+            ("LINESTRING(0 0,1 0,1 1)".to_string(), 0)
+        );
+    }
+
+    #[test]
+    fn tolerance() {
+        let wkt = "POLYGON((0 0,10 0,10 10,0.0001 0))";
+        assert_eq!(
+            close(wkt, 0.0),
+            ("POLYGON((0 0,10 0,10 10,0.0001 0,0 0))".to_string(), 1)
+        );
+        assert_eq!(close(wkt, 0.001), (wkt.to_string(), 0));
+    }
+}
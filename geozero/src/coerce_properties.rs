@@ -0,0 +1,446 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+use std::fmt::Write;
+
+/// Kind of information lost by a property coercion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoercionLoss {
+    /// Integer not exactly representable as `f64`
+    Precision,
+    /// String truncated from the given length in bytes
+    Truncated(usize),
+}
+
+/// Lossy coercion performed by [`CoerceProperties`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LossyCoercion {
+    /// Index of the feature
+    pub feature_idx: u64,
+    /// Property name
+    pub property: String,
+    pub loss: CoercionLoss,
+}
+
+/// Processor wrapper converting property values for targets with limited type systems,
+/// e.g. the field types of a Shapefile DBF.
+///
+/// Coercions are applied in the following order:
+/// booleans to integers, numbers to `f64`, all values to strings, string width.
+/// Binary values are converted to hex strings. Lossy coercions are recorded
+/// and can be inspected with [`CoerceProperties::lossy_coercions`].
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::{geojson::GeoJson, geojson::GeoJsonWriter, CoerceProperties, GeozeroDatasource};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = CoerceProperties::new(GeoJsonWriter::new(&mut out))
+///     .with_all_to_string(true)
+///     .with_max_width(4);
+/// GeoJson(r#"{"type": "Feature", "properties": {"name": "Zurich", "pop": 415215}, "geometry": null}"#)
+///     .process(&mut processor)
+///     .unwrap();
+/// assert_eq!(processor.lossy_coercions().len(), 2);
+/// drop(processor);
+/// assert!(String::from_utf8(out).unwrap().contains(r#""properties": {"name": "Zuri", "pop": "4152"}"#));
+/// # }
+/// ```
+pub struct CoerceProperties<P: FeatureProcessor> {
+    inner: P,
+    bools_to_int: bool,
+    numbers_to_f64: bool,
+    all_to_string: bool,
+    max_width: Option<usize>,
+    padding: bool,
+    feature_idx: u64,
+    lossy: Vec<LossyCoercion>,
+}
+
+impl<P: FeatureProcessor> CoerceProperties<P> {
+    /// Pass properties unchanged until coercions are enabled.
+    pub fn new(inner: P) -> Self {
+        CoerceProperties {
+            inner,
+            bools_to_int: false,
+            numbers_to_f64: false,
+            all_to_string: false,
+            max_width: None,
+            padding: false,
+            feature_idx: 0,
+            lossy: Vec::new(),
+        }
+    }
+    /// Convert booleans to `Int` values 0 and 1.
+    pub fn with_bools_to_int(mut self, enabled: bool) -> Self {
+        self.bools_to_int = enabled;
+        self
+    }
+    /// Convert integer and float values to `Double`.
+    pub fn with_numbers_to_f64(mut self, enabled: bool) -> Self {
+        self.numbers_to_f64 = enabled;
+        self
+    }
+    /// Convert all values to `String`.
+    pub fn with_all_to_string(mut self, enabled: bool) -> Self {
+        self.all_to_string = enabled;
+        self
+    }
+    /// Truncate string, JSON and date values to `width` bytes.
+    pub fn with_max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+    /// Pad shorter string values with spaces to the max width.
+    pub fn with_padding(mut self, enabled: bool) -> Self {
+        self.padding = enabled;
+        self
+    }
+    /// Lossy coercions performed so far.
+    pub fn lossy_coercions(&self) -> &[LossyCoercion] {
+        &self.lossy
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    fn record(&mut self, name: &str, loss: CoercionLoss) {
+        self.lossy.push(LossyCoercion {
+            feature_idx: self.feature_idx,
+            property: name.to_string(),
+            loss,
+        });
+    }
+}
+
+/// Numeric value as `f64` and whether the conversion is exact.
+fn to_f64(value: &ColumnValue) -> Option<(f64, bool)> {
+    let v = match *value {
+        ColumnValue::Byte(v) => f64::from(v),
+        ColumnValue::UByte(v) => f64::from(v),
+        ColumnValue::Short(v) => f64::from(v),
+        ColumnValue::UShort(v) => f64::from(v),
+        ColumnValue::Int(v) => f64::from(v),
+        ColumnValue::UInt(v) => f64::from(v),
+        ColumnValue::Float(v) => f64::from(v),
+        ColumnValue::Double(v) => v,
+        ColumnValue::Long(v) => return Some((v as f64, v as f64 as i64 == v)),
+        ColumnValue::ULong(v) => return Some((v as f64, v as f64 as u64 == v)),
+        _ => return None,
+    };
+    Some((v, true))
+}
+
+fn to_string(value: &ColumnValue) -> String {
+    match value {
+        ColumnValue::Binary(v) => v.iter().fold(String::new(), |mut s, b| {
+            let _ = write!(s, "{b:02x}");
+            s
+        }),
+        _ => value.to_string(),
+    }
+}
+
+impl<P: FeatureProcessor> GeomProcessor for CoerceProperties<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.inner.input_dimensions(dimensions)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.inner.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.inner.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.inner.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.tin_end(idx)
+    }
+}
+
+impl<P: FeatureProcessor> PropertyProcessor for CoerceProperties<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let converted: String;
+        let sized: String;
+        let mut value = value.clone();
+        if self.bools_to_int {
+            if let ColumnValue::Bool(v) = value {
+                value = ColumnValue::Int(i32::from(v));
+            }
+        }
+        if self.numbers_to_f64 {
+            if let Some((v, exact)) = to_f64(&value) {
+                if !exact {
+                    self.record(name, CoercionLoss::Precision);
+                }
+                value = ColumnValue::Double(v);
+            }
+        }
+        if self.all_to_string
+            && !matches!(
+                value,
+                ColumnValue::String(_) | ColumnValue::Json(_) | ColumnValue::DateTime(_)
+            )
+        {
+            converted = to_string(&value);
+            value = ColumnValue::String(&converted);
+        }
+        if let Some(width) = self.max_width {
+            if let ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) = value
+            {
+                if v.len() > width {
+                    self.record(name, CoercionLoss::Truncated(v.len()));
+                    let mut end = width;
+                    while !v.is_char_boundary(end) {
+                        end -= 1;
+                    }
+                    sized = v[..end].to_string();
+                } else if self.padding {
+                    sized = format!("{v}{}", " ".repeat(width - v.len()));
+                } else {
+                    sized = v.to_string();
+                }
+                value = match value {
+                    ColumnValue::Json(_) => ColumnValue::Json(&sized),
+                    ColumnValue::DateTime(_) => ColumnValue::DateTime(&sized),
+                    _ => ColumnValue::String(&sized),
+                };
+            }
+        }
+        self.inner.property(idx, name, &value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for CoerceProperties<P> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.feature_idx = idx;
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.inner.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+    fn skip_geometry(&self) -> bool {
+        self.inner.skip_geometry()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonWriter;
+    use crate::test_util::process_geojson_with;
+
+    const INPUT: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"name": "Bern", "flag": true, "big": 9007199254740993}, "geometry": {"type": "Point", "coordinates": [7.4, 46.9]}},
+        {"type": "Feature", "properties": {"name": "Straße", "flag": false, "big": 1}, "geometry": {"type": "Point", "coordinates": [8.5, 47.4]}}
+    ]}"#;
+
+    fn properties(out: &str) -> Vec<serde_json::Value> {
+        let json: serde_json::Value = serde_json::from_str(out).unwrap();
+        json["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["properties"].clone())
+            .collect()
+    }
+
+    #[test]
+    fn numbers() {
+        let (out, lossy) = process_geojson_with(
+            INPUT,
+            |out| {
+                CoerceProperties::new(GeoJsonWriter::new(out))
+                    .with_bools_to_int(true)
+                    .with_numbers_to_f64(true)
+            },
+            |processor| processor.lossy_coercions().to_vec(),
+        );
+        assert_eq!(
+            lossy,
+            &[LossyCoercion {
+                feature_idx: 0,
+                property: "big".to_string(),
+                loss: CoercionLoss::Precision,
+            }]
+        );
+        let props = properties(&out);
+        assert_eq!(props[0]["flag"], 1);
+        assert_eq!(props[1]["flag"], 0);
+        assert_eq!(props[1]["big"], 1.0);
+    }
+
+    #[test]
+    fn strings() {
+        let (out, lossy) = process_geojson_with(
+            INPUT,
+            |out| {
+                CoerceProperties::new(GeoJsonWriter::new(out))
+                    .with_all_to_string(true)
+                    .with_max_width(5)
+                    .with_padding(true)
+            },
+            |processor| processor.lossy_coercions().to_vec(),
+        );
+        // Truncation keeps whole characters
+        assert_eq!(
+            lossy,
+            &[
+                LossyCoercion {
+                    feature_idx: 0,
+                    property: "big".to_string(),
+                    loss: CoercionLoss::Truncated(16),
+                },
+                LossyCoercion {
+                    feature_idx: 1,
+                    property: "name".to_string(),
+                    loss: CoercionLoss::Truncated(7),
+                },
+            ]
+        );
+        let props = properties(&out);
+        assert_eq!(
+            props[0],
+            serde_json::json!({"name": "Bern ", "flag": "true ", "big": "90071"})
+        );
+        assert_eq!(
+            props[1],
+            serde_json::json!({"name": "Stra", "flag": "false", "big": "1    "})
+        );
+    }
+}
@@ -2,11 +2,14 @@ use crate::error::{GeozeroError, Result};
 use crate::{ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry};
 
 use std::io::Read;
-use std::str::FromStr;
+
+/// Name of the WKT geometry column used when none is given.
+pub const DEFAULT_GEOMETRY_COLUMN: &str = "geometry";
 
 pub struct Csv<'a> {
     csv_text: &'a str,
     geometry_column_name: String,
+    has_headers: bool,
 }
 
 impl<'a> Csv<'a> {
@@ -14,26 +17,37 @@ impl<'a> Csv<'a> {
         Self {
             csv_text,
             geometry_column_name: geometry_column_name.to_string(),
+            has_headers: true,
         }
     }
+
+    /// Whether the first row contains column names (default: `true`).
+    ///
+    /// Without a header row, columns are named `field_1`, `field_2`, ...
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
 }
 
 impl GeozeroDatasource for Csv<'_> {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
-        process_csv_features(
+        read_csv_features(
             self.csv_text.as_bytes(),
             processor,
             &self.geometry_column_name,
+            self.has_headers,
         )
     }
 }
 
 impl GeozeroGeometry for Csv<'_> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_csv_geom(
+        read_csv_geom(
             self.csv_text.as_bytes(),
             processor,
             &self.geometry_column_name,
+            self.has_headers,
         )
     }
 }
@@ -41,6 +55,7 @@ impl GeozeroGeometry for Csv<'_> {
 pub struct CsvString {
     csv_text: String,
     geometry_column_name: String,
+    has_headers: bool,
 }
 
 impl CsvString {
@@ -48,26 +63,37 @@ impl CsvString {
         Self {
             csv_text,
             geometry_column_name: geometry_column_name.to_string(),
+            has_headers: true,
         }
     }
+
+    /// Whether the first row contains column names (default: `true`).
+    ///
+    /// Without a header row, columns are named `field_1`, `field_2`, ...
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
 }
 
 impl GeozeroDatasource for CsvString {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
-        process_csv_features(
+        read_csv_features(
             self.csv_text.as_bytes(),
             processor,
             &self.geometry_column_name,
+            self.has_headers,
         )
     }
 }
 
 impl GeozeroGeometry for CsvString {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_csv_geom(
+        read_csv_geom(
             self.csv_text.as_bytes(),
             processor,
             &self.geometry_column_name,
+            self.has_headers,
         )
     }
 }
@@ -75,6 +101,7 @@ impl GeozeroGeometry for CsvString {
 pub struct CsvReader<R: Read> {
     inner: R,
     geometry_column_name: String,
+    has_headers: bool,
 }
 
 impl<R: Read> CsvReader<R> {
@@ -82,19 +109,38 @@ impl<R: Read> CsvReader<R> {
         Self {
             inner,
             geometry_column_name: geometry_column_name.to_string(),
+            has_headers: true,
         }
     }
+
+    /// Whether the first row contains column names (default: `true`).
+    ///
+    /// Without a header row, columns are named `field_1`, `field_2`, ...
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
 }
 
 impl<R: Read> GeozeroDatasource for CsvReader<R> {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
-        process_csv_features(&mut self.inner, processor, &self.geometry_column_name)
+        read_csv_features(
+            &mut self.inner,
+            processor,
+            &self.geometry_column_name,
+            self.has_headers,
+        )
     }
 }
 
 impl<R: Read + Clone> GeozeroGeometry for CsvReader<R> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
-        process_csv_geom(self.inner.clone(), processor, &self.geometry_column_name)
+        read_csv_geom(
+            self.inner.clone(),
+            processor,
+            &self.geometry_column_name,
+            self.has_headers,
+        )
     }
 }
 
@@ -103,8 +149,45 @@ pub fn process_csv_geom(
     processor: &mut impl GeomProcessor,
     geometry_column: &str,
 ) -> Result<()> {
-    let mut reader = csv::Reader::from_reader(input);
-    let headers = reader.headers()?.clone();
+    read_csv_geom(input, processor, geometry_column, true)
+}
+
+pub fn process_csv_features(
+    input: impl Read,
+    processor: &mut impl FeatureProcessor,
+    geometry_column: &str,
+) -> Result<()> {
+    read_csv_features(input, processor, geometry_column, true)
+}
+
+fn csv_reader<R: Read>(input: R, has_headers: bool) -> Result<(csv::Reader<R>, csv::StringRecord)> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(has_headers)
+        .from_reader(input);
+    let headers = if has_headers {
+        reader.headers()?.clone()
+    } else {
+        // Peeks at the first record without consuming it
+        (1..=reader.headers()?.len())
+            .map(|i| format!("field_{i}"))
+            .collect()
+    };
+    Ok((reader, headers))
+}
+
+/// Line number of a record for error messages.
+fn line_number(record_idx: usize, has_headers: bool) -> usize {
+    // +1 to start at line 1, +1 to account for the header row
+    record_idx + 1 + usize::from(has_headers)
+}
+
+fn read_csv_geom(
+    input: impl Read,
+    processor: &mut impl GeomProcessor,
+    geometry_column: &str,
+    has_headers: bool,
+) -> Result<()> {
+    let (reader, headers) = csv_reader(input, has_headers)?;
 
     let geometry_idx = headers
         .iter()
@@ -118,8 +201,6 @@ pub fn process_csv_geom(
         let geometry_field = record
             .get(geometry_idx)
             .ok_or(GeozeroError::ColumnNotFound)?;
-        let wkt = wkt::Wkt::from_str(geometry_field)
-            .map_err(|e| GeozeroError::Geometry(e.to_string()))?;
 
         // We don't know how many lines are in the file, so we dont' know the size of the geometry collection,
         // but at this point we *do* know that it's non-zero. Currently there aren't any other significant
@@ -132,10 +213,9 @@ pub fn process_csv_geom(
             processor.geometrycollection_begin(1, 0)?;
         }
 
-        crate::wkt::wkt_reader::process_wkt_geom_n(&wkt.item, record_idx, processor).map_err(
+        crate::wkt::wkt_parser::read_wkt_str(geometry_field, record_idx, processor).map_err(
             |e| {
-                // +2 to start at line 1 and to account for the header row
-                let line = record_idx + 2;
+                let line = line_number(record_idx, has_headers);
                 log::warn!("line {line}: invalid WKT: '{geometry_field}', record: {record:?}");
                 e
             },
@@ -149,13 +229,13 @@ pub fn process_csv_geom(
     processor.geometrycollection_end(0)
 }
 
-pub fn process_csv_features(
+fn read_csv_features(
     input: impl Read,
     processor: &mut impl FeatureProcessor,
     geometry_column: &str,
+    has_headers: bool,
 ) -> Result<()> {
-    let mut reader = csv::Reader::from_reader(input);
-    let headers = reader.headers()?.clone();
+    let (reader, headers) = csv_reader(input, has_headers)?;
     processor.dataset_begin(None)?;
 
     let geometry_idx = headers
@@ -193,8 +273,7 @@ pub fn process_csv_features(
             processor.geometry_begin()?;
             crate::wkt::wkt_reader::read_wkt(&mut geometry_field.as_bytes(), processor).map_err(
                 |e| {
-                    // +2 to start at line 1 and to account for the header row
-                    let line = feature_idx + 2;
+                    let line = line_number(feature_idx, has_headers);
                     log::warn!("line {line}: invalid WKT: '{geometry_field}', record: {record:?}");
                     e
                 },
@@ -450,4 +529,45 @@ mod tests {
         let expected = "GEOMETRYCOLLECTION EMPTY";
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn quoted_geometry_without_headers() {
+        use crate::geojson::conversion::ProcessToJson;
+
+        let input = r#"Foo,"POLYGON((1 1,2 2,1 1))"
+"Bar, Baz","POLYGON((3 1,3 2,3 1))"
+"#;
+        let mut csv = CsvReader::new("field_2", input.as_bytes()).with_headers(false);
+
+        let expected_geojson = serde_json::json!({
+            "type": "FeatureCollection",
+            "features": [
+               {
+                   "type": "Feature",
+                   "geometry": {
+                       "type": "Polygon",
+                       "coordinates": [[[1, 1], [2, 2], [1, 1]]]
+                   },
+                   "properties": {
+                       "field_1": "Foo"
+                   }
+               },
+               {
+                   "type": "Feature",
+                   "geometry": {
+                       "type": "Polygon",
+                       "coordinates": [[[3, 1], [3, 2], [3, 1]]]
+                   },
+                   "properties": {
+                       "field_1": "Bar, Baz"
+                   }
+               }
+            ]
+        });
+
+        let actual_geojson = csv.to_json().unwrap();
+        let actual_geojson: serde_json::Value = serde_json::from_str(&actual_geojson).unwrap();
+
+        assert_eq!(expected_geojson, actual_geojson)
+    }
 }
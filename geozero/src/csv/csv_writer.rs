@@ -1,11 +1,34 @@
+use crate::csv::DEFAULT_GEOMETRY_COLUMN;
 use crate::error::Result;
-use crate::wkt::WktWriter;
-use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use crate::geojson::GeoJsonWriter;
+use crate::wkt::{WktDialect, WktStr, WktWriter};
+use crate::{
+    ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, GeozeroGeometry,
+    PropertyProcessor,
+};
 
 use std::io::Write;
 
+/// Serialization of the geometry column written by [`CsvWriter`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CsvGeometryFormat {
+    /// OGC WKT, e.g. `POINT(1 2)` (default)
+    #[default]
+    Wkt,
+    /// PostGIS EWKT with SRID prefix, e.g. `SRID=4326;POINT(1 2)`
+    Ewkt,
+    /// GeoJSON geometry object, e.g. `{"type": "Point", "coordinates": [1,2]}`
+    GeoJson,
+}
+
 pub struct CsvWriter<W: Write> {
-    csv: csv::Writer<W>,
+    builder: csv::WriterBuilder,
+    /// Output, until the CSV writer is created with the configured options
+    out: Option<W>,
+    csv: Option<csv::Writer<W>>,
+    geometry_column_name: String,
+    geometry_format: CsvGeometryFormat,
+    null_string: Option<String>,
     headers: Vec<String>,
     has_written_first_record: bool,
     current_row_props: Vec<String>,
@@ -19,16 +42,96 @@ impl<W: Write> CsvWriter<W> {
 
     pub fn with_dims(out: W, dims: CoordDimensions) -> Self {
         Self {
-            csv: csv::Writer::from_writer(out),
-            headers: vec!["geometry".to_string()],
+            builder: csv::WriterBuilder::new(),
+            out: Some(out),
+            csv: None,
+            geometry_column_name: DEFAULT_GEOMETRY_COLUMN.to_string(),
+            geometry_format: CsvGeometryFormat::default(),
+            null_string: None,
+            headers: vec![DEFAULT_GEOMETRY_COLUMN.to_string()],
             has_written_first_record: false,
             current_row_props: vec![],
             wkt_writer: WktWriter::with_dims(vec![], dims),
         }
     }
 
+    /// Name of the WKT geometry column (default: `geometry`).
+    pub fn with_geometry_column(mut self, geometry_column_name: &str) -> Self {
+        self.geometry_column_name = geometry_column_name.to_string();
+        self.headers = vec![self.geometry_column_name.clone()];
+        self
+    }
+
+    /// Field delimiter (default: `b','`). Use `b'\t'` for TSV output.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.builder.delimiter(delimiter);
+        self
+    }
+
+    /// Quote character (default: `b'"'`).
+    ///
+    /// Fields containing the delimiter, the quote character or a line break are quoted,
+    /// quote characters within fields are doubled.
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.builder.quote(quote);
+        self
+    }
+
+    /// Text written for null property values and missing geometries.
+    ///
+    /// By default, null properties are written as `null` and missing geometries as empty field.
+    /// Use `""` for PostgreSQL `COPY ... (FORMAT csv)` or `"\\N"` for `COPY ... (FORMAT text)`.
+    pub fn with_null_string(mut self, null_string: &str) -> Self {
+        self.null_string = Some(null_string.to_string());
+        self
+    }
+
+    /// Serialization of the geometry column (default: WKT).
+    pub fn with_geometry_format(mut self, format: CsvGeometryFormat) -> Self {
+        let dialect = if format == CsvGeometryFormat::Ewkt {
+            WktDialect::Ewkt
+        } else {
+            WktDialect::Wkt
+        };
+        self.wkt_writer = WktWriter::with_opts(vec![], dialect, self.wkt_writer.dimensions(), None);
+        self.geometry_format = format;
+        self
+    }
+
+    fn csv(&mut self) -> &mut csv::Writer<W> {
+        let builder = &self.builder;
+        let out = &mut self.out;
+        self.csv.get_or_insert_with(|| {
+            builder.from_writer(out.take().expect("output is moved into CSV writer once"))
+        })
+    }
+
+    /// Geometry field of the current row
+    fn geometry_field(&self) -> Result<Vec<u8>> {
+        let wkt = &self.wkt_writer.out;
+        if wkt.is_empty() {
+            return Ok(self
+                .null_string
+                .as_ref()
+                .map(|null| null.as_bytes().to_vec())
+                .unwrap_or_default());
+        }
+        match self.geometry_format {
+            CsvGeometryFormat::Wkt | CsvGeometryFormat::Ewkt => Ok(wkt.clone()),
+            CsvGeometryFormat::GeoJson => {
+                let mut json = Vec::new();
+                let wkt = String::from_utf8_lossy(wkt);
+                WktStr(&wkt).process_geom(&mut GeoJsonWriter::with_dims(
+                    &mut json,
+                    self.wkt_writer.dimensions(),
+                ))?;
+                Ok(json)
+            }
+        }
+    }
+
     fn has_started_writing_geometry_in_this_row(&self) -> bool {
-        !self.wkt_writer.out.is_empty()
+        !self.wkt_writer.out.is_empty() || self.wkt_writer.has_pending_geometry()
     }
 
     fn offset_geom_idx(&self, input_idx: usize) -> usize {
@@ -43,11 +146,11 @@ impl<W: Write> CsvWriter<W> {
 
 impl<W: Write> FeatureProcessor for CsvWriter<W> {
     fn dataset_begin(&mut self, _name: Option<&str>) -> Result<()> {
-        debug_assert_eq!(self.headers, &["geometry"]);
+        debug_assert_eq!(self.headers, &[self.geometry_column_name.as_str()]);
         Ok(())
     }
     fn dataset_end(&mut self) -> Result<()> {
-        self.headers = vec!["geometry".to_string()];
+        self.headers = vec![self.geometry_column_name.clone()];
         Ok(())
     }
     fn feature_begin(&mut self, _idx: u64) -> Result<()> {
@@ -58,18 +161,20 @@ impl<W: Write> FeatureProcessor for CsvWriter<W> {
     fn feature_end(&mut self, _idx: u64) -> Result<()> {
         if !self.has_written_first_record {
             self.has_written_first_record = true;
-            self.csv.write_record(self.headers.clone())?;
+            let headers = self.headers.clone();
+            self.csv().write_record(headers)?;
         }
 
-        let geom = &self.wkt_writer.out;
-        self.csv.write_field(geom)?;
+        let geom = self.geometry_field()?;
+        self.csv().write_field(geom)?;
         self.wkt_writer.out.clear();
+        self.wkt_writer.reset_header();
 
-        for field in &self.current_row_props {
-            self.csv.write_field(field)?;
+        let props = std::mem::take(&mut self.current_row_props);
+        for field in &props {
+            self.csv().write_field(field)?;
         }
-        self.csv.write_record(None::<&[u8]>)?;
-        self.current_row_props.clear();
+        self.csv().write_record(None::<&[u8]>)?;
 
         Ok(())
     }
@@ -103,7 +208,11 @@ impl<W: Write> PropertyProcessor for CsvWriter<W> {
         }
 
         // TODO: support non-string colval
-        self.current_row_props.push(colval.to_string());
+        let field = match (&self.null_string, colval) {
+            (Some(null), ColumnValue::Json("null")) => null.clone(),
+            _ => colval.to_string(),
+        };
+        self.current_row_props.push(field);
         Ok(false)
     }
 }
@@ -112,6 +221,12 @@ impl<W: Write> GeomProcessor for CsvWriter<W> {
     fn dimensions(&self) -> CoordDimensions {
         self.wkt_writer.dimensions()
     }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.wkt_writer.srid(srid)
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.wkt_writer.input_dimensions(dimensions)
+    }
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
         self.wkt_writer.xy(x, y, idx)
     }
@@ -371,4 +486,123 @@ POINT(1 45),904 7th Av,05/22/2019 12:55:00 PM,F190051945,Car Fire
 
         assert_eq!(expected_output, actual_output);
     }
+
+    #[test]
+    fn custom_geometry_column() {
+        use crate::csv::{CsvReader, CsvWriter};
+        use crate::GeozeroDatasource;
+
+        let input = r#"name,wkt
+Foo,"POLYGON((1 1,2 2,1 1))"
+Bar,POINT(3 1)
+"#;
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = CsvWriter::new(&mut out).with_geometry_column("wkt");
+        CsvReader::new("wkt", input.as_bytes())
+            .process(&mut writer)
+            .unwrap();
+        drop(writer);
+
+        let expected_output = r#"wkt,name
+"POLYGON((1 1,2 2,1 1))",Foo
+POINT(3 1),Bar
+"#;
+        assert_eq!(expected_output, String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn tsv_with_nulls() {
+        use crate::csv::CsvWriter;
+        use crate::GeozeroDatasource;
+
+        let input_geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                { "type": "Feature", "properties": { "name": "tab\there", "note": null }, "geometry": { "type": "Point", "coordinates": [1, 2] } },
+                { "type": "Feature", "properties": { "name": "say \"hi\"", "note": "line\nbreak" }, "geometry": null }
+            ]
+        }"#;
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = CsvWriter::new(&mut out)
+            .with_delimiter(b'\t')
+            .with_null_string("");
+        crate::geojson::GeoJson(input_geojson)
+            .process(&mut writer)
+            .unwrap();
+        drop(writer);
+
+        let expected_output = "geometry\tname\tnote
+POINT(1 2)\t\"tab\there\"\t
+\t\"say \"\"hi\"\"\"\t\"line
+break\"
+";
+        assert_eq!(expected_output, String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn custom_quote() {
+        use crate::csv::CsvWriter;
+        use crate::GeozeroDatasource;
+
+        let input_geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                { "type": "Feature", "properties": { "name": "it's" }, "geometry": { "type": "LineString", "coordinates": [[1, 2], [3, 4]] } }
+            ]
+        }"#;
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = CsvWriter::new(&mut out).with_quote(b'\'');
+        crate::geojson::GeoJson(input_geojson)
+            .process(&mut writer)
+            .unwrap();
+        drop(writer);
+
+        let expected_output = "geometry,name
+'LINESTRING(1 2,3 4)','it''s'
+";
+        assert_eq!(expected_output, String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn geometry_formats() {
+        use crate::csv::{CsvGeometryFormat, CsvWriter};
+        use crate::wkt::WktStr;
+        use crate::{FeatureProcessor, GeomProcessor, GeozeroGeometry};
+
+        let write = |format: CsvGeometryFormat| {
+            let mut out: Vec<u8> = Vec::new();
+            let mut writer = CsvWriter::new(&mut out).with_geometry_format(format);
+            writer.dataset_begin(None).unwrap();
+            for (idx, wkt) in ["POINT(1 2)", "LINESTRING(1 2,3 4)"].iter().enumerate() {
+                writer.feature_begin(idx as u64).unwrap();
+                writer.geometry_begin().unwrap();
+                writer.srid(Some(4326)).unwrap();
+                WktStr(wkt).process_geom(&mut writer).unwrap();
+                writer.geometry_end().unwrap();
+                writer.feature_end(idx as u64).unwrap();
+            }
+            writer.dataset_end().unwrap();
+            drop(writer);
+            String::from_utf8(out).unwrap()
+        };
+
+        assert_eq!(
+            write(CsvGeometryFormat::Wkt),
+            "geometry\nPOINT(1 2)\n\"LINESTRING(1 2,3 4)\"\n"
+        );
+        assert_eq!(
+            write(CsvGeometryFormat::Ewkt),
+            "geometry\nSRID=4326;POINT(1 2)\n\"SRID=4326;LINESTRING(1 2,3 4)\"\n"
+        );
+        assert_eq!(
+            write(CsvGeometryFormat::GeoJson),
+            r#"geometry
+"{""type"": ""Point"", ""coordinates"": [1,2]}"
+"{""type"": ""LineString"", ""coordinates"": [[1,2],[3,4]]}"
+"#
+        );
+    }
 }
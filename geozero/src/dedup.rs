@@ -0,0 +1,418 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geom_type_filter::{Event, GeomEvent, Value};
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+use crate::wkb::{WkbDialect, WkbWriter};
+use crate::GeomType;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::Hasher;
+
+/// Feature processor wrapper removing features with an already seen geometry.
+///
+/// Geometries are compared by hashing their WKB encoding, optionally combined with the
+/// values of selected properties. Features without geometry are always passed.
+/// Emitted features are numbered contiguously. The number of removed duplicates is
+/// logged at `dataset_end` and available with [`Dedup::duplicates`].
+///
+/// Only a 64 bit hash is kept per distinct feature, but the hash set grows with the number
+/// of distinct features, i.e. about 16 bytes per feature or 160 MB for 10 million features.
+/// [`Dedup::with_max_entries`] limits the memory usage for large inputs.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{Dedup, GeozeroDatasource};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut dedup = Dedup::new(GeoJsonWriter::new(&mut out)).with_key_properties(&["name"]);
+/// GeoJson(r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+///     {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 1]}}
+/// ]}"#)
+///     .process(&mut dedup)
+///     .unwrap();
+/// assert_eq!(dedup.duplicates(), 1);
+/// # }
+/// ```
+pub struct Dedup<P: FeatureProcessor> {
+    inner: P,
+    /// Properties included in the key
+    key_properties: Vec<String>,
+    max_entries: usize,
+    /// Hashes of emitted features
+    seen: HashSet<u64>,
+    /// Buffered callbacks of the current feature
+    feature: Option<Vec<Event>>,
+    geometry: Vec<GeomEvent>,
+    duplicates: u64,
+    /// Number of emitted features
+    next_idx: u64,
+}
+
+impl<P: FeatureProcessor> Dedup<P> {
+    pub fn new(inner: P) -> Self {
+        Dedup {
+            inner,
+            key_properties: Vec::new(),
+            max_entries: usize::MAX,
+            seen: HashSet::new(),
+            feature: None,
+            geometry: Vec::new(),
+            duplicates: 0,
+            next_idx: 0,
+        }
+    }
+    /// Compare the values of these properties in addition to the geometry.
+    pub fn with_key_properties(mut self, names: &[&str]) -> Self {
+        self.key_properties = names.iter().map(|name| name.to_string()).collect();
+        self
+    }
+    /// Maximal number of hashes kept in memory (default: unlimited).
+    ///
+    /// When the limit is reached, no more hashes are added. Duplicates of features
+    /// seen after reaching the limit are passed.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+    /// Number of removed features.
+    pub fn duplicates(&self) -> u64 {
+        self.duplicates
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    /// Hash of the WKB geometry and the key properties
+    fn feature_hash(&self, events: &[Event]) -> Result<u64> {
+        let mut wkb = Vec::new();
+        let mut writer = WkbWriter::with_opts(
+            &mut wkb,
+            WkbDialect::Wkb,
+            self.inner.dimensions(),
+            None,
+            Vec::new(),
+        );
+        for geom_event in &self.geometry {
+            geom_event.replay(&mut writer)?;
+        }
+        let mut hasher = DefaultHasher::new();
+        hasher.write(&wkb);
+        for key in &self.key_properties {
+            let value = events.iter().find_map(|event| match event {
+                Event::Property(_, name, value) if name == key => Some(value),
+                _ => None,
+            });
+            // Debug output includes the value type
+            hasher.write(format!("{value:?}").as_bytes());
+        }
+        Ok(hasher.finish())
+    }
+    fn emit_feature(&mut self, events: &[Event]) -> Result<()> {
+        let idx = self.next_idx;
+        self.next_idx += 1;
+        self.inner.feature_begin(idx)?;
+        for event in events {
+            match event {
+                Event::FeatureId(id) => self.inner.feature_id(id.as_column_value())?,
+                Event::PropertiesBegin => self.inner.properties_begin()?,
+                Event::Property(i, name, value) => {
+                    self.inner.property(*i, name, &value.as_column_value())?;
+                }
+                Event::PropertiesEnd => self.inner.properties_end()?,
+                Event::Geometry => {
+                    self.inner.geometry_begin()?;
+                    for geom_event in &self.geometry {
+                        geom_event.replay(&mut self.inner)?;
+                    }
+                    self.inner.geometry_end()?;
+                }
+            }
+        }
+        self.inner.feature_end(idx)
+    }
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        if self.feature.is_some() {
+            self.geometry.push(event);
+            Ok(())
+        } else {
+            event.replay(&mut self.inner)
+        }
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for Dedup<P> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.next_idx = 0;
+        self.duplicates = 0;
+        self.seen.clear();
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        log::info!("Removed {} duplicate features", self.duplicates);
+        self.inner.dataset_end()
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.feature = Some(Vec::new());
+        self.geometry.clear();
+        Ok(())
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::FeatureId(Value::from(&id)));
+        }
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let Some(events) = self.feature.take() else {
+            return Ok(());
+        };
+        if !self.geometry.is_empty() {
+            let hash = self.feature_hash(&events)?;
+            if self.seen.contains(&hash) {
+                self.duplicates += 1;
+                return Ok(());
+            }
+            if self.seen.len() < self.max_entries {
+                self.seen.insert(hash);
+            }
+        }
+        self.emit_feature(&events)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::PropertiesBegin);
+        }
+        Ok(())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::PropertiesEnd);
+        }
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        match self.feature.as_mut() {
+            Some(events) => events.push(Event::Geometry),
+            None => self.inner.geometry_begin()?,
+        }
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        if self.feature.is_none() {
+            self.inner.geometry_end()?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: FeatureProcessor> PropertyProcessor for Dedup<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        match self.feature.as_mut() {
+            Some(events) => {
+                events.push(Event::Property(idx, name.to_string(), Value::from(value)));
+                Ok(false)
+            }
+            None => self.inner.property(idx, name, value),
+        }
+    }
+}
+
+impl<P: FeatureProcessor> GeomProcessor for Dedup<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.geom_event(GeomEvent::Srid(srid))
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.geom_event(GeomEvent::InputDimensions(dimensions))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Xy(x, y, idx))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.geom_event(GeomEvent::Coordinate(x, y, z, m, t, tm, idx))
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::EmptyPoint(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Point, true, 1, idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Point, true, idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPoint, true, size, idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPoint, true, idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::LineString, tagged, size, idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::LineString, tagged, idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiLineString, true, size, idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiLineString, true, idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Polygon, tagged, size, idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Polygon, tagged, idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPolygon, true, size, idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPolygon, true, idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::GeometryCollection,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::GeometryCollection, true, idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CircularString, true, size, idx))
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CircularString, true, idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CompoundCurve, true, size, idx))
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CompoundCurve, true, idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CurvePolygon, true, size, idx))
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CurvePolygon, true, idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiCurve, true, size, idx))
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiCurve, true, idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiSurface, true, size, idx))
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiSurface, true, idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Triangle, tagged, size, idx))
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Triangle, tagged, idx))
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::PolyhedralSurface,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::PolyhedralSurface, true, idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Tin, true, size, idx))
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Tin, true, idx))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+    use crate::GeozeroDatasource;
+
+    const INPUT: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"n": 0, "name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+        {"type": "Feature", "properties": {"n": 1, "name": "b"}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+        {"type": "Feature", "properties": {"n": 2, "name": "a"}, "geometry": {"type": "LineString", "coordinates": [[0, 0], [1, 1]]}},
+        {"type": "Feature", "properties": {"n": 3, "name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+        {"type": "Feature", "properties": {"n": 4, "name": "a"}, "geometry": null},
+        {"type": "Feature", "properties": {"n": 5, "name": "a"}, "geometry": null},
+        {"type": "Feature", "properties": {"n": 6, "name": "a"}, "geometry": {"type": "LineString", "coordinates": [[0, 0], [1, 1]]}}
+    ]}"#;
+
+    type JsonDedup<'a> = Dedup<GeoJsonWriter<&'a mut Vec<u8>>>;
+
+    /// Property `n` of the emitted features and number of duplicates
+    fn dedup(configure: impl FnOnce(JsonDedup<'_>) -> JsonDedup<'_>) -> (Vec<i64>, u64) {
+        let mut out: Vec<u8> = Vec::new();
+        let mut dedup = configure(Dedup::new(GeoJsonWriter::new(&mut out)));
+        GeoJson(INPUT).process(&mut dedup).unwrap();
+        let duplicates = dedup.duplicates();
+        drop(dedup);
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let n = json["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["properties"]["n"].as_i64().unwrap())
+            .collect();
+        (n, duplicates)
+    }
+
+    #[test]
+    fn geometry_key() {
+        assert_eq!(dedup(|dedup| dedup), (vec![0, 2, 4, 5], 3));
+    }
+
+    #[test]
+    fn property_key() {
+        assert_eq!(
+            dedup(|dedup| dedup.with_key_properties(&["name"])),
+            (vec![0, 1, 2, 4, 5], 2)
+        );
+    }
+
+    #[test]
+    fn max_entries() {
+        assert_eq!(
+            dedup(|dedup| dedup.with_max_entries(1)),
+            (vec![0, 2, 4, 5, 6], 2)
+        );
+    }
+}
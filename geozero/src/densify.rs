@@ -0,0 +1,157 @@
+use crate::error::Result;
+use crate::geom_event_processor::{geom_event_processor, Coord, LineBuffer, LineEvent};
+use crate::geom_type_filter::GeomEvent;
+use crate::geometry_processor::GeomProcessor;
+
+/// Processor wrapper inserting vertices into linestrings and polygon rings before
+/// passing them to the inner processor, so that no segment is longer than a maximal length.
+///
+/// Intermediate vertices are interpolated linearly, including Z, M and T values.
+/// Densifying long straight segments before reprojection makes them follow the
+/// curved path in the target CRS. Points, multipoints and circular strings are passed unchanged.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, wkt::WktWriter, Densify, GeozeroGeometry};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = Densify::new(WktWriter::new(&mut out), 1.0);
+/// WktStr("LINESTRING(0 0,3 0)").process_geom(&mut processor).unwrap();
+/// drop(processor);
+/// assert_eq!(String::from_utf8(out).unwrap(), "LINESTRING(0 0,1 0,2 0,3 0)");
+/// # }
+/// ```
+pub struct Densify<P: GeomProcessor> {
+    inner: P,
+    max_length: f64,
+    lines: LineBuffer,
+}
+
+impl<P: GeomProcessor> Densify<P> {
+    /// Densify segments longer than `max_length` in coordinate units.
+    pub fn new(inner: P, max_length: f64) -> Self {
+        Densify {
+            inner,
+            max_length,
+            lines: LineBuffer::new(false),
+        }
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+/// Line with each segment split into equal parts not longer than `max_length`.
+fn densify(line: &[Coord], max_length: f64) -> Vec<Coord> {
+    if line.len() < 2 || max_length.is_nan() || max_length <= 0.0 {
+        return line.to_vec();
+    }
+    let mut result = Vec::with_capacity(line.len());
+    result.push(line[0]);
+    for pair in line.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        let parts = ((b.x - a.x).hypot(b.y - a.y) / max_length).ceil();
+        if parts.is_finite() {
+            for i in 1..parts as usize {
+                result.push(interpolate(a, b, i as f64 / parts));
+            }
+        }
+        result.push(*b);
+    }
+    result
+}
+
+/// Coordinate at fraction `f` of the segment from `a` to `b`.
+fn interpolate(a: &Coord, b: &Coord, f: f64) -> Coord {
+    let lerp = |u: f64, v: f64| u + (v - u) * f;
+    let lerp_opt = |u: Option<f64>, v: Option<f64>| match (u, v) {
+        (Some(u), Some(v)) => Some(lerp(u, v)),
+        _ => u,
+    };
+    Coord {
+        x: lerp(a.x, b.x),
+        y: lerp(a.y, b.y),
+        extra: match (a.extra, b.extra) {
+            (Some((z1, m1, t1, tm)), Some((z2, m2, t2, _))) => {
+                Some((lerp_opt(z1, z2), lerp_opt(m1, m2), lerp_opt(t1, t2), tm))
+            }
+            (extra, _) => extra,
+        },
+    }
+}
+
+impl<P: GeomProcessor> Densify<P> {
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        match self.lines.push(event) {
+            LineEvent::Pass(event) => event.replay(&mut self.inner),
+            LineEvent::Buffered => Ok(()),
+            LineEvent::Line(mut line) => {
+                line.coords = densify(&line.coords, self.max_length);
+                line.replay(&mut self.inner)
+            }
+        }
+    }
+}
+
+geom_event_processor!(Densify);
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::test_util::process_wkt;
+    use crate::wkt::WktWriter;
+    use crate::CoordDimensions;
+
+    fn densify_wkt(wkt: &str, max_length: f64) -> String {
+        process_wkt(wkt, |out| Densify::new(WktWriter::new(out), max_length))
+    }
+
+    #[test]
+    fn segment() {
+        assert_eq!(
+            densify_wkt("LINESTRING(0 0,10 0)", 2.0),
+            "LINESTRING(0 0,2 0,4 0,6 0,8 0,10 0)"
+        );
+        // Segment split into equal parts
+        assert_eq!(
+            densify_wkt("LINESTRING(0 0,0 6)", 4.0),
+            "LINESTRING(0 0,0 3,0 6)"
+        );
+        // Short segments are unchanged
+        assert_eq!(
+            densify_wkt("LINESTRING(0 0,1 1,2 1)", 2.0),
+            "LINESTRING(0 0,1 1,2 1)"
+        );
+    }
+
+    #[test]
+    fn ring() {
+        assert_eq!(
+            densify_wkt("POLYGON((0 0,4 0,4 4,0 4,0 0))", 2.0),
+            "POLYGON((0 0,2 0,4 0,4 2,4 4,2 4,0 4,0 2,0 0))"
+        );
+    }
+
+    #[test]
+    fn points_unchanged() {
+        assert_eq!(
+            densify_wkt("MULTIPOINT(0 0,10 0)", 2.0),
+            "MULTIPOINT(0 0,10 0)"
+        );
+    }
+
+    #[test]
+    fn interpolate_z() {
+        assert_eq!(
+            process_wkt("LINESTRING Z (0 0 0,10 0 100)", |out| {
+                Densify::new(WktWriter::with_dims(out, CoordDimensions::xyz()), 5.0)
+            }),
+            "LINESTRING Z (0 0 0,5 0 50,10 0 100)"
+        );
+    }
+}
@@ -36,6 +36,10 @@ pub enum GeozeroError {
     Srid(i32),
     #[error("processing geometry `{0}`")]
     Geometry(String),
+    #[error("limit exceeded: {0}")]
+    LimitExceeded(String),
+    #[error("maximum nesting depth exceeded")]
+    MaxDepthExceeded,
     // General
     #[error("I/O error `{0}`")]
     IoError(#[from] std::io::Error),
@@ -45,6 +49,12 @@ pub enum GeozeroError {
     #[cfg(feature = "with-gdal")]
     #[error("GDAL error `{0}`")]
     GdalError(#[from] crate::gdal::GdalError),
+    #[cfg(feature = "with-proj")]
+    #[error("PROJ error `{0}`")]
+    ProjError(#[from] proj::ProjError),
+    #[cfg(feature = "with-proj")]
+    #[error("PROJ error `{0}`")]
+    ProjCreateError(#[from] proj::ProjCreateError),
 }
 
 pub type Result<T> = std::result::Result<T, GeozeroError>;
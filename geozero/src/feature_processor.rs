@@ -1,10 +1,16 @@
 use crate::error::Result;
 use crate::geometry_processor::GeomProcessor;
-use crate::property_processor::PropertyProcessor;
+use crate::property_processor::{ColumnValue, PropertyProcessor};
 
 /// Feature processing trait
 #[allow(unused_variables)]
 pub trait FeatureProcessor: GeomProcessor + PropertyProcessor {
+    /// Coordinate reference system of dataset (WKT or authority identifier)
+    ///
+    /// Emitted before dataset begin
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        Ok(())
+    }
     /// Begin of dataset processing
     fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
         Ok(())
@@ -17,6 +23,12 @@ pub trait FeatureProcessor: GeomProcessor + PropertyProcessor {
     fn feature_begin(&mut self, idx: u64) -> Result<()> {
         Ok(())
     }
+    /// Feature identifier
+    ///
+    /// Emitted after feature begin, if the feature has an identifier
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        Ok(())
+    }
     /// End of feature processing
     fn feature_end(&mut self, idx: u64) -> Result<()> {
         Ok(())
@@ -37,4 +49,12 @@ pub trait FeatureProcessor: GeomProcessor + PropertyProcessor {
     fn geometry_end(&mut self) -> Result<()> {
         Ok(())
     }
+    /// Geometry of the current feature is not needed
+    ///
+    /// Queried by readers after properties end. Readers may then skip decoding
+    /// the geometry and omit its callbacks. Wrappers passing feature callbacks
+    /// through to an inner processor forward this query.
+    fn skip_geometry(&self) -> bool {
+        false
+    }
 }
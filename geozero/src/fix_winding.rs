@@ -0,0 +1,141 @@
+use crate::error::Result;
+use crate::geom_event_processor::{geom_event_processor, Coord, LineBuffer, LineEvent};
+use crate::geom_type_filter::GeomEvent;
+use crate::geometry_processor::GeomProcessor;
+
+/// Orientation of polygon exterior rings. Interior rings have the opposite orientation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WindingOrder {
+    /// Counter-clockwise exterior rings, as required by GeoJSON (RFC 7946)
+    #[default]
+    CounterClockwise,
+    /// Clockwise exterior rings, as used by Shapefiles
+    Clockwise,
+}
+
+/// Processor wrapper reversing polygon rings not matching the target [`WindingOrder`]
+/// before passing them to the inner processor.
+///
+/// The first ring of a polygon is the exterior ring, all others are interior rings.
+/// Rings without area and curved rings are passed unchanged.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, wkt::WktWriter, FixWinding, GeozeroGeometry, WindingOrder};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = FixWinding::new(WktWriter::new(&mut out), WindingOrder::CounterClockwise);
+/// WktStr("POLYGON((0 0,0 1,1 1,0 0))").process_geom(&mut processor).unwrap();
+/// drop(processor);
+/// assert_eq!(String::from_utf8(out).unwrap(), "POLYGON((0 0,1 1,0 1,0 0))");
+/// # }
+/// ```
+pub struct FixWinding<P: GeomProcessor> {
+    inner: P,
+    order: WindingOrder,
+    /// Collected polygon rings
+    lines: LineBuffer,
+}
+
+impl<P: GeomProcessor> FixWinding<P> {
+    /// Enforce `order` for exterior rings and the opposite order for interior rings.
+    pub fn new(inner: P, order: WindingOrder) -> Self {
+        FixWinding {
+            inner,
+            order,
+            lines: LineBuffer::new(true),
+        }
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+/// Shoelace formula, positive for counter-clockwise rings.
+fn signed_area(ring: &[Coord]) -> f64 {
+    ring.windows(2)
+        .map(|pair| pair[0].x * pair[1].y - pair[1].x * pair[0].y)
+        .sum::<f64>()
+        / 2.0
+}
+
+impl<P: GeomProcessor> FixWinding<P> {
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        match self.lines.push(event) {
+            LineEvent::Pass(event) => event.replay(&mut self.inner),
+            LineEvent::Buffered => Ok(()),
+            LineEvent::Line(mut ring) => {
+                // The first ring of a polygon is the exterior ring
+                let exterior = ring.idx == 0;
+                let area = signed_area(&ring.coords);
+                let ccw = match self.order {
+                    WindingOrder::CounterClockwise => exterior,
+                    WindingOrder::Clockwise => !exterior,
+                };
+                if (ccw && area < 0.0) || (!ccw && area > 0.0) {
+                    ring.coords.reverse();
+                }
+                ring.replay(&mut self.inner)
+            }
+        }
+    }
+}
+
+geom_event_processor!(FixWinding);
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::test_util::process_wkt;
+    use crate::wkt::WktWriter;
+    use crate::CoordDimensions;
+
+    fn fix_winding(wkt: &str, order: WindingOrder) -> String {
+        process_wkt(wkt, |out| FixWinding::new(WktWriter::new(out), order))
+    }
+
+    #[test]
+    fn polygon_with_hole() {
+        // Clockwise exterior and counter-clockwise hole
+        let shp = "POLYGON((0 0,0 4,4 4,4 0,0 0),(1 1,2 1,2 2,1 1))";
+        let rfc = "POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,2 2,2 1,1 1))";
+        assert_eq!(fix_winding(shp, WindingOrder::CounterClockwise), rfc);
+        assert_eq!(fix_winding(rfc, WindingOrder::CounterClockwise), rfc);
+        assert_eq!(fix_winding(rfc, WindingOrder::Clockwise), shp);
+    }
+
+    #[test]
+    fn multipolygon() {
+        assert_eq!(
+            fix_winding(
+                "MULTIPOLYGON(((0 0,0 1,1 1,0 0)),((5 5,6 5,6 6,5 5)))",
+                WindingOrder::CounterClockwise
+            ),
+            "MULTIPOLYGON(((0 0,1 1,0 1,0 0)),((5 5,6 5,6 6,5 5)))"
+        );
+    }
+
+    #[test]
+    fn lines_unchanged() {
+        let wkt = "MULTILINESTRING((0 0,0 1,1 1,0 0))";
+        assert_eq!(fix_winding(wkt, WindingOrder::CounterClockwise), wkt);
+    }
+
+    #[test]
+    fn reverse_z() {
+        assert_eq!(
+            process_wkt("POLYGON Z ((0 0 1,0 1 2,1 1 3,0 0 1))", |out| {
+                FixWinding::new(
+                    WktWriter::with_dims(out, CoordDimensions::xyz()),
+                    WindingOrder::CounterClockwise,
+                )
+            }),
+            "POLYGON Z ((0 0 1,1 1 3,0 1 2,0 0 1))"
+        );
+    }
+}
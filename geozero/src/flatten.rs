@@ -0,0 +1,522 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geom_type_filter::{geometry_type, Event, GeomEvent, Value};
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+use crate::GeomType;
+
+/// Feature processor wrapper splitting geometry collection features into one feature per member.
+///
+/// Properties are duplicated for every emitted feature and features are numbered contiguously.
+/// Nested collections are flattened recursively. A collection without members results in
+/// a feature without geometry.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{FlattenCollections, GeozeroDatasource};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut flatten = FlattenCollections::new(GeoJsonWriter::new(&mut out))
+///     .with_split_multi(true)
+///     .with_source_index("src_idx");
+/// GeoJson(r#"{"type": "Feature", "properties": {"name": "a"},
+///     "geometry": {"type": "MultiPoint", "coordinates": [[1, 1], [2, 2]]}}"#)
+///     .process(&mut flatten)
+///     .unwrap();
+/// # }
+/// ```
+pub struct FlattenCollections<P: FeatureProcessor> {
+    inner: P,
+    split_multi: bool,
+    /// Property name for the index of the source feature
+    source_index: Option<String>,
+    /// Buffered callbacks of the current feature
+    feature: Option<Vec<Event>>,
+    geometry: Vec<GeomEvent>,
+    /// Index of the current source feature
+    source_idx: u64,
+    /// Number of emitted features
+    next_idx: u64,
+}
+
+/// Member geometries of the geometry starting at `events[0]`, as top-level geometries.
+///
+/// Coordinates of multipoints are converted to points.
+fn members(events: &[GeomEvent]) -> Vec<Vec<GeomEvent>> {
+    let mut members = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, event) in events.iter().enumerate() {
+        match *event {
+            GeomEvent::Begin(..) => {
+                if depth == 1 {
+                    start = i;
+                }
+                depth += 1;
+            }
+            GeomEvent::End(..) => {
+                depth -= 1;
+                if depth == 1 {
+                    members.push(top_level(&events[start..i + 1]));
+                }
+            }
+            GeomEvent::EmptyPoint(_) if depth == 1 => members.push(vec![GeomEvent::EmptyPoint(0)]),
+            GeomEvent::Xy(x, y, _) if depth == 1 => members.push(vec![
+                GeomEvent::Begin(GeomType::Point, true, 1, 0),
+                GeomEvent::Xy(x, y, 0),
+                GeomEvent::End(GeomType::Point, true, 0),
+            ]),
+            GeomEvent::Coordinate(x, y, z, m, t, tm, _) if depth == 1 => members.push(vec![
+                GeomEvent::Begin(GeomType::Point, true, 1, 0),
+                GeomEvent::Coordinate(x, y, z, m, t, tm, 0),
+                GeomEvent::End(GeomType::Point, true, 0),
+            ]),
+            _ => {}
+        }
+    }
+    members
+}
+
+/// Convert a member geometry into a tagged geometry with index 0.
+fn top_level(member: &[GeomEvent]) -> Vec<GeomEvent> {
+    let mut member = member.to_vec();
+    let last = member.len() - 1;
+    for i in [0, last] {
+        member[i] = match member[i].with_idx(0) {
+            GeomEvent::Begin(geom_type, _, size, idx) => {
+                GeomEvent::Begin(geom_type, true, size, idx)
+            }
+            GeomEvent::End(geom_type, _, idx) => GeomEvent::End(geom_type, true, idx),
+            event => event,
+        };
+    }
+    member
+}
+
+/// Split `geometry` into single geometries. Leading `srid` calls are kept for every part.
+fn flatten(geometry: &[GeomEvent], split_multi: bool, parts: &mut Vec<Vec<GeomEvent>>) {
+    let Some(start) = geometry
+        .iter()
+        .position(|event| matches!(event, GeomEvent::Begin(..) | GeomEvent::EmptyPoint(_)))
+    else {
+        return;
+    };
+    let (prefix, geom) = geometry.split_at(start);
+    let split = match geometry_type(geom) {
+        Some(GeomType::GeometryCollection) => true,
+        Some(
+            GeomType::MultiPoint
+            | GeomType::MultiLineString
+            | GeomType::MultiPolygon
+            | GeomType::MultiCurve
+            | GeomType::MultiSurface,
+        ) => split_multi,
+        _ => false,
+    };
+    if !split {
+        parts.push(geometry.to_vec());
+        return;
+    }
+    for member in members(geom) {
+        let mut part = prefix.to_vec();
+        part.extend(member);
+        flatten(&part, split_multi, parts);
+    }
+}
+
+impl<P: FeatureProcessor> FlattenCollections<P> {
+    pub fn new(inner: P) -> Self {
+        FlattenCollections {
+            inner,
+            split_multi: false,
+            source_index: None,
+            feature: None,
+            geometry: Vec::new(),
+            source_idx: 0,
+            next_idx: 0,
+        }
+    }
+    /// Split multi-geometries into single-part geometries.
+    pub fn with_split_multi(mut self, split_multi: bool) -> Self {
+        self.split_multi = split_multi;
+        self
+    }
+    /// Add the index of the source feature as property `name`.
+    pub fn with_source_index(mut self, name: &str) -> Self {
+        self.source_index = Some(name.to_string());
+        self
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        if self.feature.is_some() {
+            self.geometry.push(event);
+            Ok(())
+        } else {
+            event.replay(&mut self.inner)
+        }
+    }
+    fn source_index_property(&mut self, idx: usize) -> Result<()> {
+        if let Some(name) = &self.source_index {
+            self.inner
+                .property(idx, name, &ColumnValue::ULong(self.source_idx))?;
+        }
+        Ok(())
+    }
+    fn emit_feature(&mut self, events: &[Event], geometry: Option<&[GeomEvent]>) -> Result<()> {
+        let idx = self.next_idx;
+        self.next_idx += 1;
+        self.inner.feature_begin(idx)?;
+        let mut n_props = 0;
+        let mut has_properties = false;
+        for event in events {
+            match event {
+                Event::FeatureId(id) => self.inner.feature_id(id.as_column_value())?,
+                Event::PropertiesBegin => self.inner.properties_begin()?,
+                Event::Property(i, name, value) => {
+                    n_props = i + 1;
+                    self.inner.property(*i, name, &value.as_column_value())?;
+                }
+                Event::PropertiesEnd => {
+                    self.source_index_property(n_props)?;
+                    self.inner.properties_end()?;
+                    has_properties = true;
+                }
+                Event::Geometry => {
+                    if let Some(geometry) = geometry {
+                        self.inner.geometry_begin()?;
+                        for geom_event in geometry {
+                            geom_event.replay(&mut self.inner)?;
+                        }
+                        self.inner.geometry_end()?;
+                    }
+                }
+            }
+        }
+        if !has_properties && self.source_index.is_some() {
+            self.inner.properties_begin()?;
+            self.source_index_property(0)?;
+            self.inner.properties_end()?;
+        }
+        self.inner.feature_end(idx)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for FlattenCollections<P> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.next_idx = 0;
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.source_idx = idx;
+        self.feature = Some(Vec::new());
+        self.geometry.clear();
+        Ok(())
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::FeatureId(Value::from(&id)));
+        }
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let Some(events) = self.feature.take() else {
+            return Ok(());
+        };
+        let geometry = std::mem::take(&mut self.geometry);
+        let mut parts = Vec::new();
+        flatten(&geometry, self.split_multi, &mut parts);
+        if parts.is_empty() {
+            return self.emit_feature(&events, None);
+        }
+        for part in parts {
+            self.emit_feature(&events, Some(&part))?;
+        }
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::PropertiesBegin);
+        }
+        Ok(())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::PropertiesEnd);
+        }
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        match self.feature.as_mut() {
+            Some(events) => events.push(Event::Geometry),
+            None => self.inner.geometry_begin()?,
+        }
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        if self.feature.is_none() {
+            self.inner.geometry_end()?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: FeatureProcessor> PropertyProcessor for FlattenCollections<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        match self.feature.as_mut() {
+            Some(events) => {
+                events.push(Event::Property(idx, name.to_string(), Value::from(value)));
+                Ok(false)
+            }
+            None => self.inner.property(idx, name, value),
+        }
+    }
+}
+
+impl<P: FeatureProcessor> GeomProcessor for FlattenCollections<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.geom_event(GeomEvent::Srid(srid))
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.geom_event(GeomEvent::InputDimensions(dimensions))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Xy(x, y, idx))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.geom_event(GeomEvent::Coordinate(x, y, z, m, t, tm, idx))
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::EmptyPoint(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Point, true, 1, idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Point, true, idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPoint, true, size, idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPoint, true, idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::LineString, tagged, size, idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::LineString, tagged, idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiLineString, true, size, idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiLineString, true, idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Polygon, tagged, size, idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Polygon, tagged, idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPolygon, true, size, idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPolygon, true, idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::GeometryCollection,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::GeometryCollection, true, idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CircularString, true, size, idx))
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CircularString, true, idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CompoundCurve, true, size, idx))
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CompoundCurve, true, idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CurvePolygon, true, size, idx))
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CurvePolygon, true, idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiCurve, true, size, idx))
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiCurve, true, idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiSurface, true, size, idx))
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiSurface, true, idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Triangle, tagged, size, idx))
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Triangle, tagged, idx))
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::PolyhedralSurface,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::PolyhedralSurface, true, idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Tin, true, size, idx))
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Tin, true, idx))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+    use crate::GeozeroDatasource;
+    use serde_json::json;
+
+    const INPUT: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"n": 0}, "geometry": {"type": "GeometryCollection", "geometries": [
+            {"type": "Point", "coordinates": [1, 1]},
+            {"type": "MultiPolygon", "coordinates": [
+                [[[0, 0], [2, 0], [2, 2], [0, 0]]],
+                [[[0, 0], [3, 0], [3, 3], [0, 0]]]
+            ]}
+        ]}},
+        {"type": "Feature", "properties": {"n": 1}, "geometry": {"type": "MultiPoint", "coordinates": [[0, 0], [1, 1]]}},
+        {"type": "Feature", "properties": {"n": 2}, "geometry": {"type": "LineString", "coordinates": [[0, 0], [1, 1]]}}
+    ]}"#;
+
+    fn flatten(split_multi: bool) -> serde_json::Value {
+        let mut out: Vec<u8> = Vec::new();
+        let mut flatten = FlattenCollections::new(GeoJsonWriter::new(&mut out))
+            .with_split_multi(split_multi)
+            .with_source_index("src");
+        GeoJson(INPUT).process(&mut flatten).unwrap();
+        drop(flatten);
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    fn features(json: &serde_json::Value) -> Vec<(i64, u64, String)> {
+        json["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| {
+                (
+                    f["properties"]["n"].as_i64().unwrap(),
+                    f["properties"]["src"].as_u64().unwrap(),
+                    f["geometry"]["type"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn collections() {
+        let json = flatten(false);
+        assert_eq!(
+            features(&json),
+            vec![
+                (0, 0, "Point".to_string()),
+                (0, 0, "MultiPolygon".to_string()),
+                (1, 1, "MultiPoint".to_string()),
+                (2, 2, "LineString".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_multi() {
+        let json = flatten(true);
+        assert_eq!(
+            features(&json),
+            vec![
+                (0, 0, "Point".to_string()),
+                (0, 0, "Polygon".to_string()),
+                (0, 0, "Polygon".to_string()),
+                (1, 1, "Point".to_string()),
+                (1, 1, "Point".to_string()),
+                (2, 2, "LineString".to_string()),
+            ]
+        );
+        assert_eq!(
+            json["features"][2]["geometry"],
+            json!({"type": "Polygon", "coordinates": [[[0, 0], [3, 0], [3, 3], [0, 0]]]})
+        );
+        assert_eq!(
+            json["features"][4]["geometry"],
+            json!({"type": "Point", "coordinates": [1, 1]})
+        );
+    }
+
+    #[derive(Default)]
+    struct FeatureIndices(Vec<u64>);
+
+    impl GeomProcessor for FeatureIndices {}
+    impl PropertyProcessor for FeatureIndices {}
+    impl FeatureProcessor for FeatureIndices {
+        fn feature_begin(&mut self, idx: u64) -> Result<()> {
+            self.0.push(idx);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn renumbered_indices() {
+        let mut flatten = FlattenCollections::new(FeatureIndices::default()).with_split_multi(true);
+        GeoJson(INPUT).process(&mut flatten).unwrap();
+        assert_eq!(flatten.into_inner().0, vec![0, 1, 2, 3, 4, 5]);
+    }
+}
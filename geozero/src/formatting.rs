@@ -0,0 +1,32 @@
+//! Number formatting shared by text writers.
+use crate::error::Result;
+use std::io::Write;
+
+/// Maximal number of decimals for integer scaled rounding
+pub(crate) const MAX_PRECISION: usize = 15;
+
+/// Write `v` rounded to `decimals` places, without trailing zeros.
+pub(crate) fn write_rounded<W: Write>(mut out: W, v: f64, decimals: usize) -> Result<()> {
+    let scale = 10u64.pow(decimals as u32);
+    let scaled = (v * scale as f64).round();
+    if !scaled.is_finite() || scaled.abs() >= u64::MAX as f64 {
+        // Out of range for integer scaling
+        write!(out, "{v}")?;
+        return Ok(());
+    }
+    let abs = scaled.abs() as u64;
+    let (int_part, mut frac_part) = (abs / scale, abs % scale);
+    if scaled < 0.0 && abs > 0 {
+        out.write_all(b"-")?;
+    }
+    write!(out, "{int_part}")?;
+    if frac_part > 0 {
+        let mut width = decimals;
+        while frac_part % 10 == 0 {
+            frac_part /= 10;
+            width -= 1;
+        }
+        write!(out, ".{frac_part:0width$}")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use crate::error::Result;
+use crate::gdal::process_geom;
+use crate::{ColumnValue, FeatureProcessor, GeozeroDatasource, PropertyProcessor};
+use gdal::vector::{FieldValue, Layer, LayerAccess};
+use serde_json::json;
+
+/// Reader for GDAL/OGR vector layers.
+///
+/// Makes all vector formats supported by GDAL (FileGDB, KML, GML, DXF, ...) available as datasource.
+pub struct GdalDatasource<'a> {
+    layer: Layer<'a>,
+}
+
+impl<'a> GdalDatasource<'a> {
+    pub fn new(layer: Layer<'a>) -> Self {
+        GdalDatasource { layer }
+    }
+
+    /// Spatial reference of the layer as authority identifier (e.g. `EPSG:4326`) or WKT.
+    pub fn crs(&self) -> Option<String> {
+        let srs = self.layer.spatial_ref()?;
+        match (srs.auth_name(), srs.auth_code()) {
+            (Ok(name), Ok(code)) => Some(format!("{name}:{code}")),
+            _ => srs.to_wkt().ok(),
+        }
+    }
+}
+
+impl GeozeroDatasource for GdalDatasource<'_> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        if let Some(crs) = self.crs() {
+            processor.crs(&crs)?;
+        }
+        let name = self.layer.name();
+        processor.dataset_begin(Some(&name))?;
+        for (idx, feature) in self.layer.features().enumerate() {
+            let idx = idx as u64;
+            processor.feature_begin(idx)?;
+            if let Some(fid) = feature.fid() {
+                processor.feature_id(ColumnValue::ULong(fid))?;
+            }
+            processor.properties_begin()?;
+            for (i, (name, value)) in feature.fields().enumerate() {
+                if let Some(value) = value {
+                    if process_field(i, &name, &value, processor)? {
+                        break;
+                    }
+                }
+            }
+            processor.properties_end()?;
+            if let Some(geom) = feature.geometry() {
+                processor.geometry_begin()?;
+                process_geom(geom, processor)?;
+                processor.geometry_end()?;
+            }
+            processor.feature_end(idx)?;
+        }
+        processor.dataset_end()
+    }
+}
+
+/// Process GDAL field value. List values are passed as JSON arrays.
+fn process_field<P: PropertyProcessor>(
+    i: usize,
+    name: &str,
+    value: &FieldValue,
+    processor: &mut P,
+) -> Result<bool> {
+    match value {
+        FieldValue::IntegerValue(v) => processor.property(i, name, &ColumnValue::Int(*v)),
+        FieldValue::Integer64Value(v) => processor.property(i, name, &ColumnValue::Long(*v)),
+        FieldValue::RealValue(v) => processor.property(i, name, &ColumnValue::Double(*v)),
+        FieldValue::StringValue(v) => processor.property(i, name, &ColumnValue::String(v)),
+        FieldValue::DateValue(v) => {
+            processor.property(i, name, &ColumnValue::DateTime(&v.to_string()))
+        }
+        FieldValue::DateTimeValue(v) => {
+            processor.property(i, name, &ColumnValue::DateTime(&v.to_rfc3339()))
+        }
+        FieldValue::IntegerListValue(v) => process_json(i, name, json!(v), processor),
+        FieldValue::Integer64ListValue(v) => process_json(i, name, json!(v), processor),
+        FieldValue::RealListValue(v) => process_json(i, name, json!(v), processor),
+        FieldValue::StringListValue(v) => process_json(i, name, json!(v), processor),
+    }
+}
+
+fn process_json<P: PropertyProcessor>(
+    i: usize,
+    name: &str,
+    value: serde_json::Value,
+    processor: &mut P,
+) -> Result<bool> {
+    processor.property(i, name, &ColumnValue::Json(&value.to_string()))
+}
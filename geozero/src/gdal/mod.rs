@@ -1,8 +1,10 @@
 //! GDAL conversions.
+pub(crate) mod gdal_datasource;
 mod gdal_error;
 pub(crate) mod gdal_reader;
 pub(crate) mod gdal_writer;
 
+pub use gdal_datasource::*;
 pub use gdal_error::GdalError;
 pub use gdal_reader::*;
 pub use gdal_writer::*;
@@ -1,6 +1,6 @@
 use crate::error::Result;
-use crate::{GeomProcessor, GeozeroGeometry};
-use geo_types::{Coord, Geometry, LineString, Polygon};
+use crate::{FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry};
+use geo_types::{Coord, Geometry, GeometryCollection, LineString, Polygon};
 
 impl GeozeroGeometry for Geometry<f64> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
@@ -8,6 +8,38 @@ impl GeozeroGeometry for Geometry<f64> {
     }
 }
 
+/// Members of the collection are processed as features without properties.
+impl GeozeroDatasource for GeometryCollection<f64> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        process_geometries(&self.0, processor)
+    }
+}
+
+/// Geometries are processed as features without properties.
+impl GeozeroDatasource for Vec<Geometry<f64>> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        process_geometries(self, processor)
+    }
+}
+
+/// Process geo-types geometries as features without properties.
+pub fn process_geometries<P: FeatureProcessor>(
+    geoms: &[Geometry<f64>],
+    processor: &mut P,
+) -> Result<()> {
+    processor.dataset_begin(None)?;
+    for (idx, geom) in geoms.iter().enumerate() {
+        processor.feature_begin(idx as u64)?;
+        processor.properties_begin()?;
+        processor.properties_end()?;
+        processor.geometry_begin()?;
+        process_geom_n(geom, 0, processor)?;
+        processor.geometry_end()?;
+        processor.feature_end(idx as u64)?;
+    }
+    processor.dataset_end()
+}
+
 /// Process geo-types geometry.
 pub fn process_geom<P: GeomProcessor>(geom: &Geometry<f64>, processor: &mut P) -> Result<()> {
     process_geom_n(geom, 0, processor)
@@ -102,6 +134,10 @@ fn process_polygon<P: GeomProcessor>(
     processor: &mut P,
 ) -> Result<()> {
     let interiors = geom.interiors();
+    if geom.exterior().0.is_empty() && interiors.is_empty() {
+        processor.polygon_begin(tagged, 0, idx)?;
+        return processor.polygon_end(tagged, idx);
+    }
     processor.polygon_begin(tagged, interiors.len() + 1, idx)?;
     // Exterior ring
     process_linestring(geom.exterior(), false, 0, processor)?;
@@ -174,4 +210,33 @@ mod test {
         let geo = Geometry::try_from(wkt::Wkt::from_str(wkt).unwrap()).unwrap();
         assert_eq!(geo.to_wkt().unwrap(), wkt);
     }
+
+    #[test]
+    fn collection_datasource() {
+        let mut collection = GeometryCollection(vec![
+            geo_types::Point::new(1.0, 1.0).into(),
+            geo_types::LineString::from(vec![(1.0, 1.0), (2.0, 2.0)]).into(),
+        ]);
+        let mut wkt_data: Vec<u8> = Vec::new();
+        collection
+            .process(&mut WktWriter::new(&mut wkt_data))
+            .unwrap();
+        assert_eq!(
+            std::str::from_utf8(&wkt_data).unwrap(),
+            "POINT(1 1)LINESTRING(1 1,2 2)"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn vec_to_geojson() {
+        use crate::ProcessToJson;
+        let mut geoms: Vec<Geometry<f64>> = vec![geo_types::Point::new(1.0, 2.0).into()];
+        assert_eq!(
+            geoms.to_json().unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1,2]}}]}"#
+        );
+    }
 }
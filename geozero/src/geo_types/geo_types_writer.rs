@@ -7,6 +7,9 @@ use geo_types::{
 use std::mem;
 
 /// Generator for geo-types geometry type.
+///
+/// geo-types has no empty Point, so empty points are mapped to an empty MultiPoint,
+/// or skipped when they are members of a MultiPoint.
 #[derive(Default)]
 pub struct GeoWriter {
     geoms: Vec<Geometry<f64>>,
@@ -58,6 +61,14 @@ impl GeomProcessor for GeoWriter {
         Ok(())
     }
 
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        if self.coords.is_some() {
+            // Member of a MultiPoint
+            return Ok(());
+        }
+        self.finish_geometry(MultiPoint(vec![]).into())
+    }
+
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
         debug_assert!(self.coords.is_none());
         self.coords = Some(Vec::with_capacity(1));
@@ -69,8 +80,11 @@ impl GeomProcessor for GeoWriter {
             .coords
             .take()
             .ok_or(GeozeroError::Geometry("No coords for Point".to_string()))?;
-        debug_assert!(coords.len() == 1);
-        self.finish_geometry(Point(coords[0]).into())
+        debug_assert!(coords.len() <= 1);
+        match coords.first() {
+            Some(coord) => self.finish_geometry(Point(*coord).into()),
+            None => self.finish_geometry(MultiPoint(vec![]).into()),
+        }
     }
 
     fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
@@ -288,6 +302,19 @@ mod test {
         assert!(wkt.to_geo().is_ok());
     }
 
+    #[test]
+    fn empty_point() {
+        use crate::wkt::WktStr;
+        let actual = WktStr("GEOMETRYCOLLECTION(POINT EMPTY,POINT(1 2))")
+            .to_geo()
+            .unwrap();
+        let expected = Geometry::GeometryCollection(GeometryCollection(vec![
+            MultiPoint(vec![]).into(),
+            Point::new(1.0, 2.0).into(),
+        ]));
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn to_geo() -> Result<()> {
         let geom: Geometry<f64> = Point::new(10.0, 20.0).into();
@@ -41,6 +41,44 @@ mod wkb {
                 .ok_or(GeozeroError::Geometry("Missing Geometry".to_string()))
         }
     }
+
+    #[cfg(test)]
+    mod test {
+        use crate::wkb::{FromWkb, WkbDialect};
+        use crate::{CoordDimensions, ToGeo, ToWkb};
+        use geo_types::{
+            line_string, point, polygon, Geometry, GeometryCollection, MultiLineString, MultiPoint,
+            MultiPolygon,
+        };
+
+        #[test]
+        fn wkb_roundtrip() {
+            let poly =
+                polygon![(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0), (x: 0.0, y: 0.0)];
+            let line = line_string![(x: 1.0, y: 2.0), (x: 3.0, y: 4.0)];
+            let geoms: Vec<Geometry<f64>> = vec![
+                point!(x: 1.0, y: 2.0).into(),
+                line.clone().into(),
+                poly.clone().into(),
+                MultiPoint(vec![point!(x: 1.0, y: 2.0), point!(x: 3.0, y: 4.0)]).into(),
+                MultiLineString(vec![line.clone(), line.clone()]).into(),
+                MultiPolygon(vec![poly.clone(), poly.clone()]).into(),
+                Geometry::GeometryCollection(GeometryCollection(vec![
+                    point!(x: 1.0, y: 2.0).into(),
+                    Geometry::GeometryCollection(GeometryCollection(vec![
+                        line.into(),
+                        poly.into(),
+                    ])),
+                ])),
+            ];
+            for geom in geoms {
+                let wkb = geom.to_wkb(CoordDimensions::xy()).unwrap();
+                let actual = Geometry::from_wkb(&mut wkb.as_slice(), WkbDialect::Wkb).unwrap();
+                assert_eq!(actual, geom);
+                assert_eq!(geom.to_geo().unwrap(), geom);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
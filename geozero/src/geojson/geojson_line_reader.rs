@@ -3,7 +3,8 @@ use crate::{
     FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
 };
 
-use super::{process_geojson_geom_n, process_properties};
+use super::geojson_reader::{parse_geojson, process_properties_with_mode};
+use super::{process_feature_id, process_geojson_geom_n, PropertyMode};
 
 use std::io::{BufRead, BufReader, Read};
 
@@ -12,25 +13,81 @@ use geojson::{Feature, GeoJson as GeoGeoJson, Geometry};
 /// Line Delimited GeoJSON Reader: One feature per line.
 ///
 /// See <https://jsonlines.org>
-pub struct GeoJsonLineReader<R: Read>(pub(crate) R);
-impl<R: Read> GeoJsonLineReader<R> {
+pub struct GeoJsonLineReader<'a, R: Read> {
+    reader: R,
+    progress: Option<(u64, ProgressFn<'a>)>,
+    capacity: Option<usize>,
+    property_mode: PropertyMode,
+}
+
+type ProgressFn<'a> = Box<dyn FnMut(u64, Option<u64>) + 'a>;
+
+impl<'a, R: Read> GeoJsonLineReader<'a, R> {
     pub fn new(read: R) -> Self {
-        Self(read)
+        Self {
+            reader: read,
+            progress: None,
+            capacity: None,
+            property_mode: PropertyMode::default(),
+        }
+    }
+
+    /// Conversion of property values
+    pub fn with_property_mode(mut self, property_mode: PropertyMode) -> Self {
+        self.property_mode = property_mode;
+        self
+    }
+
+    /// Read lines with a buffer of `capacity` bytes instead of the `BufReader` default
+    ///
+    /// Larger buffers speed up sequential reads from spinning disks or network filesystems.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    /// Call `callback` with the number of processed lines after every `interval` lines
+    /// and at the end of the input
+    ///
+    /// The total is always `None`, since the input is streamed.
+    pub fn with_progress(
+        mut self,
+        interval: u64,
+        callback: impl FnMut(u64, Option<u64>) + 'a,
+    ) -> Self {
+        self.progress = Some((interval.max(1), Box::new(callback)));
+        self
     }
 }
 
-impl<R: Read + Clone> GeozeroGeometry for GeoJsonLineReader<R> {
+impl<R: Read + Clone> GeozeroGeometry for GeoJsonLineReader<'_, R> {
     fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()>
     where
         Self: Sized,
     {
-        read_geojson_line_geometries(&mut self.0.clone(), processor)
+        read_geojson_line_geometries(&mut self.reader.clone(), processor)
     }
 }
 
-impl<R: Read> GeozeroDatasource for GeoJsonLineReader<R> {
+impl<R: Read> GeozeroDatasource for GeoJsonLineReader<'_, R> {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
-        read_geojson_lines(&mut self.0, processor)
+        let buf_reader = match self.capacity {
+            Some(capacity) => BufReader::with_capacity(capacity, &mut self.reader),
+            None => BufReader::new(&mut self.reader),
+        };
+        let property_mode = self.property_mode;
+        match &mut self.progress {
+            Some((interval, callback)) => {
+                let mut reported = 0;
+                read_lines(buf_reader, property_mode, processor, |processed, done| {
+                    if processed != reported && (done || processed % *interval == 0) {
+                        reported = processed;
+                        callback(processed, None);
+                    }
+                })
+            }
+            None => read_lines(buf_reader, property_mode, processor, |_, _| {}),
+        }
     }
 }
 
@@ -58,7 +115,7 @@ pub fn read_geojson_line_geometries(
     };
 
     for (idx, line) in buf_reader.lines().enumerate() {
-        match line?.parse::<GeoGeoJson>()? {
+        match parse_geojson(&line?)? {
             GeoGeoJson::Feature(feature) => {
                 if let Some(geometry) = feature.geometry {
                     add_geometry_to_collection(idx, &geometry)?;
@@ -81,33 +138,59 @@ pub fn read_geojson_line_geometries(
 
 /// Read and process line delimited GeoJSON (one object per line).
 pub fn read_geojson_lines(reader: impl Read, processor: &mut impl FeatureProcessor) -> Result<()> {
-    let buf_reader = BufReader::new(reader);
+    read_lines(
+        BufReader::new(reader),
+        PropertyMode::default(),
+        processor,
+        |_, _| {},
+    )
+}
 
+/// Process lines, calling `progress` with the line count after each line and once at the end
+fn read_lines(
+    buf_reader: impl BufRead,
+    property_mode: PropertyMode,
+    processor: &mut impl FeatureProcessor,
+    mut progress: impl FnMut(u64, bool),
+) -> Result<()> {
     processor.dataset_begin(None)?;
+    let mut processed = 0;
     for (idx, line) in buf_reader.lines().enumerate() {
-        match line?.parse::<GeoGeoJson>()? {
-            GeoGeoJson::Feature(feature) => process_feature(processor, idx, &feature)?,
+        match parse_geojson(&line?)? {
+            GeoGeoJson::Feature(feature) => {
+                process_feature(processor, idx, &feature, property_mode)?
+            }
             GeoGeoJson::Geometry(geometry) => process_geometry(processor, idx, &geometry)?,
             _ => {
                 return Err(GeozeroError::Dataset("line-delimited GeoJson ('geojsonl') files must have one Feature or Geometry per line".to_string()));
             }
         }
+        processed += 1;
+        progress(processed, false);
     }
-    processor.dataset_end()
+    processor.dataset_end()?;
+    progress(processed, true);
+    Ok(())
 }
 
 fn process_feature(
     processor: &mut impl FeatureProcessor,
     idx: usize,
     feature: &Feature,
+    property_mode: PropertyMode,
 ) -> Result<()> {
     processor.feature_begin(idx as u64)?;
+    process_feature_id(feature, processor)?;
     if let Some(ref properties) = feature.properties {
         processor.properties_begin()?;
-        process_properties(properties, processor)?;
+        process_properties_with_mode(properties, property_mode, processor)?;
         processor.properties_end()?;
     }
-    if let Some(ref geometry) = feature.geometry {
+    if let Some(geometry) = feature
+        .geometry
+        .as_ref()
+        .filter(|_| !processor.skip_geometry())
+    {
         processor.geometry_begin()?;
         process_geometry(processor, 0, geometry)?;
         processor.geometry_end()?;
@@ -137,7 +220,7 @@ mod tests {
 { "type": "Point", "coordinates": [2.1, 2.2] }
 { "type": "Point", "coordinates": [3.1, 3.2] }
 "#;
-        let reader = GeoJsonLineReader(input.as_bytes());
+        let reader = GeoJsonLineReader::new(input.as_bytes());
         let wkt = reader.to_wkt().unwrap();
         let expected = "GEOMETRYCOLLECTION(POINT(1.1 1.2),POINT(2.1 2.2),POINT(3.1 3.2))";
         assert_eq!(wkt, expected);
@@ -149,7 +232,7 @@ mod tests {
 { "type": "Feature", "geometry": { "type": "Point", "coordinates": [2.1, 2.2] }, "properties": { "name": "second" } }
 { "type": "Feature", "geometry": { "type": "Point", "coordinates": [3.1, 3.3] }, "properties": { "name": "third" } }
 "#;
-        let mut reader = GeoJsonLineReader(input.as_bytes());
+        let mut reader = GeoJsonLineReader::new(input.as_bytes());
         let json_string = reader.to_json().unwrap();
         let json: serde_json::Value = serde_json::from_str(&json_string)
             .unwrap_or_else(|err| panic!("invalid json: `{json_string}`: {err}"));
@@ -163,13 +246,23 @@ mod tests {
         assert_eq!(json, expected);
     }
 
+    #[test]
+    fn small_buffer() {
+        let input = r#"{ "type": "Feature", "geometry": { "type": "Point", "coordinates": [1.1, 1.2] }, "properties": { "name": "first" } }
+{ "type": "Feature", "geometry": { "type": "Point", "coordinates": [2.1, 2.2] }, "properties": { "name": "second" } }
+"#;
+        let expected = GeoJsonLineReader::new(input.as_bytes()).to_json().unwrap();
+        let mut reader = GeoJsonLineReader::new(input.as_bytes()).with_capacity(8);
+        assert_eq!(reader.to_json().unwrap(), expected);
+    }
+
     #[test]
     fn malformed_json() {
         let input = r#"{ "type": "Feature", "geometry": { "type": "Point", "coordinates": [1.1, 1.2] }, "properties": { "name": "first" } }
 ooops this is malformed json { "type": "Feature", "geometry": { "type": "Point", "coordinates": [2.1, 2.2] }, "properties": { "name": "second" } }
 { "type": "Feature", "geometry": { "type": "Point", "coordinates": [3.1, 3.3] }, "properties": { "name": "third" } }
 "#;
-        let mut reader = GeoJsonLineReader(input.as_bytes());
+        let mut reader = GeoJsonLineReader::new(input.as_bytes());
         _ = reader.to_json().unwrap_err();
     }
     #[test]
@@ -182,7 +275,21 @@ ooops this is malformed json { "type": "Feature", "geometry": { "type": "Point",
             }, "
             properties": { "name": "first" }
         }"#;
-        let mut reader = GeoJsonLineReader(input.as_bytes());
+        let mut reader = GeoJsonLineReader::new(input.as_bytes());
         _ = reader.to_json().unwrap_err();
     }
+
+    #[test]
+    fn progress() {
+        let input = r#"{ "type": "Point", "coordinates": [1.1, 1.2] }
+{ "type": "Point", "coordinates": [2.1, 2.2] }
+{ "type": "Point", "coordinates": [3.1, 3.2] }
+"#;
+        let mut calls = Vec::new();
+        let mut reader = GeoJsonLineReader::new(input.as_bytes())
+            .with_progress(2, |processed, total| calls.push((processed, total)));
+        reader.to_json().unwrap();
+        drop(reader);
+        assert_eq!(calls, vec![(2, None), (3, None)]);
+    }
 }
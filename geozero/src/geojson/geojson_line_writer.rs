@@ -0,0 +1,163 @@
+use crate::error::Result;
+use crate::geojson::GeoJsonWriter;
+use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::io::Write;
+
+/// Line Delimited GeoJSON Writer: One feature per line.
+///
+/// Features are written as compact GeoJSON `Feature` objects without an enclosing
+/// `FeatureCollection`. The output is flushed after each feature.
+///
+/// See <https://jsonlines.org>
+pub struct GeoJsonLineWriter<W: Write> {
+    writer: GeoJsonWriter<W>,
+}
+
+impl<W: Write> GeoJsonLineWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self::with_dims(out, CoordDimensions::default())
+    }
+    pub fn with_dims(out: W, dims: CoordDimensions) -> Self {
+        GeoJsonLineWriter {
+            writer: GeoJsonWriter::with_dims(out, dims),
+        }
+    }
+}
+
+impl<W: Write> FeatureProcessor for GeoJsonLineWriter<W> {
+    fn dataset_begin(&mut self, _name: Option<&str>) -> Result<()> {
+        Ok(())
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        // Every feature starts on a new line, without separator
+        self.writer.feature_begin(0)
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        self.writer.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.writer.feature_end(idx)?;
+        self.writer.out.write_all(b"\n")?;
+        self.writer.out.flush()?;
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.writer.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.writer.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.writer.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.writer.geometry_end()
+    }
+}
+
+impl<W: Write> GeomProcessor for GeoJsonLineWriter<W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.writer.dimensions()
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.writer.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.writer.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.writer.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.writer.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.writer.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.writer.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.writer.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.writer.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.writer.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.writer.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.writer.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.writer.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.writer.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.writer.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.writer.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.writer.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.writer.geometrycollection_end(idx)
+    }
+}
+
+impl<W: Write> PropertyProcessor for GeoJsonLineWriter<W> {
+    fn property(&mut self, i: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        self.writer.property(i, colname, colval)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geojson::{read_geojson, GeoJsonLineReader};
+    use crate::GeozeroDatasource;
+
+    #[test]
+    fn feature_collection() -> Result<()> {
+        let geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "properties": {"name": "first"}, "geometry": {"type": "Point", "coordinates": [1.1,1.2]}},
+                {"type": "Feature", "properties": {"name": "second"}, "geometry": {"type": "LineString", "coordinates": [[2.1,2.2],[3.1,3.2]]}}
+            ]
+        }"#;
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson(geojson.as_bytes(), &mut GeoJsonLineWriter::new(&mut out))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{"type": "Feature", "properties": {"name": "first"}, "geometry": {"type": "Point", "coordinates": [1.1,1.2]}}
+{"type": "Feature", "properties": {"name": "second"}, "geometry": {"type": "LineString", "coordinates": [[2.1,2.2],[3.1,3.2]]}}
+"#
+        );
+
+        // Roundtrip
+        let mut out2: Vec<u8> = Vec::new();
+        GeoJsonLineReader::new(out.as_slice()).process(&mut GeoJsonLineWriter::new(&mut out2))?;
+        assert_eq!(out, out2);
+        Ok(())
+    }
+}
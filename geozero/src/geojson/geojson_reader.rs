@@ -1,13 +1,13 @@
-use crate::error::Result;
+use crate::error::{GeozeroError, Result};
 use crate::{
     ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry,
     PropertyProcessor,
 };
-use geojson::{Feature, FeatureReader};
+use geojson::{feature::Id, Feature, FeatureReader};
 use geojson::{GeoJson as GeoGeoJson, Geometry, Value};
 use serde_json::map::Map;
 use serde_json::value::Value as JsonValue;
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read};
 
 /// GeoJSON String.
 #[derive(Debug)]
@@ -43,28 +43,263 @@ impl GeozeroDatasource for GeoJson<'_> {
 /// GeoJSON Reader.
 pub struct GeoJsonReader<R: Read>(pub R);
 
+/// GeoJSON Reader with configurable property conversion.
+///
+/// Created with [`GeoJsonReader::with_property_mode`].
+pub struct GeoJsonReaderWithMode<R: Read> {
+    reader: R,
+    property_mode: PropertyMode,
+}
+
+/// Conversion of GeoJSON property values to [`ColumnValue`]s.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PropertyMode {
+    pub numbers: NumberMode,
+    pub nested: NestedMode,
+}
+
+/// Typing of GeoJSON number properties.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NumberMode {
+    /// Integral numbers as `Long` (`ULong` above `i64::MAX`), others as `Double` (default)
+    #[default]
+    Narrowest,
+    /// All numbers as `Double`
+    Double,
+}
+
+/// Handling of object and array properties.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NestedMode {
+    /// Serialized JSON as `ColumnValue::String` (default)
+    #[default]
+    String,
+    /// Serialized JSON as `ColumnValue::Json`, e.g. written verbatim by [`GeoJsonWriter`](super::GeoJsonWriter)
+    Json,
+}
+
+impl<R: Read> GeoJsonReader<R> {
+    /// Conversion of property values.
+    pub fn with_property_mode(self, property_mode: PropertyMode) -> GeoJsonReaderWithMode<R> {
+        GeoJsonReaderWithMode {
+            reader: self.0,
+            property_mode,
+        }
+    }
+}
+
+impl<R: Read + Clone> GeozeroGeometry for GeoJsonReader<R> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        read_geojson_geom(&mut self.0.clone(), processor)
+    }
+}
+
 impl<R: Read> GeozeroDatasource for GeoJsonReader<R> {
     fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
         read_geojson(&mut self.0, processor)
     }
 }
 
+impl<R: Read> GeozeroDatasource for GeoJsonReaderWithMode<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_geojson_with_mode(&mut self.reader, self.property_mode, processor)
+    }
+}
+
 /// Read and process GeoJSON.
-pub fn read_geojson<R: Read, P: FeatureProcessor>(mut reader: R, processor: &mut P) -> Result<()> {
+///
+/// The root object may be a FeatureCollection, a single Feature or a bare geometry.
+/// A bare geometry is processed as dataset with one feature without properties.
+pub fn read_geojson<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    read_geojson_with_mode(reader, PropertyMode::default(), processor)
+}
+
+/// Read and process GeoJSON with the given property value conversion.
+pub fn read_geojson_with_mode<R: Read, P: FeatureProcessor>(
+    mut reader: R,
+    property_mode: PropertyMode,
+    processor: &mut P,
+) -> Result<()> {
     let mut geojson_str = String::new();
     reader.read_to_string(&mut geojson_str)?;
-    let geojson = geojson_str.parse::<GeoGeoJson>()?;
-    process_geojson(&geojson, processor)
+    let geojson = parse_geojson(&geojson_str)?;
+    process_geojson(&geojson, property_mode, processor)
+}
+
+/// Read GeoJSON from an async reader and process it.
+///
+/// Features are processed while reading, see [`GeoJsonAsyncReader`]
+/// for a size limit and property conversion.
+#[cfg(feature = "with-tokio")]
+pub async fn read_geojson_async<R: tokio::io::AsyncRead + Unpin, P: FeatureProcessor>(
+    reader: R,
+    processor: &mut P,
+) -> Result<()> {
+    GeoJsonAsyncReader::new(reader).process(processor).await
+}
+
+/// GeoJSON reader for async sources.
+///
+/// Only reading is async, the processor is called synchronously for each feature
+/// as soon as it is read. Only the current feature of a FeatureCollection is kept
+/// in memory, other documents are buffered completely, so untrusted input like
+/// request bodies should be read with a size limit.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::geojson::GeoJsonAsyncReader;
+/// use geozero::ProcessorSink;
+///
+/// # async fn read() -> geozero::error::Result<()> {
+/// let geojson = r#"{"type": "Point", "coordinates": [1, 2]}"#;
+/// GeoJsonAsyncReader::new(geojson.as_bytes())
+///     .with_limit(1024 * 1024)
+///     .process(&mut ProcessorSink::new())
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "with-tokio")]
+pub struct GeoJsonAsyncReader<R> {
+    reader: R,
+    property_mode: PropertyMode,
+    limit: Option<u64>,
+}
+
+#[cfg(feature = "with-tokio")]
+impl<R: tokio::io::AsyncRead + Unpin> GeoJsonAsyncReader<R> {
+    pub fn new(reader: R) -> Self {
+        GeoJsonAsyncReader {
+            reader,
+            property_mode: PropertyMode::default(),
+            limit: None,
+        }
+    }
+    /// Conversion of property values.
+    pub fn with_property_mode(mut self, property_mode: PropertyMode) -> Self {
+        self.property_mode = property_mode;
+        self
+    }
+    /// Maximal input size in bytes.
+    ///
+    /// Larger input fails with [`GeozeroError::LimitExceeded`].
+    pub fn with_limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+    /// Read the input and process it.
+    ///
+    /// Features of a FeatureCollection are processed as soon as they are read,
+    /// so features before exceeding the size limit are already processed.
+    pub async fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        use super::geojson_stream_reader::StreamParser;
+        use tokio::io::AsyncReadExt;
+        let mut parser = StreamParser::new(self.property_mode);
+        let mut chunk = vec![0; 8 * 1024];
+        let mut size = 0u64;
+        loop {
+            let len = self.reader.read(&mut chunk).await?;
+            if len == 0 {
+                break;
+            }
+            size += len as u64;
+            if let Some(limit) = self.limit.filter(|limit| size > *limit) {
+                return Err(GeozeroError::LimitExceeded(format!(
+                    "more than {limit} bytes of GeoJSON input"
+                )));
+            }
+            parser.feed(&chunk[..len], processor)?;
+        }
+        parser.finish(processor)
+    }
 }
 
 pub fn read_geojson_fc<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    read_geojson_fc_with_mode(reader, PropertyMode::default(), processor)
+}
+
+/// Read and process the features of a GeoJSON FeatureCollection with the given property value conversion.
+pub fn read_geojson_fc_with_mode<R: Read, P: FeatureProcessor>(
+    reader: R,
+    property_mode: PropertyMode,
+    processor: &mut P,
+) -> Result<()> {
     for (idx, feature) in FeatureReader::from_reader(reader).features().enumerate() {
-        process_geojson_feature(&feature?, idx, processor)?;
+        process_geojson_feature(&feature?, idx, property_mode, processor)?;
     }
 
     Ok(())
 }
 
+/// Count the features of a GeoJSON FeatureCollection without parsing them.
+///
+/// Counts the objects in the top-level `features` array in a single buffered pass,
+/// e.g. for pre-sizing collections or progress reporting. The input is not validated,
+/// so the count is only an estimate for malformed input.
+pub fn geojson_feature_count<R: Read>(reader: R) -> Result<usize> {
+    const FEATURES: &[u8] = b"features";
+    let mut reader = BufReader::new(reader);
+    let mut count = 0;
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escape = false;
+    // Last string at depth 1 is "features"
+    let mut features_key = false;
+    // Position in the last string at depth 1, while it matches a prefix of "features"
+    let mut key_pos = Some(0);
+    let mut in_features = false;
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        for &b in buf {
+            if in_string {
+                if escape {
+                    escape = false;
+                } else if b == b'\\' {
+                    escape = true;
+                } else if b == b'"' {
+                    in_string = false;
+                    if depth == 1 {
+                        features_key = key_pos == Some(FEATURES.len());
+                    }
+                } else if depth == 1 {
+                    key_pos = key_pos
+                        .filter(|&pos| FEATURES.get(pos) == Some(&b))
+                        .map(|pos| pos + 1);
+                }
+                continue;
+            }
+            match b {
+                b'"' => {
+                    in_string = true;
+                    key_pos = Some(0);
+                }
+                b'{' | b'[' => {
+                    if depth == 1 && b == b'[' && features_key {
+                        in_features = true;
+                    } else if depth == 2 && b == b'{' && in_features {
+                        count += 1;
+                    }
+                    depth += 1;
+                }
+                b'}' | b']' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 1 {
+                        in_features = false;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let len = buf.len();
+        reader.consume(len);
+    }
+    Ok(count)
+}
+
 /// Read and process GeoJSON geometry.
 pub fn read_geojson_geom<R: Read, P: GeomProcessor>(
     reader: &mut R,
@@ -72,53 +307,96 @@ pub fn read_geojson_geom<R: Read, P: GeomProcessor>(
 ) -> Result<()> {
     let mut geojson_str = String::new();
     reader.read_to_string(&mut geojson_str)?;
-    let geojson = geojson_str.parse::<GeoGeoJson>()?;
+    let geojson = parse_geojson(&geojson_str)?;
     process_geojson_geom(&geojson, processor)
 }
 
 /// Process top-level GeoJSON items
-fn process_geojson<P: FeatureProcessor>(gj: &GeoGeoJson, processor: &mut P) -> Result<()> {
+pub(crate) fn process_geojson<P: FeatureProcessor>(
+    gj: &GeoGeoJson,
+    property_mode: PropertyMode,
+    processor: &mut P,
+) -> Result<()> {
     match *gj {
         GeoGeoJson::FeatureCollection(ref collection) => {
             processor.dataset_begin(None)?;
             for (idx, feature) in collection.features.iter().enumerate() {
-                processor.feature_begin(idx as u64)?;
-                if let Some(ref properties) = feature.properties {
-                    processor.properties_begin()?;
-                    process_properties(properties, processor)?;
-                    processor.properties_end()?;
-                }
-                if let Some(ref geometry) = feature.geometry {
-                    processor.geometry_begin()?;
-                    process_geojson_geom_n(geometry, idx, processor)?;
-                    processor.geometry_end()?;
-                }
-                processor.feature_end(idx as u64)?;
+                process_collection_feature(feature, idx, property_mode, processor)?;
             }
             processor.dataset_end()
         }
-        GeoGeoJson::Feature(ref feature) => process_geojson_feature(feature, 0, processor),
-        GeoGeoJson::Geometry(ref geometry) => process_geojson_geom_n(geometry, 0, processor),
+        GeoGeoJson::Feature(ref feature) => {
+            process_geojson_feature(feature, 0, property_mode, processor)
+        }
+        GeoGeoJson::Geometry(ref geometry) => process_geometry_feature(geometry, processor),
+    }
+}
+
+/// Process a top-level geometry as single feature without properties
+fn process_geometry_feature<P: FeatureProcessor>(
+    geometry: &Geometry,
+    processor: &mut P,
+) -> Result<()> {
+    processor.dataset_begin(None)?;
+    processor.feature_begin(0)?;
+    processor.properties_begin()?;
+    processor.properties_end()?;
+    processor.geometry_begin()?;
+    process_geojson_geom_n(geometry, 0, processor)?;
+    processor.geometry_end()?;
+    processor.feature_end(0)?;
+    processor.dataset_end()
+}
+
+/// Process a feature of a FeatureCollection
+pub(crate) fn process_collection_feature<P: FeatureProcessor>(
+    feature: &Feature,
+    idx: usize,
+    property_mode: PropertyMode,
+    processor: &mut P,
+) -> Result<()> {
+    processor.feature_begin(idx as u64)?;
+    process_feature_id(feature, processor)?;
+    if let Some(ref properties) = feature.properties {
+        processor.properties_begin()?;
+        process_properties_with_mode(properties, property_mode, processor)?;
+        processor.properties_end()?;
     }
+    if let Some(geometry) = feature
+        .geometry
+        .as_ref()
+        .filter(|_| !processor.skip_geometry())
+    {
+        processor.geometry_begin()?;
+        process_geojson_geom_n(geometry, 0, processor)?;
+        processor.geometry_end()?;
+    }
+    processor.feature_end(idx as u64)
 }
 
 /// Process top-level GeoJSON items
 fn process_geojson_feature<P: FeatureProcessor>(
     feature: &Feature,
     idx: usize,
+    property_mode: PropertyMode,
     processor: &mut P,
 ) -> Result<()> {
     processor.dataset_begin(None)?;
     if feature.geometry.is_some() || feature.properties.is_some() {
         processor.feature_begin(idx as u64)?;
+        process_feature_id(feature, processor)?;
         if let Some(ref properties) = feature.properties {
             processor.properties_begin()?;
-            process_properties(properties, processor)?;
+            process_properties_with_mode(properties, property_mode, processor)?;
             processor.properties_end()?;
         }
-        if let Some(ref geometry) = feature.geometry {
+        if let Some(geometry) = feature
+            .geometry
+            .as_ref()
+            .filter(|_| !processor.skip_geometry())
+        {
             processor.geometry_begin()?;
-            process_geojson_geom_n(geometry, idx, processor)?;
+            process_geojson_geom_n(geometry, 0, processor)?;
             processor.geometry_end()?;
         }
         processor.feature_end(idx as u64)?;
@@ -159,6 +437,7 @@ pub(crate) fn process_geojson_geom_n<P: GeomProcessor>(
     processor: &mut P,
 ) -> Result<()> {
     match geom.value {
+        Value::Point(ref geometry) if geometry.is_empty() => processor.empty_point(idx),
         Value::Point(ref geometry) => {
             processor.point_begin(idx)?;
             process_coord(geometry, processor.multi_dim(), 0, processor)?;
@@ -198,15 +477,49 @@ pub(crate) fn process_geojson_geom_n<P: GeomProcessor>(
     }
 }
 
+/// Process GeoJSON feature id
+pub(crate) fn process_feature_id<P: FeatureProcessor>(
+    feature: &Feature,
+    processor: &mut P,
+) -> Result<()> {
+    match &feature.id {
+        Some(Id::String(id)) => processor.feature_id(ColumnValue::String(id)),
+        Some(Id::Number(id)) => {
+            if let Some(v) = id.as_u64() {
+                processor.feature_id(ColumnValue::ULong(v))
+            } else if let Some(v) = id.as_i64() {
+                processor.feature_id(ColumnValue::Long(v))
+            } else if let Some(v) = id.as_f64() {
+                processor.feature_id(ColumnValue::Double(v))
+            } else {
+                Ok(())
+            }
+        }
+        None => Ok(()),
+    }
+}
+
 /// Process GeoJSON properties
 pub(crate) fn process_properties<P: PropertyProcessor>(
     properties: &Map<String, JsonValue>,
     processor: &mut P,
+) -> Result<()> {
+    process_properties_with_mode(properties, PropertyMode::default(), processor)
+}
+
+/// Process GeoJSON properties with the given value conversion
+pub(crate) fn process_properties_with_mode<P: PropertyProcessor>(
+    properties: &Map<String, JsonValue>,
+    property_mode: PropertyMode,
+    processor: &mut P,
 ) -> Result<()> {
     for (i, (key, value)) in properties.iter().enumerate() {
         // Could we provide a stable property index?
         match value {
             JsonValue::String(v) => processor.property(i, key, &ColumnValue::String(v))?,
+            JsonValue::Number(v) if property_mode.numbers == NumberMode::Double => {
+                processor.property(i, key, &ColumnValue::Double(v.as_f64().unwrap_or(f64::NAN)))?
+            }
             JsonValue::Number(v) if v.is_f64() => {
                 processor.property(i, key, &ColumnValue::Double(v.as_f64().unwrap()))?
             }
@@ -217,8 +530,16 @@ pub(crate) fn process_properties<P: PropertyProcessor>(
                 processor.property(i, key, &ColumnValue::ULong(v.as_u64().unwrap()))?
             }
             JsonValue::Bool(v) => processor.property(i, key, &ColumnValue::Bool(*v))?,
-            // Null, Array(Vec<Value>), Object(Map<String, Value>)
-            _ => processor.property(i, key, &ColumnValue::String(&value.to_string()))?,
+            JsonValue::Null => processor.property(i, key, &ColumnValue::Json("null"))?,
+            // Array(Vec<Value>), Object(Map<String, Value>)
+            _ => match property_mode.nested {
+                NestedMode::String => {
+                    processor.property(i, key, &ColumnValue::String(&value.to_string()))?
+                }
+                NestedMode::Json => {
+                    processor.property(i, key, &ColumnValue::Json(&value.to_string()))?
+                }
+            },
         };
     }
     Ok(())
@@ -235,6 +556,9 @@ fn process_coord<P: GeomProcessor>(
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
+    if point_type.len() < 2 {
+        return Err(GeozeroError::Coord);
+    }
     if multi_dim {
         processor.coordinate(
             point_type[0],
@@ -277,6 +601,137 @@ fn process_polygon<P: GeomProcessor>(
     processor.polygon_end(tagged, idx)
 }
 
+/// Parse GeoJSON text.
+///
+/// Geometries are converted by geozero instead of the `geojson` crate, which rejects empty
+/// points (`"coordinates": []`) and doesn't report invalid positions as [`GeozeroError::Coord`].
+pub(crate) fn parse_geojson(geojson_str: &str) -> Result<GeoGeoJson> {
+    let value: JsonValue =
+        serde_json::from_str(geojson_str).map_err(|e| GeozeroError::Geometry(e.to_string()))?;
+    geojson_from_value(value)
+}
+
+fn geojson_from_value(value: JsonValue) -> Result<GeoGeoJson> {
+    let mut object = json_object(value)?;
+    match object.get("type").and_then(JsonValue::as_str) {
+        Some("FeatureCollection") => {
+            let features = match object.insert("features".to_string(), JsonValue::Array(vec![])) {
+                Some(JsonValue::Array(features)) => features
+                    .into_iter()
+                    .map(feature_from_value)
+                    .collect::<Result<Vec<_>>>()?,
+                _ => {
+                    return Err(GeozeroError::Geometry(
+                        "expected `features` array".to_string(),
+                    ))
+                }
+            };
+            let mut collection = geojson::FeatureCollection::try_from(object)?;
+            collection.features = features;
+            Ok(GeoGeoJson::FeatureCollection(collection))
+        }
+        Some("Feature") => feature_from_value(JsonValue::Object(object)).map(GeoGeoJson::Feature),
+        _ => geometry_from_object(object).map(GeoGeoJson::Geometry),
+    }
+}
+
+/// Convert a JSON value into a Feature, with the geometry converted by geozero.
+pub(crate) fn feature_from_value(value: JsonValue) -> Result<Feature> {
+    let mut object = json_object(value)?;
+    let geometry = match object.get_mut("geometry") {
+        Some(geometry @ JsonValue::Object(_)) => {
+            Some(geometry_from_object(json_object(geometry.take())?)?)
+        }
+        _ => None,
+    };
+    let mut feature = Feature::try_from(object)?;
+    if geometry.is_some() {
+        feature.geometry = geometry;
+    }
+    Ok(feature)
+}
+
+fn geometry_from_object(mut object: Map<String, JsonValue>) -> Result<Geometry> {
+    let geometry_type = match object.get("type").and_then(JsonValue::as_str) {
+        Some(geometry_type) => geometry_type.to_string(),
+        None => return Err(GeozeroError::Geometry("expected geometry type".to_string())),
+    };
+    if geometry_type == "GeometryCollection" {
+        let geometries = match object.remove("geometries") {
+            Some(JsonValue::Array(geometries)) => geometries
+                .into_iter()
+                .map(|geometry| geometry_from_object(json_object(geometry)?))
+                .collect::<Result<Vec<_>>>()?,
+            _ => {
+                return Err(GeozeroError::Geometry(
+                    "expected `geometries` array".to_string(),
+                ))
+            }
+        };
+        return Ok(Geometry::new(Value::GeometryCollection(geometries)));
+    }
+    let coordinates = object
+        .remove("coordinates")
+        .ok_or_else(|| GeozeroError::Geometry("expected `coordinates` array".to_string()))?;
+    let value = match geometry_type.as_str() {
+        "Point" => match json_array(coordinates)? {
+            empty if empty.is_empty() => Value::Point(Vec::new()),
+            position => Value::Point(json_position(JsonValue::Array(position))?),
+        },
+        "MultiPoint" => Value::MultiPoint(json_positions(coordinates)?),
+        "LineString" => Value::LineString(json_positions(coordinates)?),
+        "MultiLineString" => Value::MultiLineString(json_lines(coordinates)?),
+        "Polygon" => Value::Polygon(json_lines(coordinates)?),
+        "MultiPolygon" => Value::MultiPolygon(
+            json_array(coordinates)?
+                .into_iter()
+                .map(json_lines)
+                .collect::<Result<_>>()?,
+        ),
+        _ => {
+            return Err(GeozeroError::Geometry(format!(
+                "unknown geometry type `{geometry_type}`"
+            )))
+        }
+    };
+    Ok(Geometry::new(value))
+}
+
+fn json_object(value: JsonValue) -> Result<Map<String, JsonValue>> {
+    match value {
+        JsonValue::Object(object) => Ok(object),
+        _ => Err(GeozeroError::Geometry("expected JSON object".to_string())),
+    }
+}
+
+fn json_array(value: JsonValue) -> Result<Vec<JsonValue>> {
+    match value {
+        JsonValue::Array(array) => Ok(array),
+        _ => Err(GeozeroError::Geometry(
+            "expected coordinate array".to_string(),
+        )),
+    }
+}
+
+fn json_position(value: JsonValue) -> Result<Vec<f64>> {
+    let position = json_array(value)?
+        .iter()
+        .map(|ordinate| ordinate.as_f64().ok_or(GeozeroError::Coord))
+        .collect::<Result<Vec<_>>>()?;
+    if position.len() < 2 {
+        return Err(GeozeroError::Coord);
+    }
+    Ok(position)
+}
+
+fn json_positions(value: JsonValue) -> Result<Vec<Vec<f64>>> {
+    json_array(value)?.into_iter().map(json_position).collect()
+}
+
+fn json_lines(value: JsonValue) -> Result<Vec<Vec<Vec<f64>>>> {
+    json_array(value)?.into_iter().map(json_positions).collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -285,6 +740,78 @@ mod test {
     use crate::{CoordDimensions, ProcessToSvg, ToJson, ToWkt};
     use std::fs::File;
 
+    #[cfg(feature = "with-tokio")]
+    #[tokio::test]
+    async fn async_reader() -> Result<()> {
+        let geojson = r#"{"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 2]}}"#;
+        let mut wkt_data: Vec<u8> = Vec::new();
+        read_geojson_async(geojson.as_bytes(), &mut WktWriter::new(&mut wkt_data)).await?;
+        assert_eq!(std::str::from_utf8(&wkt_data).unwrap(), "POINT(1 2)");
+        Ok(())
+    }
+
+    #[cfg(feature = "with-tokio")]
+    #[tokio::test]
+    async fn async_reader_collection() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"name": "b"}, "geometry": {"type": "Point", "coordinates": [3, 4]}}
+        ]}"#;
+        let mut expected: Vec<u8> = Vec::new();
+        read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut expected))?;
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson_async(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).await?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            std::str::from_utf8(&expected).unwrap()
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "with-tokio")]
+    #[tokio::test]
+    async fn async_reader_options() -> Result<()> {
+        let geojson =
+            r#"{"type": "Feature", "properties": {"n": 1, "obj": {"k": 1}}, "geometry": null}"#;
+        let mut types = PropertyTypes::default();
+        GeoJsonAsyncReader::new(geojson.as_bytes())
+            .with_property_mode(PropertyMode {
+                numbers: NumberMode::Double,
+                nested: NestedMode::Json,
+            })
+            .with_limit(geojson.len() as u64)
+            .process(&mut types)
+            .await?;
+        assert_eq!(types.0, ["n=Double(1.0)", r#"obj=Json("{\"k\":1}")"#]);
+
+        let result = GeoJsonAsyncReader::new(geojson.as_bytes())
+            .with_limit(geojson.len() as u64 - 1)
+            .process(&mut PropertyTypes::default())
+            .await;
+        assert!(matches!(result, Err(GeozeroError::LimitExceeded(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn feature_count() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "name": "[\"features\"]", "features": [
+            {"type": "Feature", "properties": {"features": [{"type": "Feature"}], "s": "}{"}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": null, "geometry": null}
+        ], "bbox": [{"features": [{}]}]}"#;
+        assert_eq!(geojson_feature_count(geojson.as_bytes())?, 2);
+        assert_eq!(
+            geojson_feature_count(r#"{"type": "FeatureCollection", "features": []}"#.as_bytes())?,
+            0
+        );
+        assert_eq!(
+            geojson_feature_count(r#"{"type": "Point", "coordinates": [1, 2]}"#.as_bytes())?,
+            0
+        );
+        let f = File::open("tests/data/places.json")?;
+        assert_eq!(geojson_feature_count(f)?, 1249);
+        Ok(())
+    }
+
     #[test]
     fn line_string() -> Result<()> {
         let geojson = r#"{
@@ -309,7 +836,7 @@ mod test {
         let mut out = WktWriter::with_dims(&mut wkt_data, CoordDimensions::xyz());
         assert!(read_geojson_geom(&mut geojson.as_bytes(), &mut out).is_ok());
         let wkt = std::str::from_utf8(&wkt_data).unwrap();
-        assert_eq!(wkt, "LINESTRING(1 1 10,2 2 20)");
+        assert_eq!(wkt, "LINESTRING Z (1 1 10,2 2 20)");
 
         let geojson = r#"{"type": "LineString", "coordinates": [[1,1],[2,2]]}"#;
         let mut wkt_data: Vec<u8> = Vec::new();
@@ -379,6 +906,50 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn geometry_collection() -> Result<()> {
+        let geojson = GeoJson(
+            r#"{"type": "GeometryCollection", "geometries": [
+                {"type": "Point", "coordinates": [1, 2]},
+                {"type": "GeometryCollection", "geometries": [
+                    {"type": "LineString", "coordinates": [[0, 0], [1, 1]]},
+                    {"type": "Point", "coordinates": []}
+                ]},
+                {"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 0]]]}
+            ]}"#,
+        );
+        let wkt = "GEOMETRYCOLLECTION(POINT(1 2),GEOMETRYCOLLECTION(LINESTRING(0 0,1 1),POINT EMPTY),POLYGON((0 0,1 0,1 1,0 0)))";
+        assert_eq!(geojson.to_wkt()?, wkt);
+
+        #[cfg(feature = "with-wkb")]
+        {
+            use crate::ToWkb;
+            let wkb = crate::wkb::Wkb(geojson.to_wkb(CoordDimensions::xy())?);
+            assert_eq!(wkb.to_wkt()?, wkt);
+        }
+
+        let mut out: Vec<u8> = Vec::new();
+        geojson.process_geom(&mut GeoJsonWriter::new(&mut out))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{"type": "GeometryCollection", "geometries": [{"type": "Point", "coordinates": [1,2]},{"type": "GeometryCollection", "geometries": [{"type": "LineString", "coordinates": [[0,0],[1,1]]},{"type": "Point", "coordinates": []}]},{"type": "Polygon", "coordinates": [[[0,0],[1,0],[1,1],[0,0]]]}]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_position() {
+        let geojson = GeoJson(r#"{"type": "LineString", "coordinates": [[0, 0], [1]]}"#);
+        assert!(matches!(geojson.to_wkt(), Err(GeozeroError::Coord)));
+    }
+
+    #[test]
+    fn reader_geometry() -> Result<()> {
+        let reader = GeoJsonReader(r#"{"type": "Point", "coordinates": [10, 20]}"#.as_bytes());
+        assert_eq!(reader.to_wkt()?, "POINT(10 20)");
+        Ok(())
+    }
+
     #[test]
     fn from_file() -> Result<()> {
         let f = File::open("tests/data/places.json")?;
@@ -387,11 +958,11 @@ mod test {
         let wkt = std::str::from_utf8(&wkt_data).unwrap();
         assert_eq!(
             &wkt[0..100],
-            "POINT(32.533299524864844 0.583299105614628),POINT(30.27500161597942 0.671004121125236),POINT(15.7989"
+            "POINT(32.533299524864844 0.583299105614628)POINT(30.27500161597942 0.671004121125236)POINT(15.798996"
         );
         assert_eq!(
             &wkt[wkt.len()-100..],
-            "06510862875),POINT(103.85387481909902 1.294979325105942),POINT(114.18306345846304 22.30692675357551)"
+            "1806510862875)POINT(103.85387481909902 1.294979325105942)POINT(114.18306345846304 22.30692675357551)"
         );
         Ok(())
     }
@@ -404,11 +975,11 @@ mod test {
         let wkt = std::str::from_utf8(&wkt_data).unwrap();
         assert_eq!(
             &wkt[0..100],
-            "POINT(32.533299524864844 0.583299105614628),POINT(30.27500161597942 0.671004121125236),POINT(15.7989"
+            "POINT(32.533299524864844 0.583299105614628)POINT(30.27500161597942 0.671004121125236)POINT(15.798996"
         );
         assert_eq!(
             &wkt[wkt.len()-100..],
-            "06510862875),POINT(103.85387481909902 1.294979325105942),POINT(114.18306345846304 22.30692675357551)"
+            "1806510862875)POINT(103.85387481909902 1.294979325105942)POINT(114.18306345846304 22.30692675357551)"
         );
         Ok(())
     }
@@ -423,12 +994,136 @@ mod test {
         println!("{}", &svg[svg.len() - 100..]);
         assert_eq!(
             &svg[svg.len() - 100..],
-            r#"387481909902 1.294979325105942 Z"/>
-<path d="M 114.18306345846304 22.30692675357551 Z"/>
+            r#".294979325105942" r="1"/>
+<circle cx="114.18306345846304" cy="22.30692675357551" r="1"/>
 </g>
 </svg>"#
         );
 
         Ok(())
     }
+
+    #[test]
+    fn root_objects() -> Result<()> {
+        fn to_json(geojson: &str) -> serde_json::Value {
+            let mut out: Vec<u8> = Vec::new();
+            read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).unwrap();
+            serde_json::from_slice(&out).unwrap()
+        }
+        let expected = serde_json::json!({"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}}
+        ]});
+
+        let collection = r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}}]}"#;
+        assert_eq!(to_json(collection), expected);
+        let feature = r#"{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}}"#;
+        assert_eq!(to_json(feature), expected);
+        let geometry = r#"{"type": "Point", "coordinates": [1, 2]}"#;
+        assert_eq!(to_json(geometry), expected);
+
+        // Datasources
+        let mut out: Vec<u8> = Vec::new();
+        GeoJson(geometry).process(&mut GeoJsonWriter::new(&mut out))?;
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&out).unwrap(),
+            expected
+        );
+        let mut wkt_data: Vec<u8> = Vec::new();
+        GeoJsonReader(geometry.as_bytes()).process(&mut WktWriter::new(&mut wkt_data))?;
+        assert_eq!(std::str::from_utf8(&wkt_data).unwrap(), "POINT(1 2)");
+        Ok(())
+    }
+
+    #[derive(Default)]
+    struct PropertyTypes(Vec<String>);
+
+    impl GeomProcessor for PropertyTypes {}
+    impl FeatureProcessor for PropertyTypes {}
+    impl PropertyProcessor for PropertyTypes {
+        fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+            self.0.push(format!("{name}={value:?}"));
+            Ok(false)
+        }
+    }
+
+    fn property_types(mode: PropertyMode) -> Result<Vec<String>> {
+        let geojson = r#"{"type": "Feature", "properties": {"a_int": 42, "b_neg": -7, "c_float": 1.5, "d_bool": true, "e_null": null, "f_obj": {"k": [1, "x"]}, "g_arr": [1, 2]}, "geometry": null}"#;
+        let mut types = PropertyTypes::default();
+        GeoJsonReader(geojson.as_bytes())
+            .with_property_mode(mode)
+            .process(&mut types)?;
+        types.0.sort();
+        Ok(types.0)
+    }
+
+    #[test]
+    fn property_modes() -> Result<()> {
+        assert_eq!(
+            property_types(PropertyMode::default())?,
+            [
+                "a_int=Long(42)",
+                "b_neg=Long(-7)",
+                "c_float=Double(1.5)",
+                "d_bool=Bool(true)",
+                r#"e_null=Json("null")"#,
+                r#"f_obj=String("{\"k\":[1,\"x\"]}")"#,
+                r#"g_arr=String("[1,2]")"#,
+            ]
+        );
+
+        let mode = PropertyMode {
+            numbers: NumberMode::Double,
+            nested: NestedMode::Json,
+        };
+        assert_eq!(
+            property_types(mode)?,
+            [
+                "a_int=Double(42.0)",
+                "b_neg=Double(-7.0)",
+                "c_float=Double(1.5)",
+                "d_bool=Bool(true)",
+                r#"e_null=Json("null")"#,
+                r#"f_obj=Json("{\"k\":[1,\"x\"]}")"#,
+                r#"g_arr=Json("[1,2]")"#,
+            ]
+        );
+
+        // Nested values as JSON are written verbatim
+        let geojson = r#"{"type": "Feature", "properties": {"obj": {"k": 1}}, "geometry": {"type": "Point", "coordinates": [1, 2]}}"#;
+        let mut out: Vec<u8> = Vec::new();
+        GeoJsonReader(geojson.as_bytes())
+            .with_property_mode(mode)
+            .process(&mut GeoJsonWriter::new(&mut out))?;
+        let json = std::str::from_utf8(&out).unwrap();
+        assert!(json.contains(r#""properties": {"obj": {"k":1}}"#), "{json}");
+
+        Ok(())
+    }
+
+    #[test]
+    fn property_mode_entry_points() -> Result<()> {
+        let mode = PropertyMode {
+            numbers: NumberMode::Double,
+            nested: NestedMode::Json,
+        };
+        let feature =
+            r#"{"type": "Feature", "properties": {"n": 1, "obj": {"k": 1}}, "geometry": null}"#;
+        let collection = format!(r#"{{"type": "FeatureCollection", "features": [{feature}]}}"#);
+        let expected = ["n=Double(1.0)", r#"obj=Json("{\"k\":1}")"#];
+
+        let mut types = PropertyTypes::default();
+        read_geojson_fc_with_mode(collection.as_bytes(), mode, &mut types)?;
+        assert_eq!(types.0, expected);
+
+        let mut types = PropertyTypes::default();
+        crate::geojson::read_geojson_streaming_with_mode(collection.as_bytes(), mode, &mut types)?;
+        assert_eq!(types.0, expected);
+
+        let mut types = PropertyTypes::default();
+        crate::geojson::GeoJsonLineReader::new(feature.as_bytes())
+            .with_property_mode(mode)
+            .process(&mut types)?;
+        assert_eq!(types.0, expected);
+        Ok(())
+    }
 }
@@ -0,0 +1,498 @@
+use crate::error::{GeozeroError, Result};
+use crate::geojson::geojson_reader::{
+    feature_from_value, parse_geojson, process_collection_feature, process_geojson, PropertyMode,
+};
+use crate::{FeatureProcessor, GeozeroDatasource};
+use std::io::{BufRead, BufReader, Read};
+
+/// Streaming GeoJSON Reader.
+///
+/// Features of a FeatureCollection are processed one at a time while reading,
+/// without loading the whole document into memory.
+/// Use [`read_geojson_streaming_with_mode`] for a property conversion other than the default.
+pub struct GeoJsonStreamReader<R: Read>(pub R);
+
+impl<R: Read> GeozeroDatasource for GeoJsonStreamReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_geojson_streaming(&mut self.0, processor)
+    }
+}
+
+/// Read and process GeoJSON, keeping only one feature in memory at a time.
+///
+/// Members of the top-level object other than `features` are skipped.
+/// Documents without `features` array, i.e. a single Feature or Geometry,
+/// are read completely and processed like [`read_geojson`](super::read_geojson).
+pub fn read_geojson_streaming<R: Read, P: FeatureProcessor>(
+    reader: R,
+    processor: &mut P,
+) -> Result<()> {
+    read_geojson_streaming_with_mode(reader, PropertyMode::default(), processor)
+}
+
+/// Read and process GeoJSON like [`read_geojson_streaming`] with the given property value conversion.
+pub fn read_geojson_streaming_with_mode<R: Read, P: FeatureProcessor>(
+    reader: R,
+    property_mode: PropertyMode,
+    processor: &mut P,
+) -> Result<()> {
+    let mut reader = BufReader::new(reader);
+    let mut parser = StreamParser::new(property_mode);
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            break;
+        }
+        let len = chunk.len();
+        parser.feed(chunk, processor)?;
+        reader.consume(len);
+    }
+    parser.finish(processor)
+}
+
+/// Position in the top-level object.
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Start,
+    ObjectStart,
+    Key,
+    Colon,
+    Member,
+    AfterMember,
+    FeaturesStart,
+    FirstFeature,
+    Feature,
+    AfterFeature,
+    End,
+}
+
+/// Incremental GeoJSON reader, fed with chunks of the input.
+///
+/// Features of the `features` array are processed as soon as they are complete.
+pub(crate) struct StreamParser {
+    scanner: JsonScanner,
+    state: State,
+    property_mode: PropertyMode,
+    key: Vec<u8>,
+    // Members of documents without features
+    members: Vec<(Vec<u8>, Vec<u8>)>,
+    features_read: bool,
+    idx: usize,
+}
+
+impl StreamParser {
+    pub(crate) fn new(property_mode: PropertyMode) -> Self {
+        StreamParser {
+            scanner: JsonScanner::default(),
+            state: State::Start,
+            property_mode,
+            key: Vec::new(),
+            members: Vec::new(),
+            features_read: false,
+            idx: 0,
+        }
+    }
+
+    /// Append a chunk of input and process the features completed by it.
+    pub(crate) fn feed<P: FeatureProcessor>(
+        &mut self,
+        chunk: &[u8],
+        processor: &mut P,
+    ) -> Result<()> {
+        self.scanner.push(chunk);
+        while self.state != State::End && self.step(processor)? {}
+        Ok(())
+    }
+
+    /// Process the rest of the document after the last chunk.
+    pub(crate) fn finish<P: FeatureProcessor>(self, processor: &mut P) -> Result<()> {
+        if self.state != State::End {
+            return Err(GeozeroError::Dataset(
+                "unexpected end of JSON input".to_string(),
+            ));
+        }
+        if !self.features_read {
+            let mut json = b"{".to_vec();
+            for (i, (key, value)) in self.members.iter().enumerate() {
+                if i > 0 {
+                    json.push(b',');
+                }
+                json.extend_from_slice(key);
+                json.push(b':');
+                json.extend_from_slice(value);
+            }
+            json.push(b'}');
+            let geojson = parse_geojson(
+                std::str::from_utf8(&json).map_err(|e| GeozeroError::Dataset(e.to_string()))?,
+            )?;
+            process_geojson(&geojson, self.property_mode, processor)?;
+        }
+        Ok(())
+    }
+
+    /// Advance to the next state. Returns `false` if more input is needed.
+    fn step<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<bool> {
+        match self.state {
+            State::Start => {
+                if !self.scanner.expect(b'{')? {
+                    return Ok(false);
+                }
+                self.state = State::ObjectStart;
+            }
+            State::ObjectStart => match self.scanner.next_if(b'}')? {
+                None => return Ok(false),
+                Some(true) => self.state = State::End,
+                Some(false) => self.state = State::Key,
+            },
+            State::Key => {
+                let Some(key) = self.scanner.read_value()? else {
+                    return Ok(false);
+                };
+                self.key = key.to_vec();
+                self.state = State::Colon;
+            }
+            State::Colon => {
+                if !self.scanner.expect(b':')? {
+                    return Ok(false);
+                }
+                if !self.features_read && self.key == br#""features""# {
+                    self.features_read = true;
+                    processor.dataset_begin(None)?;
+                    self.state = State::FeaturesStart;
+                } else {
+                    self.state = State::Member;
+                }
+            }
+            State::Member => {
+                let Some(value) = self.scanner.read_value()? else {
+                    return Ok(false);
+                };
+                if !self.features_read {
+                    self.members
+                        .push((std::mem::take(&mut self.key), value.to_vec()));
+                }
+                self.state = State::AfterMember;
+            }
+            State::AfterMember => match self.scanner.next_item(b'}')? {
+                None => return Ok(false),
+                Some(true) => self.state = State::Key,
+                Some(false) => self.state = State::End,
+            },
+            State::FeaturesStart => {
+                if !self.scanner.expect(b'[')? {
+                    return Ok(false);
+                }
+                self.state = State::FirstFeature;
+            }
+            State::FirstFeature => match self.scanner.next_if(b']')? {
+                None => return Ok(false),
+                Some(true) => {
+                    processor.dataset_end()?;
+                    self.state = State::AfterMember;
+                }
+                Some(false) => self.state = State::Feature,
+            },
+            State::Feature => {
+                let Some(value) = self.scanner.read_value()? else {
+                    return Ok(false);
+                };
+                let feature = feature_from_value(
+                    serde_json::from_slice(value)
+                        .map_err(|e| GeozeroError::Feature(e.to_string()))?,
+                )?;
+                process_collection_feature(&feature, self.idx, self.property_mode, processor)?;
+                self.idx += 1;
+                self.state = State::AfterFeature;
+            }
+            State::AfterFeature => match self.scanner.next_item(b']')? {
+                None => return Ok(false),
+                Some(true) => self.state = State::Feature,
+                Some(false) => {
+                    processor.dataset_end()?;
+                    self.state = State::AfterMember;
+                }
+            },
+            State::End => {}
+        }
+        Ok(true)
+    }
+}
+
+/// Minimal JSON tokenizer for splitting a document into values.
+///
+/// Input is pushed in chunks, methods return `None` if more input is needed.
+#[derive(Default)]
+struct JsonScanner {
+    buf: Vec<u8>,
+    pos: usize,
+    /// Scanning state of a value not yet complete
+    value: Option<ValueScan>,
+}
+
+#[derive(Default)]
+struct ValueScan {
+    len: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl JsonScanner {
+    fn push(&mut self, chunk: &[u8]) {
+        self.buf.drain(..self.pos);
+        self.pos = 0;
+        self.buf.extend_from_slice(chunk);
+    }
+    /// Next non-whitespace character, without consuming it.
+    fn peek(&mut self) -> Option<u8> {
+        while let Some(&b) = self.buf.get(self.pos) {
+            if !b.is_ascii_whitespace() {
+                return Some(b);
+            }
+            self.pos += 1;
+        }
+        None
+    }
+    /// Consume `expected`, which has to be the next non-whitespace character.
+    fn expect(&mut self, expected: u8) -> Result<bool> {
+        let Some(b) = self.peek() else {
+            return Ok(false);
+        };
+        if b != expected {
+            return Err(GeozeroError::Dataset(format!(
+                "invalid JSON: expected `{}`, found `{}`",
+                expected as char, b as char
+            )));
+        }
+        self.pos += 1;
+        Ok(true)
+    }
+    /// Consume `expected`, if it's the next non-whitespace character.
+    fn next_if(&mut self, expected: u8) -> Result<Option<bool>> {
+        match self.peek() {
+            None => Ok(None),
+            Some(b) if b == expected => {
+                self.pos += 1;
+                Ok(Some(true))
+            }
+            Some(_) => Ok(Some(false)),
+        }
+    }
+    /// Consume the separator after an array element or object member.
+    ///
+    /// Returns `false` at the `end` of the array or object.
+    fn next_item(&mut self, end: u8) -> Result<Option<bool>> {
+        match self.next_if(b',')? {
+            Some(false) => Ok(self.expect(end)?.then_some(false)),
+            next => Ok(next),
+        }
+    }
+    /// Consume the next JSON value and return its raw bytes.
+    fn read_value(&mut self) -> Result<Option<&[u8]>> {
+        if self.value.is_none() {
+            if self.peek().is_none() {
+                return Ok(None);
+            }
+            self.value = Some(ValueScan::default());
+        }
+        let Some(scan) = self.value.as_mut() else {
+            return Ok(None);
+        };
+        let mut complete = false;
+        while let Some(&b) = self.buf.get(self.pos + scan.len) {
+            if scan.in_string {
+                scan.len += 1;
+                if scan.escaped {
+                    scan.escaped = false;
+                } else if b == b'\\' {
+                    scan.escaped = true;
+                } else if b == b'"' {
+                    scan.in_string = false;
+                    complete = scan.depth == 0;
+                    if complete {
+                        break;
+                    }
+                }
+                continue;
+            }
+            match b {
+                b'"' => scan.in_string = true,
+                b'{' | b'[' => scan.depth += 1,
+                b'}' | b']' if scan.depth > 0 => {
+                    scan.depth -= 1;
+                    if scan.depth == 0 {
+                        scan.len += 1;
+                        complete = true;
+                        break;
+                    }
+                }
+                // End of number or literal
+                _ if scan.depth == 0
+                    && (b.is_ascii_whitespace() || matches!(b, b',' | b'}' | b']')) =>
+                {
+                    if scan.len == 0 {
+                        return Err(GeozeroError::Dataset(format!(
+                            "invalid JSON: unexpected `{}`",
+                            b as char
+                        )));
+                    }
+                    complete = true;
+                    break;
+                }
+                _ => {}
+            }
+            scan.len += 1;
+        }
+        if !complete {
+            return Ok(None);
+        }
+        let start = self.pos;
+        self.pos += scan.len;
+        self.value = None;
+        Ok(Some(&self.buf[start..self.pos]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonWriter;
+    use crate::wkt::WktWriter;
+    use crate::ProcessorSink;
+
+    #[test]
+    fn feature_collection() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "bbox": [0, 0, 5, 5], "name": "a \"]}\" b",
+            "features": [
+                {"type": "Feature", "id": 1, "properties": {"name": "x]}\""}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+                {"type": "Feature", "properties": null, "geometry": {"type": "LineString", "coordinates": [[0, 0], [5, 5.5e0]]}}
+            ],
+            "crs": {"type": "name", "properties": {"name": "EPSG:4326"}}
+        }"#;
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson_streaming(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "id": 1, "properties": {"name": "x]}\""}, "geometry": {"type": "Point", "coordinates": [1,2]}},
+{"type": "Feature", "geometry": {"type": "LineString", "coordinates": [[0,0],[5,5.5]]}}]}"#
+        );
+
+        let mut wkt: Vec<u8> = Vec::new();
+        GeoJsonStreamReader(r#"{"features": [], "type": "FeatureCollection"}"#.as_bytes())
+            .process(&mut WktWriter::new(&mut wkt))?;
+        assert!(wkt.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn single_feature() -> Result<()> {
+        let mut wkt: Vec<u8> = Vec::new();
+        read_geojson_streaming(
+            r#"{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}}"#.as_bytes(),
+            &mut WktWriter::new(&mut wkt),
+        )?;
+        assert_eq!(std::str::from_utf8(&wkt).unwrap(), "POINT(1 2)");
+
+        let mut wkt: Vec<u8> = Vec::new();
+        read_geojson_streaming(
+            r#" {"coordinates": [1, 2], "type": "Point"} "#.as_bytes(),
+            &mut WktWriter::new(&mut wkt),
+        )?;
+        assert_eq!(std::str::from_utf8(&wkt).unwrap(), "POINT(1 2)");
+        Ok(())
+    }
+
+    #[test]
+    fn byte_chunks() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"s": "a\"}]"}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"n": 1.5e1}, "geometry": null}
+        ]}"#;
+        let mut expected: Vec<u8> = Vec::new();
+        crate::geojson::read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut expected))?;
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out);
+        let mut parser = StreamParser::new(PropertyMode::default());
+        for b in geojson.as_bytes() {
+            parser.feed(std::slice::from_ref(b), &mut writer)?;
+        }
+        parser.finish(&mut writer)?;
+        drop(writer);
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            std::str::from_utf8(&expected).unwrap()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_json() {
+        for geojson in [
+            r#"[]"#,
+            r#"{"type": "FeatureCollection", "features": [{"type": "Feature"}"#,
+            r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "geometry": null}}]}"#,
+            r#"{"type": "FeatureCollection", "features": [1]}"#,
+        ] {
+            assert!(read_geojson_streaming(geojson.as_bytes(), &mut ProcessorSink::new()).is_err());
+        }
+    }
+
+    /// Generated FeatureCollection with `count` features
+    struct GeneratedFeatures {
+        count: usize,
+        idx: usize,
+        chunk: Vec<u8>,
+        pos: usize,
+    }
+
+    impl Read for GeneratedFeatures {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.pos == self.chunk.len() {
+                self.pos = 0;
+                self.chunk = if self.idx == 0 {
+                    br#"{"type": "FeatureCollection", "features": ["#.to_vec()
+                } else if self.idx <= self.count {
+                    let sep = if self.idx > 1 { "," } else { "" };
+                    format!(r#"{sep}{{"type": "Feature", "properties": {{"idx": {}}}, "geometry": {{"type": "Point", "coordinates": [{}, 0]}}}}"#, self.idx, self.idx).into_bytes()
+                } else if self.idx == self.count + 1 {
+                    b"]}".to_vec()
+                } else {
+                    return Ok(0);
+                };
+                self.idx += 1;
+            }
+            let n = buf.len().min(self.chunk.len() - self.pos);
+            buf[..n].copy_from_slice(&self.chunk[self.pos..self.pos + n]);
+            self.pos += n;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn generated_input() -> Result<()> {
+        struct FeatureCount(u64);
+        impl crate::GeomProcessor for FeatureCount {}
+        impl crate::PropertyProcessor for FeatureCount {}
+        impl FeatureProcessor for FeatureCount {
+            fn feature_end(&mut self, _idx: u64) -> Result<()> {
+                self.0 += 1;
+                Ok(())
+            }
+        }
+
+        // Only the current feature is kept in memory
+        let input = GeneratedFeatures {
+            count: 100_000,
+            idx: 0,
+            chunk: Vec::new(),
+            pos: 0,
+        };
+        let mut counter = FeatureCount(0);
+        read_geojson_streaming(input, &mut counter)?;
+        assert_eq!(counter.0, 100_000);
+        Ok(())
+    }
+}
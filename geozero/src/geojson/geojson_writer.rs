@@ -1,4 +1,5 @@
-use crate::error::Result;
+use crate::error::{GeozeroError, Result};
+use crate::formatting::{write_rounded, MAX_PRECISION};
 use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
 use std::fmt::Display;
 use std::io::Write;
@@ -6,18 +7,208 @@ use std::io::Write;
 /// GeoJSON writer.
 pub struct GeoJsonWriter<W: Write> {
     dims: CoordDimensions,
-    out: W,
+    crs: Option<String>,
+    /// CRS set with `with_crs`, taking precedence over the dataset CRS
+    fixed_crs: Option<String>,
+    precision: Option<usize>,
+    write_bbox: bool,
+    write_feature_bbox: bool,
+    bbox: Bounds,
+    feature_bbox: Bounds,
+    foreign_members: Vec<(String, serde_json::Value)>,
+    null_mode: NullMode,
+    /// Number of properties written for the current feature
+    property_count: usize,
+    /// Number of positions written in the current MultiPoint
+    multipoint_len: Option<usize>,
+    pub(crate) out: JsonOut<W>,
+}
+
+/// Handling of null property values by [`GeoJsonWriter`].
+///
+/// Null values are passed as `ColumnValue::Json("null")`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NullMode {
+    /// Write null values as `"key": null` (default)
+    #[default]
+    Emit,
+    /// Omit properties with null values
+    Skip,
+}
+
+/// Bounds of processed coordinates
+#[derive(Clone, Copy, Debug)]
+struct Bounds {
+    min: [f64; 3],
+    max: [f64; 3],
+    has_z: bool,
+}
+
+impl Default for Bounds {
+    fn default() -> Self {
+        Bounds {
+            min: [f64::INFINITY; 3],
+            max: [f64::NEG_INFINITY; 3],
+            has_z: false,
+        }
+    }
+}
+
+impl Bounds {
+    fn is_empty(&self) -> bool {
+        self.min[0] > self.max[0]
+    }
+    fn expand(&mut self, x: f64, y: f64, z: Option<f64>) {
+        self.min[0] = self.min[0].min(x);
+        self.min[1] = self.min[1].min(y);
+        self.max[0] = self.max[0].max(x);
+        self.max[1] = self.max[1].max(y);
+        if let Some(z) = z {
+            self.min[2] = self.min[2].min(z);
+            self.max[2] = self.max[2].max(z);
+            self.has_z = true;
+        }
+    }
+    fn values(&self) -> Vec<f64> {
+        if self.has_z {
+            vec![
+                self.min[0],
+                self.min[1],
+                self.min[2],
+                self.max[0],
+                self.max[1],
+                self.max[2],
+            ]
+        } else {
+            vec![self.min[0], self.min[1], self.max[0], self.max[1]]
+        }
+    }
 }
 
 impl<W: Write> GeoJsonWriter<W> {
     pub fn new(out: W) -> Self {
+        Self::with_dims(out, CoordDimensions::default())
+    }
+    pub fn with_dims(out: W, dims: CoordDimensions) -> Self {
         GeoJsonWriter {
-            dims: CoordDimensions::default(),
-            out,
+            dims,
+            crs: None,
+            fixed_crs: None,
+            precision: None,
+            write_bbox: false,
+            write_feature_bbox: false,
+            bbox: Bounds::default(),
+            feature_bbox: Bounds::default(),
+            foreign_members: Vec::new(),
+            null_mode: NullMode::default(),
+            property_count: 0,
+            multipoint_len: None,
+            out: JsonOut {
+                inner: out,
+                pretty: None,
+            },
         }
     }
-    pub fn with_dims(out: W, dims: CoordDimensions) -> Self {
-        GeoJsonWriter { dims, out }
+    /// Round coordinates to the given number of decimal places.
+    ///
+    /// Trailing zeros are omitted, e.g. `1.50` is written as `1.5`.
+    pub fn with_precision(mut self, decimals: usize) -> Self {
+        self.precision = Some(decimals.min(MAX_PRECISION));
+        self
+    }
+    /// Write a `bbox` member with the extent of all features into the FeatureCollection.
+    ///
+    /// Since the output is streamed, the bbox is computed while processing and
+    /// written as last member of the FeatureCollection object, after `features`.
+    /// The `bbox` is omitted for empty datasets.
+    pub fn with_bbox(mut self, enabled: bool) -> Self {
+        self.write_bbox = enabled;
+        self
+    }
+    /// Write a `bbox` member into each Feature object.
+    ///
+    /// The bbox is written as last member of the Feature, after `geometry` and `properties`.
+    pub fn with_feature_bbox(mut self, enabled: bool) -> Self {
+        self.write_feature_bbox = enabled;
+        self
+    }
+    /// Add a foreign member to the FeatureCollection object.
+    ///
+    /// The value is written verbatim. The reserved keys `type`, `features` and `geometry` are rejected.
+    pub fn with_foreign_member(mut self, key: &str, value: serde_json::Value) -> Result<Self> {
+        if matches!(key, "type" | "features" | "geometry") {
+            return Err(GeozeroError::Dataset(format!(
+                "Reserved GeoJSON member `{key}` can not be used as foreign member"
+            )));
+        }
+        self.foreign_members.push((key.to_string(), value));
+        Ok(self)
+    }
+    /// Write a named `crs` member (e.g. `urn:ogc:def:crs:EPSG::3857`) into the FeatureCollection.
+    ///
+    /// The `crs` member was removed by RFC 7946, but is still required by legacy clients
+    /// for data not in WGS84. Overrides the CRS passed by the reader.
+    /// Without this option, a `crs` member is only written for a CRS passed by the reader.
+    pub fn with_crs(mut self, name: &str) -> Self {
+        self.fixed_crs = Some(name.to_string());
+        self
+    }
+    /// Write a named `crs` member with the OGC URN of an EPSG code, see [`Self::with_crs`].
+    pub fn with_crs_epsg(self, epsg: u32) -> Self {
+        self.with_crs(&format!("urn:ogc:def:crs:EPSG::{epsg}"))
+    }
+    /// Handling of null property values. Nulls are emitted by default.
+    pub fn with_null_handling(mut self, null_mode: NullMode) -> Self {
+        self.null_mode = null_mode;
+        self
+    }
+    /// Write indented, human-readable JSON. Output is compact by default.
+    ///
+    /// Every object member and array element, including coordinate values, is written
+    /// on its own line. The indentation width is 2 spaces, unless set with [`Self::with_indent`].
+    pub fn with_pretty(mut self, enabled: bool) -> Self {
+        self.out.pretty = if enabled {
+            Some(Pretty::new(
+                self.out.pretty.as_ref().map_or(2, |pretty| pretty.indent),
+            ))
+        } else {
+            None
+        };
+        self
+    }
+    /// Number of spaces per indentation level of pretty output. Enables pretty output.
+    pub fn with_indent(mut self, indent: usize) -> Self {
+        self.out.pretty = Some(Pretty::new(indent));
+        self
+    }
+    fn expand_bbox(&mut self, x: f64, y: f64, z: Option<f64>) {
+        if self.write_bbox {
+            self.bbox.expand(x, y, z);
+        }
+        if self.write_feature_bbox {
+            self.feature_bbox.expand(x, y, z);
+        }
+    }
+    fn write_bbox_member(&mut self, bounds: Bounds) -> Result<()> {
+        if bounds.is_empty() {
+            return Ok(());
+        }
+        self.out.write_all(br#", "bbox": ["#)?;
+        for (i, v) in bounds.values().into_iter().enumerate() {
+            self.comma(i)?;
+            self.write_num(v)?;
+        }
+        self.out.write_all(b"]")?;
+        Ok(())
+    }
+    fn write_num(&mut self, v: f64) -> Result<()> {
+        match self.precision {
+            Some(decimals) => write_rounded(&mut self.out, v, decimals),
+            None => {
+                write!(self.out, "{v}")?;
+                Ok(())
+            }
+        }
     }
     fn comma(&mut self, idx: usize) -> Result<()> {
         if idx > 0 {
@@ -25,9 +216,28 @@ impl<W: Write> GeoJsonWriter<W> {
         }
         Ok(())
     }
+    /// Separator before a position, counting written positions of MultiPoints
+    fn position_comma(&mut self, idx: usize) -> Result<()> {
+        match &mut self.multipoint_len {
+            Some(len) => {
+                let written = *len;
+                *len += 1;
+                self.comma(written)
+            }
+            None => self.comma(idx),
+        }
+    }
+    /// Empty MultiPoint member, which has no GeoJSON representation
+    fn is_empty_member(&self, x: f64, y: f64) -> bool {
+        self.multipoint_len.is_some() && x.is_nan() && y.is_nan()
+    }
 }
 
 impl<W: Write> FeatureProcessor for GeoJsonWriter<W> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.crs = Some(crs.to_string());
+        Ok(())
+    }
     fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
         self.out.write_all(
             br#"{
@@ -36,6 +246,21 @@ impl<W: Write> FeatureProcessor for GeoJsonWriter<W> {
         if let Some(name) = name {
             write!(self.out, ",\n\"name\": \"{name}\"")?;
         }
+        if let Some(crs) = self.fixed_crs.as_ref().or(self.crs.as_ref()) {
+            let crs =
+                serde_json::to_string(crs).map_err(|e| GeozeroError::Dataset(e.to_string()))?;
+            write!(
+                self.out,
+                ",\n\"crs\": {{\"type\": \"name\", \"properties\": {{\"name\": {crs}}}}}"
+            )?;
+        }
+        for (key, value) in &self.foreign_members {
+            write!(
+                self.out,
+                ",\n{}: {value}",
+                serde_json::Value::from(key.as_str())
+            )?;
+        }
         self.out.write_all(
             br#",
 "features": ["#,
@@ -43,7 +268,11 @@ impl<W: Write> FeatureProcessor for GeoJsonWriter<W> {
         Ok(())
     }
     fn dataset_end(&mut self) -> Result<()> {
-        self.out.write_all(b"]}")?;
+        self.out.write_all(b"]")?;
+        if self.write_bbox {
+            self.write_bbox_member(self.bbox)?;
+        }
+        self.out.write_all(b"}")?;
         Ok(())
     }
     fn feature_begin(&mut self, idx: u64) -> Result<()> {
@@ -51,14 +280,41 @@ impl<W: Write> FeatureProcessor for GeoJsonWriter<W> {
             self.out.write_all(b",\n")?;
         }
         self.out.write_all(br#"{"type": "Feature""#)?;
+        self.feature_bbox = Bounds::default();
+        Ok(())
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        self.out.write_all(br#", "id": "#)?;
+        match id {
+            ColumnValue::String(v) => write!(self.out, "{}", serde_json::Value::from(v))?,
+            ColumnValue::Byte(v) => write!(self.out, "{v}")?,
+            ColumnValue::UByte(v) => write!(self.out, "{v}")?,
+            ColumnValue::Short(v) => write!(self.out, "{v}")?,
+            ColumnValue::UShort(v) => write!(self.out, "{v}")?,
+            ColumnValue::Int(v) => write!(self.out, "{v}")?,
+            ColumnValue::UInt(v) => write!(self.out, "{v}")?,
+            ColumnValue::Long(v) => write!(self.out, "{v}")?,
+            ColumnValue::ULong(v) => write!(self.out, "{v}")?,
+            ColumnValue::Float(v) => write!(self.out, "{v}")?,
+            ColumnValue::Double(v) => write!(self.out, "{v}")?,
+            other => {
+                return Err(GeozeroError::Feature(format!(
+                    "Unsupported feature id value {other:?}"
+                )))
+            }
+        }
         Ok(())
     }
     fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        if self.write_feature_bbox {
+            self.write_bbox_member(self.feature_bbox)?;
+        }
         self.out.write_all(b"}")?;
         Ok(())
     }
     fn properties_begin(&mut self) -> Result<()> {
         self.out.write_all(br#", "properties": {"#)?;
+        self.property_count = 0;
         Ok(())
     }
     fn properties_end(&mut self) -> Result<()> {
@@ -79,8 +335,16 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
         self.dims
     }
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
-        self.comma(idx)?;
-        self.out.write_all(format!("[{x},{y}]").as_bytes())?;
+        if self.is_empty_member(x, y) {
+            return Ok(());
+        }
+        self.expand_bbox(x, y, None);
+        self.position_comma(idx)?;
+        self.out.write_all(b"[")?;
+        self.write_num(x)?;
+        self.out.write_all(b",")?;
+        self.write_num(y)?;
+        self.out.write_all(b"]")?;
         Ok(())
     }
     fn coordinate(
@@ -93,10 +357,18 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
         _tm: Option<u64>,
         idx: usize,
     ) -> Result<()> {
-        self.comma(idx)?;
-        self.out.write_all(format!("[{x},{y}").as_bytes())?;
+        if self.is_empty_member(x, y) {
+            return Ok(());
+        }
+        self.expand_bbox(x, y, z);
+        self.position_comma(idx)?;
+        self.out.write_all(b"[")?;
+        self.write_num(x)?;
+        self.out.write_all(b",")?;
+        self.write_num(y)?;
         if let Some(z) = z {
-            self.out.write_all(format!(",{z}").as_bytes())?;
+            self.out.write_all(b",")?;
+            self.write_num(z)?;
         }
         self.out.write_all(b"]")?;
         Ok(())
@@ -121,9 +393,11 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
         self.comma(idx)?;
         self.out
             .write_all(br#"{"type": "MultiPoint", "coordinates": ["#)?;
+        self.multipoint_len = Some(0);
         Ok(())
     }
     fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.multipoint_len = None;
         self.out.write_all(b"]}")?;
         Ok(())
     }
@@ -195,6 +469,106 @@ impl<W: Write> GeomProcessor for GeoJsonWriter<W> {
     }
 }
 
+/// Output of [`GeoJsonWriter`], optionally reformatting the compact JSON with indentation
+pub(crate) struct JsonOut<W: Write> {
+    inner: W,
+    pretty: Option<Pretty>,
+}
+
+/// Indentation state of pretty output
+struct Pretty {
+    indent: usize,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// An object or array was opened, the line break is deferred until it turns out to be non-empty
+    opened: bool,
+}
+
+impl Pretty {
+    fn new(indent: usize) -> Self {
+        Pretty {
+            indent,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            opened: false,
+        }
+    }
+    fn newline(&self, buf: &mut Vec<u8>) {
+        buf.push(b'\n');
+        buf.resize(buf.len() + self.depth * self.indent, b' ');
+    }
+    fn reformat(&mut self, input: &[u8], buf: &mut Vec<u8>) {
+        for &c in input {
+            if self.in_string {
+                buf.push(c);
+                if self.escaped {
+                    self.escaped = false;
+                } else if c == b'\\' {
+                    self.escaped = true;
+                } else if c == b'"' {
+                    self.in_string = false;
+                }
+                continue;
+            }
+            if c.is_ascii_whitespace() {
+                continue;
+            }
+            let closing = matches!(c, b'}' | b']');
+            if self.opened {
+                self.opened = false;
+                if closing {
+                    // Empty object or array
+                    self.depth -= 1;
+                    buf.push(c);
+                    continue;
+                }
+                self.newline(buf);
+            }
+            match c {
+                b'{' | b'[' => {
+                    buf.push(c);
+                    self.depth += 1;
+                    self.opened = true;
+                }
+                b'}' | b']' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    self.newline(buf);
+                    buf.push(c);
+                }
+                b',' => {
+                    buf.push(c);
+                    self.newline(buf);
+                }
+                b':' => buf.extend_from_slice(b": "),
+                b'"' => {
+                    buf.push(c);
+                    self.in_string = true;
+                }
+                _ => buf.push(c),
+            }
+        }
+    }
+}
+
+impl<W: Write> Write for JsonOut<W> {
+    fn write(&mut self, input: &[u8]) -> std::io::Result<usize> {
+        match &mut self.pretty {
+            None => self.inner.write(input),
+            Some(pretty) => {
+                let mut buf = Vec::with_capacity(input.len() * 2);
+                pretty.reformat(input, &mut buf);
+                self.inner.write_all(&buf)?;
+                Ok(input.len())
+            }
+        }
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 fn write_num_prop<W: Write>(mut out: W, colname: &str, v: &dyn Display) -> Result<()> {
     let colname = colname.replace('\"', "\\\"");
     out.write_all(format!(r#""{colname}": {v}"#).as_bytes())?;
@@ -209,10 +583,14 @@ fn write_str_prop<W: Write>(mut out: W, colname: &str, v: &str) -> Result<()> {
 }
 
 impl<W: Write> PropertyProcessor for GeoJsonWriter<W> {
-    fn property(&mut self, i: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
-        if i > 0 {
+    fn property(&mut self, _i: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        if self.null_mode == NullMode::Skip && *colval == ColumnValue::Json("null") {
+            return Ok(false);
+        }
+        if self.property_count > 0 {
             self.out.write_all(b", ")?;
         }
+        self.property_count += 1;
         match colval {
             ColumnValue::Byte(v) => write_num_prop(&mut self.out, colname, &v)?,
             ColumnValue::UByte(v) => write_num_prop(&mut self.out, colname, &v)?,
@@ -228,7 +606,7 @@ impl<W: Write> PropertyProcessor for GeoJsonWriter<W> {
             ColumnValue::String(v) | ColumnValue::DateTime(v) => {
                 write_str_prop(&mut self.out, colname, v)?;
             }
-            ColumnValue::Json(_v) => (),
+            ColumnValue::Json(v) => write_num_prop(&mut self.out, colname, v)?,
             ColumnValue::Binary(_v) => (),
         };
         Ok(false)
@@ -238,9 +616,9 @@ impl<W: Write> PropertyProcessor for GeoJsonWriter<W> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::geojson::read_geojson;
+    use crate::geojson::{read_geojson, read_geojson_geom};
     use crate::wkt::WktStr;
-    use crate::ToJson;
+    use crate::{GeozeroGeometry, ToJson};
 
     #[test]
     fn geometries() -> Result<()> {
@@ -254,7 +632,9 @@ mod test {
             ]]]
         }"#;
         let mut out: Vec<u8> = Vec::new();
-        assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
+        assert!(
+            read_geojson_geom(&mut geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok()
+        );
         // Has Multi-Ring Polygon
         assert_json_eq(&out, geojson);
 
@@ -268,7 +648,9 @@ mod test {
             ]]]
         }"#;
         let mut out: Vec<u8> = Vec::new();
-        assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
+        assert!(
+            read_geojson_geom(&mut geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok()
+        );
         // Has multiple Polygons
         assert_json_eq(&out, geojson);
 
@@ -280,7 +662,9 @@ mod test {
             ]
         }"#;
         let mut out: Vec<u8> = Vec::new();
-        assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
+        assert!(
+            read_geojson_geom(&mut geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok()
+        );
         assert_json_eq(&out, geojson);
 
         // ne_10m_geographic_lines.fgb, first feature
@@ -291,14 +675,18 @@ mod test {
             ]]
         }"#;
         let mut out: Vec<u8> = Vec::new();
-        assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
+        assert!(
+            read_geojson_geom(&mut geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok()
+        );
         assert_json_eq(&out, geojson);
 
         // ne_10m_admin_0_country_points.fgb, first feature
         let geojson =
             r#"{"type": "Point", "coordinates": [2223639.4731508396,-15878634.348995442]}"#;
         let mut out: Vec<u8> = Vec::new();
-        assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
+        assert!(
+            read_geojson_geom(&mut geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok()
+        );
         assert_json_eq(&out, geojson);
 
         // geoz_lod1_gebaeude_max_3d_extract.fgb, first feature
@@ -329,9 +717,250 @@ mod test {
             ]
         }"#;
         let mut out: Vec<u8> = Vec::new();
-        assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
+        assert!(
+            read_geojson_geom(&mut geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok()
+        );
+        assert_json_eq(&out, geojson);
+
+        Ok(())
+    }
+
+    #[test]
+    fn precision() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer =
+            GeoJsonWriter::with_dims(&mut out, CoordDimensions::xyz()).with_precision(2);
+        WktStr("LINESTRING(1.5 -0.001 10,2.12345 -2.999 0.004,-1.005 1e20 -7)")
+            .process_geom(&mut writer)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{"type": "LineString", "coordinates": [[1.5,0,10],[2.12,-3,0],[-1,100000000000000000000,-7]]}"#
+        );
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out).with_precision(6);
+        WktStr("POINT(8.123456789 47.05)").process_geom(&mut writer)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{"type": "Point", "coordinates": [8.123457,47.05]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bbox() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": 1}, "geometry": {"type": "Point", "coordinates": [1,2]}},
+            {"type": "Feature", "properties": {"id": 2}, "geometry": {"type": "LineString", "coordinates": [[-3,5],[4,-1.5]]}}
+        ]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out).with_bbox(true);
+        read_geojson(geojson.as_bytes(), &mut writer)?;
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["bbox"], serde_json::json!([-3, -1.5, 4, 5]));
+        assert!(json["features"][0].get("bbox").is_none());
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out).with_feature_bbox(true);
+        read_geojson(geojson.as_bytes(), &mut writer)?;
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert!(json.get("bbox").is_none());
+        assert_eq!(json["features"][0]["bbox"], serde_json::json!([1, 2, 1, 2]));
+        assert_eq!(
+            json["features"][1]["bbox"],
+            serde_json::json!([-3, -1.5, 4, 5])
+        );
+
+        // 3D bbox
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[1,1,10],[2,2,20]]}}
+        ]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::with_dims(&mut out, CoordDimensions::xyz()).with_bbox(true);
+        read_geojson(geojson.as_bytes(), &mut writer)?;
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["bbox"], serde_json::json!([1, 1, 10, 2, 2, 20]));
+
+        // Empty dataset
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out).with_bbox(true);
+        read_geojson(
+            r#"{"type": "FeatureCollection", "features": []}"#.as_bytes(),
+            &mut writer,
+        )?;
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert!(json.get("bbox").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn foreign_members() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out)
+            .with_foreign_member("generator", serde_json::json!("geozero"))?
+            .with_foreign_member("meta", serde_json::json!({"zoom": [0, 14]}))?;
+        read_geojson(
+            r#"{"type": "FeatureCollection", "features": []}"#.as_bytes(),
+            &mut writer,
+        )?;
+        assert_json_eq(
+            &out,
+            r#"{"type": "FeatureCollection", "generator": "geozero", "meta": {"zoom": [0, 14]}, "features": []}"#,
+        );
+
+        for key in ["type", "features", "geometry"] {
+            assert!(GeoJsonWriter::new(Vec::new())
+                .with_foreign_member(key, serde_json::Value::Null)
+                .is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn named_crs() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": []}"#;
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out).with_crs_epsg(3857);
+        read_geojson(geojson.as_bytes(), &mut writer)?;
+        assert_json_eq(
+            &out,
+            r#"{"type": "FeatureCollection", "crs": {"type": "name", "properties": {"name": "urn:ogc:def:crs:EPSG::3857"}}, "features": []}"#,
+        );
+
+        // Explicit CRS overrides the dataset CRS
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out).with_crs("urn:ogc:def:crs:OGC:1.3:CRS84");
+        writer.crs("EPSG:2056")?;
+        read_geojson(geojson.as_bytes(), &mut writer)?;
+        assert_json_eq(
+            &out,
+            r#"{"type": "FeatureCollection", "crs": {"type": "name", "properties": {"name": "urn:ogc:def:crs:OGC:1.3:CRS84"}}, "features": []}"#,
+        );
+
+        // Names are escaped
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out).with_crs("a\\\"b\n");
+        read_geojson(geojson.as_bytes(), &mut writer)?;
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["crs"]["properties"]["name"], "a\\\"b\n");
+
+        // No crs member by default
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out))?;
+        assert_json_eq(&out, geojson);
+        Ok(())
+    }
+
+    #[test]
+    fn null_properties() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"a": null, "b": 1, "c": null, "d": "x"}, "geometry": {"type": "Point", "coordinates": [1,2]}}
+        ]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out))?;
+        assert_json_eq(&out, geojson);
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::new(&mut out).with_null_handling(NullMode::Skip);
+        read_geojson(geojson.as_bytes(), &mut writer)?;
+        assert_json_eq(
+            &out,
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {"b": 1, "d": "x"}, "geometry": {"type": "Point", "coordinates": [1,2]}}
+            ]}"#,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn pretty() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"count": 1, "name": "a, b"}, "geometry": {"type": "Point", "coordinates": [1,2]}},
+            {"type": "Feature", "properties": {"name": "{c}"}, "geometry": {"type": "LineString", "coordinates": [[1,2],[3,4]]}}
+        ]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson(
+            geojson.as_bytes(),
+            &mut GeoJsonWriter::new(&mut out).with_pretty(true),
+        )?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+  "type": "FeatureCollection",
+  "features": [
+    {
+      "type": "Feature",
+      "properties": {
+        "count": 1,
+        "name": "a, b"
+      },
+      "geometry": {
+        "type": "Point",
+        "coordinates": [
+          1,
+          2
+        ]
+      }
+    },
+    {
+      "type": "Feature",
+      "properties": {
+        "name": "{c}"
+      },
+      "geometry": {
+        "type": "LineString",
+        "coordinates": [
+          [
+            1,
+            2
+          ],
+          [
+            3,
+            4
+          ]
+        ]
+      }
+    }
+  ]
+}"#
+        );
         assert_json_eq(&out, geojson);
 
+        let mut out: Vec<u8> = Vec::new();
+        let wkt = WktStr("GEOMETRYCOLLECTION(POINT EMPTY,POINT(1 2))");
+        wkt.process_geom(&mut GeoJsonWriter::new(&mut out).with_indent(4))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{
+    "type": "GeometryCollection",
+    "geometries": [
+        {
+            "type": "Point",
+            "coordinates": []
+        },
+        {
+            "type": "Point",
+            "coordinates": [
+                1,
+                2
+            ]
+        }
+    ]
+}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn feature_id() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "id": "a\"1", "properties": {"name": "first"}, "geometry": {"type": "Point", "coordinates": [1,2]}},
+            {"type": "Feature", "id": 42, "properties": {"name": "second"}, "geometry": {"type": "Point", "coordinates": [3,4]}},
+            {"type": "Feature", "properties": {"name": "third"}, "geometry": {"type": "Point", "coordinates": [5,6]}}
+        ]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out))?;
+        assert_json_eq(&out, geojson);
         Ok(())
     }
 
@@ -341,7 +970,7 @@ mod test {
         let mut out: Vec<u8> = Vec::new();
         let mut writer = GeoJsonWriter::new(&mut out);
         writer.dims = CoordDimensions::xyz();
-        assert!(read_geojson(&mut geojson.as_bytes(), &mut writer).is_ok());
+        assert!(read_geojson_geom(&mut geojson.as_bytes(), &mut writer).is_ok());
         assert_json_eq(&out, geojson);
 
         Ok(())
@@ -362,7 +991,9 @@ mod test {
             ]
         }"#;
         let mut out: Vec<u8> = Vec::new();
-        assert!(read_geojson(geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok());
+        assert!(
+            read_geojson_geom(&mut geojson.as_bytes(), &mut GeoJsonWriter::new(&mut out)).is_ok()
+        );
         assert_json_eq(&out, geojson);
         Ok(())
     }
@@ -435,7 +1066,13 @@ mod test {
         assert_eq!(
             &geom.to_json().unwrap(),
             r#"{"type": "Point", "coordinates": []}"#
-        )
+        );
+
+        let geom = WktStr("LINESTRING(1 2,3 4)");
+        assert_eq!(
+            &geom.to_geojson().unwrap(),
+            r#"{"type": "LineString", "coordinates": [[1,2],[3,4]]}"#
+        );
     }
 
     fn assert_json_eq(a: &[u8], b: &str) {
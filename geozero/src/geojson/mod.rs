@@ -1,11 +1,17 @@
 //! GeoJSON conversions.
 pub(crate) mod geojson_line_reader;
+pub(crate) mod geojson_line_writer;
 pub(crate) mod geojson_reader;
+pub(crate) mod geojson_stream_reader;
 pub(crate) mod geojson_writer;
+pub(crate) mod topojson_reader;
 
 pub use geojson_line_reader::*;
+pub use geojson_line_writer::*;
 pub use geojson_reader::*;
+pub use geojson_stream_reader::*;
 pub use geojson_writer::*;
+pub use topojson_reader::*;
 
 pub(crate) mod conversion {
     use crate::error::Result;
@@ -13,9 +19,20 @@ pub(crate) mod conversion {
     use crate::{GeozeroDatasource, GeozeroGeometry};
 
     /// Convert to GeoJSON.
+    ///
+    /// ```
+    /// use geozero::{wkt::WktStr, ToJson};
+    ///
+    /// let geom = WktStr("POINT(10 20)");
+    /// assert_eq!(geom.to_geojson().unwrap(), r#"{"type": "Point", "coordinates": [10,20]}"#);
+    /// ```
     pub trait ToJson {
         /// Convert to GeoJSON String.
         fn to_json(&self) -> Result<String>;
+        /// Convert to GeoJSON String (same as [`to_json`](ToJson::to_json)).
+        fn to_geojson(&self) -> Result<String> {
+            self.to_json()
+        }
     }
 
     impl<T: GeozeroGeometry> ToJson for T {
@@ -0,0 +1,358 @@
+use crate::error::{GeozeroError, Result};
+use crate::geojson::geojson_reader::process_properties;
+use crate::{ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource};
+use serde_json::Value;
+use std::io::Read;
+
+/// TopoJSON reader.
+///
+/// Arcs are resolved into absolute coordinates, applying the `transform` of quantized
+/// topologies. The members of top-level GeometryCollection objects are processed
+/// as features, other objects as single features.
+pub struct TopoJsonReader<R: Read> {
+    reader: R,
+    object: Option<String>,
+}
+
+impl<R: Read> TopoJsonReader<R> {
+    pub fn new(reader: R) -> Self {
+        TopoJsonReader {
+            reader,
+            object: None,
+        }
+    }
+    /// Read only the object with the given name (default: all objects).
+    pub fn with_object(mut self, name: &str) -> Self {
+        self.object = Some(name.to_string());
+        self
+    }
+}
+
+impl<R: Read> GeozeroDatasource for TopoJsonReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_topojson(&mut self.reader, self.object.as_deref(), processor)
+    }
+}
+
+/// Read and process TopoJSON, optionally only the object with the name `object`.
+pub fn read_topojson<R: Read, P: FeatureProcessor>(
+    reader: R,
+    object: Option<&str>,
+    processor: &mut P,
+) -> Result<()> {
+    let topology: Value = serde_json::from_reader(reader).map_err(invalid)?;
+    if topology["type"] != "Topology" {
+        return Err(invalid("TopoJSON Topology expected"));
+    }
+    let topology = Topology::from_json(&topology)?;
+    let objects = topology.objects;
+    if let Some(name) = object {
+        if !objects.contains_key(name) {
+            return Err(GeozeroError::Dataset(format!("object `{name}` not found")));
+        }
+    }
+    processor.dataset_begin(object)?;
+    let mut idx = 0;
+    for (name, obj) in objects {
+        if object.is_some_and(|object| object != name) {
+            continue;
+        }
+        match (obj["type"].as_str(), obj["geometries"].as_array()) {
+            (Some("GeometryCollection"), Some(geometries)) => {
+                for geometry in geometries {
+                    topology.process_feature(geometry, idx, processor)?;
+                    idx += 1;
+                }
+            }
+            _ => {
+                topology.process_feature(obj, idx, processor)?;
+                idx += 1;
+            }
+        }
+    }
+    processor.dataset_end()
+}
+
+fn invalid(e: impl std::fmt::Display) -> GeozeroError {
+    GeozeroError::Geometry(e.to_string())
+}
+
+#[derive(Clone, Copy)]
+struct Transform {
+    scale: [f64; 2],
+    translate: [f64; 2],
+}
+
+impl Transform {
+    fn apply(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            x * self.scale[0] + self.translate[0],
+            y * self.scale[1] + self.translate[1],
+        )
+    }
+}
+
+struct Topology<'a> {
+    transform: Option<Transform>,
+    /// Arcs with absolute coordinates
+    arcs: Vec<Vec<(f64, f64)>>,
+    objects: &'a serde_json::Map<String, Value>,
+}
+
+fn position(pos: &Value) -> Result<(f64, f64)> {
+    match (
+        pos.get(0).and_then(Value::as_f64),
+        pos.get(1).and_then(Value::as_f64),
+    ) {
+        (Some(x), Some(y)) => Ok((x, y)),
+        _ => Err(GeozeroError::Coord),
+    }
+}
+
+fn array(value: &Value) -> Result<&Vec<Value>> {
+    value
+        .as_array()
+        .ok_or_else(|| invalid(format!("TopoJSON array expected, found `{value}`")))
+}
+
+impl<'a> Topology<'a> {
+    fn from_json(topology: &'a Value) -> Result<Self> {
+        let transform = match &topology["transform"] {
+            Value::Null => None,
+            transform => match (
+                position(&transform["scale"]),
+                position(&transform["translate"]),
+            ) {
+                (Ok((sx, sy)), Ok((tx, ty))) => Some(Transform {
+                    scale: [sx, sy],
+                    translate: [tx, ty],
+                }),
+                _ => return Err(invalid("invalid TopoJSON transform")),
+            },
+        };
+        // Positions of quantized arcs are delta-encoded
+        let arcs = array(&topology["arcs"])?
+            .iter()
+            .map(|arc| {
+                let (mut x, mut y) = (0.0, 0.0);
+                array(arc)?
+                    .iter()
+                    .map(|pos| {
+                        let (dx, dy) = position(pos)?;
+                        match transform {
+                            Some(transform) => {
+                                x += dx;
+                                y += dy;
+                                Ok(transform.apply((x, y)))
+                            }
+                            None => Ok((dx, dy)),
+                        }
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<_>>()?;
+        let objects = topology["objects"]
+            .as_object()
+            .ok_or_else(|| invalid("TopoJSON objects expected"))?;
+        Ok(Topology {
+            transform,
+            arcs,
+            objects,
+        })
+    }
+
+    fn point(&self, pos: &Value) -> Result<(f64, f64)> {
+        let pos = position(pos)?;
+        Ok(self.transform.map_or(pos, |t| t.apply(pos)))
+    }
+
+    /// Coordinates of a line from arc indexes. Negative indexes reference reversed arcs (`!idx`).
+    fn line(&self, arc_refs: &Value) -> Result<Vec<(f64, f64)>> {
+        let mut coords = Vec::new();
+        for arc_ref in array(arc_refs)? {
+            let arc_ref = arc_ref
+                .as_i64()
+                .ok_or_else(|| invalid(format!("invalid TopoJSON arc index `{arc_ref}`")))?;
+            let (arc_idx, reversed) = if arc_ref < 0 {
+                (!arc_ref as usize, true)
+            } else {
+                (arc_ref as usize, false)
+            };
+            let arc = self
+                .arcs
+                .get(arc_idx)
+                .ok_or_else(|| invalid(format!("TopoJSON arc index `{arc_ref}` out of range")))?;
+            // Consecutive arcs share their end and start point
+            let skip = usize::from(!coords.is_empty());
+            if reversed {
+                coords.extend(arc.iter().rev().skip(skip));
+            } else {
+                coords.extend(arc.iter().skip(skip));
+            }
+        }
+        Ok(coords)
+    }
+
+    fn process_feature<P: FeatureProcessor>(
+        &self,
+        geometry: &Value,
+        idx: u64,
+        processor: &mut P,
+    ) -> Result<()> {
+        processor.feature_begin(idx)?;
+        match &geometry["id"] {
+            Value::String(id) => processor.feature_id(ColumnValue::String(id))?,
+            Value::Number(id) => {
+                if let Some(v) = id.as_u64() {
+                    processor.feature_id(ColumnValue::ULong(v))?;
+                } else if let Some(v) = id.as_i64() {
+                    processor.feature_id(ColumnValue::Long(v))?;
+                } else if let Some(v) = id.as_f64() {
+                    processor.feature_id(ColumnValue::Double(v))?;
+                }
+            }
+            _ => {}
+        }
+        if let Some(properties) = geometry["properties"].as_object() {
+            processor.properties_begin()?;
+            process_properties(properties, processor)?;
+            processor.properties_end()?;
+        }
+        // Geometries with type `null` have no coordinates
+        if !geometry["type"].is_null() {
+            processor.geometry_begin()?;
+            self.process_geom(geometry, 0, processor)?;
+            processor.geometry_end()?;
+        }
+        processor.feature_end(idx)
+    }
+
+    fn process_geom<P: GeomProcessor>(
+        &self,
+        geometry: &Value,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        match geometry["type"].as_str() {
+            Some("Point") => {
+                let (x, y) = self.point(&geometry["coordinates"])?;
+                processor.point_begin(idx)?;
+                processor.xy(x, y, 0)?;
+                processor.point_end(idx)
+            }
+            Some("MultiPoint") => {
+                let points = array(&geometry["coordinates"])?;
+                processor.multipoint_begin(points.len(), idx)?;
+                for (i, pos) in points.iter().enumerate() {
+                    let (x, y) = self.point(pos)?;
+                    processor.xy(x, y, i)?;
+                }
+                processor.multipoint_end(idx)
+            }
+            Some("LineString") => self.process_line(&geometry["arcs"], true, idx, processor),
+            Some("MultiLineString") => {
+                let lines = array(&geometry["arcs"])?;
+                processor.multilinestring_begin(lines.len(), idx)?;
+                for (i, line) in lines.iter().enumerate() {
+                    self.process_line(line, false, i, processor)?;
+                }
+                processor.multilinestring_end(idx)
+            }
+            Some("Polygon") => self.process_polygon(&geometry["arcs"], true, idx, processor),
+            Some("MultiPolygon") => {
+                let polygons = array(&geometry["arcs"])?;
+                processor.multipolygon_begin(polygons.len(), idx)?;
+                for (i, polygon) in polygons.iter().enumerate() {
+                    self.process_polygon(polygon, false, i, processor)?;
+                }
+                processor.multipolygon_end(idx)
+            }
+            Some("GeometryCollection") => {
+                let geometries = array(&geometry["geometries"])?;
+                processor.geometrycollection_begin(geometries.len(), idx)?;
+                for (i, geometry) in geometries.iter().enumerate() {
+                    self.process_geom(geometry, i, processor)?;
+                }
+                processor.geometrycollection_end(idx)
+            }
+            _ => Err(invalid(format!(
+                "unsupported TopoJSON geometry type `{}`",
+                geometry["type"]
+            ))),
+        }
+    }
+
+    fn process_line<P: GeomProcessor>(
+        &self,
+        arc_refs: &Value,
+        tagged: bool,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let coords = self.line(arc_refs)?;
+        processor.linestring_begin(tagged, coords.len(), idx)?;
+        for (i, (x, y)) in coords.into_iter().enumerate() {
+            processor.xy(x, y, i)?;
+        }
+        processor.linestring_end(tagged, idx)
+    }
+
+    fn process_polygon<P: GeomProcessor>(
+        &self,
+        rings: &Value,
+        tagged: bool,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let rings = array(rings)?;
+        processor.polygon_begin(tagged, rings.len(), idx)?;
+        for (i, ring) in rings.iter().enumerate() {
+            self.process_line(ring, false, i, processor)?;
+        }
+        processor.polygon_end(tagged, idx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::wkt::WktWriter;
+    use crate::ProcessToJson;
+    use std::fs::File;
+
+    #[test]
+    fn shared_arcs() -> Result<()> {
+        let f = File::open("tests/data/shared_edge.topojson")?;
+        let geojson = TopoJsonReader::new(f).with_object("regions").to_json()?;
+        assert_eq!(
+            geojson,
+            r#"{
+"type": "FeatureCollection",
+"name": "regions",
+"features": [{"type": "Feature", "id": "L", "properties": {"name": "left"}, "geometry": {"type": "Polygon", "coordinates": [[[102,10],[102,12],[100,12],[100,10],[102,10]]]}},
+{"type": "Feature", "id": "R", "properties": {"name": "right"}, "geometry": {"type": "Polygon", "coordinates": [[[102,10],[104,10],[104,12],[102,12],[102,10]]]}},
+{"type": "Feature", "properties": {"name": "both"}, "geometry": {"type": "MultiPolygon", "coordinates": [[[[102,10],[102,12],[100,12],[100,10],[102,10]]],[[[102,10],[104,10],[104,12],[102,12],[102,10]]]]}}]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn all_objects() -> Result<()> {
+        let f = File::open("tests/data/shared_edge.topojson")?;
+        let mut wkt_data: Vec<u8> = Vec::new();
+        TopoJsonReader::new(f).process(&mut WktWriter::new(&mut wkt_data))?;
+        let wkt = std::str::from_utf8(&wkt_data).unwrap();
+        // Reversed arc
+        assert!(wkt.contains("LINESTRING(102 10,100 10,100 12,102 12)"));
+        assert!(wkt.contains("POINT(101 11)"));
+        assert_eq!(wkt.matches("POLYGON(").count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_object() {
+        let topojson = r#"{"type": "Topology", "objects": {}, "arcs": []}"#;
+        let mut reader = TopoJsonReader::new(topojson.as_bytes()).with_object("missing");
+        assert!(reader.to_json().is_err());
+    }
+}
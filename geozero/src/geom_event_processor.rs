@@ -0,0 +1,488 @@
+use crate::error::Result;
+use crate::geom_type_filter::GeomEvent;
+use crate::geometry_processor::GeomProcessor;
+use crate::GeomType;
+
+/// Implement the processor traits for a processor wrapper `$name<P>` with an `inner` processor.
+///
+/// Geometry callbacks are passed as [`GeomEvent`] to the method `geom_event` of the wrapper.
+/// Feature and property callbacks, including [`skip_geometry`](crate::FeatureProcessor::skip_geometry),
+/// are passed to the inner processor. Requested dimensions are the ones of the inner processor,
+/// unless given as `self => expression`.
+macro_rules! geom_event_processor {
+    ($name:ident) => {
+        $crate::geom_event_processor::geom_event_processor!($name, self => self.inner.dimensions());
+    };
+    ($name:ident, $self:ident => $dimensions:expr) => {
+        impl<P: $crate::GeomProcessor> $crate::GeomProcessor for $name<P> {
+            fn dimensions(&$self) -> $crate::CoordDimensions {
+                $dimensions
+            }
+            fn multi_dim(&self) -> bool {
+                self.inner.multi_dim()
+            }
+            fn srid(&mut self, srid: Option<i32>) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Srid(srid))
+            }
+            fn input_dimensions(
+                &mut self,
+                dimensions: $crate::CoordDimensions,
+            ) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::InputDimensions(
+                    dimensions,
+                ))
+            }
+            fn xy(&mut self, x: f64, y: f64, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Xy(x, y, idx))
+            }
+            fn coordinate(
+                &mut self,
+                x: f64,
+                y: f64,
+                z: Option<f64>,
+                m: Option<f64>,
+                t: Option<f64>,
+                tm: Option<u64>,
+                idx: usize,
+            ) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Coordinate(
+                    x, y, z, m, t, tm, idx,
+                ))
+            }
+            fn empty_point(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::EmptyPoint(idx))
+            }
+            fn point_begin(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::Point,
+                    true,
+                    1,
+                    idx,
+                ))
+            }
+            fn point_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::Point,
+                    true,
+                    idx,
+                ))
+            }
+            fn multipoint_begin(&mut self, size: usize, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::MultiPoint,
+                    true,
+                    size,
+                    idx,
+                ))
+            }
+            fn multipoint_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::MultiPoint,
+                    true,
+                    idx,
+                ))
+            }
+            fn linestring_begin(
+                &mut self,
+                tagged: bool,
+                size: usize,
+                idx: usize,
+            ) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::LineString,
+                    tagged,
+                    size,
+                    idx,
+                ))
+            }
+            fn linestring_end(&mut self, tagged: bool, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::LineString,
+                    tagged,
+                    idx,
+                ))
+            }
+            fn multilinestring_begin(
+                &mut self,
+                size: usize,
+                idx: usize,
+            ) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::MultiLineString,
+                    true,
+                    size,
+                    idx,
+                ))
+            }
+            fn multilinestring_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::MultiLineString,
+                    true,
+                    idx,
+                ))
+            }
+            fn polygon_begin(
+                &mut self,
+                tagged: bool,
+                size: usize,
+                idx: usize,
+            ) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::Polygon,
+                    tagged,
+                    size,
+                    idx,
+                ))
+            }
+            fn polygon_end(&mut self, tagged: bool, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::Polygon,
+                    tagged,
+                    idx,
+                ))
+            }
+            fn multipolygon_begin(&mut self, size: usize, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::MultiPolygon,
+                    true,
+                    size,
+                    idx,
+                ))
+            }
+            fn multipolygon_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::MultiPolygon,
+                    true,
+                    idx,
+                ))
+            }
+            fn geometrycollection_begin(
+                &mut self,
+                size: usize,
+                idx: usize,
+            ) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::GeometryCollection,
+                    true,
+                    size,
+                    idx,
+                ))
+            }
+            fn geometrycollection_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::GeometryCollection,
+                    true,
+                    idx,
+                ))
+            }
+            fn circularstring_begin(
+                &mut self,
+                size: usize,
+                idx: usize,
+            ) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::CircularString,
+                    true,
+                    size,
+                    idx,
+                ))
+            }
+            fn circularstring_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::CircularString,
+                    true,
+                    idx,
+                ))
+            }
+            fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::CompoundCurve,
+                    true,
+                    size,
+                    idx,
+                ))
+            }
+            fn compoundcurve_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::CompoundCurve,
+                    true,
+                    idx,
+                ))
+            }
+            fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::CurvePolygon,
+                    true,
+                    size,
+                    idx,
+                ))
+            }
+            fn curvepolygon_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::CurvePolygon,
+                    true,
+                    idx,
+                ))
+            }
+            fn multicurve_begin(&mut self, size: usize, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::MultiCurve,
+                    true,
+                    size,
+                    idx,
+                ))
+            }
+            fn multicurve_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::MultiCurve,
+                    true,
+                    idx,
+                ))
+            }
+            fn multisurface_begin(&mut self, size: usize, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::MultiSurface,
+                    true,
+                    size,
+                    idx,
+                ))
+            }
+            fn multisurface_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::MultiSurface,
+                    true,
+                    idx,
+                ))
+            }
+            fn triangle_begin(
+                &mut self,
+                tagged: bool,
+                size: usize,
+                idx: usize,
+            ) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::Triangle,
+                    tagged,
+                    size,
+                    idx,
+                ))
+            }
+            fn triangle_end(&mut self, tagged: bool, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::Triangle,
+                    tagged,
+                    idx,
+                ))
+            }
+            fn polyhedralsurface_begin(
+                &mut self,
+                size: usize,
+                idx: usize,
+            ) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::PolyhedralSurface,
+                    true,
+                    size,
+                    idx,
+                ))
+            }
+            fn polyhedralsurface_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::PolyhedralSurface,
+                    true,
+                    idx,
+                ))
+            }
+            fn tin_begin(&mut self, size: usize, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::Begin(
+                    $crate::GeomType::Tin,
+                    true,
+                    size,
+                    idx,
+                ))
+            }
+            fn tin_end(&mut self, idx: usize) -> $crate::error::Result<()> {
+                self.geom_event($crate::geom_type_filter::GeomEvent::End(
+                    $crate::GeomType::Tin,
+                    true,
+                    idx,
+                ))
+            }
+        }
+
+        impl<P: $crate::GeomProcessor + $crate::PropertyProcessor> $crate::PropertyProcessor
+            for $name<P>
+        {
+            fn property(
+                &mut self,
+                idx: usize,
+                name: &str,
+                value: &$crate::ColumnValue,
+            ) -> $crate::error::Result<bool> {
+                self.inner.property(idx, name, value)
+            }
+        }
+
+        impl<P: $crate::FeatureProcessor> $crate::FeatureProcessor for $name<P> {
+            fn crs(&mut self, crs: &str) -> $crate::error::Result<()> {
+                self.inner.crs(crs)
+            }
+            fn dataset_begin(&mut self, name: Option<&str>) -> $crate::error::Result<()> {
+                self.inner.dataset_begin(name)
+            }
+            fn dataset_end(&mut self) -> $crate::error::Result<()> {
+                self.inner.dataset_end()
+            }
+            fn feature_begin(&mut self, idx: u64) -> $crate::error::Result<()> {
+                self.inner.feature_begin(idx)
+            }
+            fn feature_id(&mut self, id: $crate::ColumnValue) -> $crate::error::Result<()> {
+                self.inner.feature_id(id)
+            }
+            fn feature_end(&mut self, idx: u64) -> $crate::error::Result<()> {
+                self.inner.feature_end(idx)
+            }
+            fn properties_begin(&mut self) -> $crate::error::Result<()> {
+                self.inner.properties_begin()
+            }
+            fn properties_end(&mut self) -> $crate::error::Result<()> {
+                self.inner.properties_end()
+            }
+            fn geometry_begin(&mut self) -> $crate::error::Result<()> {
+                self.inner.geometry_begin()
+            }
+            fn geometry_end(&mut self) -> $crate::error::Result<()> {
+                self.inner.geometry_end()
+            }
+            fn skip_geometry(&self) -> bool {
+                self.inner.skip_geometry()
+            }
+        }
+    };
+}
+
+pub(crate) use geom_event_processor;
+
+/// z, m, t, tm values of `coordinate` calls
+type CoordExtra = (Option<f64>, Option<f64>, Option<f64>, Option<u64>);
+
+/// Coordinate of a buffered linestring.
+#[derive(Clone, Copy)]
+pub(crate) struct Coord {
+    pub(crate) x: f64,
+    pub(crate) y: f64,
+    /// Set for coordinates of `coordinate` calls
+    pub(crate) extra: Option<CoordExtra>,
+}
+
+impl Coord {
+    fn from_event(event: &GeomEvent) -> Option<Coord> {
+        match *event {
+            GeomEvent::Xy(x, y, _) => Some(Coord { x, y, extra: None }),
+            GeomEvent::Coordinate(x, y, z, m, t, tm, _) => Some(Coord {
+                x,
+                y,
+                extra: Some((z, m, t, tm)),
+            }),
+            _ => None,
+        }
+    }
+    pub(crate) fn replay<P: GeomProcessor>(&self, idx: usize, p: &mut P) -> Result<()> {
+        match self.extra {
+            Some((z, m, t, tm)) => p.coordinate(self.x, self.y, z, m, t, tm, idx),
+            None => p.xy(self.x, self.y, idx),
+        }
+    }
+}
+
+/// Linestring collected by [`LineBuffer`].
+pub(crate) struct Line {
+    pub(crate) tagged: bool,
+    pub(crate) idx: usize,
+    /// Linestring is a polygon ring
+    pub(crate) ring: bool,
+    pub(crate) coords: Vec<Coord>,
+}
+
+impl Line {
+    /// Pass the linestring with its current coordinates to `p`.
+    pub(crate) fn replay<P: GeomProcessor>(&self, p: &mut P) -> Result<()> {
+        p.linestring_begin(self.tagged, self.coords.len(), self.idx)?;
+        for (i, coord) in self.coords.iter().enumerate() {
+            coord.replay(i, p)?;
+        }
+        p.linestring_end(self.tagged, self.idx)
+    }
+}
+
+/// Result of [`LineBuffer::push`].
+pub(crate) enum LineEvent {
+    /// Event outside of collected linestrings
+    Pass(GeomEvent),
+    /// Event added to the current linestring
+    Buffered,
+    /// Completed linestring
+    Line(Line),
+}
+
+/// Collects linestrings, e.g. for simplification, whose size is known at their end only.
+pub(crate) struct LineBuffer {
+    /// Collect polygon rings only
+    rings_only: bool,
+    /// Open polygons, whose linestrings are rings
+    polygon_depth: usize,
+    /// Open compound curves, whose linestrings are not rings
+    compoundcurve_depth: usize,
+    /// Current linestring
+    line: Option<Line>,
+}
+
+impl LineBuffer {
+    pub(crate) fn new(rings_only: bool) -> Self {
+        LineBuffer {
+            rings_only,
+            polygon_depth: 0,
+            compoundcurve_depth: 0,
+            line: None,
+        }
+    }
+    pub(crate) fn push(&mut self, event: GeomEvent) -> LineEvent {
+        if let Some(line) = self.line.as_mut() {
+            if let Some(coord) = Coord::from_event(&event) {
+                line.coords.push(coord);
+                return LineEvent::Buffered;
+            }
+            if let GeomEvent::End(GeomType::LineString, ..) = event {
+                return self
+                    .line
+                    .take()
+                    .map_or(LineEvent::Buffered, LineEvent::Line);
+            }
+        }
+        match event {
+            GeomEvent::Begin(
+                GeomType::Polygon | GeomType::CurvePolygon | GeomType::Triangle,
+                ..,
+            ) => self.polygon_depth += 1,
+            GeomEvent::End(GeomType::Polygon | GeomType::CurvePolygon | GeomType::Triangle, ..) => {
+                self.polygon_depth -= 1
+            }
+            GeomEvent::Begin(GeomType::CompoundCurve, ..) => self.compoundcurve_depth += 1,
+            GeomEvent::End(GeomType::CompoundCurve, ..) => self.compoundcurve_depth -= 1,
+            GeomEvent::Begin(GeomType::LineString, tagged, size, idx) => {
+                let ring = self.polygon_depth > 0 && self.compoundcurve_depth == 0;
+                if ring || !self.rings_only {
+                    self.line = Some(Line {
+                        tagged,
+                        idx,
+                        ring,
+                        coords: Vec::with_capacity(size),
+                    });
+                    return LineEvent::Buffered;
+                }
+            }
+            _ => {}
+        }
+        LineEvent::Pass(event)
+    }
+}
@@ -0,0 +1,660 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+
+/// Geometry type as signalled by the first `*_begin` callback of a geometry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeomType {
+    Point,
+    MultiPoint,
+    LineString,
+    MultiLineString,
+    Polygon,
+    MultiPolygon,
+    GeometryCollection,
+    CircularString,
+    CompoundCurve,
+    CurvePolygon,
+    MultiCurve,
+    MultiSurface,
+    Triangle,
+    PolyhedralSurface,
+    Tin,
+}
+
+/// Handling of geometry collections not allowed by a [`GeomTypeFilter`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CollectionMode {
+    /// Keep the whole collection if any member matches
+    #[default]
+    KeepIfAny,
+    /// Emit every matching member as a separate feature
+    Split,
+}
+
+/// Feature processor wrapper passing only features with allowed geometry types.
+///
+/// Feature callbacks are buffered until the geometry type is known.
+/// Emitted features are numbered contiguously.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{GeomType, GeomTypeFilter, GeozeroDatasource};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut filter = GeomTypeFilter::new(
+///     GeoJsonWriter::new(&mut out),
+///     &[GeomType::Polygon, GeomType::MultiPolygon],
+/// );
+/// GeoJson(r#"{"type": "Point", "coordinates": [1, 1]}"#)
+///     .process(&mut filter)
+///     .unwrap();
+/// # }
+/// ```
+pub struct GeomTypeFilter<P: FeatureProcessor> {
+    inner: P,
+    allowed: Vec<GeomType>,
+    collection_mode: CollectionMode,
+    /// Buffered callbacks of the current feature
+    feature: Option<Vec<Event>>,
+    geometry: Vec<GeomEvent>,
+    /// Number of emitted features
+    next_idx: u64,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) enum Value {
+    Byte(i8),
+    UByte(u8),
+    Bool(bool),
+    Short(i16),
+    UShort(u16),
+    Int(i32),
+    UInt(u32),
+    Long(i64),
+    ULong(u64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    Json(String),
+    DateTime(String),
+    Binary(Vec<u8>),
+}
+
+impl From<&ColumnValue<'_>> for Value {
+    fn from(v: &ColumnValue) -> Self {
+        match *v {
+            ColumnValue::Byte(v) => Value::Byte(v),
+            ColumnValue::UByte(v) => Value::UByte(v),
+            ColumnValue::Bool(v) => Value::Bool(v),
+            ColumnValue::Short(v) => Value::Short(v),
+            ColumnValue::UShort(v) => Value::UShort(v),
+            ColumnValue::Int(v) => Value::Int(v),
+            ColumnValue::UInt(v) => Value::UInt(v),
+            ColumnValue::Long(v) => Value::Long(v),
+            ColumnValue::ULong(v) => Value::ULong(v),
+            ColumnValue::Float(v) => Value::Float(v),
+            ColumnValue::Double(v) => Value::Double(v),
+            ColumnValue::String(v) => Value::String(v.to_string()),
+            ColumnValue::Json(v) => Value::Json(v.to_string()),
+            ColumnValue::DateTime(v) => Value::DateTime(v.to_string()),
+            ColumnValue::Binary(v) => Value::Binary(v.to_vec()),
+        }
+    }
+}
+
+impl Value {
+    pub(crate) fn as_column_value(&self) -> ColumnValue<'_> {
+        match self {
+            Value::Byte(v) => ColumnValue::Byte(*v),
+            Value::UByte(v) => ColumnValue::UByte(*v),
+            Value::Bool(v) => ColumnValue::Bool(*v),
+            Value::Short(v) => ColumnValue::Short(*v),
+            Value::UShort(v) => ColumnValue::UShort(*v),
+            Value::Int(v) => ColumnValue::Int(*v),
+            Value::UInt(v) => ColumnValue::UInt(*v),
+            Value::Long(v) => ColumnValue::Long(*v),
+            Value::ULong(v) => ColumnValue::ULong(*v),
+            Value::Float(v) => ColumnValue::Float(*v),
+            Value::Double(v) => ColumnValue::Double(*v),
+            Value::String(v) => ColumnValue::String(v),
+            Value::Json(v) => ColumnValue::Json(v),
+            Value::DateTime(v) => ColumnValue::DateTime(v),
+            Value::Binary(v) => ColumnValue::Binary(v),
+        }
+    }
+}
+
+/// Feature level callback
+#[derive(Clone, Debug)]
+pub(crate) enum Event {
+    FeatureId(Value),
+    PropertiesBegin,
+    Property(usize, String, Value),
+    PropertiesEnd,
+    /// Position of the geometry, from `geometry_begin` to `geometry_end`
+    Geometry,
+}
+
+/// Geometry callback
+#[derive(Clone, Debug)]
+pub(crate) enum GeomEvent {
+    Srid(Option<i32>),
+    InputDimensions(CoordDimensions),
+    Xy(f64, f64, usize),
+    Coordinate(
+        f64,
+        f64,
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        Option<u64>,
+        usize,
+    ),
+    EmptyPoint(usize),
+    /// Begin of a geometry with type, tagged flag, size and index
+    Begin(GeomType, bool, usize, usize),
+    /// End of a geometry with type, tagged flag and index
+    End(GeomType, bool, usize),
+}
+
+impl GeomEvent {
+    pub(crate) fn with_idx(&self, new_idx: usize) -> GeomEvent {
+        match *self {
+            GeomEvent::EmptyPoint(_) => GeomEvent::EmptyPoint(new_idx),
+            GeomEvent::Begin(t, tagged, size, _) => GeomEvent::Begin(t, tagged, size, new_idx),
+            GeomEvent::End(t, tagged, _) => GeomEvent::End(t, tagged, new_idx),
+            ref event => event.clone(),
+        }
+    }
+    pub(crate) fn with_coord_idx(&self, new_idx: usize) -> GeomEvent {
+        match *self {
+            GeomEvent::Xy(x, y, _) => GeomEvent::Xy(x, y, new_idx),
+            GeomEvent::Coordinate(x, y, z, m, t, tm, _) => {
+                GeomEvent::Coordinate(x, y, z, m, t, tm, new_idx)
+            }
+            ref event => event.clone(),
+        }
+    }
+    /// x/y of a coordinate event
+    pub(crate) fn xy(&self) -> Option<(f64, f64)> {
+        match *self {
+            GeomEvent::Xy(x, y, _) | GeomEvent::Coordinate(x, y, ..) => Some((x, y)),
+            _ => None,
+        }
+    }
+    pub(crate) fn replay<P: GeomProcessor>(&self, p: &mut P) -> Result<()> {
+        match *self {
+            GeomEvent::Srid(srid) => p.srid(srid),
+            GeomEvent::InputDimensions(dimensions) => p.input_dimensions(dimensions),
+            GeomEvent::Xy(x, y, idx) => p.xy(x, y, idx),
+            GeomEvent::Coordinate(x, y, z, m, t, tm, idx) => p.coordinate(x, y, z, m, t, tm, idx),
+            GeomEvent::EmptyPoint(idx) => p.empty_point(idx),
+            GeomEvent::Begin(geom_type, tagged, size, idx) => match geom_type {
+                GeomType::Point => p.point_begin(idx),
+                GeomType::MultiPoint => p.multipoint_begin(size, idx),
+                GeomType::LineString => p.linestring_begin(tagged, size, idx),
+                GeomType::MultiLineString => p.multilinestring_begin(size, idx),
+                GeomType::Polygon => p.polygon_begin(tagged, size, idx),
+                GeomType::MultiPolygon => p.multipolygon_begin(size, idx),
+                GeomType::GeometryCollection => p.geometrycollection_begin(size, idx),
+                GeomType::CircularString => p.circularstring_begin(size, idx),
+                GeomType::CompoundCurve => p.compoundcurve_begin(size, idx),
+                GeomType::CurvePolygon => p.curvepolygon_begin(size, idx),
+                GeomType::MultiCurve => p.multicurve_begin(size, idx),
+                GeomType::MultiSurface => p.multisurface_begin(size, idx),
+                GeomType::Triangle => p.triangle_begin(tagged, size, idx),
+                GeomType::PolyhedralSurface => p.polyhedralsurface_begin(size, idx),
+                GeomType::Tin => p.tin_begin(size, idx),
+            },
+            GeomEvent::End(geom_type, tagged, idx) => match geom_type {
+                GeomType::Point => p.point_end(idx),
+                GeomType::MultiPoint => p.multipoint_end(idx),
+                GeomType::LineString => p.linestring_end(tagged, idx),
+                GeomType::MultiLineString => p.multilinestring_end(idx),
+                GeomType::Polygon => p.polygon_end(tagged, idx),
+                GeomType::MultiPolygon => p.multipolygon_end(idx),
+                GeomType::GeometryCollection => p.geometrycollection_end(idx),
+                GeomType::CircularString => p.circularstring_end(idx),
+                GeomType::CompoundCurve => p.compoundcurve_end(idx),
+                GeomType::CurvePolygon => p.curvepolygon_end(idx),
+                GeomType::MultiCurve => p.multicurve_end(idx),
+                GeomType::MultiSurface => p.multisurface_end(idx),
+                GeomType::Triangle => p.triangle_end(tagged, idx),
+                GeomType::PolyhedralSurface => p.polyhedralsurface_end(idx),
+                GeomType::Tin => p.tin_end(idx),
+            },
+        }
+    }
+}
+
+/// Type of the first geometry in `events`.
+pub(crate) fn geometry_type(events: &[GeomEvent]) -> Option<GeomType> {
+    events.iter().find_map(|event| match event {
+        GeomEvent::Begin(geom_type, ..) => Some(*geom_type),
+        GeomEvent::EmptyPoint(_) => Some(GeomType::Point),
+        _ => None,
+    })
+}
+
+/// Event ranges of the direct members of the geometry collection starting at `events[0]`.
+fn collection_members(events: &[GeomEvent]) -> Vec<std::ops::Range<usize>> {
+    let mut members = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, event) in events.iter().enumerate() {
+        match event {
+            GeomEvent::Begin(..) => {
+                if depth == 1 {
+                    start = i;
+                }
+                depth += 1;
+            }
+            GeomEvent::End(..) => {
+                depth -= 1;
+                if depth == 1 {
+                    members.push(start..i + 1);
+                }
+            }
+            GeomEvent::EmptyPoint(_) if depth == 1 => members.push(i..i + 1),
+            _ => {}
+        }
+    }
+    members
+}
+
+impl<P: FeatureProcessor> GeomTypeFilter<P> {
+    /// Pass only features with a geometry of one of the `allowed` types.
+    pub fn new(inner: P, allowed: &[GeomType]) -> Self {
+        GeomTypeFilter {
+            inner,
+            allowed: allowed.to_vec(),
+            collection_mode: CollectionMode::default(),
+            feature: None,
+            geometry: Vec::new(),
+            next_idx: 0,
+        }
+    }
+    /// Handling of geometry collections, if `GeometryCollection` is not allowed.
+    pub fn with_collections(mut self, collection_mode: CollectionMode) -> Self {
+        self.collection_mode = collection_mode;
+        self
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    fn is_allowed(&self, geometry: &[GeomEvent]) -> bool {
+        geometry_type(geometry).is_some_and(|geom_type| self.allowed.contains(&geom_type))
+    }
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        if self.feature.is_some() {
+            self.geometry.push(event);
+            Ok(())
+        } else {
+            event.replay(&mut self.inner)
+        }
+    }
+    fn emit_feature(&mut self, events: &[Event], geometry: &[GeomEvent]) -> Result<()> {
+        let idx = self.next_idx;
+        self.next_idx += 1;
+        self.inner.feature_begin(idx)?;
+        for event in events {
+            match event {
+                Event::FeatureId(id) => self.inner.feature_id(id.as_column_value())?,
+                Event::PropertiesBegin => self.inner.properties_begin()?,
+                Event::Property(i, name, value) => {
+                    self.inner.property(*i, name, &value.as_column_value())?;
+                }
+                Event::PropertiesEnd => self.inner.properties_end()?,
+                Event::Geometry => {
+                    self.inner.geometry_begin()?;
+                    for geom_event in geometry {
+                        geom_event.replay(&mut self.inner)?;
+                    }
+                    self.inner.geometry_end()?;
+                }
+            }
+        }
+        self.inner.feature_end(idx)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for GeomTypeFilter<P> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.next_idx = 0;
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.feature = Some(Vec::new());
+        self.geometry.clear();
+        Ok(())
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::FeatureId(Value::from(&id)));
+        }
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let Some(events) = self.feature.take() else {
+            return Ok(());
+        };
+        let geometry = std::mem::take(&mut self.geometry);
+        if self.is_allowed(&geometry) {
+            return self.emit_feature(&events, &geometry);
+        }
+        if geometry_type(&geometry) != Some(GeomType::GeometryCollection) {
+            return Ok(());
+        }
+        let start = geometry
+            .iter()
+            .position(|event| matches!(event, GeomEvent::Begin(..)))
+            .unwrap_or_default();
+        let members = collection_members(&geometry[start..]);
+        match self.collection_mode {
+            CollectionMode::KeepIfAny => {
+                if members
+                    .iter()
+                    .any(|member| self.is_allowed(&geometry[start..][member.clone()]))
+                {
+                    self.emit_feature(&events, &geometry)?;
+                }
+            }
+            CollectionMode::Split => {
+                for member in members {
+                    let member = &geometry[start..][member];
+                    if self.is_allowed(member) {
+                        // Members become top-level geometries with index 0
+                        let mut member = member.to_vec();
+                        let last = member.len() - 1;
+                        member[0] = member[0].with_idx(0);
+                        member[last] = member[last].with_idx(0);
+                        self.emit_feature(&events, &member)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::PropertiesBegin);
+        }
+        Ok(())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::PropertiesEnd);
+        }
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        match self.feature.as_mut() {
+            Some(events) => events.push(Event::Geometry),
+            None => self.inner.geometry_begin()?,
+        }
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        if self.feature.is_none() {
+            self.inner.geometry_end()?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: FeatureProcessor> PropertyProcessor for GeomTypeFilter<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        match self.feature.as_mut() {
+            Some(events) => {
+                events.push(Event::Property(idx, name.to_string(), Value::from(value)));
+                Ok(false)
+            }
+            None => self.inner.property(idx, name, value),
+        }
+    }
+}
+
+impl<P: FeatureProcessor> GeomProcessor for GeomTypeFilter<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.geom_event(GeomEvent::Srid(srid))
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.geom_event(GeomEvent::InputDimensions(dimensions))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Xy(x, y, idx))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.geom_event(GeomEvent::Coordinate(x, y, z, m, t, tm, idx))
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::EmptyPoint(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Point, true, 1, idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Point, true, idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPoint, true, size, idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPoint, true, idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::LineString, tagged, size, idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::LineString, tagged, idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiLineString, true, size, idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiLineString, true, idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Polygon, tagged, size, idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Polygon, tagged, idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPolygon, true, size, idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPolygon, true, idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::GeometryCollection,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::GeometryCollection, true, idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CircularString, true, size, idx))
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CircularString, true, idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CompoundCurve, true, size, idx))
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CompoundCurve, true, idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CurvePolygon, true, size, idx))
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CurvePolygon, true, idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiCurve, true, size, idx))
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiCurve, true, idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiSurface, true, size, idx))
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiSurface, true, idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Triangle, tagged, size, idx))
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Triangle, tagged, idx))
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::PolyhedralSurface,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::PolyhedralSurface, true, idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Tin, true, size, idx))
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Tin, true, idx))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::GeozeroDatasource;
+
+    const INPUT: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"n": 0}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+        {"type": "Feature", "properties": {"n": 1}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 0]]]}},
+        {"type": "Feature", "properties": {"n": 2}, "geometry": {"type": "GeometryCollection", "geometries": [
+            {"type": "Point", "coordinates": [2, 2]},
+            {"type": "Polygon", "coordinates": [[[0, 0], [2, 0], [2, 2], [0, 0]]]},
+            {"type": "Polygon", "coordinates": [[[0, 0], [3, 0], [3, 3], [0, 0]]]}
+        ]}},
+        {"type": "Feature", "properties": {"n": 3}, "geometry": {"type": "LineString", "coordinates": [[0, 0], [1, 1]]}}
+    ]}"#;
+
+    #[derive(Default)]
+    struct FeatureIndices(Vec<u64>);
+
+    impl GeomProcessor for FeatureIndices {}
+    impl PropertyProcessor for FeatureIndices {}
+    impl FeatureProcessor for FeatureIndices {
+        fn feature_begin(&mut self, idx: u64) -> Result<()> {
+            self.0.push(idx);
+            Ok(())
+        }
+    }
+
+    fn filter(mode: CollectionMode) -> serde_json::Value {
+        let mut out: Vec<u8> = Vec::new();
+        let writer = crate::geojson::GeoJsonWriter::new(&mut out);
+        let mut filter = GeomTypeFilter::new(writer, &[GeomType::Polygon]).with_collections(mode);
+        GeoJson(INPUT).process(&mut filter).unwrap();
+        drop(filter);
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    fn property_values(json: &serde_json::Value) -> Vec<i64> {
+        json["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["properties"]["n"].as_i64().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn keep_if_any() {
+        let json = filter(CollectionMode::KeepIfAny);
+        assert_eq!(property_values(&json), vec![1, 2]);
+        assert_eq!(
+            json["features"][1]["geometry"]["type"],
+            "GeometryCollection"
+        );
+    }
+
+    #[test]
+    fn split() {
+        let json = filter(CollectionMode::Split);
+        assert_eq!(property_values(&json), vec![1, 2, 2]);
+        assert_eq!(
+            json["features"][2]["geometry"],
+            serde_json::json!({"type": "Polygon", "coordinates": [[[0, 0], [3, 0], [3, 3], [0, 0]]]})
+        );
+    }
+
+    #[test]
+    fn contiguous_indices() {
+        let mut filter = GeomTypeFilter::new(FeatureIndices::default(), &[GeomType::LineString])
+            .with_collections(CollectionMode::Split);
+        GeoJson(INPUT).process(&mut filter).unwrap();
+        assert_eq!(filter.into_inner().0, vec![0]);
+
+        let mut filter = GeomTypeFilter::new(FeatureIndices::default(), &[GeomType::Polygon])
+            .with_collections(CollectionMode::Split);
+        GeoJson(INPUT).process(&mut filter).unwrap();
+        assert_eq!(filter.into_inner().0, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn geometries_pass() {
+        let mut out: Vec<u8> = Vec::new();
+        let writer = crate::geojson::GeoJsonWriter::new(&mut out);
+        let mut filter = GeomTypeFilter::new(writer, &[GeomType::Polygon]);
+        crate::GeozeroGeometry::process_geom(
+            &GeoJson(r#"{"type": "Point", "coordinates": [1, 1]}"#),
+            &mut filter,
+        )
+        .unwrap();
+        drop(filter);
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            r#"{"type": "Point", "coordinates": [1,1]}"#
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::error::{GeozeroError, Result};
 
 /// Dimensions requested for processing
-#[derive(Default, Clone, Copy)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
 pub struct CoordDimensions {
     /// height
     pub z: bool,
@@ -50,6 +50,15 @@ impl CoordDimensions {
 
 /// Geometry processing trait
 ///
+/// # Empty geometries
+///
+/// Empty geometries are processed with a begin/end pair with size 0, e.g.
+/// `MULTIPOLYGON EMPTY` as `multipolygon_begin(0, idx)` followed by `multipolygon_end(idx)`.
+/// This also applies to empty members like the rings of an empty Polygon, which
+/// is processed without rings. Empty Points are processed with `empty_point`,
+/// since a Point always has one coordinate. Empty members of a MultiPoint are
+/// passed as coordinates with NaN values, like in WKB.
+///
 /// # Usage example:
 ///
 /// ```rust
@@ -83,6 +92,15 @@ pub trait GeomProcessor {
         Ok(())
     }
 
+    /// Dimensions of the input coordinates, if known before processing the geometry
+    ///
+    /// Emitted before geometry begin by readers with typed geometries, like WKB or shapefiles.
+    /// Coordinates only include the dimensions requested with `dimensions`,
+    /// so an input dimension may be missing in the processed coordinates.
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        Ok(())
+    }
+
     /// Process coordinate with x,y dimensions
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
         Ok(())
@@ -103,6 +121,8 @@ pub trait GeomProcessor {
     }
 
     /// Process empty coordinates, like WKT's `POINT EMPTY`
+    ///
+    /// Emitted instead of `point_begin`/`point_end`.
     fn empty_point(&mut self, idx: usize) -> Result<()> {
         Err(GeozeroError::Geometry(
             "The input was an empty Point, but the output doesn't support empty Points".to_string(),
@@ -206,7 +226,9 @@ pub trait GeomProcessor {
     ///
     /// Next: size * xy/coordinate
     fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        Ok(())
+        Err(GeozeroError::Geometry(
+            "The input was a CircularString, but the output doesn't support curves".to_string(),
+        ))
     }
 
     /// End of CircularString processing
@@ -220,7 +242,9 @@ pub trait GeomProcessor {
     ///
     /// Next: size * (CircularString | LineString (untagged))
     fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        Ok(())
+        Err(GeozeroError::Geometry(
+            "The input was a CompoundCurve, but the output doesn't support curves".to_string(),
+        ))
     }
 
     /// End of CompoundCurve processing
@@ -234,7 +258,9 @@ pub trait GeomProcessor {
     ///
     /// Next: size * (CircularString | LineString (untagged) | CompoundCurve)
     fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        Ok(())
+        Err(GeozeroError::Geometry(
+            "The input was a CurvePolygon, but the output doesn't support curves".to_string(),
+        ))
     }
 
     /// End of CurvePolygon processing
@@ -248,7 +274,9 @@ pub trait GeomProcessor {
     ///
     /// Next: size * (CircularString | LineString (untagged) | CompoundCurve)
     fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        Ok(())
+        Err(GeozeroError::Geometry(
+            "The input was a MultiCurve, but the output doesn't support curves".to_string(),
+        ))
     }
 
     /// End of MultiCurve processing
@@ -262,7 +290,9 @@ pub trait GeomProcessor {
     ///
     /// Next: size * (CurvePolygon | Polygon (untagged))
     fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
-        Ok(())
+        Err(GeozeroError::Geometry(
+            "The input was a MultiSurface, but the output doesn't support curves".to_string(),
+        ))
     }
 
     /// End of MultiSurface processing
@@ -45,6 +45,7 @@ fn process_geom_n<'a, P: GeomProcessor, G: Geom<'a>>(
     processor: &mut P,
 ) -> Result<()> {
     match ggeom.geometry_type() {
+        GeometryTypes::Point if ggeom.is_empty()? => processor.empty_point(idx),
         GeometryTypes::Point => {
             processor.point_begin(idx)?;
             process_point(ggeom, 0, processor)?;
@@ -155,6 +156,10 @@ fn process_polygon<'a, P: GeomProcessor, G: Geom<'a>>(
     idx: usize,
     processor: &mut P,
 ) -> Result<()> {
+    if ggeom.is_empty()? {
+        processor.polygon_begin(tagged, 0, idx)?;
+        return processor.polygon_end(tagged, idx);
+    }
     let nb_interiors = ggeom.get_num_interior_rings()?;
 
     processor.polygon_begin(tagged, nb_interiors + 1, idx)?;
@@ -211,7 +216,7 @@ mod test {
 
     #[test]
     fn line_geom_3d() {
-        let wkt = "LINESTRING(1 1 10,2 2 20)";
+        let wkt = "LINESTRING Z (1 1 10,2 2 20)";
         let ggeom = GGeometry::new_from_wkt(wkt).unwrap();
 
         let mut wkt_data: Vec<u8> = Vec::new();
@@ -1,14 +1,18 @@
 use crate::error::{GeozeroError, Result};
-use crate::{FeatureProcessor, GeomProcessor, PropertyProcessor};
-use geos::{CoordDimensions, CoordSeq, GResult, Geometry as GGeometry};
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use geos::{CoordSeq, GResult, Geometry as GGeometry};
 
 /// Generator for GEOS geometry type.
+///
+/// Z values are preserved, if present in the input.
 pub struct GeosWriter<'a> {
     pub(crate) geom: GGeometry<'a>,
-    // CoordSeq for Points, Lines and Rings
-    cs: Vec<CoordSeq<'a>>,
+    // Coordinates of Points, Lines and Rings
+    cs: Vec<Vec<[f64; 3]>>,
     // Polygons or MultiPolygons
     polys: Vec<GGeometry<'a>>,
+    // Stack of in-progress (potentially nested) GeometryCollections
+    collections: Vec<Vec<GGeometry<'a>>>,
 }
 
 impl<'a> GeosWriter<'a> {
@@ -16,47 +20,86 @@ impl<'a> GeosWriter<'a> {
         Self::default()
     }
     fn add_coord_seq(&mut self, len: usize) -> Result<()> {
+        self.cs.push(Vec::with_capacity(len));
+        Ok(())
+    }
+    fn pop_coord_seq(&mut self) -> Result<CoordSeq<'a>> {
+        let coords = self
+            .cs
+            .pop()
+            .ok_or_else(|| GeozeroError::Geometry("CoordSeq missing".to_string()))?;
+        Ok(coord_seq(&coords)?)
+    }
+    fn push_coord(&mut self, coord: [f64; 3]) -> Result<()> {
         self.cs
-            .push(CoordSeq::new(len as u32, CoordDimensions::TwoD)?);
+            .last_mut()
+            .ok_or_else(|| GeozeroError::Geometry("CoordSeq missing".to_string()))?
+            .push(coord);
         Ok(())
     }
+    fn finish_geometry(&mut self, geom: GGeometry<'a>) {
+        if let Some(collection) = self.collections.last_mut() {
+            collection.push(geom);
+        } else {
+            self.geom = geom;
+        }
+    }
     pub fn geometry(&self) -> &GGeometry<'a> {
         &self.geom
     }
 }
 
+/// Create a 2D or 3D CoordSeq, depending on the presence of Z values.
+fn coord_seq<'a>(coords: &[[f64; 3]]) -> GResult<CoordSeq<'a>> {
+    if coords.iter().any(|c| !c[2].is_nan()) {
+        CoordSeq::new_from_vec(coords)
+    } else {
+        let coords: Vec<[f64; 2]> = coords.iter().map(|c| [c[0], c[1]]).collect();
+        CoordSeq::new_from_vec(&coords)
+    }
+}
+
 impl<'a> Default for GeosWriter<'a> {
     fn default() -> Self {
         GeosWriter {
             geom: GGeometry::create_empty_point().unwrap(),
             cs: Vec::new(),
             polys: Vec::new(),
+            collections: Vec::new(),
         }
     }
 }
 
 impl GeomProcessor for GeosWriter<'_> {
-    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
-        if self.cs.is_empty() {
-            return Err(GeozeroError::Geometry("CoordSeq missing".to_string()));
-        }
-        let n = self.cs.len() - 1;
-        let coord_seq = &mut self.cs[n];
-        coord_seq.set_x(idx, x)?;
-        coord_seq.set_y(idx, y)?;
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyz()
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.push_coord([x, y, f64::NAN])
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.push_coord([x, y, z.unwrap_or(f64::NAN)])
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.finish_geometry(GGeometry::create_empty_point()?);
         Ok(())
     }
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
         self.cs = Vec::with_capacity(1);
-        self.add_coord_seq(1)?;
-        Ok(())
+        self.add_coord_seq(1)
     }
     fn point_end(&mut self, _idx: usize) -> Result<()> {
-        let cs = self
-            .cs
-            .pop()
-            .ok_or_else(|| GeozeroError::Geometry("CoordSeq missing".to_string()))?;
-        self.geom = GGeometry::create_point(cs)?;
+        let cs = self.pop_coord_seq()?;
+        self.finish_geometry(GGeometry::create_point(cs)?);
         Ok(())
     }
     fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
@@ -64,21 +107,16 @@ impl GeomProcessor for GeosWriter<'_> {
         self.add_coord_seq(size)
     }
     fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
-        // Create points from CoordSeq elements
-        let cs = self
+        // Create points from coordinates
+        let coords = self
             .cs
             .pop()
             .ok_or_else(|| GeozeroError::Geometry("CoordSeq missing".to_string()))?;
-        let size = cs.size()?;
-        let ggpts = (0..size)
-            .map(|i| {
-                GGeometry::create_point(
-                    CoordSeq::new_from_vec(&[&[cs.get_x(i).unwrap(), cs.get_y(i).unwrap()]])
-                        .unwrap(),
-                )
-            })
+        let ggpts = coords
+            .iter()
+            .map(|coord| GGeometry::create_point(coord_seq(&[*coord])?))
             .collect::<GResult<Vec<GGeometry>>>()?;
-        self.geom = GGeometry::create_multipoint(ggpts)?;
+        self.finish_geometry(GGeometry::create_multipoint(ggpts)?);
         Ok(())
     }
     fn linestring_begin(&mut self, tagged: bool, size: usize, _idx: usize) -> Result<()> {
@@ -89,11 +127,8 @@ impl GeomProcessor for GeosWriter<'_> {
     }
     fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
         if tagged {
-            let cs = self
-                .cs
-                .pop()
-                .ok_or_else(|| GeozeroError::Geometry("CoordSeq missing".to_string()))?;
-            self.geom = GGeometry::create_line_string(cs)?;
+            let cs = self.pop_coord_seq()?;
+            self.finish_geometry(GGeometry::create_line_string(cs)?);
         }
         Ok(())
     }
@@ -105,9 +140,9 @@ impl GeomProcessor for GeosWriter<'_> {
         let gglines = self
             .cs
             .drain(..)
-            .map(GGeometry::create_line_string)
+            .map(|coords| GGeometry::create_line_string(coord_seq(&coords)?))
             .collect::<GResult<Vec<GGeometry>>>()?;
-        self.geom = GGeometry::create_multiline_string(gglines)?;
+        self.finish_geometry(GGeometry::create_multiline_string(gglines)?);
         Ok(())
     }
     fn polygon_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
@@ -116,19 +151,25 @@ impl GeomProcessor for GeosWriter<'_> {
     }
     fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
         if self.cs.is_empty() {
-            return Err(GeozeroError::Geometry("CoordSeq missing".to_string()));
+            let gpoly = GGeometry::create_empty_polygon()?;
+            if tagged {
+                self.finish_geometry(gpoly);
+            } else {
+                self.polys.push(gpoly);
+            }
+            return Ok(());
         }
         // TODO: We need to ensure that rings of polygons are closed
         // to create valid GEOS LinearRings
-        let exterior_ring = GGeometry::create_linear_ring(self.cs.remove(0))?;
-        let interiors = self
+        let mut rings = self
             .cs
             .drain(..)
-            .map(GGeometry::create_linear_ring)
+            .map(|coords| GGeometry::create_linear_ring(coord_seq(&coords)?))
             .collect::<GResult<Vec<GGeometry>>>()?;
-        let gpoly = GGeometry::create_polygon(exterior_ring, interiors)?;
+        let exterior_ring = rings.remove(0);
+        let gpoly = GGeometry::create_polygon(exterior_ring, rings)?;
         if tagged {
-            self.geom = gpoly;
+            self.finish_geometry(gpoly);
         } else {
             self.polys.push(gpoly);
         }
@@ -139,7 +180,20 @@ impl GeomProcessor for GeosWriter<'_> {
         Ok(())
     }
     fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
-        self.geom = GGeometry::create_multipolygon(std::mem::take(&mut self.polys))?;
+        let gpolys = std::mem::take(&mut self.polys);
+        self.finish_geometry(GGeometry::create_multipolygon(gpolys)?);
+        Ok(())
+    }
+    fn geometrycollection_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.collections.push(Vec::with_capacity(size));
+        Ok(())
+    }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        let geoms = self
+            .collections
+            .pop()
+            .ok_or_else(|| GeozeroError::Geometry("GeometryCollection missing".to_string()))?;
+        self.finish_geometry(GGeometry::create_geometry_collection(geoms)?);
         Ok(())
     }
 }
@@ -181,13 +235,17 @@ mod test {
         assert_eq!(geos.to_wkt().unwrap(), wkt);
     }
 
-    // #[test]
-    // fn line_geom_3d() {
-    //     let geojson = GeoJson(r#"{"type": "LineString", "coordinates": [[1,1,10], [2,2,20]]}"#);
-    //     let wkt = "LINESTRING (1 1 10, 2 2 20)";
-    //     let geos = geojson.to_geos().unwrap();
-    //     assert_eq!(geos.to_wkt().unwrap(), wkt);
-    // }
+    #[test]
+    fn line_geom_3d() {
+        let geojson = GeoJson(r#"{"type": "LineString", "coordinates": [[1,1,10], [2,2,20]]}"#);
+        let geos = geojson.to_geos().unwrap();
+        assert!(geos.has_z().unwrap());
+        assert_eq!(geos.get_coord_seq().unwrap().get_z(1).unwrap(), 20.0);
+
+        let geojson = GeoJson(r#"{"type": "LineString", "coordinates": [[1,1], [2,2]]}"#);
+        let geos = geojson.to_geos().unwrap();
+        assert!(!geos.has_z().unwrap());
+    }
 
     #[test]
     fn multiline_geom() {
@@ -228,13 +286,18 @@ mod test {
         assert_eq!(geos.to_wkt().unwrap(), wkt);
     }
 
-    // #[test]
-    // fn geometry_collection_geom() {
-    //     let geojson = GeoJson(r#"{"type": "Point", "coordinates": [1, 1]}"#);
-    //     let wkt = "GEOMETRYCOLLECTION(POINT(1 1), LINESTRING(1 1, 2 2))";
-    //     let geos = geojson.to_geos().unwrap();
-    //     assert_eq!(geos.to_wkt().unwrap(), wkt);
-    // }
+    #[test]
+    fn geometry_collection_geom() {
+        let geojson = GeoJson(
+            r#"{"type": "GeometryCollection", "geometries": [
+                {"type": "Point", "coordinates": [1, 1]},
+                {"type": "GeometryCollection", "geometries": [{"type": "LineString", "coordinates": [[1, 1], [2, 2]]}]}
+            ]}"#,
+        );
+        let wkt = "GEOMETRYCOLLECTION (POINT (1.0000000000000000 1.0000000000000000), GEOMETRYCOLLECTION (LINESTRING (1.0000000000000000 1.0000000000000000, 2.0000000000000000 2.0000000000000000)))";
+        let geos = geojson.to_geos().unwrap();
+        assert_eq!(geos.to_wkt().unwrap(), wkt);
+    }
 
     #[test]
     #[cfg(feature = "with-geo")]
@@ -0,0 +1,205 @@
+use super::quote_ident;
+use crate::error::{GeozeroError, Result};
+use crate::wkb::{process_gpkg_geom, read_gpkg_header};
+use crate::{ColumnValue, FeatureProcessor, GeometryBbox};
+use futures_util::{Stream, TryStreamExt};
+use sqlx::sqlite::{SqlitePool, SqliteRow};
+use sqlx::{Column, Row, TypeInfo, ValueRef};
+
+/// GeoPackage feature table reader.
+///
+/// # Usage example:
+///
+/// Process features of table `pt2d` intersecting a bounding box:
+/// ```
+/// use geozero::gpkg::GpkgReader;
+/// use geozero::ProcessorSink;
+/// use sqlx::sqlite::SqlitePool;
+///
+/// # async fn select_bbox() -> geozero::error::Result<()> {
+/// let pool = SqlitePool::connect("sqlite://points.gpkg").await.unwrap();
+/// let reader = GpkgReader::new(pool);
+/// reader
+///     .select_bbox("pt2d", 1.5, 2.0, 2.0, 2.2, &mut ProcessorSink::new())
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct GpkgReader {
+    pool: SqlitePool,
+}
+
+impl GpkgReader {
+    pub fn new(pool: SqlitePool) -> Self {
+        GpkgReader { pool }
+    }
+
+    /// Process all features of `table`.
+    pub async fn select_all<P: FeatureProcessor>(
+        &self,
+        table: &str,
+        processor: &mut P,
+    ) -> Result<()> {
+        let geom_column = self.geometry_column(table).await?;
+        let sql = format!("SELECT * FROM {}", quote_ident(table));
+        let rows = sqlx::query(&sql).fetch(&self.pool);
+        process_rows(table, rows, &geom_column, None, processor).await
+    }
+
+    /// Process features of `table` intersecting the given bounding box.
+    ///
+    /// Candidates are selected with the R-tree spatial index `rtree_<table>_<geometry column>`.
+    /// Without spatial index, all features are read and filtered by their bounding box.
+    pub async fn select_bbox<P: FeatureProcessor>(
+        &self,
+        table: &str,
+        min_x: f64,
+        min_y: f64,
+        max_x: f64,
+        max_y: f64,
+        processor: &mut P,
+    ) -> Result<()> {
+        let geom_column = self.geometry_column(table).await?;
+        let rtree = format!("rtree_{table}_{geom_column}");
+        let has_rtree = self.table_exists(&rtree).await?;
+        let sql = if has_rtree {
+            format!(
+                "SELECT * FROM {} WHERE rowid IN (SELECT id FROM {} WHERE maxx >= ? AND minx <= ? AND maxy >= ? AND miny <= ?)",
+                quote_ident(table),
+                quote_ident(&rtree)
+            )
+        } else {
+            format!("SELECT * FROM {}", quote_ident(table))
+        };
+        let mut query = sqlx::query(&sql);
+        if has_rtree {
+            query = query.bind(min_x).bind(max_x).bind(min_y).bind(max_y);
+        }
+        // The R-tree stores rounded single precision bounds, so candidates are checked in any case
+        let bbox = [min_x, min_y, max_x, max_y];
+        process_rows(
+            table,
+            query.fetch(&self.pool),
+            &geom_column,
+            Some(bbox),
+            processor,
+        )
+        .await
+    }
+
+    /// Name of the geometry column of `table`.
+    async fn geometry_column(&self, table: &str) -> Result<String> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT column_name FROM gpkg_geometry_columns WHERE table_name = ?")
+                .bind(table)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(dataset_error)?;
+        row.map(|(column,)| column).ok_or_else(|| {
+            GeozeroError::Dataset(format!("no geometry column found for table `{table}`"))
+        })
+    }
+
+    async fn table_exists(&self, name: &str) -> Result<bool> {
+        let row: Option<(String,)> =
+            sqlx::query_as("SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?")
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(dataset_error)?;
+        Ok(row.is_some())
+    }
+}
+
+fn dataset_error(e: sqlx::Error) -> GeozeroError {
+    GeozeroError::Dataset(e.to_string())
+}
+
+fn intersects(a: &[f64; 4], b: &[f64; 4]) -> bool {
+    a[0] <= b[2] && a[2] >= b[0] && a[1] <= b[3] && a[3] >= b[1]
+}
+
+/// Bounds of a GPKG geometry blob as `[minx, miny, maxx, maxy]`.
+///
+/// Uses the envelope of the blob header if present, otherwise the geometry is processed.
+fn blob_bounds(blob: &[u8]) -> Result<Option<[f64; 4]>> {
+    let header = read_gpkg_header(&mut &blob[..])?;
+    if let [minx, maxx, miny, maxy, ..] = header.envelope[..] {
+        return Ok(Some([minx, miny, maxx, maxy]));
+    }
+    let mut geom_bbox = GeometryBbox::new();
+    process_gpkg_geom(&mut &blob[..], &mut geom_bbox)?;
+    Ok(geom_bbox.bounds())
+}
+
+/// Process rows one by one as they are fetched from the database.
+async fn process_rows<P: FeatureProcessor>(
+    table: &str,
+    mut rows: impl Stream<Item = std::result::Result<SqliteRow, sqlx::Error>> + Unpin,
+    geom_column: &str,
+    bbox: Option<[f64; 4]>,
+    processor: &mut P,
+) -> Result<()> {
+    processor.dataset_begin(Some(table))?;
+    let mut idx = 0;
+    while let Some(row) = rows.try_next().await.map_err(dataset_error)? {
+        let geom: Option<&[u8]> = row.try_get(geom_column).map_err(dataset_error)?;
+        if let Some(bbox) = &bbox {
+            let bounds = match geom {
+                Some(blob) => blob_bounds(blob)?,
+                None => None,
+            };
+            match bounds {
+                Some(bounds) if intersects(&bounds, bbox) => {}
+                _ => continue,
+            }
+        }
+        processor.feature_begin(idx)?;
+        processor.properties_begin()?;
+        let mut i = 0;
+        for column in row.columns() {
+            if column.name() == geom_column {
+                continue;
+            }
+            let value = row.try_get_raw(column.ordinal()).map_err(dataset_error)?;
+            if value.is_null() {
+                continue;
+            }
+            let ordinal = column.ordinal();
+            let name = column.name();
+            let type_name = value.type_info().name().to_string();
+            let done = match type_name.as_str() {
+                "INTEGER" => {
+                    let v: i64 = row.try_get(ordinal).map_err(dataset_error)?;
+                    processor.property(i, name, &ColumnValue::Long(v))?
+                }
+                "REAL" => {
+                    let v: f64 = row.try_get(ordinal).map_err(dataset_error)?;
+                    processor.property(i, name, &ColumnValue::Double(v))?
+                }
+                "TEXT" => {
+                    let v: &str = row.try_get(ordinal).map_err(dataset_error)?;
+                    processor.property(i, name, &ColumnValue::String(v))?
+                }
+                "BLOB" => {
+                    let v: &[u8] = row.try_get(ordinal).map_err(dataset_error)?;
+                    processor.property(i, name, &ColumnValue::Binary(v))?
+                }
+                _ => continue,
+            };
+            i += 1;
+            if done {
+                break;
+            }
+        }
+        processor.properties_end()?;
+        if let Some(mut blob) = geom {
+            processor.geometry_begin()?;
+            process_gpkg_geom(&mut blob, processor)?;
+            processor.geometry_end()?;
+        }
+        processor.feature_end(idx)?;
+        idx += 1;
+    }
+    processor.dataset_end()
+}
@@ -0,0 +1,458 @@
+use super::quote_ident;
+use crate::error::{GeozeroError, Result};
+use crate::wkb::{process_wkb_geom, WkbDialect, WkbWriter};
+use crate::{
+    ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, GeometryBbox, PropertyProcessor,
+};
+use futures_executor::block_on;
+use sqlx::sqlite::{Sqlite, SqlitePool};
+use sqlx::Transaction;
+
+/// GeoPackage writer.
+///
+/// Processed features are inserted into a new feature table of a GeoPackage within a
+/// transaction started with [`GpkgWriter::begin`] and committed with [`GpkgWriter::commit`].
+/// Columns of the feature table are inferred from the properties of the first feature.
+/// Properties of later features with other names are ignored.
+///
+/// Processing callbacks block on the database inserts.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::gpkg::GpkgWriter;
+/// use geozero::GeozeroDatasource;
+/// use sqlx::sqlite::SqlitePoolOptions;
+///
+/// # async fn geojson_to_gpkg() -> geozero::error::Result<()> {
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// let pool = SqlitePoolOptions::new()
+///     .connect("sqlite://countries.gpkg?mode=rwc")
+///     .await
+///     .unwrap();
+/// let mut writer = GpkgWriter::new("countries");
+/// writer.begin(&pool).await?;
+/// let mut geojson = geozero::geojson::GeoJsonReader(std::fs::File::open("countries.geojson")?);
+/// geojson.process(&mut writer)?;
+/// writer.commit().await?;
+/// # }
+/// # Ok(())
+/// # }
+/// ```
+pub struct GpkgWriter {
+    table: String,
+    geom_column: String,
+    srid: i32,
+    srs_definition: Option<String>,
+    dims: CoordDimensions,
+    /// Column names and types, inferred from the first feature
+    columns: Vec<(String, &'static str)>,
+    /// Transaction started with [`GpkgWriter::begin`]
+    tx: Option<Transaction<'static, Sqlite>>,
+    /// Insert statement, set when the feature table is created
+    insert_sql: Option<String>,
+    /// Bounds of all geometries
+    bbox: GeometryBbox,
+    // Processing state
+    row: Option<FeatureRow>,
+    wkb: Option<WkbWriter<Vec<u8>>>,
+}
+
+struct FeatureRow {
+    geometry: Option<Vec<u8>>,
+    values: Vec<Option<Value>>,
+}
+
+#[derive(Clone)]
+enum Value {
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl GpkgWriter {
+    /// Writer for feature table `table` with geometry column `geom` in EPSG:4326.
+    pub fn new(table: &str) -> Self {
+        GpkgWriter {
+            table: table.to_string(),
+            geom_column: "geom".to_string(),
+            srid: 4326,
+            srs_definition: None,
+            dims: CoordDimensions::xy(),
+            columns: Vec::new(),
+            tx: None,
+            insert_sql: None,
+            bbox: GeometryBbox::new(),
+            row: None,
+            wkb: None,
+        }
+    }
+    /// Set name of the geometry column.
+    pub fn with_geometry_column(mut self, name: &str) -> Self {
+        self.geom_column = name.to_string();
+        self
+    }
+    /// Set SRID of the geometry column.
+    ///
+    /// Only the undefined SRIDs -1 and 0 and EPSG:4326 are known without definition,
+    /// use [`GpkgWriter::with_srs`] for other SRIDs.
+    pub fn with_srid(mut self, srid: i32) -> Self {
+        self.srid = srid;
+        self
+    }
+    /// Set SRID of the geometry column with its EPSG code and WKT definition.
+    pub fn with_srs(mut self, srid: i32, definition: &str) -> Self {
+        self.srid = srid;
+        self.srs_definition = Some(definition.to_string());
+        self
+    }
+    /// Set coordinate dimensions of written geometries.
+    pub fn with_dims(mut self, dims: CoordDimensions) -> Self {
+        self.dims = dims;
+        self
+    }
+
+    /// Start a transaction and create the GeoPackage metadata tables.
+    ///
+    /// Fails for SRIDs without definition other than -1, 0 and 4326.
+    pub async fn begin(&mut self, pool: &SqlitePool) -> Result<()> {
+        if self.srs_definition.is_none() && ![-1, 0, 4326].contains(&self.srid) {
+            return Err(GeozeroError::Srid(self.srid));
+        }
+        let mut tx = pool.begin().await.map_err(dataset_error)?;
+        for sql in [
+            // 'GPKG' application id and version 1.3.0
+            "PRAGMA application_id = 1196444487",
+            "PRAGMA user_version = 10300",
+            "CREATE TABLE IF NOT EXISTS gpkg_spatial_ref_sys (srs_name TEXT NOT NULL, srs_id INTEGER PRIMARY KEY, organization TEXT NOT NULL, organization_coordsys_id INTEGER NOT NULL, definition TEXT NOT NULL, description TEXT)",
+            "CREATE TABLE IF NOT EXISTS gpkg_contents (table_name TEXT NOT NULL PRIMARY KEY, data_type TEXT NOT NULL, identifier TEXT UNIQUE, description TEXT DEFAULT '', last_change DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')), min_x DOUBLE, min_y DOUBLE, max_x DOUBLE, max_y DOUBLE, srs_id INTEGER, CONSTRAINT fk_gc_r_srs_id FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys(srs_id))",
+            "CREATE TABLE IF NOT EXISTS gpkg_geometry_columns (table_name TEXT NOT NULL, column_name TEXT NOT NULL, geometry_type_name TEXT NOT NULL, srs_id INTEGER NOT NULL, z TINYINT NOT NULL, m TINYINT NOT NULL, CONSTRAINT pk_geom_cols PRIMARY KEY (table_name, column_name), CONSTRAINT fk_gc_tn FOREIGN KEY (table_name) REFERENCES gpkg_contents(table_name), CONSTRAINT fk_gc_srs FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys (srs_id))",
+            "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES ('Undefined cartesian SRS', -1, 'NONE', -1, 'undefined', 'undefined cartesian coordinate reference system')",
+            "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES ('Undefined geographic SRS', 0, 'NONE', 0, 'undefined', 'undefined geographic coordinate reference system')",
+            r#"INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES ('WGS 84 geodetic', 4326, 'EPSG', 4326, 'GEOGCS["WGS 84",DATUM["WGS_1984",SPHEROID["WGS 84",6378137,298.257223563,AUTHORITY["EPSG","7030"]],AUTHORITY["EPSG","6326"]],PRIMEM["Greenwich",0,AUTHORITY["EPSG","8901"]],UNIT["degree",0.0174532925199433,AUTHORITY["EPSG","9122"]],AXIS["Latitude",NORTH],AXIS["Longitude",EAST],AUTHORITY["EPSG","4326"]]', 'longitude/latitude coordinates in decimal degrees on the WGS 84 spheroid')"#,
+        ] {
+            sqlx::query(sql)
+                .execute(&mut *tx)
+                .await
+                .map_err(dataset_error)?;
+        }
+        if let Some(definition) = &self.srs_definition {
+            sqlx::query(
+                "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES (?, ?, 'EPSG', ?, ?, NULL)",
+            )
+            .bind(format!("EPSG:{}", self.srid))
+            .bind(self.srid)
+            .bind(self.srid)
+            .bind(definition.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(dataset_error)?;
+        }
+        self.tx = Some(tx);
+        Ok(())
+    }
+
+    /// Register the feature table in the GeoPackage metadata and commit the transaction.
+    pub async fn commit(&mut self) -> Result<()> {
+        let mut tx = self.tx.take().ok_or_else(not_started)?;
+        if self.insert_sql.is_none() {
+            // No features processed
+            let (create_sql, insert_sql) = table_sql(&self.table, &self.geom_column, &self.columns);
+            sqlx::query(&create_sql)
+                .execute(&mut *tx)
+                .await
+                .map_err(dataset_error)?;
+            self.insert_sql = Some(insert_sql);
+        }
+
+        let bounds = self.bbox.bounds();
+        sqlx::query("INSERT INTO gpkg_contents (table_name, data_type, identifier, min_x, min_y, max_x, max_y, srs_id) VALUES (?, 'features', ?, ?, ?, ?, ?, ?)")
+            .bind(&self.table)
+            .bind(&self.table)
+            .bind(bounds.map(|b| b[0]))
+            .bind(bounds.map(|b| b[1]))
+            .bind(bounds.map(|b| b[2]))
+            .bind(bounds.map(|b| b[3]))
+            .bind(self.srid)
+            .execute(&mut *tx)
+            .await
+            .map_err(dataset_error)?;
+        sqlx::query("INSERT INTO gpkg_geometry_columns VALUES (?, ?, 'GEOMETRY', ?, ?, ?)")
+            .bind(&self.table)
+            .bind(&self.geom_column)
+            .bind(self.srid)
+            .bind(self.dims.z as i32)
+            .bind(self.dims.m as i32)
+            .execute(&mut *tx)
+            .await
+            .map_err(dataset_error)?;
+
+        tx.commit().await.map_err(dataset_error)
+    }
+
+    /// WKB writer for the current geometry
+    fn wkb(&mut self) -> &mut WkbWriter<Vec<u8>> {
+        let dims = self.dims;
+        self.wkb.get_or_insert_with(|| {
+            WkbWriter::with_opts(Vec::new(), WkbDialect::Wkb, dims, None, Vec::new())
+        })
+    }
+
+    /// Convert WKB into a GeoPackage geometry blob with envelope
+    fn gpkg_geometry(&mut self, wkb: &[u8]) -> Result<Vec<u8>> {
+        let mut bbox = GeometryBbox::new();
+        process_wkb_geom(&mut &wkb[..], &mut bbox)?;
+        // [minx, maxx, miny, maxy]
+        let envelope = match bbox.bounds() {
+            Some([minx, miny, maxx, maxy]) => {
+                process_wkb_geom(&mut &wkb[..], &mut self.bbox)?;
+                vec![minx, maxx, miny, maxy]
+            }
+            None => Vec::new(),
+        };
+        let mut blob = Vec::with_capacity(wkb.len() + 40);
+        let mut writer = WkbWriter::with_opts(
+            &mut blob,
+            WkbDialect::Geopackage,
+            self.dims,
+            Some(self.srid),
+            envelope,
+        );
+        process_wkb_geom(&mut &wkb[..], &mut writer)?;
+        Ok(blob)
+    }
+}
+
+fn dataset_error(e: sqlx::Error) -> GeozeroError {
+    GeozeroError::Dataset(e.to_string())
+}
+
+fn not_started() -> GeozeroError {
+    GeozeroError::Dataset("GpkgWriter::begin not called".to_string())
+}
+
+/// Create and insert statements of the feature table.
+fn table_sql(table: &str, geom_column: &str, columns: &[(String, &str)]) -> (String, String) {
+    let mut defs = vec![
+        "fid INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL".to_string(),
+        format!("{} GEOMETRY", quote_ident(geom_column)),
+    ];
+    defs.extend(
+        columns
+            .iter()
+            .map(|(name, sql_type)| format!("{} {sql_type}", quote_ident(name))),
+    );
+    let create_sql = format!("CREATE TABLE {} ({})", quote_ident(table), defs.join(", "));
+
+    let mut names = vec![quote_ident(geom_column)];
+    names.extend(columns.iter().map(|(name, _)| quote_ident(name)));
+    let insert_sql = format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        quote_ident(table),
+        names.join(", "),
+        vec!["?"; names.len()].join(", ")
+    );
+    (create_sql, insert_sql)
+}
+
+impl GeomProcessor for GpkgWriter {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.wkb().xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.wkb().coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.wkb().empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.wkb().point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.wkb().linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.wkb().linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.wkb().polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.wkb().polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.wkb().triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.wkb().triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.wkb().tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.wkb().tin_end(idx)
+    }
+}
+
+impl PropertyProcessor for GpkgWriter {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let Some(row) = self.row.as_mut() else {
+            return Ok(false);
+        };
+        let (sql_type, value) = match value {
+            ColumnValue::Byte(v) => ("INTEGER", Value::Integer(*v as i64)),
+            ColumnValue::UByte(v) => ("INTEGER", Value::Integer(*v as i64)),
+            ColumnValue::Bool(v) => ("BOOLEAN", Value::Integer(*v as i64)),
+            ColumnValue::Short(v) => ("INTEGER", Value::Integer(*v as i64)),
+            ColumnValue::UShort(v) => ("INTEGER", Value::Integer(*v as i64)),
+            ColumnValue::Int(v) => ("INTEGER", Value::Integer(*v as i64)),
+            ColumnValue::UInt(v) => ("INTEGER", Value::Integer(*v as i64)),
+            ColumnValue::Long(v) => ("INTEGER", Value::Integer(*v)),
+            ColumnValue::ULong(v) => ("INTEGER", Value::Integer(*v as i64)),
+            ColumnValue::Float(v) => ("FLOAT", Value::Real(*v as f64)),
+            ColumnValue::Double(v) => ("DOUBLE", Value::Real(*v)),
+            ColumnValue::String(v) | ColumnValue::Json(v) => ("TEXT", Value::Text(v.to_string())),
+            ColumnValue::DateTime(v) => ("DATETIME", Value::Text(v.to_string())),
+            ColumnValue::Binary(v) => ("BLOB", Value::Blob(v.to_vec())),
+        };
+        let col = match self.columns.iter().position(|(n, _)| n == name) {
+            Some(col) => col,
+            None if self.insert_sql.is_none() => {
+                self.columns.push((name.to_string(), sql_type));
+                row.values.push(None);
+                self.columns.len() - 1
+            }
+            None => return Ok(false),
+        };
+        row.values[col] = Some(value);
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for GpkgWriter {
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        if self.tx.is_none() {
+            return Err(not_started());
+        }
+        self.row = Some(FeatureRow {
+            geometry: None,
+            values: vec![None; self.columns.len()],
+        });
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let Some(row) = self.row.take() else {
+            return Ok(());
+        };
+        let tx = self.tx.as_mut().ok_or_else(not_started)?;
+        if self.insert_sql.is_none() {
+            // Columns are known after the first feature
+            let (create_sql, insert_sql) = table_sql(&self.table, &self.geom_column, &self.columns);
+            block_on(sqlx::query(&create_sql).execute(&mut **tx)).map_err(dataset_error)?;
+            self.insert_sql = Some(insert_sql);
+        }
+        let mut query = sqlx::query(self.insert_sql.as_deref().unwrap_or_default())
+            .bind(row.geometry.as_deref());
+        for value in &row.values {
+            query = match value {
+                Some(Value::Integer(v)) => query.bind(*v),
+                Some(Value::Real(v)) => query.bind(*v),
+                Some(Value::Text(v)) => query.bind(v.as_str()),
+                Some(Value::Blob(v)) => query.bind(v.as_slice()),
+                None => query.bind(None::<i64>),
+            };
+        }
+        block_on(query.execute(&mut **tx)).map_err(dataset_error)?;
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.wkb = None;
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        if let Some(wkb) = self.wkb.take() {
+            let geometry = self.gpkg_geometry(&wkb.into_inner())?;
+            if let Some(row) = self.row.as_mut() {
+                row.geometry = Some(geometry);
+            }
+        }
+        Ok(())
+    }
+}
@@ -28,3 +28,12 @@
 //! ```
 
 mod geopackage;
+mod gpkg_reader;
+mod gpkg_writer;
+pub use gpkg_reader::GpkgReader;
+pub use gpkg_writer::GpkgWriter;
+
+/// Quote an SQL identifier like a table or column name.
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
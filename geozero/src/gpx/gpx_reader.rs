@@ -1,4 +1,5 @@
 use crate::error::GeozeroError;
+use crate::{ColumnValue, FeatureProcessor, GeomProcessor};
 use std::io;
 
 /// GPX geometry collection
@@ -22,6 +23,21 @@ impl<R: io::Read> crate::GeozeroDatasource for GpxReader<R> {
     }
 }
 
+/// GPX feature reader
+///
+/// Waypoints are processed as Point features, routes as LineString features and
+/// tracks as LineString or, with more than one segment, MultiLineString features.
+/// Elevation is passed as Z coordinate. Name, description and time (start time for routes and tracks)
+/// are passed as properties, together with waypoint elevation and a `kind` property
+/// (`waypoint`, `route` or `track`).
+pub struct GpxFeatureReader<R: io::Read>(pub R);
+
+impl<R: io::Read> crate::GeozeroDatasource for GpxFeatureReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> crate::error::Result<()> {
+        read_gpx_features(&mut self.0, processor)
+    }
+}
+
 pub fn read_gpx<R: io::Read, P: crate::GeomProcessor>(
     reader: &mut R,
     processor: &mut P,
@@ -144,3 +160,144 @@ fn process_waypoints_iter<'a, P: crate::GeomProcessor>(
     }
     Ok(())
 }
+
+/// Read GPX waypoints, routes and tracks as features.
+pub fn read_gpx_features<R: io::Read, P: FeatureProcessor>(
+    reader: &mut R,
+    processor: &mut P,
+) -> crate::error::Result<()> {
+    let gpx = gpx::read(reader).map_err(|e| GeozeroError::Geometry(e.to_string()))?;
+
+    let name = gpx.metadata.as_ref().and_then(|m| m.name.as_deref());
+    processor.dataset_begin(name)?;
+    let mut idx = 0;
+    for waypoint in &gpx.waypoints {
+        processor.feature_begin(idx)?;
+        process_properties(
+            "waypoint",
+            waypoint.name.as_deref(),
+            waypoint.description.as_deref(),
+            waypoint.time.as_ref(),
+            waypoint.elevation,
+            processor,
+        )?;
+        processor.geometry_begin()?;
+        processor.point_begin(0)?;
+        process_coord(waypoint, 0, processor)?;
+        processor.point_end(0)?;
+        processor.geometry_end()?;
+        processor.feature_end(idx)?;
+        idx += 1;
+    }
+    for route in &gpx.routes {
+        processor.feature_begin(idx)?;
+        process_properties(
+            "route",
+            route.name.as_deref(),
+            route.description.as_deref(),
+            route.points.first().and_then(|p| p.time.as_ref()),
+            None,
+            processor,
+        )?;
+        processor.geometry_begin()?;
+        process_line(&route.points, true, 0, processor)?;
+        processor.geometry_end()?;
+        processor.feature_end(idx)?;
+        idx += 1;
+    }
+    for track in &gpx.tracks {
+        processor.feature_begin(idx)?;
+        // Start time of the track
+        let time = track
+            .segments
+            .iter()
+            .find_map(|seg| seg.points.first())
+            .and_then(|p| p.time.as_ref());
+        process_properties(
+            "track",
+            track.name.as_deref(),
+            track.description.as_deref(),
+            time,
+            None,
+            processor,
+        )?;
+        processor.geometry_begin()?;
+        if let [segment] = track.segments.as_slice() {
+            process_line(&segment.points, true, 0, processor)?;
+        } else {
+            processor.multilinestring_begin(track.segments.len(), 0)?;
+            for (i, segment) in track.segments.iter().enumerate() {
+                process_line(&segment.points, false, i, processor)?;
+            }
+            processor.multilinestring_end(0)?;
+        }
+        processor.geometry_end()?;
+        processor.feature_end(idx)?;
+        idx += 1;
+    }
+    processor.dataset_end()
+}
+
+fn process_properties<P: FeatureProcessor>(
+    kind: &str,
+    name: Option<&str>,
+    description: Option<&str>,
+    time: Option<&gpx::Time>,
+    elevation: Option<f64>,
+    processor: &mut P,
+) -> crate::error::Result<()> {
+    processor.properties_begin()?;
+    let mut i = 0;
+    processor.property(i, "kind", &ColumnValue::String(kind))?;
+    if let Some(name) = name {
+        i += 1;
+        processor.property(i, "name", &ColumnValue::String(name))?;
+    }
+    if let Some(description) = description {
+        i += 1;
+        processor.property(i, "description", &ColumnValue::String(description))?;
+    }
+    if let Some(time) = time.and_then(|t| t.format().ok()) {
+        i += 1;
+        processor.property(i, "time", &ColumnValue::DateTime(&time))?;
+    }
+    if let Some(elevation) = elevation {
+        i += 1;
+        processor.property(i, "elevation", &ColumnValue::Double(elevation))?;
+    }
+    processor.properties_end()
+}
+
+fn process_line<P: GeomProcessor>(
+    points: &[gpx::Waypoint],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> crate::error::Result<()> {
+    processor.linestring_begin(tagged, points.len(), idx)?;
+    for (i, point) in points.iter().enumerate() {
+        process_coord(point, i, processor)?;
+    }
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_coord<P: GeomProcessor>(
+    waypoint: &gpx::Waypoint,
+    idx: usize,
+    processor: &mut P,
+) -> crate::error::Result<()> {
+    let point = waypoint.point();
+    if processor.multi_dim() {
+        processor.coordinate(
+            point.x(),
+            point.y(),
+            waypoint.elevation,
+            None,
+            None,
+            None,
+            idx,
+        )
+    } else {
+        processor.xy(point.x(), point.y(), idx)
+    }
+}
@@ -1,4 +1,4 @@
 mod gpx_reader;
 
-pub use gpx_reader::read_gpx;
-pub use gpx_reader::{Gpx, GpxReader};
+pub use gpx_reader::{read_gpx, read_gpx_features};
+pub use gpx_reader::{Gpx, GpxFeatureReader, GpxReader};
@@ -0,0 +1,439 @@
+use crate::error::{GeozeroError, Result};
+use crate::{ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource};
+use quick_xml::events::{BytesStart, Event};
+use std::io::{BufReader, Read};
+
+/// KML reader.
+///
+/// Placemarks are processed as features. `name`, `description` and `ExtendedData`
+/// (`Data` and `SimpleData`) are passed as string properties.
+/// Supported geometries are Point, LineString, LinearRing, Polygon and MultiGeometry.
+/// Homogeneous MultiGeometries are passed as MultiPoint, MultiLineString or MultiPolygon,
+/// other MultiGeometries as GeometryCollection. Altitudes are passed as Z coordinate.
+pub struct KmlReader<R: Read>(pub R);
+
+impl<R: Read> GeozeroDatasource for KmlReader<R> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        read_kml(&mut self.0, processor)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Coord {
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Geom {
+    Point(Vec<Coord>),
+    LineString(Vec<Coord>),
+    Polygon(Vec<Vec<Coord>>),
+    Multi(Vec<Geom>),
+}
+
+#[derive(Default)]
+struct Placemark {
+    properties: Vec<(String, String)>,
+    geometry: Option<Geom>,
+}
+
+/// Read and process KML.
+pub fn read_kml<R: Read, P: FeatureProcessor>(reader: R, processor: &mut P) -> Result<()> {
+    let mut reader = quick_xml::Reader::from_reader(BufReader::new(reader));
+    reader.trim_text(true);
+    let mut buf = Vec::new();
+    // Local names of the open elements
+    let mut path: Vec<Vec<u8>> = Vec::new();
+    let mut text = String::new();
+    let mut dataset_name: Option<String> = None;
+    let mut dataset_started = false;
+    let mut placemark: Option<Placemark> = None;
+    // Open geometries of the current placemark
+    let mut geoms: Vec<Geom> = Vec::new();
+    // `name` attribute of the current `Data` or `SimpleData` element
+    let mut data_name: Option<String> = None;
+    let mut idx = 0;
+    loop {
+        buf.clear();
+        match reader.read_event_into(&mut buf).map_err(xml_error)? {
+            Event::Start(e) => {
+                text.clear();
+                let name = e.local_name().as_ref().to_vec();
+                match name.as_slice() {
+                    b"Placemark" => {
+                        if !dataset_started {
+                            processor.dataset_begin(dataset_name.as_deref())?;
+                            dataset_started = true;
+                        }
+                        placemark = Some(Placemark::default());
+                        geoms.clear();
+                    }
+                    b"Point" => geoms.push(Geom::Point(Vec::new())),
+                    b"LineString" | b"LinearRing" => geoms.push(Geom::LineString(Vec::new())),
+                    b"Polygon" => geoms.push(Geom::Polygon(Vec::new())),
+                    b"MultiGeometry" => geoms.push(Geom::Multi(Vec::new())),
+                    b"Data" | b"SimpleData" => data_name = name_attribute(&e)?,
+                    _ => {}
+                }
+                path.push(name);
+            }
+            Event::Empty(e) => {
+                // Self-closing geometries are empty
+                let geom = match e.local_name().as_ref() {
+                    b"Point" => Geom::Point(Vec::new()),
+                    b"LineString" | b"LinearRing" => Geom::LineString(Vec::new()),
+                    b"Polygon" => Geom::Polygon(Vec::new()),
+                    b"MultiGeometry" => Geom::Multi(Vec::new()),
+                    _ => continue,
+                };
+                if let Some(placemark) = placemark.as_mut() {
+                    add_geometry(geom, &path, &mut geoms, placemark);
+                }
+            }
+            Event::Text(t) => text.push_str(&t.unescape().map_err(xml_error)?),
+            Event::CData(t) => text.push_str(&String::from_utf8_lossy(&t.into_inner())),
+            Event::End(_) => {
+                let Some(name) = path.pop() else {
+                    continue;
+                };
+                let parent = path.last().map(|p| p.as_slice());
+                let Some(pm) = placemark.as_mut() else {
+                    if name == b"name"
+                        && parent == Some(b"Document".as_slice())
+                        && dataset_name.is_none()
+                    {
+                        dataset_name = Some(text.clone());
+                    }
+                    continue;
+                };
+                match name.as_slice() {
+                    b"coordinates" => {
+                        let coords = parse_coordinates(&text)?;
+                        match geoms.last_mut() {
+                            Some(Geom::Point(c)) | Some(Geom::LineString(c)) => *c = coords,
+                            _ => {}
+                        }
+                    }
+                    b"Point" | b"LineString" | b"LinearRing" | b"Polygon" | b"MultiGeometry" => {
+                        if let Some(geom) = geoms.pop() {
+                            add_geometry(geom, &path, &mut geoms, pm);
+                        }
+                    }
+                    b"name" | b"description" if parent == Some(b"Placemark".as_slice()) => {
+                        let name = String::from_utf8_lossy(&name).to_string();
+                        pm.properties.push((name, text.clone()));
+                    }
+                    b"value" if parent == Some(b"Data".as_slice()) => {
+                        if let Some(name) = data_name.take() {
+                            pm.properties.push((name, text.clone()));
+                        }
+                    }
+                    b"SimpleData" => {
+                        if let Some(name) = data_name.take() {
+                            pm.properties.push((name, text.clone()));
+                        }
+                    }
+                    b"Placemark" => {
+                        if let Some(pm) = placemark.take() {
+                            process_placemark(&pm, idx, processor)?;
+                            idx += 1;
+                        }
+                    }
+                    _ => {}
+                }
+                text.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    if !dataset_started {
+        processor.dataset_begin(dataset_name.as_deref())?;
+    }
+    processor.dataset_end()
+}
+
+fn xml_error(e: impl std::fmt::Display) -> GeozeroError {
+    GeozeroError::Geometry(e.to_string())
+}
+
+fn name_attribute(e: &BytesStart) -> Result<Option<String>> {
+    match e.try_get_attribute("name").map_err(xml_error)? {
+        Some(attr) => Ok(Some(attr.unescape_value().map_err(xml_error)?.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Add a completed geometry to its parent geometry or to the placemark.
+fn add_geometry(geom: Geom, path: &[Vec<u8>], geoms: &mut [Geom], placemark: &mut Placemark) {
+    match (geoms.last_mut(), geom) {
+        (Some(Geom::Multi(members)), geom) => members.push(geom),
+        (Some(Geom::Polygon(rings)), Geom::LineString(ring)) => {
+            // The outer ring is always the first ring
+            if path.last().map(|p| p.as_slice()) == Some(b"outerBoundaryIs".as_slice()) {
+                rings.insert(0, ring);
+            } else {
+                rings.push(ring);
+            }
+        }
+        (Some(_), _) => {}
+        (None, geom) => placemark.geometry = Some(geom),
+    }
+}
+
+/// Parse a KML coordinates string into tuples of `lon,lat[,alt]`.
+///
+/// Tuples are separated by whitespace, values within a tuple by commas.
+/// Whitespace around commas, as found in hand-written files, is ignored.
+fn parse_coordinates(text: &str) -> Result<Vec<Coord>> {
+    let mut normalized = String::with_capacity(text.len());
+    let mut after_comma = false;
+    for c in text.chars() {
+        if c == ',' {
+            normalized.truncate(normalized.trim_end().len());
+            normalized.push(c);
+            after_comma = true;
+        } else if c.is_whitespace() {
+            if !after_comma {
+                normalized.push(' ');
+            }
+        } else {
+            normalized.push(c);
+            after_comma = false;
+        }
+    }
+    normalized
+        .split_whitespace()
+        .map(|tuple| {
+            let invalid = || GeozeroError::Geometry(format!("invalid KML coordinate `{tuple}`"));
+            let values = tuple
+                .split(',')
+                .filter(|v| !v.is_empty())
+                .map(|v| v.parse::<f64>().map_err(|_| invalid()))
+                .collect::<Result<Vec<_>>>()?;
+            match values[..] {
+                [x, y] => Ok(Coord { x, y, z: None }),
+                [x, y, z] => Ok(Coord { x, y, z: Some(z) }),
+                _ => Err(invalid()),
+            }
+        })
+        .collect()
+}
+
+fn process_placemark<P: FeatureProcessor>(
+    placemark: &Placemark,
+    idx: u64,
+    processor: &mut P,
+) -> Result<()> {
+    processor.feature_begin(idx)?;
+    processor.properties_begin()?;
+    for (i, (name, value)) in placemark.properties.iter().enumerate() {
+        if processor.property(i, name, &ColumnValue::String(value))? {
+            break;
+        }
+    }
+    processor.properties_end()?;
+    if let Some(geom) = &placemark.geometry {
+        processor.geometry_begin()?;
+        process_geom(geom, true, 0, processor)?;
+        processor.geometry_end()?;
+    }
+    processor.feature_end(idx)
+}
+
+fn process_geom<P: GeomProcessor>(
+    geom: &Geom,
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    match geom {
+        Geom::Point(coords) => match coords.first() {
+            Some(coord) => {
+                processor.point_begin(idx)?;
+                process_coord(coord, 0, processor)?;
+                processor.point_end(idx)
+            }
+            None => processor.empty_point(idx),
+        },
+        Geom::LineString(coords) => process_line(coords, tagged, idx, processor),
+        Geom::Polygon(rings) => process_polygon(rings, tagged, idx, processor),
+        Geom::Multi(members) => {
+            if !members.is_empty()
+                && members
+                    .iter()
+                    .all(|g| matches!(g, Geom::Point(c) if !c.is_empty()))
+            {
+                processor.multipoint_begin(members.len(), idx)?;
+                for (i, member) in members.iter().enumerate() {
+                    if let Geom::Point(coords) = member {
+                        process_coord(&coords[0], i, processor)?;
+                    }
+                }
+                processor.multipoint_end(idx)
+            } else if !members.is_empty()
+                && members.iter().all(|g| matches!(g, Geom::LineString(_)))
+            {
+                processor.multilinestring_begin(members.len(), idx)?;
+                for (i, member) in members.iter().enumerate() {
+                    if let Geom::LineString(coords) = member {
+                        process_line(coords, false, i, processor)?;
+                    }
+                }
+                processor.multilinestring_end(idx)
+            } else if !members.is_empty() && members.iter().all(|g| matches!(g, Geom::Polygon(_))) {
+                processor.multipolygon_begin(members.len(), idx)?;
+                for (i, member) in members.iter().enumerate() {
+                    if let Geom::Polygon(rings) = member {
+                        process_polygon(rings, false, i, processor)?;
+                    }
+                }
+                processor.multipolygon_end(idx)
+            } else {
+                processor.geometrycollection_begin(members.len(), idx)?;
+                for (i, member) in members.iter().enumerate() {
+                    process_geom(member, true, i, processor)?;
+                }
+                processor.geometrycollection_end(idx)
+            }
+        }
+    }
+}
+
+fn process_line<P: GeomProcessor>(
+    coords: &[Coord],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.linestring_begin(tagged, coords.len(), idx)?;
+    for (i, coord) in coords.iter().enumerate() {
+        process_coord(coord, i, processor)?;
+    }
+    processor.linestring_end(tagged, idx)
+}
+
+fn process_polygon<P: GeomProcessor>(
+    rings: &[Vec<Coord>],
+    tagged: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    processor.polygon_begin(tagged, rings.len(), idx)?;
+    for (i, ring) in rings.iter().enumerate() {
+        process_line(ring, false, i, processor)?;
+    }
+    processor.polygon_end(tagged, idx)
+}
+
+fn process_coord<P: GeomProcessor>(coord: &Coord, idx: usize, processor: &mut P) -> Result<()> {
+    if processor.multi_dim() {
+        processor.coordinate(coord.x, coord.y, coord.z, None, None, None, idx)
+    } else {
+        processor.xy(coord.x, coord.y, idx)
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonWriter;
+    use crate::wkt::WktWriter;
+    use crate::{CoordDimensions, ProcessToJson};
+
+    const KML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2">
+  <Document>
+    <name>Places</name>
+    <Placemark>
+      <name>Zürich &amp; more</name>
+      <ExtendedData>
+        <Data name="population"><value>421878</value></Data>
+        <SchemaData schemaUrl="s1"><SimpleData name="canton">ZH</SimpleData></SchemaData>
+      </ExtendedData>
+      <Point><coordinates>8.55,47.37,408</coordinates></Point>
+    </Placemark>
+    <Placemark>
+      <name>Area</name>
+      <MultiGeometry>
+        <Polygon>
+          <innerBoundaryIs><LinearRing><coordinates>1,1 2,1 2,2 1,1</coordinates></LinearRing></innerBoundaryIs>
+          <outerBoundaryIs><LinearRing><coordinates>
+            0,0 10,0
+            10,10 0,0
+          </coordinates></LinearRing></outerBoundaryIs>
+        </Polygon>
+        <LineString><coordinates>0, 0 1 ,1</coordinates></LineString>
+      </MultiGeometry>
+    </Placemark>
+  </Document>
+</kml>"#;
+
+    #[test]
+    fn placemarks() -> Result<()> {
+        let geojson = KmlReader(KML.as_bytes()).to_json()?;
+        assert_eq!(
+            geojson,
+            r#"{
+"type": "FeatureCollection",
+"name": "Places",
+"features": [{"type": "Feature", "properties": {"name": "Zürich & more", "population": "421878", "canton": "ZH"}, "geometry": {"type": "Point", "coordinates": [8.55,47.37]}},
+{"type": "Feature", "properties": {"name": "Area"}, "geometry": {"type": "GeometryCollection", "geometries": [{"type": "Polygon", "coordinates": [[[0,0],[10,0],[10,10],[0,0]],[[1,1],[2,1],[2,2],[1,1]]]},{"type": "LineString", "coordinates": [[0,0],[1,1]]}]}}]}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn altitude() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::with_dims(&mut out, CoordDimensions::xyz());
+        KmlReader(KML.as_bytes()).process(&mut writer)?;
+        let geojson = std::str::from_utf8(&out).unwrap();
+        assert!(geojson.contains(r#"{"type": "Point", "coordinates": [8.55,47.37,408]}"#));
+        Ok(())
+    }
+
+    #[test]
+    fn multi_geometry() -> Result<()> {
+        let kml = r#"<kml><Placemark><MultiGeometry>
+            <LineString><coordinates>1,2 3,4</coordinates></LineString>
+            <LineString><coordinates>5,6,7 8,9,10</coordinates></LineString>
+        </MultiGeometry></Placemark></kml>"#;
+        let mut out: Vec<u8> = Vec::new();
+        KmlReader(kml.as_bytes()).process(&mut WktWriter::new(&mut out))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "MULTILINESTRING((1 2,3 4),(5 6,8 9))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn coordinates() {
+        assert_eq!(
+            parse_coordinates("\n  1,2,3\t4 , 5\n6,7, ").unwrap(),
+            vec![
+                Coord {
+                    x: 1.0,
+                    y: 2.0,
+                    z: Some(3.0)
+                },
+                Coord {
+                    x: 4.0,
+                    y: 5.0,
+                    z: None
+                },
+                Coord {
+                    x: 6.0,
+                    y: 7.0,
+                    z: None
+                },
+            ]
+        );
+        assert!(parse_coordinates("1").is_err());
+        assert!(parse_coordinates("1,a").is_err());
+    }
+}
@@ -0,0 +1,313 @@
+use crate::error::{GeozeroError, Result};
+use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::io::Write;
+
+/// KML writer.
+///
+/// Features are written as Placemarks. The `name` and `description` properties are written
+/// as Placemark elements, all other properties as `ExtendedData`.
+/// Multi geometries and geometry collections are written as MultiGeometry.
+/// Curves and surfaces are not supported.
+pub struct KmlWriter<W: Write> {
+    dims: CoordDimensions,
+    out: W,
+    /// Properties of the current feature, written at the end of the properties
+    properties: Vec<(String, String)>,
+    /// Index of the next ring when writing a polygon
+    ring_idx: Option<usize>,
+    in_multipoint: bool,
+}
+
+impl<W: Write> KmlWriter<W> {
+    pub fn new(out: W) -> Self {
+        Self::with_dims(out, CoordDimensions::default())
+    }
+    /// Write altitudes with `CoordDimensions::xyz()`.
+    pub fn with_dims(out: W, dims: CoordDimensions) -> Self {
+        KmlWriter {
+            dims,
+            out,
+            properties: Vec::new(),
+            ring_idx: None,
+            in_multipoint: false,
+        }
+    }
+    fn write_coord(&mut self, x: f64, y: f64, z: Option<f64>, idx: usize) -> Result<()> {
+        if self.in_multipoint {
+            self.out.write_all(b"<Point><coordinates>")?;
+        } else if idx > 0 {
+            self.out.write_all(b" ")?;
+        }
+        match z {
+            Some(z) if self.dims.z => write!(self.out, "{x},{y},{z}")?,
+            _ => write!(self.out, "{x},{y}")?,
+        }
+        if self.in_multipoint {
+            self.out.write_all(b"</coordinates></Point>")?;
+        }
+        Ok(())
+    }
+}
+
+fn unsupported(geometry_type: &str) -> Result<()> {
+    Err(GeozeroError::Geometry(format!(
+        "{geometry_type} is not supported in KML"
+    )))
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+impl<W: Write> GeomProcessor for KmlWriter<W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.write_coord(x, y, None, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.write_coord(x, y, z, idx)
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.out
+            .write_all(b"<Point><coordinates></coordinates></Point>")?;
+        Ok(())
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.out.write_all(b"<Point><coordinates>")?;
+        Ok(())
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.out.write_all(b"</coordinates></Point>")?;
+        Ok(())
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.out.write_all(b"<MultiGeometry>")?;
+        self.in_multipoint = true;
+        Ok(())
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.in_multipoint = false;
+        self.out.write_all(b"</MultiGeometry>")?;
+        Ok(())
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        match self.ring_idx {
+            Some(0) => self
+                .out
+                .write_all(b"<outerBoundaryIs><LinearRing><coordinates>")?,
+            Some(_) => self
+                .out
+                .write_all(b"<innerBoundaryIs><LinearRing><coordinates>")?,
+            None => self.out.write_all(b"<LineString><coordinates>")?,
+        }
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        match self.ring_idx {
+            Some(0) => self
+                .out
+                .write_all(b"</coordinates></LinearRing></outerBoundaryIs>")?,
+            Some(_) => self
+                .out
+                .write_all(b"</coordinates></LinearRing></innerBoundaryIs>")?,
+            None => self.out.write_all(b"</coordinates></LineString>")?,
+        }
+        if let Some(ring_idx) = self.ring_idx.as_mut() {
+            *ring_idx += 1;
+        }
+        Ok(())
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.out.write_all(b"<MultiGeometry>")?;
+        Ok(())
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.out.write_all(b"</MultiGeometry>")?;
+        Ok(())
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.out.write_all(b"<Polygon>")?;
+        self.ring_idx = Some(0);
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.ring_idx = None;
+        self.out.write_all(b"</Polygon>")?;
+        Ok(())
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.out.write_all(b"<MultiGeometry>")?;
+        Ok(())
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.out.write_all(b"</MultiGeometry>")?;
+        Ok(())
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.out.write_all(b"<MultiGeometry>")?;
+        Ok(())
+    }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        self.out.write_all(b"</MultiGeometry>")?;
+        Ok(())
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("CircularString")
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("CompoundCurve")
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("CurvePolygon")
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("MultiCurve")
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("MultiSurface")
+    }
+    fn triangle_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("Triangle")
+    }
+    fn polyhedralsurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("PolyhedralSurface")
+    }
+    fn tin_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        unsupported("Tin")
+    }
+}
+
+impl<W: Write> PropertyProcessor for KmlWriter<W> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.properties.push((name.to_string(), value.to_string()));
+        Ok(false)
+    }
+}
+
+impl<W: Write> FeatureProcessor for KmlWriter<W> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.out.write_all(
+            br#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2">
+<Document>"#,
+        )?;
+        if let Some(name) = name {
+            write!(self.out, "<name>{}</name>", escape(name))?;
+        }
+        self.out.write_all(b"\n")?;
+        Ok(())
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.out.write_all(b"</Document>\n</kml>\n")?;
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.out.write_all(b"<Placemark>")?;
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.out.write_all(b"</Placemark>\n")?;
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.properties.clear();
+        Ok(())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        // Element order of the KML schema: name, description, ExtendedData
+        for element in ["name", "description"] {
+            if let Some((_, value)) = self.properties.iter().find(|(name, _)| name == element) {
+                write!(self.out, "<{element}>{}</{element}>", escape(value))?;
+            }
+        }
+        let extended_data = self
+            .properties
+            .iter()
+            .filter(|(name, _)| name != "name" && name != "description")
+            .collect::<Vec<_>>();
+        if !extended_data.is_empty() {
+            self.out.write_all(b"<ExtendedData>")?;
+            for (name, value) in extended_data {
+                write!(
+                    self.out,
+                    r#"<Data name="{}"><value>{}</value></Data>"#,
+                    escape(name),
+                    escape(value)
+                )?;
+            }
+            self.out.write_all(b"</ExtendedData>")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{read_geojson, GeoJson};
+    use crate::kml::KmlReader;
+    use crate::{GeozeroGeometry, ProcessToJson};
+
+    #[test]
+    fn features() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "name": "Places", "features": [
+            {"type": "Feature", "properties": {"name": "A & B", "pop": 12}, "geometry": {"type": "Point", "coordinates": [1, 2, 3]}},
+            {"type": "Feature", "properties": {"description": "Lake"}, "geometry": {"type": "Polygon", "coordinates": [[[0,0],[10,0],[10,10],[0,0]],[[1,1],[2,1],[2,2],[1,1]]]}}
+        ]}"#;
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = KmlWriter::with_dims(&mut out, CoordDimensions::xyz());
+        read_geojson(geojson.as_bytes(), &mut writer)?;
+        let kml = std::str::from_utf8(&out).unwrap();
+        assert_eq!(
+            kml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2">
+<Document>
+<Placemark><name>A &amp; B</name><ExtendedData><Data name="pop"><value>12</value></Data></ExtendedData><Point><coordinates>1,2,3</coordinates></Point></Placemark>
+<Placemark><description>Lake</description><Polygon><outerBoundaryIs><LinearRing><coordinates>0,0 10,0 10,10 0,0</coordinates></LinearRing></outerBoundaryIs><innerBoundaryIs><LinearRing><coordinates>1,1 2,1 2,2 1,1</coordinates></LinearRing></innerBoundaryIs></Polygon></Placemark>
+</Document>
+</kml>
+"#
+        );
+
+        // Roundtrip
+        let json = KmlReader(kml.as_bytes()).to_json()?;
+        assert!(json.contains(r#"{"type": "Feature", "properties": {"name": "A & B", "pop": "12"}, "geometry": {"type": "Point", "coordinates": [1,2]}}"#));
+        Ok(())
+    }
+
+    #[test]
+    fn multi_geometries() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        GeoJson(r#"{"type": "GeometryCollection", "geometries": [{"type": "MultiPoint", "coordinates": [[1,2],[3,4]]},{"type": "LineString", "coordinates": [[1,2],[3,4]]}]}"#)
+            .process_geom(&mut KmlWriter::new(&mut out))?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "<MultiGeometry><MultiGeometry><Point><coordinates>1,2</coordinates></Point><Point><coordinates>3,4</coordinates></Point></MultiGeometry><LineString><coordinates>1,2 3,4</coordinates></LineString></MultiGeometry>"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported_curves() {
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = KmlWriter::new(&mut out);
+        assert!(writer.circularstring_begin(3, 0).is_err());
+    }
+}
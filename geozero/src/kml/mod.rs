@@ -0,0 +1,6 @@
+//! KML conversions.
+mod kml_reader;
+mod kml_writer;
+
+pub use kml_reader::*;
+pub use kml_writer::*;
@@ -20,14 +20,16 @@
 //! |           |                         [`GeozeroGeometry`]                                                                              | Dimensions |                        [`GeozeroDatasource`]                                         | Geometry Conversion |            [`GeomProcessor`]            |
 //! |-----------|--------------------------------------------------------------------------------------------------------------------------|------------|--------------------------------------------------------------------------------------|---------------------|-----------------------------------------|
 //! | CSV       | [csv::Csv], [csv::CsvString]                                                                                             | XY         | -                                                                                    | [ProcessToCsv]      | [CsvWriter](csv::CsvWriter)             |
-//! | GDAL      | `gdal::vector::Geometry`                                                                                                 | XYZ        | -                                                                                    | [ToGdal]            | [GdalWriter](gdal::GdalWriter)          |
+//! | GDAL      | `gdal::vector::Geometry`                                                                                                 | XYZ        | [GdalDatasource](gdal::GdalDatasource)                                               | [ToGdal]            | [GdalWriter](gdal::GdalWriter)          |
 //! | geo-types | `geo_types::Geometry<f64>`                                                                                               | XY         | -                                                                                    | [ToGeo]             | [GeoWriter](geo_types::GeoWriter)       |
-//! | GeoArrow  | `arrow2::array::BinaryArray`                                                                                             | XY         | -                                                                                    | -                   | -                                       |
+//! | GeoArrow  | `arrow2::array::BinaryArray`                                                                                             | XY         | -                                                                                    | -                   | [GeoArrowWriter](arrow::GeoArrowWriter) |
 //! | GeoJSON   | [GeoJson](geojson::GeoJson), [GeoJsonString](geojson::GeoJsonString)                                                     | XYZ        | [GeoJsonReader](geojson::GeoJsonReader), [GeoJson](geojson::GeoJson)                 | [ToJson]            | [GeoJsonWriter](geojson::GeoJsonWriter) |
 //! | GEOS      | `geos::Geometry`                                                                                                         | XYZ        | -                                                                                    | [ToGeos]            | [GeosWriter](geos::GeosWriter)          |
 //! | GPX       |                                                                                                                          | XY         | [GpxReader](gpx::GpxReader)                                                          |                     |                                         |
+//! | KML       |                                                                                                                          | XYZ        | [KmlReader](kml::KmlReader)                                                          | -                   | [KmlWriter](kml::KmlWriter)             |
 //! | MVT       | [mvt::tile::Feature]                                                                                                     | XY         | [mvt::tile::Layer]                                                                   | [ToMvt]             | [MvtWriter](mvt::MvtWriter)             |
 //! | SVG       | -                                                                                                                        | XY         | -                                                                                    | [ToSvg]             | [SvgWriter](svg::SvgWriter)             |
+//! | TWKB      | [Twkb](twkb::Twkb)                                                                                                       | XYZM       | -                                                                                    | [ToTwkb]            | [TwkbWriter](twkb::TwkbWriter)          |
 //! | WKB       | [Wkb](wkb::Wkb), [Ewkb](wkb::Ewkb), [GpkgWkb](wkb::GpkgWkb), [SpatiaLiteWkb](wkb::SpatiaLiteWkb), [MySQL](wkb::MySQLWkb) | XYZM       | -                                                                                    | [ToWkb]             | [WkbWriter](wkb::WkbWriter)             |
 //! | WKT       | [wkt::WktStr], [wkt::WktString], [wkt::EwktStr], [wkt::EwktString]                                                       | XYZM       | [wkt::WktReader], [wkt::WktStr], [wkt::WktString], [wkt::EwktStr], [wkt::EwktString] | [ToWkt]             | [WktWriter](wkt::WktWriter)             |
 
@@ -47,18 +49,71 @@
     clippy::struct_excessive_bools
 )]
 
+mod affine;
 mod api;
+mod bbox;
+mod centroid;
+mod clip_mask;
+mod close_rings;
+mod coerce_properties;
+#[cfg(feature = "with-wkb")]
+mod dedup;
+mod densify;
 pub mod error;
 mod feature_processor;
+mod fix_winding;
+mod flatten;
+#[cfg(any(feature = "with-geojson", feature = "with-wkt"))]
+mod formatting;
+mod geom_event_processor;
+mod geom_type_filter;
 mod geometry_processor;
+mod limit_guard;
+mod measure;
+mod multi_normalize;
 mod multiplex;
+mod property_filter;
+mod property_mapper;
 mod property_processor;
-
+mod simplify;
+mod snap_to_grid;
+mod stats;
+mod tee;
+#[cfg(all(test, any(feature = "with-geojson", feature = "with-wkt")))]
+mod test_util;
+mod tile_split;
+mod topo_simplify;
+mod validate;
+
+pub use affine::*;
 pub use api::*;
+pub use bbox::*;
+pub use centroid::*;
+pub use clip_mask::*;
+pub use close_rings::*;
+pub use coerce_properties::*;
+#[cfg(feature = "with-wkb")]
+pub use dedup::*;
+pub use densify::*;
 pub use feature_processor::*;
+pub use fix_winding::*;
+pub use flatten::*;
+pub use geom_type_filter::*;
 pub use geometry_processor::*;
+pub use limit_guard::*;
+pub use measure::*;
+pub use multi_normalize::*;
 pub use multiplex::*;
+pub use property_filter::*;
+pub use property_mapper::*;
 pub use property_processor::*;
+pub use simplify::*;
+pub use snap_to_grid::*;
+pub use stats::*;
+pub use tee::*;
+pub use tile_split::*;
+pub use topo_simplify::*;
+pub use validate::*;
 
 #[cfg(feature = "with-arrow")]
 pub mod arrow;
@@ -94,6 +149,9 @@ pub mod gpkg;
 #[cfg(feature = "with-gpx")]
 pub mod gpx;
 
+#[cfg(feature = "with-kml")]
+pub mod kml;
+
 #[cfg(any(
     feature = "with-postgis-diesel",
     feature = "with-postgis-postgres",
@@ -101,6 +159,9 @@ pub mod gpx;
 ))]
 pub mod postgis;
 
+#[cfg(feature = "with-proj")]
+pub mod proj;
+
 #[cfg(feature = "with-svg")]
 pub mod svg;
 #[cfg(feature = "with-svg")]
@@ -109,6 +170,11 @@ pub use crate::svg::conversion::*;
 #[cfg(feature = "with-tessellator")]
 pub mod tessellator;
 
+#[cfg(feature = "with-twkb")]
+pub mod twkb;
+#[cfg(feature = "with-twkb")]
+pub use crate::twkb::conversion::*;
+
 #[cfg(feature = "with-wkb")]
 pub mod wkb;
 #[cfg(feature = "with-wkb")]
@@ -135,5 +201,21 @@ impl ProcessorSink {
 }
 
 impl FeatureProcessor for ProcessorSink {}
-impl GeomProcessor for ProcessorSink {}
+impl GeomProcessor for ProcessorSink {
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> error::Result<()> {
+        Ok(())
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> error::Result<()> {
+        Ok(())
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> error::Result<()> {
+        Ok(())
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> error::Result<()> {
+        Ok(())
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> error::Result<()> {
+        Ok(())
+    }
+}
 impl PropertyProcessor for ProcessorSink {}
@@ -0,0 +1,415 @@
+use crate::error::{GeozeroError, Result};
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+
+/// Processor wrapper enforcing size limits on geometries, e.g. for processing untrusted input.
+///
+/// Limits apply to each top-level geometry. Declared sizes of `*_begin` calls are checked
+/// before they are passed on, so writers never pre-allocate for oversized geometries.
+/// Exceeding a limit returns [`GeozeroError::LimitExceeded`].
+///
+/// Recommended defaults for user uploads, used by [`LimitGuard::new`]:
+/// 1'000'000 coordinates, 10'000 rings and a nesting depth of 16.
+/// Limits set to `usize::MAX` never fail and only cost an integer comparison.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{error::GeozeroError, wkt::WktStr, wkt::WktWriter, GeozeroGeometry, LimitGuard};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = LimitGuard::new(WktWriter::new(&mut out)).with_max_coords(2);
+/// let result = WktStr("LINESTRING(0 0,1 1,2 2)").process_geom(&mut processor);
+/// assert!(matches!(result, Err(GeozeroError::LimitExceeded(_))));
+/// # }
+/// ```
+pub struct LimitGuard<P: GeomProcessor> {
+    inner: P,
+    max_coords: usize,
+    max_rings: usize,
+    max_depth: usize,
+    /// Coordinates of the current geometry
+    coords: usize,
+    /// Rings of the current geometry
+    rings: usize,
+    /// Nesting depth of open geometries
+    depth: usize,
+    /// Depth inside the open polygon, whose direct children are rings
+    polygon_depth: Option<usize>,
+}
+
+impl<P: GeomProcessor> LimitGuard<P> {
+    /// Enforce the recommended default limits.
+    pub fn new(inner: P) -> Self {
+        LimitGuard {
+            inner,
+            max_coords: 1_000_000,
+            max_rings: 10_000,
+            max_depth: 16,
+            coords: 0,
+            rings: 0,
+            depth: 0,
+            polygon_depth: None,
+        }
+    }
+    /// Maximal number of coordinates per geometry.
+    pub fn with_max_coords(mut self, max_coords: usize) -> Self {
+        self.max_coords = max_coords;
+        self
+    }
+    /// Maximal number of polygon rings per geometry.
+    pub fn with_max_rings(mut self, max_rings: usize) -> Self {
+        self.max_rings = max_rings;
+        self
+    }
+    /// Maximal nesting depth, e.g. 3 for a ring of a polygon in a multipolygon.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    fn begin(&mut self) -> Result<()> {
+        if self.depth == 0 {
+            self.coords = 0;
+            self.rings = 0;
+        }
+        self.depth += 1;
+        if self.depth > self.max_depth {
+            return Err(exceeded("nesting depth", self.max_depth));
+        }
+        Ok(())
+    }
+    fn end(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+    /// Begin of a geometry with `size` coordinates
+    fn coords_begin(&mut self, size: usize) -> Result<()> {
+        self.begin()?;
+        if size > self.max_coords - self.coords {
+            return Err(exceeded("coordinates", self.max_coords));
+        }
+        Ok(())
+    }
+    /// Begin of a geometry, which is a ring if it is a direct child of a polygon
+    fn ring_begin(&mut self) -> Result<()> {
+        if self.polygon_depth == Some(self.depth) {
+            self.rings += 1;
+            if self.rings > self.max_rings {
+                return Err(exceeded("rings", self.max_rings));
+            }
+        }
+        Ok(())
+    }
+    /// Begin of a polygon with `size` rings
+    fn polygon_begin(&mut self, size: usize) -> Result<()> {
+        self.begin()?;
+        if size > self.max_rings - self.rings {
+            return Err(exceeded("rings", self.max_rings));
+        }
+        self.polygon_depth = Some(self.depth);
+        Ok(())
+    }
+    fn polygon_end(&mut self) {
+        self.polygon_depth = None;
+        self.end();
+    }
+    fn coord(&mut self) -> Result<()> {
+        self.coords += 1;
+        if self.coords > self.max_coords {
+            return Err(exceeded("coordinates", self.max_coords));
+        }
+        Ok(())
+    }
+}
+
+fn exceeded(what: &str, max: usize) -> GeozeroError {
+    GeozeroError::LimitExceeded(format!("more than {max} {what}"))
+}
+
+impl<P: GeomProcessor> GeomProcessor for LimitGuard<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.inner.input_dimensions(dimensions)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.coord()?;
+        self.inner.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.coord()?;
+        self.inner.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.inner.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.coords_begin(1)?;
+        self.inner.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.coords_begin(size)?;
+        self.inner.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.ring_begin()?;
+        self.coords_begin(size)?;
+        self.inner.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin()?;
+        self.inner.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.polygon_begin(size)?;
+        self.inner.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.polygon_end();
+        self.inner.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin()?;
+        self.inner.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin()?;
+        self.inner.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.ring_begin()?;
+        self.coords_begin(size)?;
+        self.inner.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.ring_begin()?;
+        self.begin()?;
+        self.inner.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.polygon_begin(size)?;
+        self.inner.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.polygon_end();
+        self.inner.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin()?;
+        self.inner.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin()?;
+        self.inner.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.polygon_begin(size)?;
+        self.inner.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.polygon_end();
+        self.inner.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin()?;
+        self.inner.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.begin()?;
+        self.inner.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.end();
+        self.inner.tin_end(idx)
+    }
+}
+
+impl<P: GeomProcessor + PropertyProcessor> PropertyProcessor for LimitGuard<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.inner.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for LimitGuard<P> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.inner.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+    fn skip_geometry(&self) -> bool {
+        self.inner.skip_geometry()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::{GeozeroGeometry, ProcessorSink};
+
+    fn check(wkt: &str, guard: LimitGuard<ProcessorSink>) -> bool {
+        let mut guard = guard;
+        match WktStr(wkt).process_geom(&mut guard) {
+            Ok(()) => true,
+            Err(GeozeroError::LimitExceeded(_)) => false,
+            Err(e) => panic!("unexpected error {e}"),
+        }
+    }
+
+    fn guard() -> LimitGuard<ProcessorSink> {
+        LimitGuard::new(ProcessorSink::new())
+    }
+
+    #[test]
+    fn coordinates() {
+        let wkt = "MULTILINESTRING((0 0,1 1),(2 2,3 3))";
+        assert!(check(wkt, guard().with_max_coords(4)));
+        assert!(!check(wkt, guard().with_max_coords(3)));
+        assert!(!check("MULTIPOINT(0 0,1 1)", guard().with_max_coords(1)));
+    }
+
+    #[test]
+    fn rings() -> Result<()> {
+        let wkt = "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5),(5.1 5.1,5.9 5.1,5.9 5.8,5.1 5.1)))";
+        assert!(check(wkt, guard().with_max_rings(3)));
+        assert!(!check(wkt, guard().with_max_rings(2)));
+        // Linestrings of compound curves are not rings
+        let mut guard = guard().with_max_rings(1);
+        guard.curvepolygon_begin(1, 0)?;
+        guard.compoundcurve_begin(2, 0)?;
+        for idx in 0..2 {
+            guard.linestring_begin(false, 2, idx)?;
+            guard.xy(0.0, 0.0, 0)?;
+            guard.xy(1.0, 1.0, 1)?;
+            guard.linestring_end(false, idx)?;
+        }
+        guard.compoundcurve_end(0)?;
+        guard.curvepolygon_end(0)?;
+        Ok(())
+    }
+
+    #[test]
+    fn depth() {
+        let wkt = "GEOMETRYCOLLECTION(GEOMETRYCOLLECTION(POINT(1 1)))";
+        assert!(check(wkt, guard().with_max_depth(3)));
+        assert!(!check(wkt, guard().with_max_depth(2)));
+    }
+
+    #[test]
+    fn per_geometry() -> Result<()> {
+        let mut out: Vec<u8> = Vec::new();
+        let mut guard = LimitGuard::new(WktWriter::new(&mut out)).with_max_coords(2);
+        WktStr("LINESTRING(0 0,1 1)").process_geom(&mut guard)?;
+        WktStr("LINESTRING(2 2,3 3)").process_geom(&mut guard)?;
+        drop(guard);
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "LINESTRING(0 0,1 1)LINESTRING(2 2,3 3)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unlimited() {
+        let unlimited = guard()
+            .with_max_coords(usize::MAX)
+            .with_max_rings(usize::MAX)
+            .with_max_depth(usize::MAX);
+        assert!(check(
+            "GEOMETRYCOLLECTION(POLYGON((0 0,1 0,1 1,0 0)),POINT(1 1))",
+            unlimited
+        ));
+    }
+}
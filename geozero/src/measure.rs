@@ -0,0 +1,316 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::GeomProcessor;
+use crate::property_processor::PropertyProcessor;
+use crate::GeozeroGeometry;
+
+/// Processor measuring the area and length of processed geometries.
+///
+/// Polygon areas are computed with the shoelace formula, with interior rings
+/// subtracted from the exterior ring. The length is the sum of linestring lengths
+/// and polygon perimeters (including interior rings). Parts of multi geometries
+/// and collections are summed up. Curve geometries are not measured.
+///
+/// With feature `with-geodesic`, [`Measure::with_geodesic`] measures lon/lat
+/// coordinates on the WGS84 ellipsoid in square meters and meters.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, GeozeroGeometry, Measure};
+///
+/// let mut measure = Measure::new();
+/// WktStr("POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,2 1,2 2,1 2,1 1))")
+///     .process_geom(&mut measure)
+///     .unwrap();
+/// assert_eq!(measure.area(), 15.0);
+/// assert_eq!(measure.length(), 20.0);
+/// # }
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Measure {
+    #[cfg(feature = "with-geodesic")]
+    geodesic: bool,
+    area: f64,
+    length: f64,
+    /// Open polygons, whose linestrings are rings
+    polygon_depth: usize,
+    /// Open curve geometries, which are not measured
+    curve_depth: usize,
+    /// Current ring is the exterior ring
+    exterior: bool,
+    /// Coordinates of the current linestring or ring
+    coords: Option<Vec<[f64; 2]>>,
+}
+
+impl Measure {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Measure lon/lat coordinates on the WGS84 ellipsoid.
+    #[cfg(feature = "with-geodesic")]
+    pub fn with_geodesic(mut self) -> Self {
+        self.geodesic = true;
+        self
+    }
+    /// Area of processed polygons.
+    pub fn area(&self) -> f64 {
+        self.area
+    }
+    /// Length of processed linestrings and perimeter of processed polygons.
+    pub fn length(&self) -> f64 {
+        self.length
+    }
+    /// Forget all processed geometries.
+    pub fn reset(&mut self) {
+        self.area = 0.0;
+        self.length = 0.0;
+    }
+    fn begin_line(&mut self, size: usize) {
+        if self.curve_depth == 0 {
+            self.coords = Some(Vec::with_capacity(size));
+        }
+    }
+    fn end_line(&mut self) {
+        let Some(coords) = self.coords.take() else {
+            return;
+        };
+        self.length += self.line_length(&coords);
+        if self.polygon_depth > 0 {
+            let area = self.ring_area(&coords);
+            if self.exterior {
+                self.area += area;
+            } else {
+                self.area -= area;
+            }
+        }
+    }
+    fn line_length(&self, coords: &[[f64; 2]]) -> f64 {
+        #[cfg(feature = "with-geodesic")]
+        if self.geodesic {
+            return geodesic::line_length(coords);
+        }
+        coords
+            .windows(2)
+            .map(|pair| (pair[1][0] - pair[0][0]).hypot(pair[1][1] - pair[0][1]))
+            .sum()
+    }
+    fn ring_area(&self, coords: &[[f64; 2]]) -> f64 {
+        #[cfg(feature = "with-geodesic")]
+        if self.geodesic {
+            return geodesic::ring_area(coords);
+        }
+        // Shoelace formula
+        let area = coords
+            .windows(2)
+            .map(|pair| pair[0][0] * pair[1][1] - pair[1][0] * pair[0][1])
+            .sum::<f64>()
+            / 2.0;
+        area.abs()
+    }
+}
+
+#[cfg(feature = "with-geodesic")]
+mod geodesic {
+    use geographiclib_rs::{Geodesic, InverseGeodesic, PolygonArea, Winding};
+
+    pub(super) fn line_length(coords: &[[f64; 2]]) -> f64 {
+        let g = Geodesic::wgs84();
+        coords
+            .windows(2)
+            .map(|pair| {
+                let s12: f64 = g.inverse(pair[0][1], pair[0][0], pair[1][1], pair[1][0]);
+                s12
+            })
+            .sum()
+    }
+
+    pub(super) fn ring_area(coords: &[[f64; 2]]) -> f64 {
+        let g = Geodesic::wgs84();
+        let mut polygon = PolygonArea::new(&g, Winding::CounterClockwise);
+        for [x, y] in coords {
+            polygon.add_point(*y, *x);
+        }
+        let (_perimeter, area, _count) = polygon.compute(false);
+        area
+    }
+}
+
+/// Planar area of a geometry, see [`Measure`].
+pub fn geometry_area(geom: &impl GeozeroGeometry) -> Result<f64> {
+    let mut measure = Measure::new();
+    geom.process_geom(&mut measure)?;
+    Ok(measure.area())
+}
+
+/// Planar length of a geometry, see [`Measure`].
+pub fn geometry_length(geom: &impl GeozeroGeometry) -> Result<f64> {
+    let mut measure = Measure::new();
+    geom.process_geom(&mut measure)?;
+    Ok(measure.length())
+}
+
+impl GeomProcessor for Measure {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        if let Some(coords) = self.coords.as_mut() {
+            coords.push([x, y]);
+        }
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        _z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.xy(x, y, idx)
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn linestring_begin(&mut self, _tagged: bool, size: usize, idx: usize) -> Result<()> {
+        // The first ring of a polygon is the exterior ring
+        self.exterior = idx == 0;
+        self.begin_line(size);
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end_line();
+        Ok(())
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.polygon_depth += 1;
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.polygon_depth -= 1;
+        Ok(())
+    }
+    fn triangle_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.polygon_depth += 1;
+        Ok(())
+    }
+    fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.polygon_depth -= 1;
+        Ok(())
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.curve_depth += 1;
+        Ok(())
+    }
+    fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
+        self.curve_depth -= 1;
+        Ok(())
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.curve_depth += 1;
+        Ok(())
+    }
+    fn compoundcurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.curve_depth -= 1;
+        Ok(())
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.curve_depth += 1;
+        Ok(())
+    }
+    fn curvepolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.curve_depth -= 1;
+        Ok(())
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.curve_depth += 1;
+        Ok(())
+    }
+    fn multicurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.curve_depth -= 1;
+        Ok(())
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.curve_depth += 1;
+        Ok(())
+    }
+    fn multisurface_end(&mut self, _idx: usize) -> Result<()> {
+        self.curve_depth -= 1;
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for Measure {}
+
+impl FeatureProcessor for Measure {}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::WktStr;
+
+    #[test]
+    fn planar() -> Result<()> {
+        assert_eq!(geometry_area(&WktStr("POINT(1 2)"))?, 0.0);
+        assert_eq!(geometry_length(&WktStr("LINESTRING(0 0,3 4,3 6)"))?, 7.0);
+        assert_eq!(geometry_area(&WktStr("LINESTRING(0 0,3 4,0 4,0 0)"))?, 0.0);
+        // Clockwise rings are measured like counter-clockwise rings
+        assert_eq!(
+            geometry_area(&WktStr("POLYGON((0 0,0 2,2 2,2 0,0 0))"))?,
+            4.0
+        );
+        assert_eq!(
+            geometry_length(&WktStr("POLYGON((0 0,0 2,2 2,2 0,0 0))"))?,
+            8.0
+        );
+        assert_eq!(
+            geometry_area(&WktStr(
+                "MULTIPOLYGON(((0 0,4 0,4 4,0 4,0 0),(1 1,1 2,2 2,2 1,1 1)),((10 10,12 10,12 11,10 10)))"
+            ))?,
+            16.0
+        );
+        assert_eq!(
+            geometry_length(&WktStr(
+                "GEOMETRYCOLLECTION(POINT(0 0),LINESTRING(0 0,0 5),POLYGON((0 0,1 0,1 1,0 1,0 0)))"
+            ))?,
+            9.0
+        );
+        assert_eq!(
+            geometry_area(&WktStr(
+                "CURVEPOLYGON(CIRCULARSTRING(0 0,1 1,2 0,1 -1,0 0))"
+            ))?,
+            0.0
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn accumulate() -> Result<()> {
+        let mut measure = Measure::new();
+        WktStr("POLYGON((0 0,2 0,2 2,0 2,0 0))").process_geom(&mut measure)?;
+        WktStr("POLYGON((0 0,3 0,3 3,0 3,0 0))").process_geom(&mut measure)?;
+        assert_eq!(measure.area(), 13.0);
+        assert_eq!(measure.length(), 20.0);
+        measure.reset();
+        assert_eq!(measure.area(), 0.0);
+        assert_eq!(measure.length(), 0.0);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-geodesic")]
+    fn geodesic() -> Result<()> {
+        let mut measure = Measure::new().with_geodesic();
+        WktStr("LINESTRING(0 0,1 0)").process_geom(&mut measure)?;
+        // One degree along the equator
+        assert!((measure.length() - 111_319.49).abs() < 0.01);
+
+        let mut measure = Measure::new().with_geodesic();
+        WktStr("POLYGON((0 0,1 0,1 1,0 1,0 0))").process_geom(&mut measure)?;
+        assert!((measure.area() / 1e6 - 12_308.8).abs() < 1.0);
+        Ok(())
+    }
+}
@@ -0,0 +1,417 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+use crate::GeomType;
+
+/// Normalization applied by [`MultiNormalize`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MultiMode {
+    /// Wrap points, linestrings and polygons into the corresponding multi-geometry
+    Promote,
+    /// Emit each part of a multipoint, multilinestring or multipolygon as separate geometry
+    Explode,
+}
+
+/// Processor wrapper converting between single and multi-geometries.
+///
+/// Members of geometry collections are promoted, but not exploded.
+/// Exploded parts are passed as consecutive geometries with their part index.
+/// Use [`FlattenCollections`](crate::FlattenCollections) to emit parts as separate features.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, wkt::WktWriter, GeozeroGeometry, MultiMode, MultiNormalize};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = MultiNormalize::new(WktWriter::new(&mut out), MultiMode::Promote);
+/// WktStr("POLYGON((0 0,1 0,1 1,0 0))").process_geom(&mut processor).unwrap();
+/// drop(processor);
+/// assert_eq!(String::from_utf8(out).unwrap(), "MULTIPOLYGON(((0 0,1 0,1 1,0 0)))");
+/// # }
+/// ```
+pub struct MultiNormalize<P: GeomProcessor> {
+    inner: P,
+    mode: MultiMode,
+    /// Types of the open geometries
+    stack: Vec<GeomType>,
+}
+
+impl<P: GeomProcessor> MultiNormalize<P> {
+    pub fn new(inner: P, mode: MultiMode) -> Self {
+        MultiNormalize {
+            inner,
+            mode,
+            stack: Vec::new(),
+        }
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    /// Single geometry at the current position is wrapped into a multi-geometry
+    fn promote(&self) -> bool {
+        self.mode == MultiMode::Promote
+            && !self
+                .stack
+                .last()
+                .is_some_and(|geom_type| *geom_type != GeomType::GeometryCollection)
+    }
+    /// Multi-geometry at the current position is exploded
+    fn explode(&self) -> bool {
+        self.mode == MultiMode::Explode && self.stack.is_empty()
+    }
+    /// Current position is a part of an exploded multi-geometry
+    fn is_part(&self) -> bool {
+        self.mode == MultiMode::Explode
+            && matches!(
+                self.stack.as_slice(),
+                [GeomType::MultiPoint | GeomType::MultiLineString | GeomType::MultiPolygon]
+            )
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for MultiNormalize<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.inner.input_dimensions(dimensions)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if self.is_part() {
+            self.inner.point_begin(idx)?;
+            self.inner.xy(x, y, 0)?;
+            self.inner.point_end(idx)
+        } else {
+            self.inner.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if self.is_part() {
+            self.inner.point_begin(idx)?;
+            self.inner.coordinate(x, y, z, m, t, tm, 0)?;
+            self.inner.point_end(idx)
+        } else {
+            self.inner.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        if self.promote() {
+            self.inner.multipoint_begin(0, idx)?;
+            self.inner.multipoint_end(idx)
+        } else {
+            self.inner.empty_point(idx)
+        }
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        let promote = self.promote();
+        self.stack.push(GeomType::Point);
+        if promote {
+            self.inner.multipoint_begin(1, idx)
+        } else {
+            self.inner.point_begin(idx)
+        }
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        if self.promote() {
+            self.inner.multipoint_end(idx)
+        } else {
+            self.inner.point_end(idx)
+        }
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let explode = self.explode();
+        self.stack.push(GeomType::MultiPoint);
+        if explode {
+            Ok(())
+        } else {
+            self.inner.multipoint_begin(size, idx)
+        }
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        if self.explode() {
+            Ok(())
+        } else {
+            self.inner.multipoint_end(idx)
+        }
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        let (promote, part) = (self.promote(), self.is_part());
+        self.stack.push(GeomType::LineString);
+        if promote {
+            self.inner.multilinestring_begin(1, idx)?;
+            self.inner.linestring_begin(false, size, 0)
+        } else if part {
+            self.inner.linestring_begin(true, size, idx)
+        } else {
+            self.inner.linestring_begin(tagged, size, idx)
+        }
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.stack.pop();
+        if self.promote() {
+            self.inner.linestring_end(false, 0)?;
+            self.inner.multilinestring_end(idx)
+        } else if self.is_part() {
+            self.inner.linestring_end(true, idx)
+        } else {
+            self.inner.linestring_end(tagged, idx)
+        }
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let explode = self.explode();
+        self.stack.push(GeomType::MultiLineString);
+        if explode {
+            Ok(())
+        } else {
+            self.inner.multilinestring_begin(size, idx)
+        }
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        if self.explode() {
+            Ok(())
+        } else {
+            self.inner.multilinestring_end(idx)
+        }
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        let (promote, part) = (self.promote(), self.is_part());
+        self.stack.push(GeomType::Polygon);
+        if promote {
+            self.inner.multipolygon_begin(1, idx)?;
+            self.inner.polygon_begin(false, size, 0)
+        } else if part {
+            self.inner.polygon_begin(true, size, idx)
+        } else {
+            self.inner.polygon_begin(tagged, size, idx)
+        }
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.stack.pop();
+        if self.promote() {
+            self.inner.polygon_end(false, 0)?;
+            self.inner.multipolygon_end(idx)
+        } else if self.is_part() {
+            self.inner.polygon_end(true, idx)
+        } else {
+            self.inner.polygon_end(tagged, idx)
+        }
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        let explode = self.explode();
+        self.stack.push(GeomType::MultiPolygon);
+        if explode {
+            Ok(())
+        } else {
+            self.inner.multipolygon_begin(size, idx)
+        }
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        if self.explode() {
+            Ok(())
+        } else {
+            self.inner.multipolygon_end(idx)
+        }
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.stack.push(GeomType::GeometryCollection);
+        self.inner.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        self.inner.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.stack.push(GeomType::CircularString);
+        self.inner.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        self.inner.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.stack.push(GeomType::CompoundCurve);
+        self.inner.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        self.inner.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.stack.push(GeomType::CurvePolygon);
+        self.inner.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        self.inner.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.stack.push(GeomType::MultiCurve);
+        self.inner.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        self.inner.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.stack.push(GeomType::MultiSurface);
+        self.inner.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        self.inner.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.stack.push(GeomType::Triangle);
+        self.inner.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.stack.pop();
+        self.inner.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.stack.push(GeomType::PolyhedralSurface);
+        self.inner.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        self.inner.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.stack.push(GeomType::Tin);
+        self.inner.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.stack.pop();
+        self.inner.tin_end(idx)
+    }
+}
+
+impl<P: GeomProcessor + PropertyProcessor> PropertyProcessor for MultiNormalize<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.inner.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for MultiNormalize<P> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.inner.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+    fn skip_geometry(&self) -> bool {
+        self.inner.skip_geometry()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::test_util::process_wkt;
+    use crate::wkt::WktWriter;
+
+    fn normalize(wkt: &str, mode: MultiMode) -> String {
+        process_wkt(wkt, |out| MultiNormalize::new(WktWriter::new(out), mode))
+    }
+
+    #[test]
+    fn promote() {
+        let promote = |wkt| normalize(wkt, MultiMode::Promote);
+        assert_eq!(promote("POINT(1 2)"), "MULTIPOINT(1 2)");
+        assert_eq!(promote("POINT EMPTY"), "MULTIPOINT EMPTY");
+        assert_eq!(promote("LINESTRING(0 0,1 1)"), "MULTILINESTRING((0 0,1 1))");
+        assert_eq!(
+            promote("POLYGON((0 0,1 0,1 1,0 0),(0.2 0.1,0.8 0.1,0.8 0.7,0.2 0.1))"),
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0),(0.2 0.1,0.8 0.1,0.8 0.7,0.2 0.1)))"
+        );
+        assert_eq!(
+            promote("MULTILINESTRING((0 0,1 1),(2 2,3 3))"),
+            "MULTILINESTRING((0 0,1 1),(2 2,3 3))"
+        );
+        assert_eq!(
+            promote("GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(0 0,1 1))"),
+            "GEOMETRYCOLLECTION(MULTIPOINT(1 2),MULTILINESTRING((0 0,1 1)))"
+        );
+    }
+
+    #[test]
+    fn explode() {
+        let explode = |wkt| normalize(wkt, MultiMode::Explode);
+        assert_eq!(explode("MULTIPOINT(1 2,3 4)"), "POINT(1 2),POINT(3 4)");
+        assert_eq!(
+            explode("MULTILINESTRING((0 0,1 1),(2 2,3 3))"),
+            "LINESTRING(0 0,1 1),LINESTRING(2 2,3 3)"
+        );
+        assert_eq!(
+            explode("MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5)))"),
+            "POLYGON((0 0,1 0,1 1,0 0)),POLYGON((5 5,6 5,6 6,5 5))"
+        );
+        assert_eq!(explode("LINESTRING(0 0,1 1)"), "LINESTRING(0 0,1 1)");
+        assert_eq!(
+            explode("GEOMETRYCOLLECTION(MULTIPOINT(1 2,3 4))"),
+            "GEOMETRYCOLLECTION(MULTIPOINT(1 2,3 4))"
+        );
+    }
+
+    #[test]
+    fn preserve_z() {
+        assert_eq!(
+            process_wkt("LINESTRING Z (0 0 1,1 1 2)", |out| {
+                MultiNormalize::new(
+                    WktWriter::with_dims(out, CoordDimensions::xyz()),
+                    MultiMode::Promote,
+                )
+            }),
+            "MULTILINESTRING Z ((0 0 1,1 1 2))"
+        );
+    }
+}
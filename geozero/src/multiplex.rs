@@ -16,6 +16,10 @@ impl<P1: FeatureProcessor, P2: FeatureProcessor> Multiplexer<P1, P2> {
 }
 
 impl<P1: FeatureProcessor, P2: FeatureProcessor> FeatureProcessor for Multiplexer<P1, P2> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.p1.crs(crs)?;
+        self.p2.crs(crs)
+    }
     fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
         self.p1.dataset_begin(name)?;
         self.p2.dataset_begin(name)
@@ -28,6 +32,10 @@ impl<P1: FeatureProcessor, P2: FeatureProcessor> FeatureProcessor for Multiplexe
         self.p1.feature_begin(idx)?;
         self.p2.feature_begin(idx)
     }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        self.p1.feature_id(id.clone())?;
+        self.p2.feature_id(id)
+    }
     fn feature_end(&mut self, idx: u64) -> Result<()> {
         self.p1.feature_end(idx)?;
         self.p2.feature_end(idx)
@@ -48,6 +56,9 @@ impl<P1: FeatureProcessor, P2: FeatureProcessor> FeatureProcessor for Multiplexe
         self.p1.geometry_end()?;
         self.p2.geometry_end()
     }
+    fn skip_geometry(&self) -> bool {
+        self.p1.skip_geometry() && self.p2.skip_geometry()
+    }
 }
 
 impl<P1: FeatureProcessor, P2: FeatureProcessor> GeomProcessor for Multiplexer<P1, P2> {
@@ -9,6 +9,9 @@ pub use tag_builder::TagsBuilder;
 mod tile_value;
 pub use tile_value::TileValue;
 
+mod web_mercator;
+pub use web_mercator::*;
+
 #[rustfmt::skip]
 mod vector_tile;
 
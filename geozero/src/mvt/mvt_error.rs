@@ -15,4 +15,10 @@ pub enum MvtError {
     GeometryFormat,
     #[error("too few coordinates in line or ring")]
     TooFewCoordinates,
+    #[error("protobuf decoding: {0}")]
+    Decode(#[from] prost::DecodeError),
+    #[error("truncated protobuf message")]
+    TruncatedMessage,
+    #[error("unsupported protobuf group field")]
+    UnsupportedGroup,
 }
@@ -1,10 +1,13 @@
-use crate::error::Result;
+use crate::error::{GeozeroError, Result};
 use crate::mvt::vector_tile::{tile, tile::GeomType};
 use crate::{ColumnValue, FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry};
+use prost::encoding::{decode_key, decode_varint, WireType};
+use prost::Message;
 
 use super::{
     mvt_commands::{Command, CommandInteger, ParameterInteger},
     mvt_error::MvtError,
+    web_mercator::{TileCrs, TileTransform},
 };
 
 impl GeozeroDatasource for tile::Layer {
@@ -17,17 +20,237 @@ impl GeozeroDatasource for tile::Layer {
 pub fn process(layer: &tile::Layer, processor: &mut impl FeatureProcessor) -> Result<()> {
     processor.dataset_begin(Some(&layer.name))?;
     for (idx, feature) in layer.features.iter().enumerate() {
-        processor.feature_begin(idx as u64)?;
+        process_feature(layer, feature, idx as u64, None, processor)?;
+    }
+    processor.dataset_end()
+}
+
+type FeatureFilter<'a> = dyn Fn(&MvtProperties) -> bool + 'a;
+
+/// MVT tile reader with layer and feature selection.
+///
+/// Only layers selected by name are decoded, each one processed as a separate dataset.
+/// Features rejected by the feature filter are skipped without decoding their geometry.
+pub struct MvtReader<'a> {
+    data: &'a [u8],
+    layers: Option<Vec<String>>,
+    feature_filter: Option<Box<FeatureFilter<'a>>>,
+    /// Tile position `(z, x, y, extent)` for geographic output coordinates
+    tile: Option<(u8, u32, u32, u32)>,
+    crs: TileCrs,
+}
+
+impl<'a> MvtReader<'a> {
+    /// Reader for an encoded MVT tile.
+    pub fn new(data: &'a [u8]) -> Self {
+        MvtReader {
+            data,
+            layers: None,
+            feature_filter: None,
+            tile: None,
+            crs: TileCrs::default(),
+        }
+    }
+    /// Process only layers with the given names.
+    pub fn select_layers(mut self, names: &[&str]) -> Self {
+        self.layers = Some(names.iter().map(|name| name.to_string()).collect());
+        self
+    }
+    /// Process only features whose properties match `filter`.
+    pub fn with_feature_filter<F: Fn(&MvtProperties) -> bool + 'a>(mut self, filter: F) -> Self {
+        self.feature_filter = Some(Box::new(filter));
+        self
+    }
+    /// Convert tile coordinates of tile `z/x/y` with the given `extent` to geographic coordinates.
+    ///
+    /// Coordinates are emitted in Web Mercator, unless another CRS is set with [`Self::with_crs`].
+    pub fn with_tile(mut self, z: u8, x: u32, y: u32, extent: u32) -> Self {
+        self.tile = Some((z, x, y, extent));
+        self
+    }
+    /// Set CRS of geographic coordinates emitted for [`Self::with_tile`].
+    pub fn with_crs(mut self, crs: TileCrs) -> Self {
+        self.crs = crs;
+        self
+    }
+    fn is_selected(&self, layer_data: &[u8]) -> Result<bool> {
+        match &self.layers {
+            Some(names) => {
+                let name = layer_name(layer_data)?;
+                Ok(names.iter().any(|n| n == name))
+            }
+            None => Ok(true),
+        }
+    }
+}
 
-        process_properties(layer, feature, processor)?;
+impl GeozeroDatasource for MvtReader<'_> {
+    fn process<P: FeatureProcessor>(&mut self, processor: &mut P) -> Result<()> {
+        let mut buf = self.data;
+        while !buf.is_empty() {
+            let (tag, wire_type) = decode_key(&mut buf).map_err(MvtError::from)?;
+            if tag == 3 && wire_type == WireType::LengthDelimited {
+                let layer_data = length_delimited(&mut buf)?;
+                if self.is_selected(layer_data)? {
+                    self.process_layer(layer_data, processor)?;
+                }
+            } else {
+                skip_field(wire_type, &mut buf)?;
+            }
+        }
+        Ok(())
+    }
+}
 
-        processor.geometry_begin()?;
-        process_geom(feature, processor)?;
-        processor.geometry_end()?;
+impl MvtReader<'_> {
+    fn process_layer<P: FeatureProcessor>(&self, data: &[u8], processor: &mut P) -> Result<()> {
+        // Decode layer without features and keep encoded features for lazy decoding
+        let mut buf = data;
+        let mut features = Vec::new();
+        let mut layer_data = Vec::new();
+        while !buf.is_empty() {
+            let field = buf;
+            let (tag, wire_type) = decode_key(&mut buf).map_err(MvtError::from)?;
+            if tag == 2 && wire_type == WireType::LengthDelimited {
+                features.push(length_delimited(&mut buf)?);
+            } else {
+                skip_field(wire_type, &mut buf)?;
+                layer_data.extend_from_slice(&field[..field.len() - buf.len()]);
+            }
+        }
+        let layer = tile::Layer::decode(layer_data.as_slice()).map_err(MvtError::from)?;
+        let transform = self
+            .tile
+            .map(|(z, x, y, extent)| TileTransform::new(z, x, y, extent, self.crs));
+
+        processor.dataset_begin(Some(&layer.name))?;
+        let mut idx = 0;
+        for feature_data in features {
+            if let Some(filter) = &self.feature_filter {
+                let tags = feature_tags(feature_data)?;
+                if !filter(&MvtProperties {
+                    layer: &layer,
+                    tags: &tags,
+                }) {
+                    continue;
+                }
+            }
+            let feature = tile::Feature::decode(feature_data).map_err(MvtError::from)?;
+            process_feature(&layer, &feature, idx, transform.as_ref(), processor)?;
+            idx += 1;
+        }
+        processor.dataset_end()
+    }
+}
 
-        processor.feature_end(idx as u64)?;
+/// Read the name of an encoded layer without decoding its features.
+fn layer_name(mut buf: &[u8]) -> Result<&str> {
+    while !buf.is_empty() {
+        let (tag, wire_type) = decode_key(&mut buf).map_err(MvtError::from)?;
+        if tag == 1 && wire_type == WireType::LengthDelimited {
+            let name = length_delimited(&mut buf)?;
+            return std::str::from_utf8(name).map_err(|e| GeozeroError::Dataset(e.to_string()));
+        }
+        skip_field(wire_type, &mut buf)?;
     }
-    processor.dataset_end()
+    Ok("")
+}
+
+/// Read the tags of an encoded feature without decoding its geometry.
+fn feature_tags(mut buf: &[u8]) -> Result<Vec<u32>> {
+    let mut tags = Vec::new();
+    while !buf.is_empty() {
+        let (tag, wire_type) = decode_key(&mut buf).map_err(MvtError::from)?;
+        match (tag, wire_type) {
+            (2, WireType::LengthDelimited) => {
+                let mut packed = length_delimited(&mut buf)?;
+                while !packed.is_empty() {
+                    tags.push(decode_varint(&mut packed).map_err(MvtError::from)? as u32);
+                }
+            }
+            (2, WireType::Varint) => {
+                tags.push(decode_varint(&mut buf).map_err(MvtError::from)? as u32);
+            }
+            _ => skip_field(wire_type, &mut buf)?,
+        }
+    }
+    Ok(tags)
+}
+
+fn length_delimited<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let len = decode_varint(buf).map_err(MvtError::from)? as usize;
+    if len > buf.len() {
+        return Err(MvtError::TruncatedMessage.into());
+    }
+    let (value, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(value)
+}
+
+fn skip_field(wire_type: WireType, buf: &mut &[u8]) -> Result<()> {
+    let len = match wire_type {
+        WireType::Varint => {
+            decode_varint(buf).map_err(MvtError::from)?;
+            0
+        }
+        WireType::SixtyFourBit => 8,
+        WireType::ThirtyTwoBit => 4,
+        WireType::LengthDelimited => {
+            length_delimited(buf)?;
+            0
+        }
+        WireType::StartGroup | WireType::EndGroup => {
+            return Err(MvtError::UnsupportedGroup.into());
+        }
+    };
+    if len > buf.len() {
+        return Err(MvtError::TruncatedMessage.into());
+    }
+    *buf = &buf[len..];
+    Ok(())
+}
+
+/// Properties of an MVT feature, resolved from the layer dictionaries.
+pub struct MvtProperties<'a> {
+    layer: &'a tile::Layer,
+    tags: &'a [u32],
+}
+
+impl<'a> MvtProperties<'a> {
+    /// Value of property `key`.
+    pub fn get(&self, key: &str) -> Option<&'a tile::Value> {
+        self.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+    /// Iterate over all properties with valid key and value indices.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &'a tile::Value)> + 'a {
+        let layer = self.layer;
+        self.tags.chunks_exact(2).filter_map(move |pair| {
+            let key = layer.keys.get(pair[0] as usize)?;
+            let value = layer.values.get(pair[1] as usize)?;
+            Some((key.as_str(), value))
+        })
+    }
+}
+
+fn process_feature(
+    layer: &tile::Layer,
+    feature: &tile::Feature,
+    idx: u64,
+    transform: Option<&TileTransform>,
+    processor: &mut impl FeatureProcessor,
+) -> Result<()> {
+    processor.feature_begin(idx)?;
+    if let Some(id) = feature.id {
+        processor.feature_id(ColumnValue::ULong(id))?;
+    }
+
+    process_properties(layer, feature, processor)?;
+
+    processor.geometry_begin()?;
+    process_geom_n(feature, 0, transform, processor)?;
+    processor.geometry_end()?;
+
+    processor.feature_end(idx)
 }
 
 fn process_properties(
@@ -78,24 +301,25 @@ impl GeozeroGeometry for tile::Feature {
 
 /// Process MVT geometry.
 pub fn process_geom<P: GeomProcessor>(geom: &tile::Feature, processor: &mut P) -> Result<()> {
-    process_geom_n(geom, 0, processor)
+    process_geom_n(geom, 0, None, processor)
 }
 
 fn process_geom_n<P: GeomProcessor>(
     geom: &tile::Feature,
     idx: usize,
+    transform: Option<&TileTransform>,
     processor: &mut P,
 ) -> Result<()> {
     let mut cursor: [i32; 2] = [0, 0];
     match geom.r#type {
         Some(r#type) if r#type == GeomType::Point as i32 => {
-            process_point(&mut cursor, &geom.geometry, idx, processor)
+            process_point(&mut cursor, &geom.geometry, idx, transform, processor)
         }
         Some(r#type) if r#type == GeomType::Linestring as i32 => {
-            process_linestrings(&mut cursor, geom, idx, processor)
+            process_linestrings(&mut cursor, geom, idx, transform, processor)
         }
         Some(r#type) if r#type == GeomType::Polygon as i32 => {
-            process_polygons(&mut cursor, geom, idx, processor)
+            process_polygons(&mut cursor, geom, idx, transform, processor)
         }
         _ => Ok(()),
     }
@@ -105,22 +329,19 @@ fn process_coord<P: GeomProcessor>(
     cursor: &mut [i32; 2],
     coord: &[u32],
     idx: usize,
+    transform: Option<&TileTransform>,
     processor: &mut P,
 ) -> Result<()> {
     cursor[0] += ParameterInteger(coord[0]).value();
     cursor[1] += ParameterInteger(coord[1]).value();
+    let (x, y) = match transform {
+        Some(transform) => transform.apply(cursor[0] as f64, cursor[1] as f64),
+        None => (cursor[0] as f64, cursor[1] as f64),
+    };
     if processor.multi_dim() {
-        processor.coordinate(
-            cursor[0] as f64,
-            cursor[1] as f64,
-            None,
-            None,
-            None,
-            None,
-            idx,
-        )
+        processor.coordinate(x, y, None, None, None, None, idx)
     } else {
-        processor.xy(cursor[0] as f64, cursor[1] as f64, idx)
+        processor.xy(x, y, idx)
     }
 }
 
@@ -128,18 +349,19 @@ fn process_point<P: GeomProcessor>(
     cursor: &mut [i32; 2],
     geom: &[u32],
     idx: usize,
+    transform: Option<&TileTransform>,
     processor: &mut P,
 ) -> Result<()> {
     let command = CommandInteger(geom[0]);
     let count = command.count() as usize;
     if count == 1 {
         processor.point_begin(idx)?;
-        process_coord(cursor, &geom[1..3], 0, processor)?;
+        process_coord(cursor, &geom[1..3], 0, transform, processor)?;
         processor.point_end(idx)
     } else {
         processor.multipoint_begin(count, idx)?;
         for i in 0..count {
-            process_coord(cursor, &geom[1 + i * 2..3 + i * 2], i, processor)?;
+            process_coord(cursor, &geom[1 + i * 2..3 + i * 2], i, transform, processor)?;
         }
         processor.multipoint_end(idx)
     }
@@ -150,6 +372,7 @@ fn process_linestring<P: GeomProcessor>(
     geom: &[u32],
     tagged: bool,
     idx: usize,
+    transform: Option<&TileTransform>,
     processor: &mut P,
 ) -> Result<()> {
     if geom[0] != CommandInteger::from(Command::MoveTo, 1) {
@@ -160,9 +383,15 @@ fn process_linestring<P: GeomProcessor>(
         return Err(MvtError::GeometryFormat.into());
     }
     processor.linestring_begin(tagged, 1 + lineto.count() as usize, idx)?;
-    process_coord(cursor, &geom[1..3], 0, processor)?;
+    process_coord(cursor, &geom[1..3], 0, transform, processor)?;
     for i in 0..lineto.count() as usize {
-        process_coord(cursor, &geom[4 + i * 2..6 + i * 2], i + 1, processor)?;
+        process_coord(
+            cursor,
+            &geom[4 + i * 2..6 + i * 2],
+            i + 1,
+            transform,
+            processor,
+        )?;
     }
     processor.linestring_end(tagged, idx)
 }
@@ -171,6 +400,7 @@ fn process_linestrings<P: GeomProcessor>(
     cursor: &mut [i32; 2],
     geom: &tile::Feature,
     idx: usize,
+    transform: Option<&TileTransform>,
     processor: &mut P,
 ) -> Result<()> {
     let mut line_string_slices: Vec<&[u32]> = vec![];
@@ -187,11 +417,18 @@ fn process_linestrings<P: GeomProcessor>(
     if line_string_slices.len() > 1 {
         processor.multilinestring_begin(line_string_slices.len(), idx)?;
         for (i, line_string_slice) in line_string_slices.iter().enumerate() {
-            process_linestring(cursor, line_string_slice, false, i, processor)?;
+            process_linestring(cursor, line_string_slice, false, i, transform, processor)?;
         }
         processor.multilinestring_end(idx)
     } else {
-        process_linestring(cursor, line_string_slices[0], true, idx, processor)
+        process_linestring(
+            cursor,
+            line_string_slices[0],
+            true,
+            idx,
+            transform,
+            processor,
+        )
     }
 }
 
@@ -200,6 +437,7 @@ fn process_polygon<P: GeomProcessor>(
     rings: &[&[u32]],
     tagged: bool,
     idx: usize,
+    transform: Option<&TileTransform>,
     processor: &mut P,
 ) -> Result<()> {
     processor.polygon_begin(tagged, rings.len(), idx)?;
@@ -217,14 +455,21 @@ fn process_polygon<P: GeomProcessor>(
         }
         processor.linestring_begin(false, 1 + lineto.count() as usize, i)?;
         let mut start_cursor = *cursor;
-        process_coord(cursor, &ring[1..3], 0, processor)?;
+        process_coord(cursor, &ring[1..3], 0, transform, processor)?;
         for i in 0..lineto.count() as usize {
-            process_coord(cursor, &ring[4 + i * 2..6 + i * 2], i + 1, processor)?;
+            process_coord(
+                cursor,
+                &ring[4 + i * 2..6 + i * 2],
+                i + 1,
+                transform,
+                processor,
+            )?;
         }
         process_coord(
             &mut start_cursor,
             &ring[1..3],
             1 + lineto.count() as usize,
+            transform,
             processor,
         )?;
         processor.linestring_end(false, i)?;
@@ -237,6 +482,7 @@ fn process_polygons<P: GeomProcessor>(
     cursor: &mut [i32; 2],
     geom: &tile::Feature,
     idx: usize,
+    transform: Option<&TileTransform>,
     processor: &mut P,
 ) -> Result<()> {
     let mut polygon_slices: Vec<Vec<&[u32]>> = vec![];
@@ -266,11 +512,11 @@ fn process_polygons<P: GeomProcessor>(
     if polygon_slices.len() > 1 {
         processor.multipolygon_begin(polygon_slices.len(), idx)?;
         for (i, polygon_slice) in polygon_slices.iter().enumerate() {
-            process_polygon(cursor, polygon_slice, false, i, processor)?;
+            process_polygon(cursor, polygon_slice, false, i, transform, processor)?;
         }
         processor.multipolygon_end(idx)
     } else {
-        process_polygon(cursor, &polygon_slices[0], true, idx, processor)
+        process_polygon(cursor, &polygon_slices[0], true, idx, transform, processor)
     }
 }
 
@@ -364,6 +610,7 @@ mod test {
                 "features": [
                     {
                         "type": "Feature",
+                        "id": 1,
                         "properties": {
                             "hello": "world",
                             "h": "world",
@@ -376,6 +623,7 @@ mod test {
                     },
                     {
                         "type": "Feature",
+                        "id": 2,
                         "properties": {
                             "hello": "again",
                             "count": 2
@@ -521,4 +769,159 @@ mod test {
             })
         );
     }
+
+    fn roads_tile() -> Vec<u8> {
+        let mut tile = crate::mvt::Tile::default();
+        for name in ["water", "roads", "pois"] {
+            let mut layer = tile::Layer {
+                version: 2,
+                name: name.to_string(),
+                extent: Some(4096),
+                ..Default::default()
+            };
+            layer.keys.push(String::from("class"));
+            for class in ["primary", "path"] {
+                layer.values.push(tile::Value {
+                    string_value: Some(class.to_string()),
+                    ..Default::default()
+                });
+            }
+            for id in 1..=3 {
+                let mut feature = tile::Feature {
+                    id: Some(id),
+                    tags: vec![0, (id % 2) as u32],
+                    geometry: vec![9, 2 * id as u32, 2],
+                    ..Default::default()
+                };
+                feature.set_type(GeomType::Point);
+                layer.features.push(feature);
+            }
+            tile.layers.push(layer);
+        }
+        tile.encode_to_vec()
+    }
+
+    #[derive(Default)]
+    struct FeatureIds(Vec<String>);
+    impl GeomProcessor for FeatureIds {}
+    impl crate::PropertyProcessor for FeatureIds {}
+    impl FeatureProcessor for FeatureIds {
+        fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+            self.0.push(name.unwrap_or_default().to_string());
+            Ok(())
+        }
+        fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+            self.0.push(id.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn reader() -> Result<()> {
+        let data = roads_tile();
+        let mut ids = FeatureIds::default();
+        MvtReader::new(&data).process(&mut ids)?;
+        assert_eq!(
+            ids.0,
+            ["water", "1", "2", "3", "roads", "1", "2", "3", "pois", "1", "2", "3"]
+        );
+
+        let mut ids = FeatureIds::default();
+        MvtReader::new(&data)
+            .select_layers(&["roads", "water"])
+            .process(&mut ids)?;
+        assert_eq!(ids.0, ["water", "1", "2", "3", "roads", "1", "2", "3"]);
+
+        let mut ids = FeatureIds::default();
+        MvtReader::new(&data)
+            .select_layers(&["roads"])
+            .with_feature_filter(|props| {
+                props.get("class").and_then(|v| v.string_value.as_deref()) == Some("path")
+            })
+            .process(&mut ids)?;
+        assert_eq!(ids.0, ["roads", "1", "3"]);
+
+        let mut ids = FeatureIds::default();
+        MvtReader::new(&data)
+            .select_layers(&["rivers"])
+            .process(&mut ids)?;
+        assert!(ids.0.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn reader_feature_indices() -> Result<()> {
+        let data = roads_tile();
+        let mut geojson: Vec<u8> = Vec::new();
+        MvtReader::new(&data)
+            .select_layers(&["pois"])
+            .with_feature_filter(|props| props.iter().any(|(k, _)| k == "class"))
+            .process(&mut crate::geojson::GeoJsonWriter::new(&mut geojson))?;
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&geojson).unwrap(),
+            json!({
+                "type": "FeatureCollection",
+                "name": "pois",
+                "features": [
+                    {"type": "Feature", "id": 1, "properties": {"class": "path"}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+                    {"type": "Feature", "id": 2, "properties": {"class": "primary"}, "geometry": {"type": "Point", "coordinates": [2, 1]}},
+                    {"type": "Feature", "id": 3, "properties": {"class": "path"}, "geometry": {"type": "Point", "coordinates": [3, 1]}}
+                ]
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reader_truncated() {
+        let data = roads_tile();
+        let result = MvtReader::new(&data[..data.len() - 3])
+            .select_layers(&["roads"])
+            .process(&mut FeatureIds::default());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reader_tile_coords() -> Result<()> {
+        let mut tile = crate::mvt::Tile::default();
+        let mut layer = tile::Layer {
+            version: 2,
+            name: String::from("corners"),
+            extent: Some(4096),
+            ..Default::default()
+        };
+        // Top left and bottom right corner
+        let mut feature = tile::Feature {
+            geometry: vec![17, 0, 0, 8192, 8192],
+            ..Default::default()
+        };
+        feature.set_type(GeomType::Point);
+        layer.features.push(feature);
+        tile.layers.push(layer);
+        let data = tile.encode_to_vec();
+
+        let mut geojson: Vec<u8> = Vec::new();
+        MvtReader::new(&data)
+            .with_tile(1, 1, 0, 4096)
+            .process(&mut crate::geojson::GeoJsonWriter::new(&mut geojson))?;
+        let geojson = serde_json::from_slice::<serde_json::Value>(&geojson).unwrap();
+        assert_eq!(
+            geojson["features"][0]["geometry"]["coordinates"],
+            json!([[0, 20037508.342789244], [20037508.342789244, 0]])
+        );
+
+        let mut geojson: Vec<u8> = Vec::new();
+        MvtReader::new(&data)
+            .with_tile(1, 1, 0, 4096)
+            .with_crs(TileCrs::LonLat)
+            .process(&mut crate::geojson::GeoJsonWriter::new(&mut geojson))?;
+        let geojson = serde_json::from_slice::<serde_json::Value>(&geojson).unwrap();
+        let coords = &geojson["features"][0]["geometry"]["coordinates"];
+        let coord = |i: usize, j: usize| coords[i][j].as_f64().unwrap();
+        assert!(coord(0, 0).abs() < 1e-9);
+        assert!((coord(0, 1) - 85.0511287798066).abs() < 1e-9);
+        assert!((coord(1, 0) - 180.0).abs() < 1e-9);
+        assert!(coord(1, 1).abs() < 1e-9);
+        Ok(())
+    }
 }
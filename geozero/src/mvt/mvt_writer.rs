@@ -4,11 +4,17 @@
 use crate::error::Result;
 use crate::mvt::mvt_commands::{Command, CommandInteger, ParameterInteger};
 use crate::mvt::vector_tile::{tile, tile::GeomType};
-use crate::GeomProcessor;
+use crate::mvt::{TagsBuilder, TileValue};
+use crate::{ColumnValue, FeatureProcessor, GeomProcessor, PropertyProcessor};
 
 use super::mvt_error::MvtError;
+use super::web_mercator::{lonlat_to_web_mercator, tile_bounds, TileCrs};
 
 /// Generator for MVT geometry type.
+///
+/// Used as [`FeatureProcessor`], all features of a dataset are collected into a layer.
+/// Property keys and values are stored once per layer in its `keys` and `values` pools,
+/// with values converted to [`TileValue`].
 #[derive(Default, Debug)]
 pub struct MvtWriter {
     pub(crate) feature: tile::Feature,
@@ -19,13 +25,37 @@ pub struct MvtWriter {
     bottom: f64,
     x_multiplier: f64,
     y_multiplier: f64,
+    // CRS of input coordinates
+    crs: TileCrs,
     // Writer state
     last_x: i32,
     last_y: i32,
     line_state: LineState,
     is_multiline: bool,
+    // Clipping
+    clip_box: Option<ClipBox>,
+    /// Scaled coordinates of the current part, if clipping is enabled
+    part: Vec<(f64, f64)>,
+    /// Skip remaining rings of a polygon with clipped away exterior ring
+    skip_rings: bool,
+    /// Layer of processed features
+    layer: tile::Layer,
+    /// Deduplicated keys and values of the current layer
+    tags: TagsBuilder<String>,
+    /// Properties of the current feature, added to the tags if the feature is kept
+    properties: Vec<(String, TileValue)>,
 }
 
+/// Clipping bounds in scaled tile coordinates (Y not reversed)
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ClipBox {
+    min: f64,
+    max: f64,
+}
+
+/// Start and end point of a clipped segment and whether the end point was clipped
+type ClippedSegment = ((f64, f64), (f64, f64), bool);
+
 #[derive(Default, Debug, PartialEq)]
 enum LineState {
     #[default]
@@ -48,10 +78,140 @@ impl MvtWriter {
         }
     }
 
+    /// Writer for Web Mercator tile `z/x/y` with the given `extent`.
+    ///
+    /// Input coordinates are expected in Web Mercator, unless another CRS is set with [`Self::with_crs`].
+    pub fn for_tile(z: u8, x: u32, y: u32, extent: u32) -> MvtWriter {
+        let (left, bottom, right, top) = tile_bounds(z, x, y);
+        MvtWriter::new(extent, left, bottom, right, top)
+    }
+
+    /// Set CRS of input coordinates.
+    ///
+    /// Longitude/latitude coordinates are projected to Web Mercator before scaling to the tile extent.
+    pub fn with_crs(mut self, crs: TileCrs) -> Self {
+        self.crs = crs;
+        self
+    }
+
+    /// Clip geometries to the tile extent with a buffer of `buffer` pixels.
+    ///
+    /// Polygons are clipped with the Sutherland-Hodgman algorithm and lines with
+    /// the Liang-Barsky algorithm against `[-buffer, extent + buffer]`.
+    /// Points outside of the buffered extent are dropped.
+    /// Features completely outside of the buffered extent are not added to the layer,
+    /// single geometries produce an empty feature geometry.
+    pub fn with_clip_buffer(mut self, buffer: u32) -> Self {
+        self.clip_box = Some(ClipBox {
+            min: -(buffer as f64),
+            max: self.extent as f64 + buffer as f64,
+        });
+        self
+    }
+
     pub fn geometry(&self) -> &tile::Feature {
         &self.feature
     }
 
+    /// Layer with the processed features, complete after `dataset_end`.
+    pub fn layer(&self) -> &tile::Layer {
+        &self.layer
+    }
+
+    /// Return the layer with the processed features.
+    pub fn into_layer(self) -> tile::Layer {
+        self.layer
+    }
+
+    /// Scale to tile coordinate space, without reversing Y
+    fn scale(&self, x_coord: f64, y_coord: f64) -> (f64, f64) {
+        let (x_coord, y_coord) = match self.crs {
+            TileCrs::WebMercator => (x_coord, y_coord),
+            TileCrs::LonLat => lonlat_to_web_mercator(x_coord, y_coord),
+        };
+        (
+            (x_coord - self.left) * self.x_multiplier,
+            (y_coord - self.bottom) * self.y_multiplier,
+        )
+    }
+
+    /// Convert scaled coordinates to integer tile coordinates
+    fn tile_coord(&self, (x, y): (f64, f64)) -> (i32, i32) {
+        // Y is stored as reversed
+        (x as i32, self.extent.saturating_sub(y as i32))
+    }
+
+    /// Write a tile coordinate as delta to the previous coordinate
+    fn push_coord(&mut self, (x, y): (i32, i32)) {
+        self.feature
+            .geometry
+            .push(ParameterInteger::from(x.saturating_sub(self.last_x)));
+        self.feature
+            .geometry
+            .push(ParameterInteger::from(y.saturating_sub(self.last_y)));
+        self.last_x = x;
+        self.last_y = y;
+    }
+
+    /// Tile coordinates of scaled `coords` without repeated points
+    fn tile_coords(&self, coords: &[(f64, f64)]) -> Vec<(i32, i32)> {
+        let mut tile_coords: Vec<(i32, i32)> = Vec::with_capacity(coords.len());
+        for coord in coords {
+            let coord = self.tile_coord(*coord);
+            if tile_coords.last() != Some(&coord) {
+                tile_coords.push(coord);
+            }
+        }
+        tile_coords
+    }
+
+    /// Write a clipped line part
+    fn write_line(&mut self, coords: &[(f64, f64)]) {
+        let coords = self.tile_coords(coords);
+        if coords.len() < 2 {
+            return;
+        }
+        self.reserve(2 + 2 * coords.len());
+        self.feature
+            .geometry
+            .push(CommandInteger::from(Command::MoveTo, 1));
+        self.push_coord(coords[0]);
+        self.feature.geometry.push(CommandInteger::from(
+            Command::LineTo,
+            (coords.len() - 1) as u32,
+        ));
+        for coord in &coords[1..] {
+            self.push_coord(*coord);
+        }
+    }
+
+    /// Write a clipped ring, returns `false` if the ring is degenerated
+    fn write_ring(&mut self, coords: &[(f64, f64)]) -> bool {
+        let mut coords = self.tile_coords(coords);
+        if coords.len() > 1 && coords.first() == coords.last() {
+            coords.pop();
+        }
+        if coords.len() < 3 {
+            return false;
+        }
+        self.reserve(3 + 2 * coords.len());
+        self.feature
+            .geometry
+            .push(CommandInteger::from(Command::MoveTo, 1));
+        self.push_coord(coords[0]);
+        self.feature.geometry.push(CommandInteger::from(
+            Command::LineTo,
+            (coords.len() - 1) as u32,
+        ));
+        for coord in &coords[1..] {
+            self.push_coord(*coord);
+        }
+        self.feature
+            .geometry
+            .push(CommandInteger::from(Command::ClosePath, 1));
+        true
+    }
+
     fn reserve(&mut self, capacity: usize) {
         let total = self.feature.geometry.len() + capacity;
         if total > self.feature.geometry.capacity() {
@@ -64,6 +224,12 @@ impl MvtWriter {
 
 impl GeomProcessor for MvtWriter {
     fn xy(&mut self, x_coord: f64, y_coord: f64, idx: usize) -> Result<()> {
+        if self.clip_box.is_some() {
+            let coord = self.scale(x_coord, y_coord);
+            self.part.push(coord);
+            return Ok(());
+        }
+
         // Omit last coord of ring (emit ClosePath instead)
         let last_ring_coord = if let LineState::Ring(size) = self.line_state {
             idx == size - 1
@@ -72,24 +238,14 @@ impl GeomProcessor for MvtWriter {
         };
 
         if !last_ring_coord {
-            let (x, y) = if self.extent != 0 {
+            let coord = if self.extent != 0 {
                 // scale to tile coordinate space
-                let x = ((x_coord - self.left) * self.x_multiplier) as i32;
-                let y = ((y_coord - self.bottom) * self.y_multiplier) as i32;
-                // Y is stored as reversed
-                (x, self.extent.saturating_sub(y))
+                self.tile_coord(self.scale(x_coord, y_coord))
             } else {
                 // unscaled
                 (x_coord as i32, y_coord as i32)
             };
-            self.feature
-                .geometry
-                .push(ParameterInteger::from(x.saturating_sub(self.last_x)));
-            self.feature
-                .geometry
-                .push(ParameterInteger::from(y.saturating_sub(self.last_y)));
-            self.last_x = x;
-            self.last_y = y;
+            self.push_coord(coord);
         }
 
         // Emit LineTo command after first coord in line or ring
@@ -108,6 +264,10 @@ impl GeomProcessor for MvtWriter {
 
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
         self.feature.set_type(GeomType::Point);
+        if self.clip_box.is_some() {
+            self.part.clear();
+            return Ok(());
+        }
         self.reserve(3);
         self.feature
             .geometry
@@ -115,8 +275,16 @@ impl GeomProcessor for MvtWriter {
         Ok(())
     }
 
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.multipoint_end(idx)
+    }
+
     fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
         self.feature.set_type(GeomType::Point);
+        if self.clip_box.is_some() {
+            self.part.clear();
+            return Ok(());
+        }
         self.reserve(1 + 2 * size);
         self.feature
             .geometry
@@ -124,10 +292,40 @@ impl GeomProcessor for MvtWriter {
         Ok(())
     }
 
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        let Some(clip_box) = self.clip_box else {
+            return Ok(());
+        };
+        let points: Vec<_> = std::mem::take(&mut self.part)
+            .into_iter()
+            .filter(|point| clip_box.contains(*point))
+            .collect();
+        if !points.is_empty() {
+            self.reserve(1 + 2 * points.len());
+            self.feature
+                .geometry
+                .push(CommandInteger::from(Command::MoveTo, points.len() as u32));
+            for point in points {
+                let coord = self.tile_coord(point);
+                self.push_coord(coord);
+            }
+        }
+        Ok(())
+    }
+
     fn linestring_begin(&mut self, tagged: bool, size: usize, _idx: usize) -> Result<()> {
         if tagged {
             self.feature.set_type(GeomType::Linestring);
         }
+        if self.clip_box.is_some() {
+            self.line_state = if tagged || self.is_multiline {
+                LineState::Line(size)
+            } else {
+                LineState::Ring(size)
+            };
+            self.part.clear();
+            return Ok(());
+        }
         self.line_state = if tagged || self.is_multiline {
             self.reserve(2 + 2 * size);
             LineState::Line(size)
@@ -141,7 +339,29 @@ impl GeomProcessor for MvtWriter {
         Ok(())
     }
 
-    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+    fn linestring_end(&mut self, _tagged: bool, idx: usize) -> Result<()> {
+        if let Some(clip_box) = self.clip_box {
+            let part = std::mem::take(&mut self.part);
+            match self.line_state {
+                LineState::Ring(_) => {
+                    // Holes of a clipped away exterior ring are skipped
+                    if idx == 0 {
+                        self.skip_rings = false;
+                    }
+                    if !self.skip_rings && !self.write_ring(&clip_box.clip_ring(&part)) && idx == 0
+                    {
+                        self.skip_rings = true;
+                    }
+                }
+                _ => {
+                    for line in clip_box.clip_line(&part) {
+                        self.write_line(&line);
+                    }
+                }
+            }
+            self.line_state = LineState::None;
+            return Ok(());
+        }
         if let LineState::Ring(_) = self.line_state {
             self.feature
                 .geometry
@@ -175,11 +395,200 @@ impl GeomProcessor for MvtWriter {
     }
 }
 
+impl PropertyProcessor for MvtWriter {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.properties
+            .push((name.to_string(), TileValue::from(value)));
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for MvtWriter {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.layer = tile::Layer {
+            version: 2,
+            name: name.unwrap_or_default().to_string(),
+            extent: (self.extent != 0).then_some(self.extent as u32),
+            ..Default::default()
+        };
+        self.tags = TagsBuilder::new();
+        Ok(())
+    }
+
+    fn dataset_end(&mut self) -> Result<()> {
+        let (keys, values) = std::mem::take(&mut self.tags).into_tags();
+        self.layer.keys = keys;
+        self.layer.values = values.into_iter().map(tile::Value::from).collect();
+        Ok(())
+    }
+
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        // Every feature geometry starts at the origin
+        self.feature = tile::Feature::default();
+        self.properties.clear();
+        self.last_x = 0;
+        self.last_y = 0;
+        Ok(())
+    }
+
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        self.feature.id = match id {
+            ColumnValue::UByte(id) => Some(id.into()),
+            ColumnValue::UShort(id) => Some(id.into()),
+            ColumnValue::UInt(id) => Some(id.into()),
+            ColumnValue::ULong(id) => Some(id),
+            ColumnValue::Byte(id) => u64::try_from(id).ok(),
+            ColumnValue::Short(id) => u64::try_from(id).ok(),
+            ColumnValue::Int(id) => u64::try_from(id).ok(),
+            ColumnValue::Long(id) => u64::try_from(id).ok(),
+            // MVT feature ids are unsigned integers
+            _ => None,
+        };
+        Ok(())
+    }
+
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let properties = std::mem::take(&mut self.properties);
+        if self.clip_box.is_some() && self.feature.geometry.is_empty() {
+            // Clipped away completely
+            return Ok(());
+        }
+        for (name, value) in properties {
+            let (key_idx, value_idx) = self.tags.insert(name, value);
+            self.feature.tags.push(key_idx);
+            self.feature.tags.push(value_idx);
+        }
+        self.layer.features.push(std::mem::take(&mut self.feature));
+        Ok(())
+    }
+}
+
+impl ClipBox {
+    fn contains(&self, (x, y): (f64, f64)) -> bool {
+        x >= self.min && x <= self.max && y >= self.min && y <= self.max
+    }
+
+    /// Clip a segment with the Liang-Barsky algorithm
+    ///
+    /// Returns the clipped segment and whether its end point was clipped.
+    fn clip_segment(&self, (x0, y0): (f64, f64), (x1, y1): (f64, f64)) -> Option<ClippedSegment> {
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        let mut t0 = 0.0_f64;
+        let mut t1 = 1.0_f64;
+        for (p, q) in [
+            (-dx, x0 - self.min),
+            (dx, self.max - x0),
+            (-dy, y0 - self.min),
+            (dy, self.max - y0),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else {
+                let t = q / p;
+                if p < 0.0 {
+                    t0 = t0.max(t);
+                } else {
+                    t1 = t1.min(t);
+                }
+            }
+        }
+        if t0 > t1 {
+            return None;
+        }
+        let start = if t0 > 0.0 {
+            (x0 + t0 * dx, y0 + t0 * dy)
+        } else {
+            (x0, y0)
+        };
+        let end = if t1 < 1.0 {
+            (x0 + t1 * dx, y0 + t1 * dy)
+        } else {
+            (x1, y1)
+        };
+        Some((start, end, t1 < 1.0))
+    }
+
+    /// Clip a line into the parts inside of the box
+    fn clip_line(&self, coords: &[(f64, f64)]) -> Vec<Vec<(f64, f64)>> {
+        let mut lines = Vec::new();
+        let mut line: Vec<(f64, f64)> = Vec::new();
+        for segment in coords.windows(2) {
+            match self.clip_segment(segment[0], segment[1]) {
+                Some((start, end, end_clipped)) => {
+                    if line.last() != Some(&start) {
+                        if line.len() > 1 {
+                            lines.push(std::mem::take(&mut line));
+                        }
+                        line.clear();
+                        line.push(start);
+                    }
+                    line.push(end);
+                    if end_clipped {
+                        lines.push(std::mem::take(&mut line));
+                    }
+                }
+                None => {
+                    if line.len() > 1 {
+                        lines.push(std::mem::take(&mut line));
+                    }
+                    line.clear();
+                }
+            }
+        }
+        if line.len() > 1 {
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Clip a ring with the Sutherland-Hodgman algorithm
+    fn clip_ring(&self, coords: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        let mut ring = coords.to_vec();
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        // Edges as (axis, bound, keep values greater than bound)
+        for (axis, bound, greater) in [
+            (0, self.min, true),
+            (0, self.max, false),
+            (1, self.min, true),
+            (1, self.max, false),
+        ] {
+            let value = |p: (f64, f64)| if axis == 0 { p.0 } else { p.1 };
+            let inside = |p: (f64, f64)| {
+                if greater {
+                    value(p) >= bound
+                } else {
+                    value(p) <= bound
+                }
+            };
+            let intersection = |a: (f64, f64), b: (f64, f64)| {
+                let t = (bound - value(a)) / (value(b) - value(a));
+                (a.0 + t * (b.0 - a.0), a.1 + t * (b.1 - a.1))
+            };
+            let input = std::mem::take(&mut ring);
+            for (i, current) in input.iter().enumerate() {
+                let prev = input[(i + input.len() - 1) % input.len()];
+                if inside(*current) {
+                    if !inside(prev) {
+                        ring.push(intersection(prev, *current));
+                    }
+                    ring.push(*current);
+                } else if inside(prev) {
+                    ring.push(intersection(prev, *current));
+                }
+            }
+        }
+        ring
+    }
+}
+
 #[cfg(test)]
 mod test_mvt {
     use super::*;
     use crate::mvt::vector_tile::Tile;
-    use crate::mvt::TileValue;
 
     // https://github.com/mapbox/vector-tile-spec/tree/master/2.1#45-example
     const TILE_EXAMPLE: &str = r#"Tile {
@@ -393,7 +802,7 @@ mod test {
     use super::*;
     use crate::geojson::conversion::ToJson;
     use crate::geojson::GeoJson;
-    use crate::ToMvt;
+    use crate::{GeozeroDatasource, GeozeroGeometry, ToMvt};
     use serde_json::json;
 
     // https://github.com/mapbox/vector-tile-spec/tree/master/2.1#435-example-geometry-encodings
@@ -487,6 +896,124 @@ mod test {
         );
     }
 
+    fn clipped(geojson: &str) -> Vec<u32> {
+        let mut mvt = MvtWriter::new(256, 0.0, 0.0, 256.0, 256.0).with_clip_buffer(16);
+        GeoJson(geojson).process_geom(&mut mvt).unwrap();
+        mvt.feature.geometry
+    }
+
+    #[test]
+    fn clip_points() {
+        assert_eq!(
+            clipped(r#"{"type": "MultiPoint", "coordinates": [[10, 10], [300, 10], [-20, 10]]}"#),
+            [9, 20, 492]
+        );
+        assert_eq!(
+            clipped(r#"{"type": "Point", "coordinates": [-20, 10]}"#),
+            Vec::<u32>::new()
+        );
+    }
+
+    #[test]
+    fn clip_lines() {
+        assert_eq!(
+            clipped(r#"{"type": "LineString", "coordinates": [[-48, 128], [16, 128], [16, 416]]}"#),
+            [9, 31, 256, 18, 64, 0, 0, 287]
+        );
+        // Line leaving and reentering the buffered extent
+        assert_eq!(
+            clipped(
+                r#"{"type": "LineString", "coordinates": [[0, 128], [0, 416], [128, 416], [128, 128]]}"#
+            ),
+            [9, 0, 256, 10, 0, 287, 9, 256, 0, 10, 0, 288]
+        );
+    }
+
+    #[test]
+    fn clip_polygons() {
+        let mut mvt = MvtWriter::new(256, 0.0, 0.0, 256.0, 256.0).with_clip_buffer(0);
+        GeoJson(r#"{"type": "Polygon", "coordinates": [[[-64, -64], [128, -64], [128, 128], [-64, 128], [-64, -64]]]}"#)
+            .process_geom(&mut mvt)
+            .unwrap();
+        assert_eq!(
+            mvt.feature.geometry,
+            [9, 0, 512, 26, 256, 0, 0, 255, 255, 0, 15]
+        );
+
+        // Hole of a polygon outside of the extent
+        assert_eq!(
+            clipped(
+                r#"{"type": "Polygon", "coordinates": [
+                    [[300, 300], [400, 300], [400, 400], [300, 300]],
+                    [[350, 320], [380, 320], [380, 350], [350, 320]]
+                ]}"#
+            ),
+            Vec::<u32>::new()
+        );
+    }
+
+    #[test]
+    fn clip_features() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "inside"}, "geometry": {"type": "Point", "coordinates": [10, 10]}},
+            {"type": "Feature", "properties": {"name": "outside"}, "geometry": {"type": "Point", "coordinates": [-20, 10]}}
+        ]}"#;
+        let mut mvt = MvtWriter::new(256, 0.0, 0.0, 256.0, 256.0).with_clip_buffer(16);
+        GeoJson(geojson).process(&mut mvt)?;
+        let layer = mvt.into_layer();
+        assert_eq!(layer.features.len(), 1);
+        assert_eq!(layer.features[0].geometry, [9, 20, 492]);
+        // Values of dropped features are not added to the layer
+        assert_eq!(layer.values.len(), 1);
+        assert_eq!(layer.values[0].string_value.as_deref(), Some("inside"));
+        Ok(())
+    }
+
+    #[test]
+    fn layer_values_deduplicated() -> Result<()> {
+        let features: Vec<String> = (0..1000)
+            .map(|i| {
+                format!(
+                    r#"{{"type": "Feature", "properties": {{"kind": "road", "lanes": {}}}, "geometry": {{"type": "Point", "coordinates": [{i}, 1]}}}}"#,
+                    i % 2 + 1
+                )
+            })
+            .collect();
+        let geojson = format!(
+            r#"{{"type": "FeatureCollection", "features": [{}]}}"#,
+            features.join(",")
+        );
+        let mut mvt = MvtWriter::default();
+        GeoJson(&geojson).process(&mut mvt)?;
+        let layer = mvt.into_layer();
+        assert_eq!(layer.features.len(), 1000);
+        assert_eq!(layer.keys, ["kind", "lanes"]);
+        assert_eq!(layer.values.len(), 3);
+        assert_eq!(layer.values[0].string_value.as_deref(), Some("road"));
+        assert_eq!(layer.features[0].tags, [0, 0, 1, 1]);
+        assert_eq!(layer.features[1].tags, [0, 0, 1, 2]);
+        assert_eq!(layer.features[1].geometry, [9, 2, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn property_value_types() {
+        let value = |v: ColumnValue| tile::Value::from(TileValue::from(&v));
+        assert_eq!(value(ColumnValue::Int(-3)).int_value, Some(-3));
+        assert_eq!(value(ColumnValue::UShort(3)).uint_value, Some(3));
+        assert_eq!(value(ColumnValue::Float(1.5)).float_value, Some(1.5));
+        assert_eq!(value(ColumnValue::Double(1.5)).double_value, Some(1.5));
+        assert_eq!(value(ColumnValue::Bool(true)).bool_value, Some(true));
+        assert_eq!(
+            value(ColumnValue::Json(r#"{"a": [1, 2]}"#)).string_value,
+            Some(r#"{"a": [1, 2]}"#.to_string())
+        );
+        assert_eq!(
+            value(ColumnValue::Binary(&[0, 171])).string_value,
+            Some("00ab".to_string())
+        );
+    }
+
     #[test]
     #[cfg(feature = "with-geo")]
     fn geo_screen_coords_to_mvt() -> Result<()> {
@@ -512,4 +1039,15 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn tile_lonlat() -> Result<()> {
+        let mut mvt = MvtWriter::for_tile(1, 1, 0, 4096).with_crs(TileCrs::LonLat);
+        GeoJson(r#"{"type": "Point", "coordinates": [100, 40]}"#).process_geom(&mut mvt)?;
+        assert_eq!(mvt.geometry().geometry, [9, 4550, 6204]);
+        let mut mvt = MvtWriter::for_tile(1, 1, 0, 4096);
+        GeoJson(r#"{"type": "Point", "coordinates": [0, 0]}"#).process_geom(&mut mvt)?;
+        assert_eq!(mvt.geometry().geometry, [9, 0, 8192]);
+        Ok(())
+    }
 }
@@ -1,4 +1,6 @@
 use crate::mvt::tile::Value;
+use crate::ColumnValue;
+use std::fmt::Write;
 use std::hash::Hash;
 
 /// A wrapper for the MVT value types.
@@ -48,6 +50,37 @@ impl From<TileValue> for Value {
     }
 }
 
+/// Signed integers are stored as `int_value` and unsigned integers as `uint_value`.
+/// Types without MVT equivalent are stringified: JSON as text, date-times as ISO strings
+/// and binary values as lowercase hex.
+impl From<&ColumnValue<'_>> for TileValue {
+    fn from(v: &ColumnValue) -> Self {
+        match *v {
+            ColumnValue::Byte(v) => Self::Int(v.into()),
+            ColumnValue::Short(v) => Self::Int(v.into()),
+            ColumnValue::Int(v) => Self::Int(v.into()),
+            ColumnValue::Long(v) => Self::Int(v),
+            ColumnValue::UByte(v) => Self::Uint(v.into()),
+            ColumnValue::UShort(v) => Self::Uint(v.into()),
+            ColumnValue::UInt(v) => Self::Uint(v.into()),
+            ColumnValue::ULong(v) => Self::Uint(v),
+            ColumnValue::Float(v) => Self::Float(v),
+            ColumnValue::Double(v) => Self::Double(v),
+            ColumnValue::Bool(v) => Self::Bool(v),
+            ColumnValue::String(v) | ColumnValue::Json(v) | ColumnValue::DateTime(v) => {
+                Self::Str(v.to_string())
+            }
+            ColumnValue::Binary(v) => Self::Str(v.iter().fold(
+                String::with_capacity(2 * v.len()),
+                |mut hex, byte| {
+                    let _ = write!(hex, "{byte:02x}");
+                    hex
+                },
+            )),
+        }
+    }
+}
+
 impl TryFrom<Value> for TileValue {
     type Error = ();
 
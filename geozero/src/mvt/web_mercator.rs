@@ -0,0 +1,129 @@
+//! Web Mercator (EPSG:3857) tile grid.
+use std::f64::consts::PI;
+
+/// Half of the Web Mercator world extent in meters (`π * 6378137`).
+pub const WEB_MERCATOR_EXTENT: f64 = 20037508.342789244;
+
+/// Maximal latitude of the Web Mercator projection.
+pub const WEB_MERCATOR_MAX_LAT: f64 = 85.0511287798066;
+
+/// Coordinate reference system of geographic MVT coordinates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TileCrs {
+    /// Web Mercator (EPSG:3857)
+    #[default]
+    WebMercator,
+    /// Longitude/latitude in degrees (EPSG:4326)
+    LonLat,
+}
+
+/// Bounds of tile `z/x/y` in Web Mercator coordinates as `(left, bottom, right, top)`.
+///
+/// Tiles are numbered from the top left corner of the world, like in XYZ tile URLs.
+pub fn tile_bounds(z: u8, x: u32, y: u32) -> (f64, f64, f64, f64) {
+    let size = 2.0 * WEB_MERCATOR_EXTENT / 2f64.powi(z as i32);
+    let left = -WEB_MERCATOR_EXTENT + x as f64 * size;
+    let top = WEB_MERCATOR_EXTENT - y as f64 * size;
+    (left, top - size, left + size, top)
+}
+
+/// Convert longitude/latitude in degrees to Web Mercator coordinates.
+///
+/// Latitudes are clamped to [`WEB_MERCATOR_MAX_LAT`].
+pub fn lonlat_to_web_mercator(lon: f64, lat: f64) -> (f64, f64) {
+    let lat = lat.clamp(-WEB_MERCATOR_MAX_LAT, WEB_MERCATOR_MAX_LAT);
+    let x = lon * WEB_MERCATOR_EXTENT / 180.0;
+    let y = (PI / 4.0 + lat.to_radians() / 2.0).tan().ln() * WEB_MERCATOR_EXTENT / PI;
+    (x, y)
+}
+
+/// Convert Web Mercator coordinates to longitude/latitude in degrees.
+pub fn web_mercator_to_lonlat(x: f64, y: f64) -> (f64, f64) {
+    let lon = x / WEB_MERCATOR_EXTENT * 180.0;
+    let lat = (2.0 * (y / WEB_MERCATOR_EXTENT * PI).exp().atan() - PI / 2.0).to_degrees();
+    (lon, lat)
+}
+
+/// Tile position for converting integer tile coordinates to geographic coordinates.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TileTransform {
+    left: f64,
+    top: f64,
+    x_resolution: f64,
+    y_resolution: f64,
+    crs: TileCrs,
+}
+
+impl TileTransform {
+    pub(crate) fn new(z: u8, x: u32, y: u32, extent: u32, crs: TileCrs) -> Self {
+        let (left, bottom, right, top) = tile_bounds(z, x, y);
+        TileTransform {
+            left,
+            top,
+            x_resolution: (right - left) / extent as f64,
+            y_resolution: (top - bottom) / extent as f64,
+            crs,
+        }
+    }
+    /// Geographic coordinates of tile coordinates (Y pointing down).
+    pub(crate) fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        let x = self.left + x * self.x_resolution;
+        let y = self.top - y * self.y_resolution;
+        match self.crs {
+            TileCrs::WebMercator => (x, y),
+            TileCrs::LonLat => web_mercator_to_lonlat(x, y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_close((x1, y1): (f64, f64), (x2, y2): (f64, f64)) {
+        assert!((x1 - x2).abs() < 1e-6, "{x1} != {x2}");
+        assert!((y1 - y2).abs() < 1e-6, "{y1} != {y2}");
+    }
+
+    #[test]
+    fn bounds() {
+        assert_eq!(
+            tile_bounds(0, 0, 0),
+            (
+                -WEB_MERCATOR_EXTENT,
+                -WEB_MERCATOR_EXTENT,
+                WEB_MERCATOR_EXTENT,
+                WEB_MERCATOR_EXTENT
+            )
+        );
+        assert_eq!(
+            tile_bounds(1, 1, 0),
+            (0.0, 0.0, WEB_MERCATOR_EXTENT, WEB_MERCATOR_EXTENT)
+        );
+    }
+
+    #[test]
+    fn lonlat() {
+        assert_close(
+            web_mercator_to_lonlat(-WEB_MERCATOR_EXTENT, WEB_MERCATOR_EXTENT),
+            (-180.0, WEB_MERCATOR_MAX_LAT),
+        );
+        assert_close(
+            lonlat_to_web_mercator(180.0, WEB_MERCATOR_MAX_LAT),
+            (WEB_MERCATOR_EXTENT, WEB_MERCATOR_EXTENT),
+        );
+        let (x, y) = lonlat_to_web_mercator(8.5, 47.3);
+        assert_close(web_mercator_to_lonlat(x, y), (8.5, 47.3));
+    }
+
+    #[test]
+    fn tile_corner() {
+        // Top left corner of tile 10/536/358 (Zurich)
+        let transform = TileTransform::new(10, 536, 358, 4096, TileCrs::LonLat);
+        assert_close(transform.apply(0.0, 0.0), (8.4375, 47.51720069783939));
+        assert_close(
+            transform.apply(4096.0, 4096.0),
+            (8.7890625, 47.27922900257082),
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use crate::error::Result;
+use crate::{ColumnValue, CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use proj::Proj;
+
+/// Processor wrapper transforming coordinates before passing them to the inner processor.
+///
+/// Coordinates of linestrings, circular strings and multipoints are transformed in one batch.
+/// The transformation is 2D only, Z, M and T values are passed unchanged.
+///
+/// # Usage example:
+///
+/// ```
+/// use geozero::proj::CoordTransform;
+/// use geozero::{geojson::GeoJsonWriter, wkt::WktStr, GeozeroGeometry};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor =
+///     CoordTransform::new(GeoJsonWriter::new(&mut out), "EPSG:4326", "EPSG:3857").unwrap();
+/// WktStr("POINT(10 45)").process_geom(&mut processor).unwrap();
+/// ```
+pub struct CoordTransform<P: GeomProcessor> {
+    inner: P,
+    proj: Proj,
+    target_crs: Option<String>,
+    target_srid: Option<i32>,
+    /// Coordinates of the current batch
+    batch: Option<Vec<(f64, f64)>>,
+    /// Additional values and index of the batched coordinates. `None` for `xy` calls.
+    batch_extra: Vec<(Option<Extra>, usize)>,
+}
+
+type Extra = (Option<f64>, Option<f64>, Option<f64>, Option<u64>);
+
+impl<P: GeomProcessor> CoordTransform<P> {
+    /// Transform from CRS `from` to `to`, e.g. `EPSG:4326` or a PROJ string.
+    ///
+    /// Axis order is normalized to easting/northing, i.e. lon/lat for geographic CRS.
+    pub fn new(inner: P, from: &str, to: &str) -> Result<Self> {
+        let proj = Proj::new_known_crs(from, to, None)?;
+        let mut transform = Self::from_proj(inner, proj);
+        transform.target_crs = Some(to.to_string());
+        transform.target_srid = to.strip_prefix("EPSG:").and_then(|code| code.parse().ok());
+        Ok(transform)
+    }
+    /// Transform with a custom PROJ transformation.
+    ///
+    /// CRS and SRID of the input are not passed to the inner processor.
+    pub fn from_proj(inner: P, proj: Proj) -> Self {
+        CoordTransform {
+            inner,
+            proj,
+            target_crs: None,
+            target_srid: None,
+            batch: None,
+            batch_extra: Vec::new(),
+        }
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    fn batch_begin(&mut self, size: usize) {
+        self.batch = Some(Vec::with_capacity(size));
+        self.batch_extra.clear();
+    }
+    fn batch_end(&mut self) -> Result<()> {
+        let Some(mut batch) = self.batch.take() else {
+            return Ok(());
+        };
+        self.proj.convert_array(&mut batch)?;
+        for ((x, y), (extra, idx)) in batch.into_iter().zip(self.batch_extra.drain(..)) {
+            match extra {
+                Some((z, m, t, tm)) => self.inner.coordinate(x, y, z, m, t, tm, idx)?,
+                None => self.inner.xy(x, y, idx)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for CoordTransform<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, _srid: Option<i32>) -> Result<()> {
+        // The input SRID doesn't apply to transformed coordinates
+        self.inner.srid(self.target_srid)
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.inner.input_dimensions(dimensions)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        if let Some(batch) = self.batch.as_mut() {
+            batch.push((x, y));
+            self.batch_extra.push((None, idx));
+            Ok(())
+        } else {
+            let (x, y) = self.proj.convert((x, y))?;
+            self.inner.xy(x, y, idx)
+        }
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        if let Some(batch) = self.batch.as_mut() {
+            batch.push((x, y));
+            self.batch_extra.push((Some((z, m, t, tm)), idx));
+            Ok(())
+        } else {
+            let (x, y) = self.proj.convert((x, y))?;
+            self.inner.coordinate(x, y, z, m, t, tm, idx)
+        }
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.inner.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipoint_begin(size, idx)?;
+        self.batch_begin(size);
+        Ok(())
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.batch_end()?;
+        self.inner.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.linestring_begin(tagged, size, idx)?;
+        self.batch_begin(size);
+        Ok(())
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.batch_end()?;
+        self.inner.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.circularstring_begin(size, idx)?;
+        self.batch_begin(size);
+        Ok(())
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.batch_end()?;
+        self.inner.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.tin_end(idx)
+    }
+}
+
+impl<P: GeomProcessor + PropertyProcessor> PropertyProcessor for CoordTransform<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        self.inner.property(idx, name, value)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for CoordTransform<P> {
+    fn crs(&mut self, _crs: &str) -> Result<()> {
+        if let Some(crs) = &self.target_crs {
+            self.inner.crs(crs)?;
+        }
+        Ok(())
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.inner.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+    fn skip_geometry(&self) -> bool {
+        self.inner.skip_geometry()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn transform(wkt: &str) -> Result<String> {
+        let mut out: Vec<u8> = Vec::new();
+        let writer = WktWriter::with_dims(&mut out, CoordDimensions::xyz()).with_precision(2);
+        let mut processor = CoordTransform::new(writer, "EPSG:4326", "EPSG:3857")?;
+        WktStr(wkt).process_geom(&mut processor)?;
+        drop(processor);
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn web_mercator() -> Result<()> {
+        assert_eq!(transform("POINT(10 45)")?, "POINT(1113194.91 5621521.49)");
+        assert_eq!(
+            transform("LINESTRING Z (0 0 5,10 45 7)")?,
+            "LINESTRING Z (0 0 5,1113194.91 5621521.49 7)"
+        );
+        assert_eq!(
+            transform("MULTIPOINT(10 45,0 0)")?,
+            "MULTIPOINT(1113194.91 5621521.49,0 0)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn transform_errors() {
+        assert!(CoordTransform::new(WktWriter::new(Vec::new()), "EPSG:4326", "NOCRS").is_err());
+        assert!(transform("POINT(10 100)").is_err());
+    }
+}
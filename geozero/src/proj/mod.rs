@@ -0,0 +1,4 @@
+//! Coordinate transformation with [PROJ](https://proj.org).
+mod coord_transform;
+
+pub use coord_transform::*;
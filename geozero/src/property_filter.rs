@@ -0,0 +1,507 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geom_type_filter::{Event, GeomEvent, GeomType, Value};
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+use crate::stats::numeric_value;
+
+/// Properties of the current feature, as passed to the predicate of a [`PropertyFilter`].
+pub struct FilterProperties<'a>(&'a [Event]);
+
+impl FilterProperties<'_> {
+    /// Value of the property `name`
+    pub fn get(&self, name: &str) -> Option<ColumnValue<'_>> {
+        self.iter()
+            .find_map(|(colname, value)| (colname == name).then_some(value))
+    }
+    /// Value of the property `name`, if numeric
+    pub fn number(&self, name: &str) -> Option<f64> {
+        numeric_value(&self.get(name)?)
+    }
+    /// Names and values of all properties in input order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, ColumnValue<'_>)> {
+        self.0.iter().filter_map(|event| match event {
+            Event::Property(_, name, value) => Some((name.as_str(), value.as_column_value())),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    /// Outside of a feature
+    Idle,
+    /// Buffering callbacks until the properties are complete
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+/// Feature processor wrapper passing only features matching a predicate on their properties.
+///
+/// The predicate is evaluated at properties end, or at feature end for features without
+/// properties. Callbacks are buffered until then. Rejected features report
+/// [`FeatureProcessor::skip_geometry`], so readers emitting the properties first
+/// can skip decoding their geometry. Emitted features are numbered contiguously.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{GeozeroDatasource, PropertyFilter};
+///
+/// let mut geojson = GeoJson(
+///     r#"{"type": "FeatureCollection", "features": [
+///         {"type": "Feature", "properties": {"POP": 500}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+///         {"type": "Feature", "properties": {"POP": 25000}, "geometry": {"type": "Point", "coordinates": [2, 2]}}
+///     ]}"#,
+/// );
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut filter = PropertyFilter::new(GeoJsonWriter::new(&mut out), |props| {
+///     props.number("POP").is_some_and(|pop| pop > 10000.0)
+/// });
+/// geojson.process(&mut filter).unwrap();
+/// # }
+/// ```
+pub struct PropertyFilter<P: FeatureProcessor, F: FnMut(&FilterProperties) -> bool> {
+    inner: P,
+    predicate: F,
+    state: State,
+    /// Buffered callbacks of the current feature
+    events: Vec<Event>,
+    geometry: Vec<GeomEvent>,
+    /// Index of the current emitted feature
+    idx: u64,
+    /// Number of emitted features
+    next_idx: u64,
+}
+
+impl<P: FeatureProcessor, F: FnMut(&FilterProperties) -> bool> PropertyFilter<P, F> {
+    /// Pass only features for which `predicate` returns `true`.
+    pub fn new(inner: P, predicate: F) -> Self {
+        PropertyFilter {
+            inner,
+            predicate,
+            state: State::Idle,
+            events: Vec::new(),
+            geometry: Vec::new(),
+            idx: 0,
+            next_idx: 0,
+        }
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    /// Evaluate the predicate and emit the buffered callbacks of accepted features.
+    fn decide(&mut self) -> Result<()> {
+        if !(self.predicate)(&FilterProperties(&self.events)) {
+            self.state = State::Rejected;
+            return Ok(());
+        }
+        self.state = State::Accepted;
+        self.idx = self.next_idx;
+        self.next_idx += 1;
+        self.inner.feature_begin(self.idx)?;
+        for event in &self.events {
+            match event {
+                Event::FeatureId(id) => self.inner.feature_id(id.as_column_value())?,
+                Event::PropertiesBegin => self.inner.properties_begin()?,
+                Event::Property(i, name, value) => {
+                    self.inner.property(*i, name, &value.as_column_value())?;
+                }
+                Event::PropertiesEnd => self.inner.properties_end()?,
+                Event::Geometry => {
+                    self.inner.geometry_begin()?;
+                    for geom_event in &self.geometry {
+                        geom_event.replay(&mut self.inner)?;
+                    }
+                    self.inner.geometry_end()?;
+                }
+            }
+        }
+        Ok(())
+    }
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        match self.state {
+            State::Idle | State::Accepted => event.replay(&mut self.inner),
+            State::Pending => {
+                self.geometry.push(event);
+                Ok(())
+            }
+            State::Rejected => Ok(()),
+        }
+    }
+}
+
+impl<P: FeatureProcessor, F: FnMut(&FilterProperties) -> bool> FeatureProcessor
+    for PropertyFilter<P, F>
+{
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.next_idx = 0;
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.state = State::Pending;
+        self.events.clear();
+        self.geometry.clear();
+        Ok(())
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        match self.state {
+            State::Idle | State::Accepted => self.inner.feature_id(id),
+            State::Pending => {
+                self.events.push(Event::FeatureId(Value::from(&id)));
+                Ok(())
+            }
+            State::Rejected => Ok(()),
+        }
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        if self.state == State::Pending {
+            self.decide()?;
+        }
+        match std::mem::replace(&mut self.state, State::Idle) {
+            State::Accepted => self.inner.feature_end(self.idx),
+            _ => Ok(()),
+        }
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        match self.state {
+            State::Idle | State::Accepted => self.inner.properties_begin(),
+            State::Pending => {
+                self.events.push(Event::PropertiesBegin);
+                Ok(())
+            }
+            State::Rejected => Ok(()),
+        }
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        match self.state {
+            State::Idle | State::Accepted => self.inner.properties_end(),
+            State::Pending => {
+                self.events.push(Event::PropertiesEnd);
+                self.decide()
+            }
+            State::Rejected => Ok(()),
+        }
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        match self.state {
+            State::Idle | State::Accepted => self.inner.geometry_begin(),
+            State::Pending => {
+                self.events.push(Event::Geometry);
+                Ok(())
+            }
+            State::Rejected => Ok(()),
+        }
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        match self.state {
+            State::Idle | State::Accepted => self.inner.geometry_end(),
+            State::Pending | State::Rejected => Ok(()),
+        }
+    }
+    fn skip_geometry(&self) -> bool {
+        match self.state {
+            State::Rejected => true,
+            State::Accepted => self.inner.skip_geometry(),
+            State::Idle | State::Pending => false,
+        }
+    }
+}
+
+impl<P: FeatureProcessor, F: FnMut(&FilterProperties) -> bool> PropertyProcessor
+    for PropertyFilter<P, F>
+{
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        match self.state {
+            State::Idle | State::Accepted => self.inner.property(idx, name, value),
+            State::Pending => {
+                self.events
+                    .push(Event::Property(idx, name.to_string(), Value::from(value)));
+                Ok(false)
+            }
+            State::Rejected => Ok(false),
+        }
+    }
+}
+
+impl<P: FeatureProcessor, F: FnMut(&FilterProperties) -> bool> GeomProcessor
+    for PropertyFilter<P, F>
+{
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.geom_event(GeomEvent::Srid(srid))
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.geom_event(GeomEvent::InputDimensions(dimensions))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Xy(x, y, idx))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.geom_event(GeomEvent::Coordinate(x, y, z, m, t, tm, idx))
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::EmptyPoint(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Point, true, 1, idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Point, true, idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPoint, true, size, idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPoint, true, idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::LineString, tagged, size, idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::LineString, tagged, idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiLineString, true, size, idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiLineString, true, idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Polygon, tagged, size, idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Polygon, tagged, idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPolygon, true, size, idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPolygon, true, idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::GeometryCollection,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::GeometryCollection, true, idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CircularString, true, size, idx))
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CircularString, true, idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CompoundCurve, true, size, idx))
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CompoundCurve, true, idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CurvePolygon, true, size, idx))
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CurvePolygon, true, idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiCurve, true, size, idx))
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiCurve, true, idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiSurface, true, size, idx))
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiSurface, true, idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Triangle, tagged, size, idx))
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Triangle, tagged, idx))
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::PolyhedralSurface,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::PolyhedralSurface, true, idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Tin, true, size, idx))
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Tin, true, idx))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::{GeoJson, GeoJsonWriter};
+    use crate::GeozeroDatasource;
+
+    const INPUT: &str = r#"{"type": "FeatureCollection", "features": [
+        {"type": "Feature", "properties": {"name": "a", "pop": 500}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+        {"type": "Feature", "properties": {"name": "b", "pop": 25000}, "geometry": {"type": "Point", "coordinates": [2, 2]}},
+        {"type": "Feature", "properties": {"name": "c"}, "geometry": {"type": "Point", "coordinates": [3, 3]}},
+        {"type": "Feature", "properties": {"name": "d", "pop": 12000.5}, "geometry": {"type": "Point", "coordinates": [4, 4]}}
+    ]}"#;
+
+    #[derive(Default)]
+    struct Recorder {
+        features: Vec<u64>,
+        geometries: usize,
+        coords: Vec<(f64, f64)>,
+    }
+
+    impl GeomProcessor for Recorder {
+        fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+            self.coords.push((x, y));
+            Ok(())
+        }
+    }
+    impl PropertyProcessor for Recorder {}
+    impl FeatureProcessor for Recorder {
+        fn feature_begin(&mut self, idx: u64) -> Result<()> {
+            self.features.push(idx);
+            Ok(())
+        }
+        fn geometry_begin(&mut self) -> Result<()> {
+            self.geometries += 1;
+            Ok(())
+        }
+    }
+
+    fn large(props: &FilterProperties) -> bool {
+        props.number("pop").is_some_and(|pop| pop > 10000.0)
+    }
+
+    #[test]
+    fn filter_features() {
+        let mut out: Vec<u8> = Vec::new();
+        let mut filter = PropertyFilter::new(GeoJsonWriter::new(&mut out), large);
+        GeoJson(INPUT).process(&mut filter).unwrap();
+        drop(filter);
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let names: Vec<_> = json["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["properties"]["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["b", "d"]);
+        assert_eq!(
+            json["features"][1]["geometry"],
+            serde_json::json!({"type": "Point", "coordinates": [4, 4]})
+        );
+    }
+
+    #[test]
+    fn contiguous_indices() {
+        let mut filter = PropertyFilter::new(Recorder::default(), large);
+        GeoJson(INPUT).process(&mut filter).unwrap();
+        let recorder = filter.into_inner();
+        assert_eq!(recorder.features, vec![0, 1]);
+        assert_eq!(recorder.geometries, 2);
+        assert_eq!(recorder.coords, vec![(2.0, 2.0), (4.0, 4.0)]);
+    }
+
+    #[test]
+    fn string_property() {
+        let mut filter = PropertyFilter::new(Recorder::default(), |props| {
+            props.get("name") == Some(ColumnValue::String("c"))
+        });
+        GeoJson(INPUT).process(&mut filter).unwrap();
+        assert_eq!(filter.into_inner().coords, vec![(3.0, 3.0)]);
+    }
+
+    #[test]
+    fn geometry_before_properties() -> Result<()> {
+        // Callback order of readers like MVT
+        let mut filter = PropertyFilter::new(Recorder::default(), large);
+        for (pop, x) in [(25000, 1.0), (500, 2.0)] {
+            filter.feature_begin(0)?;
+            filter.geometry_begin()?;
+            filter.point_begin(0)?;
+            filter.xy(x, x, 0)?;
+            filter.point_end(0)?;
+            filter.geometry_end()?;
+            filter.properties_begin()?;
+            filter.property(0, "pop", &ColumnValue::Int(pop))?;
+            filter.properties_end()?;
+            filter.feature_end(0)?;
+        }
+        let recorder = filter.into_inner();
+        assert_eq!(recorder.features, vec![0]);
+        assert_eq!(recorder.coords, vec![(1.0, 1.0)]);
+        Ok(())
+    }
+
+    #[test]
+    fn skip_geometry() -> Result<()> {
+        let mut filter = PropertyFilter::new(Recorder::default(), large);
+        for (pop, skip) in [(500, true), (25000, false)] {
+            filter.feature_begin(0)?;
+            filter.properties_begin()?;
+            filter.property(0, "pop", &ColumnValue::Int(pop))?;
+            assert!(!filter.skip_geometry());
+            filter.properties_end()?;
+            assert_eq!(filter.skip_geometry(), skip);
+            filter.feature_end(0)?;
+            assert!(!filter.skip_geometry());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn skip_geometry_through_wrapper() -> Result<()> {
+        let filter = PropertyFilter::new(Recorder::default(), large);
+        let mut wrapper = crate::SnapToGrid::new(crate::Simplify::new(filter, 1.0), 1.0);
+        for (pop, skip) in [(500, true), (25000, false)] {
+            wrapper.feature_begin(0)?;
+            wrapper.properties_begin()?;
+            wrapper.property(0, "pop", &ColumnValue::Int(pop))?;
+            wrapper.properties_end()?;
+            assert_eq!(wrapper.skip_geometry(), skip);
+            wrapper.feature_end(0)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,272 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+
+/// Mapping function of [`PropertyMapper`].
+///
+/// Returns the new name and value of a property, or `None` for dropping it.
+pub trait PropertyMapFn:
+    for<'a> Fn(&str, &ColumnValue<'a>) -> Option<(String, ColumnValue<'a>)>
+{
+}
+
+impl<F> PropertyMapFn for F where
+    F: for<'a> Fn(&str, &ColumnValue<'a>) -> Option<(String, ColumnValue<'a>)>
+{
+}
+
+/// Processor wrapper renaming, dropping or replacing feature properties
+/// before passing them to the inner processor.
+///
+/// Geometries and features are passed unchanged. Properties are renumbered
+/// in the order they are passed on.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::{geojson::GeoJson, geojson::GeoJsonWriter, GeozeroDatasource, PropertyMapper};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = PropertyMapper::new(GeoJsonWriter::new(&mut out), |name, value| {
+///     match name {
+///         "NAME" | "Name" => Some(("name".to_string(), value.clone())),
+///         "email" => None,
+///         _ => Some((name.to_string(), value.clone())),
+///     }
+/// });
+/// GeoJson(r#"{"type": "Feature", "properties": {"NAME": "Bern", "email": "x@example.com"}, "geometry": {"type": "Point", "coordinates": [7.4, 46.9]}}"#)
+///     .process(&mut processor)
+///     .unwrap();
+/// drop(processor);
+/// assert!(String::from_utf8(out).unwrap().contains(r#""properties": {"name": "Bern"}"#));
+/// # }
+/// ```
+pub struct PropertyMapper<P: FeatureProcessor, F: PropertyMapFn> {
+    inner: P,
+    mapper: F,
+    /// Index of the next passed property
+    idx: usize,
+}
+
+impl<P: FeatureProcessor, F: PropertyMapFn> PropertyMapper<P, F> {
+    /// Map properties with `mapper` before passing them to `inner`.
+    pub fn new(inner: P, mapper: F) -> Self
+    where
+        F: for<'a> Fn(&str, &ColumnValue<'a>) -> Option<(String, ColumnValue<'a>)>,
+    {
+        PropertyMapper {
+            inner,
+            mapper,
+            idx: 0,
+        }
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+impl<P: FeatureProcessor, F: PropertyMapFn> GeomProcessor for PropertyMapper<P, F> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.inner.srid(srid)
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.inner.input_dimensions(dimensions)
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.inner.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.inner.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.inner.empty_point(idx)
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_begin(idx)
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.point_end(idx)
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipoint_begin(size, idx)
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipoint_end(idx)
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.linestring_begin(tagged, size, idx)
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.linestring_end(tagged, idx)
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multilinestring_begin(size, idx)
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multilinestring_end(idx)
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.polygon_begin(tagged, size, idx)
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.polygon_end(tagged, idx)
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multipolygon_begin(size, idx)
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multipolygon_end(idx)
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_begin(size, idx)
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.geometrycollection_end(idx)
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.circularstring_begin(size, idx)
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.circularstring_end(idx)
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_begin(size, idx)
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.compoundcurve_end(idx)
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_begin(size, idx)
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.curvepolygon_end(idx)
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multicurve_begin(size, idx)
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multicurve_end(idx)
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.multisurface_begin(size, idx)
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.multisurface_end(idx)
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.inner.triangle_begin(tagged, size, idx)
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.inner.triangle_end(tagged, idx)
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_begin(size, idx)
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.polyhedralsurface_end(idx)
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.inner.tin_begin(size, idx)
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.inner.tin_end(idx)
+    }
+}
+
+impl<P: FeatureProcessor, F: PropertyMapFn> PropertyProcessor for PropertyMapper<P, F> {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let Some((name, value)) = (self.mapper)(name, value) else {
+            return Ok(false);
+        };
+        let idx = self.idx;
+        self.idx += 1;
+        self.inner.property(idx, &name, &value)
+    }
+}
+
+impl<P: FeatureProcessor, F: PropertyMapFn> FeatureProcessor for PropertyMapper<P, F> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.inner.dataset_end()
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_begin(idx)
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        self.inner.feature_id(id)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.inner.feature_end(idx)
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.idx = 0;
+        self.inner.properties_begin()
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.inner.properties_end()
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.inner.geometry_begin()
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.inner.geometry_end()
+    }
+    fn skip_geometry(&self) -> bool {
+        self.inner.skip_geometry()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonWriter;
+    use crate::test_util::process_geojson;
+
+    #[test]
+    fn rename_and_drop() {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"NAME": "Bern", "email": "a@example.com", "pop": 1}, "geometry": {"type": "Point", "coordinates": [7.4, 46.9]}},
+            {"type": "Feature", "properties": {"Name": "Zurich", "email": "b@example.com"}, "geometry": {"type": "Point", "coordinates": [8.5, 47.4]}}
+        ]}"#;
+        let out = process_geojson(geojson, |out| {
+            PropertyMapper::new(GeoJsonWriter::new(out), |name, value| match name {
+                "NAME" | "Name" => Some(("name".to_string(), value.clone())),
+                "email" => None,
+                "pop" => Some(("population".to_string(), ColumnValue::Long(1000))),
+                _ => Some((name.to_string(), value.clone())),
+            })
+        });
+        assert_eq!(
+            out,
+            r#"{
+"type": "FeatureCollection",
+"features": [{"type": "Feature", "properties": {"name": "Bern", "population": 1000}, "geometry": {"type": "Point", "coordinates": [7.4,46.9]}},
+{"type": "Feature", "properties": {"name": "Zurich"}, "geometry": {"type": "Point", "coordinates": [8.5,47.4]}}]}"#
+        );
+    }
+}
@@ -4,7 +4,7 @@ use std::fmt;
 use std::hash::BuildHasher;
 
 /// Feature property value.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Clone, Debug)]
 pub enum ColumnValue<'a> {
     Byte(i8),
     UByte(u8),
@@ -0,0 +1,158 @@
+use crate::error::Result;
+use crate::geom_event_processor::{geom_event_processor, Coord, LineBuffer, LineEvent};
+use crate::geom_type_filter::GeomEvent;
+use crate::geometry_processor::GeomProcessor;
+
+/// Processor wrapper simplifying linestrings and polygon rings with the
+/// Ramer–Douglas–Peucker algorithm before passing them to the inner processor.
+///
+/// Points and multipoints are passed unchanged. Simplified lines keep their end points,
+/// rings stay closed and keep at least 4 points. Rings which would collapse are passed unchanged.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, wkt::WktWriter, GeozeroGeometry, Simplify};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = Simplify::new(WktWriter::new(&mut out), 0.5);
+/// WktStr("LINESTRING(0 0,1 0.1,2 0,3 5)").process_geom(&mut processor).unwrap();
+/// drop(processor);
+/// assert_eq!(String::from_utf8(out).unwrap(), "LINESTRING(0 0,2 0,3 5)");
+/// # }
+/// ```
+pub struct Simplify<P: GeomProcessor> {
+    inner: P,
+    tolerance: f64,
+    lines: LineBuffer,
+}
+
+impl<P: GeomProcessor> Simplify<P> {
+    /// Simplify with maximal distance `tolerance` of removed points to the simplified line.
+    pub fn new(inner: P, tolerance: f64) -> Self {
+        Simplify {
+            inner,
+            tolerance,
+            lines: LineBuffer::new(false),
+        }
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+/// Flags of the points kept by Douglas–Peucker simplification.
+pub(crate) fn douglas_peucker(line: &[Coord], tolerance: f64) -> Vec<bool> {
+    let n = line.len();
+    if n <= 2 {
+        return vec![true; n];
+    }
+    let mut keep = vec![false; n];
+    keep[0] = true;
+    keep[n - 1] = true;
+    let mut stack = vec![(0, n - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let mut max_dist = 0.0;
+        let mut max_idx = first;
+        for (i, coord) in line.iter().enumerate().take(last).skip(first + 1) {
+            let dist = segment_distance(coord, &line[first], &line[last]);
+            if dist > max_dist {
+                max_dist = dist;
+                max_idx = i;
+            }
+        }
+        if max_dist > tolerance {
+            keep[max_idx] = true;
+            stack.push((first, max_idx));
+            stack.push((max_idx, last));
+        }
+    }
+    keep
+}
+
+/// Distance of `p` to the segment from `a` to `b`.
+fn segment_distance(p: &Coord, a: &Coord, b: &Coord) -> f64 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len2 = dx * dx + dy * dy;
+    if len2 == 0.0 {
+        return (p.x - a.x).hypot(p.y - a.y);
+    }
+    let t = (((p.x - a.x) * dx + (p.y - a.y) * dy) / len2).clamp(0.0, 1.0);
+    (p.x - (a.x + t * dx)).hypot(p.y - (a.y + t * dy))
+}
+
+impl<P: GeomProcessor> Simplify<P> {
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        match self.lines.push(event) {
+            LineEvent::Pass(event) => event.replay(&mut self.inner),
+            LineEvent::Buffered => Ok(()),
+            LineEvent::Line(mut line) => {
+                let mut keep = douglas_peucker(&line.coords, self.tolerance);
+                if line.ring && keep.iter().filter(|k| **k).count() < 4 {
+                    keep = vec![true; line.coords.len()];
+                }
+                let mut keep = keep.into_iter();
+                line.coords.retain(|_| keep.next().unwrap_or(true));
+                line.replay(&mut self.inner)
+            }
+        }
+    }
+}
+
+geom_event_processor!(Simplify);
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::GeozeroGeometry;
+
+    fn simplify(wkt: &str, tolerance: f64) -> String {
+        let mut out: Vec<u8> = Vec::new();
+        let mut processor = Simplify::new(WktWriter::new(&mut out), tolerance);
+        WktStr(wkt).process_geom(&mut processor).unwrap();
+        drop(processor);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn long_line() {
+        // Zigzag line up to (500, 500) and back down to (999, 1)
+        let coords = (0..1000)
+            .map(|i| {
+                let y = if i < 500 { i } else { 1000 - i };
+                let wiggle = if i % 2 == 0 { 0.0 } else { 0.1 };
+                format!("{i} {}", f64::from(y) + wiggle)
+            })
+            .collect::<Vec<_>>();
+        let wkt = format!("LINESTRING({})", coords.join(","));
+        assert_eq!(simplify(&wkt, 1.0), "LINESTRING(0 0,500 500,999 1.1)");
+        // Tolerance below the wiggle keeps all points
+        assert_eq!(simplify(&wkt, 0.01), wkt);
+    }
+
+    #[test]
+    fn rings() {
+        assert_eq!(
+            simplify("POLYGON((0 0,5 0.1,10 0,10 10,0 10,0 0))", 1.0),
+            "POLYGON((0 0,10 0,10 10,0 10,0 0))"
+        );
+        // Ring collapsing to less than 4 points is kept
+        assert_eq!(
+            simplify("POLYGON((0 0,1 0,1 1,0 0))", 5.0),
+            "POLYGON((0 0,1 0,1 1,0 0))"
+        );
+        assert_eq!(
+            simplify("MULTILINESTRING((0 0,1 0.1,2 0),(0 0,0 5))", 1.0),
+            "MULTILINESTRING((0 0,2 0),(0 0,0 5))"
+        );
+        assert_eq!(
+            simplify("MULTIPOINT(0 0,1 0.1,2 0)", 1.0),
+            "MULTIPOINT(0 0,1 0.1,2 0)"
+        );
+    }
+}
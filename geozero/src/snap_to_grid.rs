@@ -0,0 +1,165 @@
+use crate::error::Result;
+use crate::geom_event_processor::{geom_event_processor, LineBuffer, LineEvent};
+use crate::geom_type_filter::GeomEvent;
+use crate::geometry_processor::GeomProcessor;
+
+/// Processor wrapper snapping x/y coordinates to a regular grid before passing them
+/// to the inner processor.
+///
+/// Coordinates are rounded to the nearest multiple of the grid size. Consecutive duplicate
+/// vertices of linestrings and rings, resulting from snapping, are removed by default.
+/// Rings stay closed. Rings collapsing below 4 points are passed with all snapped points
+/// and counted in [`SnapToGrid::degenerate_rings`].
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, wkt::WktWriter, GeozeroGeometry, SnapToGrid};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = SnapToGrid::new(WktWriter::new(&mut out), 0.5);
+/// WktStr("LINESTRING(0.1 0.1,0.2 -0.1,1.3 0.9)").process_geom(&mut processor).unwrap();
+/// drop(processor);
+/// assert_eq!(String::from_utf8(out).unwrap(), "LINESTRING(0 0,1.5 1)");
+/// # }
+/// ```
+pub struct SnapToGrid<P: GeomProcessor> {
+    inner: P,
+    /// Inverse grid size
+    scale: f64,
+    remove_duplicates: bool,
+    lines: LineBuffer,
+    degenerate_rings: usize,
+}
+
+impl<P: GeomProcessor> SnapToGrid<P> {
+    /// Snap to a grid with cell size `grid_size` (e.g. `0.0001` degrees).
+    pub fn new(inner: P, grid_size: f64) -> Self {
+        SnapToGrid {
+            inner,
+            scale: 1.0 / grid_size,
+            remove_duplicates: true,
+            lines: LineBuffer::new(false),
+            degenerate_rings: 0,
+        }
+    }
+    /// Remove consecutive duplicate vertices after snapping (default: `true`).
+    pub fn with_remove_duplicates(mut self, remove_duplicates: bool) -> Self {
+        self.remove_duplicates = remove_duplicates;
+        self
+    }
+    /// Number of rings which collapsed below 4 points.
+    pub fn degenerate_rings(&self) -> usize {
+        self.degenerate_rings
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    fn snap(&self, val: f64) -> f64 {
+        // Dividing by the inverse grid size avoids artifacts like 0.30000000000000004
+        (val * self.scale).round() / self.scale
+    }
+}
+
+impl<P: GeomProcessor> SnapToGrid<P> {
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        let event = match event {
+            GeomEvent::Xy(x, y, idx) => GeomEvent::Xy(self.snap(x), self.snap(y), idx),
+            GeomEvent::Coordinate(x, y, z, m, t, tm, idx) => {
+                GeomEvent::Coordinate(self.snap(x), self.snap(y), z, m, t, tm, idx)
+            }
+            event => event,
+        };
+        match self.lines.push(event) {
+            LineEvent::Pass(event) => event.replay(&mut self.inner),
+            LineEvent::Buffered => Ok(()),
+            LineEvent::Line(mut line) => {
+                let coords = &line.coords;
+                let mut keep = vec![true; coords.len()];
+                if self.remove_duplicates {
+                    for i in 1..coords.len() {
+                        keep[i] = coords[i].x != coords[i - 1].x || coords[i].y != coords[i - 1].y;
+                    }
+                }
+                if line.ring && keep.iter().filter(|k| **k).count() < 4 {
+                    // Collapsed ring is passed with all snapped points
+                    self.degenerate_rings += 1;
+                    keep = vec![true; coords.len()];
+                }
+                let mut keep = keep.into_iter();
+                line.coords.retain(|_| keep.next().unwrap_or(true));
+                line.replay(&mut self.inner)
+            }
+        }
+    }
+}
+
+geom_event_processor!(SnapToGrid);
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::test_util::{process_wkt, process_wkt_with};
+    use crate::wkt::WktWriter;
+
+    fn snap(wkt: &str, grid_size: f64) -> (String, usize) {
+        process_wkt_with(
+            wkt,
+            |out| SnapToGrid::new(WktWriter::new(out), grid_size),
+            SnapToGrid::degenerate_rings,
+        )
+    }
+
+    #[test]
+    fn coordinates() {
+        assert_eq!(
+            snap("POINT(1.23456 -7.89012)", 0.001).0,
+            "POINT(1.235 -7.89)"
+        );
+        assert_eq!(
+            snap("MULTIPOINT(0.1 0.1,0.2 0.2)", 1.0).0,
+            "MULTIPOINT(0 0,0 0)"
+        );
+        assert_eq!(
+            snap("LINESTRING(0.0001 0.0002,0.1004 0.2004,0.3 0.3)", 0.001).0,
+            "LINESTRING(0 0,0.1 0.2,0.3 0.3)"
+        );
+    }
+
+    #[test]
+    fn duplicates() {
+        assert_eq!(
+            snap("LINESTRING(0 0,0.2 0.1,0.9 1.1,1 1,2 2)", 1.0).0,
+            "LINESTRING(0 0,1 1,2 2)"
+        );
+        assert_eq!(
+            process_wkt("LINESTRING(0 0,0.2 0.1,1 1)", |out| {
+                SnapToGrid::new(WktWriter::new(out), 1.0).with_remove_duplicates(false)
+            }),
+            "LINESTRING(0 0,0 0,1 1)"
+        );
+    }
+
+    #[test]
+    fn rings() {
+        assert_eq!(
+            snap("POLYGON((0.1 0,10 0.2,9.9 0.1,10.1 9.8,0 10,0.1 0))", 1.0),
+            ("POLYGON((0 0,10 0,10 10,0 10,0 0))".to_string(), 0)
+        );
+        // Collapsed ring is reported
+        assert_eq!(
+            snap(
+                "POLYGON((0 0,10 0,10 10,0 10,0 0),(1 1,1.2 1,1.2 1.2,1 1))",
+                1.0
+            ),
+            (
+                "POLYGON((0 0,10 0,10 10,0 10,0 0),(1 1,1 1,1 1,1 1))".to_string(),
+                1
+            )
+        );
+    }
+}
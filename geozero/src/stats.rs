@@ -0,0 +1,386 @@
+use crate::bbox::GeometryBbox;
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
+
+/// Statistics of a processed dataset, see [`DatasetStats`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DatasetReport {
+    pub feature_count: u64,
+    /// Number of geometries by geometry type name (e.g. `MultiPolygon`)
+    pub geometry_types: BTreeMap<&'static str, u64>,
+    pub coord_count: u64,
+    /// `[minx, miny, maxx, maxy]`, `None` if no coordinates were processed
+    pub bbox: Option<[f64; 4]>,
+    /// Property statistics in order of first occurrence
+    pub properties: Vec<PropertyReport>,
+}
+
+/// Statistics of a single property, see [`DatasetStats`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyReport {
+    pub name: String,
+    /// Number of features with a value
+    pub count: u64,
+    /// Minimum and maximum of numeric values
+    pub range: Option<(f64, f64)>,
+    /// Number of distinct values, estimated if `distinct_exact` is false
+    pub distinct: u64,
+    pub distinct_exact: bool,
+}
+
+/// Processor collecting statistics for profiling a dataset.
+///
+/// Distinct values are counted exactly up to a limit. Above the limit, the count
+/// is estimated from the smallest value hashes (k minimum values), so memory usage
+/// stays bounded.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::{geojson::GeoJson, DatasetStats, GeozeroDatasource};
+///
+/// let mut stats = DatasetStats::new();
+/// GeoJson(r#"{"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "Point", "coordinates": [1, 2]}}"#)
+///     .process(&mut stats)
+///     .unwrap();
+/// let report = stats.report();
+/// assert_eq!(report.geometry_types["Point"], 1);
+/// assert_eq!(report.bbox, Some([1.0, 2.0, 1.0, 2.0]));
+/// # }
+/// ```
+pub struct DatasetStats {
+    distinct_limit: usize,
+    feature_count: u64,
+    geometry_types: BTreeMap<&'static str, u64>,
+    coord_count: u64,
+    bbox: GeometryBbox,
+    properties: Vec<PropertyStats>,
+    /// Nesting depth of the current geometry
+    depth: usize,
+}
+
+struct PropertyStats {
+    name: String,
+    count: u64,
+    range: Option<(f64, f64)>,
+    /// Smallest value hashes, at most `distinct_limit`
+    hashes: BTreeSet<u64>,
+    /// More distinct values than `distinct_limit` were seen
+    saturated: bool,
+}
+
+impl PropertyStats {
+    fn new(name: &str) -> Self {
+        PropertyStats {
+            name: name.to_string(),
+            count: 0,
+            range: None,
+            hashes: BTreeSet::new(),
+            saturated: false,
+        }
+    }
+    fn add(&mut self, value: &ColumnValue, distinct_limit: usize) {
+        self.count += 1;
+        if let Some(v) = numeric_value(value) {
+            self.range = Some(match self.range {
+                Some((min, max)) => (min.min(v), max.max(v)),
+                None => (v, v),
+            });
+        }
+        let mut hasher = DefaultHasher::new();
+        value.to_string().hash(&mut hasher);
+        self.hashes.insert(hasher.finish());
+        if self.hashes.len() > distinct_limit {
+            self.hashes.pop_last();
+            self.saturated = true;
+        }
+    }
+    fn distinct(&self) -> u64 {
+        match self.hashes.last() {
+            Some(&max_hash) if self.saturated => {
+                // k minimum values estimate
+                let k = self.hashes.len() as f64;
+                ((k - 1.0) * (u64::MAX as f64 / max_hash as f64)).round() as u64
+            }
+            _ => self.hashes.len() as u64,
+        }
+    }
+}
+
+pub(crate) fn numeric_value(value: &ColumnValue) -> Option<f64> {
+    match *value {
+        ColumnValue::Byte(v) => Some(v as f64),
+        ColumnValue::UByte(v) => Some(v as f64),
+        ColumnValue::Short(v) => Some(v as f64),
+        ColumnValue::UShort(v) => Some(v as f64),
+        ColumnValue::Int(v) => Some(v as f64),
+        ColumnValue::UInt(v) => Some(v as f64),
+        ColumnValue::Long(v) => Some(v as f64),
+        ColumnValue::ULong(v) => Some(v as f64),
+        ColumnValue::Float(v) => Some(v as f64),
+        ColumnValue::Double(v) => Some(v),
+        _ => None,
+    }
+}
+
+impl Default for DatasetStats {
+    fn default() -> Self {
+        DatasetStats {
+            distinct_limit: 1000,
+            feature_count: 0,
+            geometry_types: BTreeMap::new(),
+            coord_count: 0,
+            bbox: GeometryBbox::new(),
+            properties: Vec::new(),
+            depth: 0,
+        }
+    }
+}
+
+impl DatasetStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Maximal number of distinct values counted exactly per property (default: 1000).
+    pub fn with_distinct_limit(mut self, limit: usize) -> Self {
+        self.distinct_limit = limit.max(2);
+        self
+    }
+    /// Statistics of the features processed so far.
+    pub fn report(&self) -> DatasetReport {
+        DatasetReport {
+            feature_count: self.feature_count,
+            geometry_types: self.geometry_types.clone(),
+            coord_count: self.coord_count,
+            bbox: self.bbox.bounds(),
+            properties: self
+                .properties
+                .iter()
+                .map(|p| PropertyReport {
+                    name: p.name.clone(),
+                    count: p.count,
+                    range: p.range,
+                    distinct: p.distinct(),
+                    distinct_exact: !p.saturated,
+                })
+                .collect(),
+        }
+    }
+    fn begin(&mut self, geometry_type: &'static str) -> Result<()> {
+        if self.depth == 0 {
+            *self.geometry_types.entry(geometry_type).or_default() += 1;
+        }
+        self.depth += 1;
+        Ok(())
+    }
+    fn end(&mut self) -> Result<()> {
+        self.depth = self.depth.saturating_sub(1);
+        Ok(())
+    }
+}
+
+impl GeomProcessor for DatasetStats {
+    fn dimensions(&self) -> CoordDimensions {
+        CoordDimensions::xyz()
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.coord_count += 1;
+        self.bbox.xy(x, y, idx)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.coord_count += 1;
+        self.bbox.coordinate(x, y, z, m, t, tm, idx)
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.begin("Point")?;
+        self.end()
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.begin("Point")
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("MultiPoint")
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn linestring_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("LineString")
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("MultiLineString")
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("Polygon")
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multipolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("MultiPolygon")
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn geometrycollection_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("GeometryCollection")
+    }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("CircularString")
+    }
+    fn circularstring_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("CompoundCurve")
+    }
+    fn compoundcurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("CurvePolygon")
+    }
+    fn curvepolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("MultiCurve")
+    }
+    fn multicurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("MultiSurface")
+    }
+    fn multisurface_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn triangle_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("Triangle")
+    }
+    fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn polyhedralsurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("PolyhedralSurface")
+    }
+    fn polyhedralsurface_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+    fn tin_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.begin("Tin")
+    }
+    fn tin_end(&mut self, _idx: usize) -> Result<()> {
+        self.end()
+    }
+}
+
+impl PropertyProcessor for DatasetStats {
+    fn property(&mut self, _idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        let distinct_limit = self.distinct_limit;
+        let stats = match self.properties.iter().position(|p| p.name == name) {
+            Some(pos) => &mut self.properties[pos],
+            None => {
+                self.properties.push(PropertyStats::new(name));
+                self.properties.last_mut().unwrap()
+            }
+        };
+        stats.add(value, distinct_limit);
+        Ok(false)
+    }
+}
+
+impl FeatureProcessor for DatasetStats {
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.feature_count += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::GeozeroDatasource;
+
+    #[test]
+    fn report() -> Result<()> {
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"name": "a", "pop": 10}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {"name": "b", "pop": 2.5}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [4, 0], [4, 4], [0, 0]]]}},
+            {"type": "Feature", "properties": {"name": "a"}, "geometry": {"type": "GeometryCollection", "geometries": [{"type": "Point", "coordinates": [-1, 5]}]}}
+        ]}"#;
+        let mut stats = DatasetStats::new();
+        GeoJson(geojson).process(&mut stats)?;
+        let report = stats.report();
+        assert_eq!(report.feature_count, 3);
+        assert_eq!(
+            report.geometry_types,
+            BTreeMap::from([("GeometryCollection", 1), ("Point", 1), ("Polygon", 1)])
+        );
+        assert_eq!(report.coord_count, 6);
+        assert_eq!(report.bbox, Some([-1.0, 0.0, 4.0, 5.0]));
+        assert_eq!(
+            report.properties,
+            vec![
+                PropertyReport {
+                    name: "name".to_string(),
+                    count: 3,
+                    range: None,
+                    distinct: 2,
+                    distinct_exact: true,
+                },
+                PropertyReport {
+                    name: "pop".to_string(),
+                    count: 2,
+                    range: Some((2.5, 10.0)),
+                    distinct: 2,
+                    distinct_exact: true,
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn approximate_distinct() -> Result<()> {
+        let mut stats = DatasetStats::new().with_distinct_limit(256);
+        for i in 0..20_000 {
+            stats.property(0, "id", &ColumnValue::Int(i % 10_000))?;
+        }
+        let report = stats.report();
+        let id = &report.properties[0];
+        assert!(!id.distinct_exact);
+        assert!((7_000..13_000).contains(&id.distinct), "{}", id.distinct);
+        Ok(())
+    }
+}
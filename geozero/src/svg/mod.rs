@@ -1,6 +1,6 @@
 //! SVG conversions.
 mod writer;
-pub use writer::SvgWriter;
+pub use writer::{SvgFeatureClass, SvgStyle, SvgWriter};
 
 /// SVG String.
 pub struct SvgString(pub String);
@@ -41,7 +41,7 @@ pub(crate) mod conversion {
     impl<T: GeozeroGeometry> ToSvg for T {
         fn to_svg(&self) -> Result<String> {
             let mut svg_data: Vec<u8> = Vec::new();
-            let mut svg = SvgWriter::new(&mut svg_data, false);
+            let mut svg = SvgWriter::with_invert_y(&mut svg_data, false);
             self.process_geom(&mut svg)?;
             String::from_utf8(svg_data).map_err(|_| {
                 crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
@@ -49,7 +49,7 @@ pub(crate) mod conversion {
         }
         fn to_svg_document(&self) -> Result<String> {
             let mut svg_data: Vec<u8> = Vec::new();
-            let mut svg = SvgWriter::new(&mut svg_data, false);
+            let mut svg = SvgWriter::with_invert_y(&mut svg_data, false);
             // svg.set_dimensions(bbox.get(0), bbox.get(1), bbox.get(2), bbox.get(3), 800, 400);
             svg.dataset_begin(None)?;
             svg.feature_begin(0)?;
@@ -71,7 +71,7 @@ pub(crate) mod conversion {
     impl<T: GeozeroDatasource> ProcessToSvg for T {
         fn to_svg(&mut self) -> Result<String> {
             let mut svg_data: Vec<u8> = Vec::new();
-            let mut svg = SvgWriter::new(&mut svg_data, false);
+            let mut svg = SvgWriter::with_invert_y(&mut svg_data, false);
             self.process(&mut svg)?;
             String::from_utf8(svg_data).map_err(|_| {
                 crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
@@ -90,7 +90,7 @@ mod wkb {
     impl FromWkb for SvgString {
         fn from_wkb<R: Read>(rdr: &mut R, dialect: WkbDialect) -> Result<Self> {
             let mut svg_data: Vec<u8> = Vec::new();
-            let mut writer = SvgWriter::new(&mut svg_data, false);
+            let mut writer = SvgWriter::with_invert_y(&mut svg_data, false);
             crate::wkb::process_wkb_type_geom(rdr, &mut writer, dialect)?;
             let svg = String::from_utf8(svg_data).map_err(|_| {
                 crate::error::GeozeroError::Geometry("Invalid UTF-8 encoding".to_string())
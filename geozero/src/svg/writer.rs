@@ -1,24 +1,127 @@
 use crate::error::Result;
-use crate::{FeatureProcessor, GeomProcessor, PropertyProcessor};
+use crate::{ColumnValue, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::io::Write;
 
+/// Presentation attributes applied to every emitted SVG element.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SvgStyle {
+    /// `stroke` color, e.g. `black` or `#ff0000`.
+    pub stroke: Option<String>,
+    /// `stroke-width`.
+    pub stroke_width: Option<f64>,
+    /// `fill` color.
+    pub fill: Option<String>,
+    /// `opacity` between 0 and 1.
+    pub opacity: Option<f64>,
+}
+
+impl SvgStyle {
+    fn attributes(&self) -> String {
+        let mut attrs = String::new();
+        if let Some(stroke) = &self.stroke {
+            let _ = write!(attrs, r#" stroke="{}""#, escape_attr(stroke));
+        }
+        if let Some(stroke_width) = self.stroke_width {
+            let _ = write!(attrs, r#" stroke-width="{stroke_width}""#);
+        }
+        if let Some(fill) = &self.fill {
+            let _ = write!(attrs, r#" fill="{}""#, escape_attr(fill));
+        }
+        if let Some(opacity) = self.opacity {
+            let _ = write!(attrs, r#" opacity="{opacity}""#);
+        }
+        attrs
+    }
+}
+
+/// Function deriving a CSS class from feature properties.
+pub type SvgFeatureClass = fn(&HashMap<String, String>) -> String;
+
 /// SVG writer.
+///
+/// World coordinates are written unchanged (Y negated when flipping) and mapped into the
+/// document by the `viewBox`. When the aspect ratio of `width` and `height` doesn't match
+/// the viewBox, SVG viewers apply the default `preserveAspectRatio="xMidYMid meet"`:
+/// the data is scaled uniformly to fit and centered, leaving empty margins instead of
+/// distorting the geometries.
 pub struct SvgWriter<W: Write> {
     out: W,
     invert_y: bool,
+    /// World extent (xmin, ymin, xmax, ymax) mapped into the viewBox
     view_box: Option<(f64, f64, f64, f64)>,
     size: Option<(u32, u32)>,
+    /// Computed from the data when no view box is set
+    extent: Option<(f64, f64, f64, f64)>,
+    dataset_name: Option<String>,
+    /// Document body, buffered until the data extent is known
+    doc_buf: Option<Vec<u8>>,
+    style_attrs: String,
+    point_radius: f64,
+    feature_class: Option<SvgFeatureClass>,
+    feature_props: HashMap<String, String>,
+    /// Output of the current feature, buffered until its class is known
+    feature_buf: Option<Vec<u8>>,
+    in_points: bool,
 }
 
 impl<W: Write> SvgWriter<W> {
-    pub fn new(out: W, invert_y: bool) -> Self {
+    /// SVG document with `width` x `height` pixels and north up.
+    ///
+    /// Without [`with_viewbox`](Self::with_viewbox), the output is buffered
+    /// until the end of the dataset to compute the viewBox from the data extent.
+    pub fn new(out: W, width: u32, height: u32) -> Self {
+        let mut writer = Self::with_invert_y(out, true);
+        writer.size = Some((width, height));
+        writer
+    }
+    /// SVG writer without document size and viewBox, optionally negating Y coordinates.
+    pub fn with_invert_y(out: W, invert_y: bool) -> Self {
         SvgWriter {
             out,
             invert_y,
             view_box: None,
             size: None,
+            extent: None,
+            dataset_name: None,
+            doc_buf: None,
+            style_attrs: String::new(),
+            point_radius: 1.0,
+            feature_class: None,
+            feature_props: HashMap::new(),
+            feature_buf: None,
+            in_points: false,
         }
     }
+    /// World extent mapped into the viewBox.
+    pub fn with_viewbox(mut self, min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Self {
+        self.view_box = Some((min_x, min_y, max_x, max_y));
+        self
+    }
+    /// Apply presentation attributes to each emitted `<path>` and `<circle>`.
+    pub fn with_style(mut self, style: SvgStyle) -> Self {
+        self.style_attrs = style.attributes();
+        self
+    }
+    /// Radius of the `<circle>` elements emitted for points (default: 1).
+    pub fn with_point_radius(mut self, radius: f64) -> Self {
+        self.point_radius = radius;
+        self
+    }
+    /// Wrap each feature into a group with a CSS class derived from its properties.
+    ///
+    /// ```
+    /// use geozero::svg::SvgWriter;
+    ///
+    /// let mut out: Vec<u8> = Vec::new();
+    /// let svg = SvgWriter::new(&mut out, 800, 600)
+    ///     .with_feature_class(|props| format!("type-{}", props["type"]));
+    /// ```
+    pub fn with_feature_class(mut self, feature_class: SvgFeatureClass) -> Self {
+        self.feature_class = Some(feature_class);
+        self
+    }
     pub fn set_dimensions(
         &mut self,
         xmin: f64,
@@ -28,17 +131,27 @@ impl<W: Write> SvgWriter<W> {
         width: u32,
         height: u32,
     ) {
-        self.view_box = if self.invert_y {
-            Some((xmin, -ymax, xmax, -ymin))
-        } else {
-            Some((xmin, ymin, xmax, ymax))
-        };
+        self.view_box = Some((xmin, ymin, xmax, ymax));
         self.size = Some((width, height));
     }
-}
-
-impl<W: Write> FeatureProcessor for SvgWriter<W> {
-    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+    fn compute_extent(&self) -> bool {
+        self.view_box.is_none() && self.size.is_some()
+    }
+    fn write_doc(&mut self, data: &[u8]) -> Result<()> {
+        match self.doc_buf.as_mut() {
+            Some(buf) => buf.extend_from_slice(data),
+            None => self.out.write_all(data)?,
+        }
+        Ok(())
+    }
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        match self.feature_buf.as_mut() {
+            Some(buf) => buf.extend_from_slice(data),
+            None => self.write_doc(data)?,
+        }
+        Ok(())
+    }
+    fn write_header(&mut self, name: Option<&str>) -> Result<()> {
         self.out.write_all(
             br#"<?xml version="1.0"?>
 <svg xmlns="http://www.w3.org/2000/svg" version="1.2" baseProfile="tiny" "#,
@@ -47,9 +160,10 @@ impl<W: Write> FeatureProcessor for SvgWriter<W> {
             self.out
                 .write_all(format!(r#"width="{width}" height="{height}" "#).as_bytes())?;
         }
-        if let Some((xmin, ymin, xmax, ymax)) = self.view_box {
+        if let Some((xmin, ymin, xmax, ymax)) = self.view_box.or(self.extent) {
             let dx = xmax - xmin;
             let dy = ymax - ymin;
+            let ymin = if self.invert_y { -ymax } else { ymin };
             self.out
                 .write_all(format!(r#"viewBox="{xmin} {ymin} {dx} {dy}" "#).as_bytes())?;
         }
@@ -63,74 +177,138 @@ impl<W: Write> FeatureProcessor for SvgWriter<W> {
         self.out.write_all(br#"">"#)?;
         Ok(())
     }
+    fn path_begin(&mut self) -> Result<()> {
+        let path = format!(r#"<path{} d=""#, self.style_attrs);
+        self.write(path.as_bytes())
+    }
+}
+
+impl<W: Write> FeatureProcessor for SvgWriter<W> {
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        if self.compute_extent() {
+            self.extent = None;
+            self.dataset_name = name.map(str::to_string);
+            self.doc_buf = Some(Vec::new());
+            Ok(())
+        } else {
+            self.write_header(name)
+        }
+    }
     fn dataset_end(&mut self) -> Result<()> {
+        if let Some(buf) = self.doc_buf.take() {
+            let name = self.dataset_name.take();
+            self.write_header(name.as_deref())?;
+            self.out.write_all(&buf)?;
+        }
         self.out.write_all(b"\n</g>\n</svg>")?;
         Ok(())
     }
     fn feature_begin(&mut self, _idx: u64) -> Result<()> {
-        self.out.write_all(b"\n")?;
+        self.write_doc(b"\n")?;
+        if self.feature_class.is_some() {
+            self.feature_props.clear();
+            self.feature_buf = Some(Vec::new());
+        }
         Ok(())
     }
     fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        if let (Some(feature_class), Some(buf)) = (self.feature_class, self.feature_buf.take()) {
+            let class = feature_class(&self.feature_props);
+            self.write_doc(format!(r#"<g class="{}">"#, escape_attr(&class)).as_bytes())?;
+            self.write_doc(&buf)?;
+            self.write_doc(b"</g>")?;
+        }
         Ok(())
     }
 }
 
 impl<W: Write> GeomProcessor for SvgWriter<W> {
     fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        if self.doc_buf.is_some() {
+            self.extent = Some(match self.extent {
+                Some((xmin, ymin, xmax, ymax)) => {
+                    (xmin.min(x), ymin.min(y), xmax.max(x), ymax.max(y))
+                }
+                None => (x, y, x, y),
+            });
+        }
         let y = if self.invert_y { -y } else { y };
-        self.out.write_all(format!("{x} {y} ").as_bytes())?;
-        Ok(())
+        if self.in_points {
+            let circle = format!(
+                r#"<circle{} cx="{x}" cy="{y}" r="{}"/>"#,
+                self.style_attrs, self.point_radius
+            );
+            self.write(circle.as_bytes())
+        } else {
+            self.write(format!("{x} {y} ").as_bytes())
+        }
     }
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
-        self.out.write_all(br#"<path d="M "#)?;
+        self.in_points = true;
         Ok(())
     }
     fn point_end(&mut self, _idx: usize) -> Result<()> {
-        self.out.write_all(br#"Z"/>"#)?;
+        self.in_points = false;
+        Ok(())
+    }
+    fn multipoint_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.in_points = true;
+        Ok(())
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.in_points = false;
         Ok(())
     }
     fn linestring_begin(&mut self, tagged: bool, _size: usize, _idx: usize) -> Result<()> {
         if tagged {
-            self.out.write_all(br#"<path d=""#)?;
+            self.path_begin()
         } else {
-            self.out.write_all(b"M ")?;
+            self.write(b"M ")
         }
-        Ok(())
     }
     fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
         if tagged {
-            self.out.write_all(br#""/>"#)?;
+            self.write(br#""/>"#)
         } else {
-            self.out.write_all(b"Z ")?;
+            self.write(b"Z ")
         }
-        Ok(())
     }
     fn multilinestring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
-        self.out.write_all(br#"<path d=""#)?;
-        Ok(())
+        self.path_begin()
     }
     fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
-        self.out.write_all(br#""/>"#)?;
-        Ok(())
+        self.write(br#""/>"#)
     }
     fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
-        self.out.write_all(br#"<path d=""#)?;
-        Ok(())
+        self.path_begin()
     }
     fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
-        self.out.write_all(br#""/>"#)?;
-        Ok(())
+        self.write(br#""/>"#)
     }
 }
 
-impl<W: Write> PropertyProcessor for SvgWriter<W> {}
+impl<W: Write> PropertyProcessor for SvgWriter<W> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if self.feature_class.is_some() {
+            self.feature_props.property(idx, name, value)?;
+        }
+        Ok(false)
+    }
+}
+
+fn escape_attr(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
 
 #[cfg(test)]
 #[cfg(feature = "with-geojson")]
 mod test {
     use super::*;
-    use crate::geojson::read_geojson;
+    use crate::geojson::{read_geojson, read_geojson_geom};
     use crate::ToSvg;
     use geo_types::polygon;
 
@@ -147,7 +325,11 @@ mod test {
         }"#;
         let mut out: Vec<u8> = Vec::new();
         let invert_y = true;
-        assert!(read_geojson(geojson.as_bytes(), &mut SvgWriter::new(&mut out, invert_y)).is_ok());
+        assert!(read_geojson_geom(
+            &mut geojson.as_bytes(),
+            &mut SvgWriter::with_invert_y(&mut out, invert_y)
+        )
+        .is_ok());
         // Has Multi-Ring Polygon
         assert_eq!(
             std::str::from_utf8(&out).unwrap(),
@@ -164,7 +346,11 @@ mod test {
             ]]]
         }"#;
         let mut out: Vec<u8> = Vec::new();
-        assert!(read_geojson(geojson.as_bytes(), &mut SvgWriter::new(&mut out, invert_y)).is_ok());
+        assert!(read_geojson_geom(
+            &mut geojson.as_bytes(),
+            &mut SvgWriter::with_invert_y(&mut out, invert_y)
+        )
+        .is_ok());
         // Has multiple Polygons
         assert_eq!(
             std::str::from_utf8(&out).unwrap(),
@@ -180,7 +366,11 @@ mod test {
         }"#;
         let mut out: Vec<u8> = Vec::new();
         let invert_y = false;
-        assert!(read_geojson(geojson.as_bytes(), &mut SvgWriter::new(&mut out, invert_y)).is_ok());
+        assert!(read_geojson_geom(
+            &mut geojson.as_bytes(),
+            &mut SvgWriter::with_invert_y(&mut out, invert_y)
+        )
+        .is_ok());
         assert_eq!(
             std::str::from_utf8(&out).unwrap(),
             r#"<path d="1875038.447610231 -3269648.6879248763 1874359.641504197 -3270196.812984864 1874141.0428635243 -3270953.7840121365 1874440.1778162003 -3271619.4315206874 1876396.0598222911 -3274138.747656357 1876442.0805243007 -3275052.60551469 1874739.312657555 -3275457.333765534 "/>"#
@@ -194,7 +384,11 @@ mod test {
             ]]
         }"#;
         let mut out: Vec<u8> = Vec::new();
-        assert!(read_geojson(geojson.as_bytes(), &mut SvgWriter::new(&mut out, invert_y)).is_ok());
+        assert!(read_geojson_geom(
+            &mut geojson.as_bytes(),
+            &mut SvgWriter::with_invert_y(&mut out, invert_y)
+        )
+        .is_ok());
         assert_eq!(
             std::str::from_utf8(&out).unwrap(),
             r#"<path d="M -20037505.025679983 2692596.21474788 -19924286.672913034 2692596.21474788 -19812966.14702537 2692596.21474788 -19701645.62113772 2692596.21474788 -19590325.09525006 2692596.21474788 -19479004.56936241 2692596.21474788 -19367684.04347475 2692596.21474788 -19256372.576874677 2692596.21474788 -19145042.991699435 2692596.21474788 -19033736.054743163 2692596.21474788 -18922401.939924125 2692596.21474788 -18811090.473324053 2692596.21474788 -18699774.4770802 2692596.21474788 -18588449.421548743 2692596.21474788 -18477128.895661093 2692596.21474788 -18365812.89941723 2692596.21474788 -18254492.373529565 2692596.21474788 -18143167.31799812 2692596.21474788 -18031860.381041847 2692596.21474788 -17920530.795866605 2692596.21474788 -17809219.329266533 2692596.21474788 -17697898.803378873 2692596.21474788 -17586578.277491223 2692596.21474788 -17475257.751603562 2692596.21474788 -17363937.225715913 2692596.21474788 -17252616.69982825 2692596.21474788 -17141296.1739406 2692596.21474788 -17029984.707340535 2692596.21474788 -16918659.651809078 2692596.21474788 -16807343.65556522 2692596.21474788 -16696014.07038997 2692596.21474788 -16584707.133433694 2692596.21474788 -16473382.077902246 2692596.21474788 -16362061.552014597 2692596.21474788 -16250745.55577073 2692596.21474788 -16139425.02988307 2692596.21474788 -16028104.50399542 2692596.21474788 -15916783.978107756 2692596.21474788 -15805472.511507692 2692596.21474788 -15694142.926332444 2692596.21474788 -15582831.459732382 2692596.21474788 -15471510.933844728 2692596.21474788 -15360190.407957068 2692596.21474788 -15248869.882069414 2692596.21474788 -15137549.356181756 2692596.21474788 -15026228.830294106 2692596.21474788 -14914908.304406442 2692596.21474788 -14803587.77851878 2692596.21474788 -14692267.252631132 2692596.21474788 -14580955.78603106 2692596.21474788 -14469630.730499614 2692596.21474788 -14358314.734255752 2692596.21474788 -14246994.20836809 2692596.21474788 -14135678.212124234 2692596.21474788 -14024353.15659278 2692596.21474788 -13913032.630705126 2692596.21474788 -13801716.634461263 2692596.21474788 -13690391.578929815 2692596.21474788 -13579080.112329746 2692596.21474788 -13467755.05679829 2692596.21474788 -13356439.060554435 2692596.21474788 -13245114.005022977 2692596.21474788 -13133802.538422909 2692596.21474788 -13022482.012535255 2692596.21474788 -12911161.4866476 2692596.21474788 -12799840.960759947 2692596.21474788 -12688520.434872286 2692596.21474788 -12577199.908984637 2692596.21474788 -12465883.912740769 2692596.21474788 -12354567.916496906 2692596.21474788 -12243238.33132166 2692596.21474788 -12131926.864721594 2692596.21474788 -12020601.809190148 2692596.21474788 -11909285.812946282 2692596.21474788 -11797969.816702416 2692596.21474788 -11686644.76117097 2692596.21474788 -11575328.764927106 2692596.21474788 -11464008.239039456 2692596.21474788 -11352687.713151794 2692596.21474788 -11241367.187264144 2692596.21474788 -11130051.191020276 2692596.21474788 -11018726.13548883 2692596.21474788 -10907414.668888764 2692596.21474788 -10796094.1430011 2692596.21474788 -10684773.61711345 2692596.21474788 -10573453.091225792 2692596.21474788 -10462132.56533813 2692596.21474788 -10350812.03945048 2692596.21474788 -10239491.513562815 2692596.21474788 -10128170.987675166 2692596.21474788 -10016854.9914313 2692596.21474788 -9905538.995187435 2692596.21474788 -9794209.410012191 2692596.21474788 -9682902.47305592 2692596.21474788 -9571577.417524474 2692596.21474788 -9460261.421280608 2692596.21474788 -9348940.895392958 2692596.21474788 -9237615.8398615 2692596.21474788 -9126299.843617637 2692596.21474788 -9014979.317729987 2692596.21474788 -8903663.321486121 2692596.21474788 -8792338.265954675 2692596.21474788 -8681022.269710807 2692596.21474788 -8569692.684535567 2692596.21474788 -8458385.747579295 2692596.21474788 -8347069.751335428 2692596.21474788 -8235749.225447779 2692596.21474788 -8124424.16991632 2692596.21474788 -8013099.114384874 2692596.21474788 -7901787.647784806 2692596.21474788 -7790467.121897143 2692596.21474788 -7679155.655297086 2692596.21474788 -7567826.070121832 2692596.21474788 -7456510.073877977 2692596.21474788 -7345185.01834652 2692596.21474788 -7233873.551746452 2692596.21474788 -7122553.025858803 2692596.21474788 -7011232.49997114 2692596.21474788 -6899911.97408349 2692596.21474788 -6788586.918552041 2692596.21474788 -6677270.922308178 2692596.21474788 -6565950.396420515 2692596.21474788 -6454638.929820447 2692596.21474788 -6343309.344645206 2692596.21474788 -6231993.34840134 2692596.21474788 -6120677.352157486 2692596.21474788 -6009356.826269826 2692596.21474788 -5898040.83002596 2692596.21474788 -5786715.774494514 2692596.21474788 -5675395.248606861 2692596.21474788 -5564074.722719202 2692596.21474788 -5452754.196831549 2692596.21474788 -5341433.67094389 2692596.21474788 -5230126.733987618 2692596.21474788 -5118797.148812373 2692596.21474788 -5007481.15256851 2692596.21474788 -4896165.156324643 2692596.21474788 -4784844.630436993 2692596.21474788 -4673524.104549334 2692596.21474788 -4562203.578661681 2692596.21474788 -4450883.052774021 2692596.21474788 -4339562.526886369 2692596.21474788 -4228242.000998709 2692596.21474788 -4116935.0640424383 2692596.21474788 -4005600.9492233978 2692596.21474788 -3894289.48262333 2692596.21474788 -3782964.427091881 2692596.21474788 -3671648.430848018 2692596.21474788 -3560327.904960355 2692596.21474788 -3449011.908716501 2692596.21474788 -3337691.382828842 2692596.21474788 -3226366.327297393 2692596.21474788 -3115050.3310535294 2692596.21474788 -3003729.8051658766 2692596.21474788 -2892418.338565809 2692596.21474788 -2781088.7533905646 2692596.21474788 -2669777.2867904967 2692596.21474788 -2558456.7609028374 2692596.21474788 -2447136.2350151846 2692596.21474788 -2335815.7091275253 2692596.21474788 -2224495.183239872 2692596.21474788 -2113174.657352213 2692596.21474788 -2001854.1314645505 2692596.21474788 -1890533.6055769008 2692596.21474788 -1779213.0796892412 2692596.21474788 -1667901.6130891703 2692596.21474788 -1556581.087201521 2692596.21474788 -1445260.5613138585 2692596.21474788 -1333940.0354262087 2692596.21474788 -1222619.5095385492 2692596.21474788 -1111298.9836508965 2692596.21474788 -999978.457763237 2692596.21474788 -888662.4615193801 2692596.21474788 -777341.9356317207 2692596.21474788 -666025.9393878573 2692596.21474788 -554696.3542126124 2692596.21474788 -443393.94690013694 2692596.21474788 -332073.4210124745 2692596.21474788 -220743.8358372327 2692596.21474788 -109432.36923716536 2692596.21474788 1897.2159380795624 2692596.21474788 113226.80111332452 2692596.21474788 224538.26771339183 2692596.21474788 335876.91217622877 2692596.21474788 447188.37877629616 2692596.21474788 558499.8453763635 2692596.21474788 669820.3712640165 2692596.21474788 781140.8971516758 2692596.21474788 892461.4230393288 2692596.21474788 1003772.889639396 2692596.21474788 1115111.534102233 2692596.21474788 1226404.882127126 2692596.21474788 1337743.52658995 2692596.21474788 1449064.0524776129 2692596.21474788 1560384.578365272 2692596.21474788 1671696.04496533 2692596.21474788 1783007.5115654003 2692596.21474788 1894328.03745306 2692596.21474788 2005648.5633407128 2692596.21474788 2116978.148515964 2692596.21474788 2228289.6151160216 2692596.21474788 2339619.2002912764 2692596.21474788 2450930.666891334 2692596.21474788 2562251.1927789967 2692596.21474788 2673571.718666656 2692596.21474788 2784901.303841901 2692596.21474788 2896212.7704419685 2692596.21474788 3007515.1777544436 2692596.21474788 3118853.822217281 2692596.21474788 3230165.2888173484 2692596.21474788 3341494.8739925832 2692596.21474788 3452797.2813050686 2692596.21474788 3564135.925767896 2692596.21474788 3675438.333080381 2692596.21474788 3786776.977543208 2692596.21474788 3898088.444143285 2692596.21474788 4009399.9107433553 2692596.21474788 4120720.4366310057 2692596.21474788 4232040.962518668 2692596.21474788 4343361.488406317 2692596.21474788 4454672.9550063815 2692596.21474788 4566011.599469221 2692596.21474788 4677323.066069286 2692596.21474788 4788643.591956948 2692596.21474788 4899964.117844598 2692596.21474788 5011284.643732261 2692596.21474788 5122596.110332319 2692596.21474788 5233907.576932389 2692596.21474788 5345246.221395223 2692596.21474788 5456548.628707701 2692596.21474788 5567887.273170535 2692596.21474788 5679189.680483013 2692596.21474788 5790519.265658256 2692596.21474788 5901830.732258332 2692596.21474788 6013151.258145982 2692596.21474788 6124471.784033645 2692596.21474788 6235801.36920888 2692596.21474788 6347112.835808957 2692596.21474788 6458433.3616966065 2692596.21474788 6569753.887584269 2692596.21474788 6681074.413471919 2692596.21474788 6792394.939359581 2692596.21474788 6903706.405959652 2692596.21474788 7015035.991134894 2692596.21474788 7126356.517022544 2692596.21474788 7237677.042910206 2692596.21474788 7348988.509510271 2692596.21474788 7460299.976110341 2692596.21474788 7571620.501997991 2692596.21474788 7682941.027885654 2692596.21474788 7794261.553773304 2692596.21474788 7905573.020373374 2692596.21474788 8016911.664836207 2692596.21474788 8128223.131436277 2692596.21474788 8239543.657323928 2692596.21474788 8350864.18321159 2692596.21474788 8462184.70909924 2692596.21474788 8573496.175699318 2692596.21474788 8684807.642299388 2692596.21474788 8796146.286762215 2692596.21474788 8907457.753362292 2692596.21474788 9018787.338537533 2692596.21474788 9130098.805137604 2692596.21474788 9241419.331025254 2692596.21474788 9352730.797625326 2692596.21474788 9464069.442088155 2692596.21474788 9575371.849400638 2692596.21474788 9686701.434575878 2692596.21474788 9798012.901175942 2692596.21474788 9909333.427063596 2692596.21474788 10020653.952951254 2692596.21474788 10131965.419551326 2692596.21474788 10243295.004726568 2692596.21474788 10354606.471326638 2692596.21474788 10465936.05650188 2692596.21474788 10577256.58238953 2692596.21474788 10688577.108277192 2692596.21474788 10799888.574877262 2692596.21474788 10911218.160052504 2692596.21474788 11022520.567364983 2692596.21474788 11133841.093252633 2692596.21474788 11245179.737715466 2692596.21474788 11356482.145027963 2692596.21474788 11467811.730203198 2692596.21474788 11579123.196803275 2692596.21474788 11690443.722690929 2692596.21474788 11801764.24857859 2692596.21474788 11913093.833753832 2692596.21474788 12024405.3003539 2692596.21474788 12135707.707666373 2692596.21474788 12247046.352129214 2692596.21474788 12358357.818729272 2692596.21474788 12469687.403904526 2692596.21474788 12580998.870504584 2692596.21474788 12692319.39639224 2692596.21474788 12803630.862992307 2692596.21474788 12914969.507455144 2692596.21474788 13026280.9740552 2692596.21474788 13137610.559230454 2692596.21474788 13248912.966542937 2692596.21474788 13360233.492430585 2692596.21474788 13471554.018318243 2692596.21474788 13582865.484918306 2692596.21474788 13694204.129381135 2692596.21474788 13805515.595981209 2692596.21474788 13916836.121868871 2692596.21474788 14028156.64775652 2692596.21474788 14139477.173644183 2692596.21474788 14250788.640244242 2692596.21474788 14362127.284707077 2692596.21474788 14473429.692019572 2692596.21474788 14584741.158619631 2692596.21474788 14696079.80308246 2692596.21474788 14807382.210394943 2692596.21474788 14918711.795570198 2692596.21474788 15030023.262170255 2692596.21474788 15141343.78805791 2692596.21474788 15252664.313945567 2692596.21474788 15364002.9584084 2692596.21474788 15475305.36572088 2692596.21474788 15586634.950896127 2692596.21474788 15697946.417496186 2692596.21474788 15809257.884096256 2692596.21474788 15920587.4692715 2692596.21474788 16031898.93587157 2692596.21474788 16143228.52104681 2692596.21474788 16254549.046934472 2692596.21474788 16365869.572822122 2692596.21474788 16477181.039422194 2692596.21474788 16588510.624597436 2692596.21474788 16699822.091197504 2692596.21474788 16811151.676372748 2692596.21474788 16922454.083685227 2692596.21474788 17033765.550285302 2692596.21474788 17145104.19474813 2692596.21474788 17256415.661348205 2692596.21474788 17367736.18723587 2692596.21474788 17479056.71312352 2692596.21474788 17590377.239011183 2692596.21474788 17701688.70561124 2692596.21474788 17813027.350074075 2692596.21474788 17924338.816674147 2692596.21474788 18035659.3425618 2692596.21474788 18146979.868449457 2692596.21474788 18258282.27576193 2692596.21474788 18369611.86093717 2692596.21474788 18480923.32753724 2692596.21474788 18592261.97200008 2692596.21474788 18703564.379312553 2692596.21474788 18814903.023775388 2692596.21474788 18926205.431087863 2692596.21474788 19037535.01626311 2692596.21474788 19148846.482863177 2692596.21474788 19260167.00875084 2692596.21474788 19371487.53463849 2692596.21474788 19482799.001238555 2692596.21474788 19594128.58641381 2692596.21474788 19705449.112301473 2692596.21474788 19816769.638189115 2692596.21474788 19921404.409836456 2692596.21474788 20037472.002420496 2692596.21474788 Z "/>"#
@@ -204,10 +398,14 @@ mod test {
         let geojson =
             r#"{"type": "Point", "coordinates": [2223639.4731508396,-15878634.348995442]}"#;
         let mut out: Vec<u8> = Vec::new();
-        assert!(read_geojson(geojson.as_bytes(), &mut SvgWriter::new(&mut out, invert_y)).is_ok());
+        assert!(read_geojson_geom(
+            &mut geojson.as_bytes(),
+            &mut SvgWriter::with_invert_y(&mut out, invert_y)
+        )
+        .is_ok());
         assert_eq!(
             std::str::from_utf8(&out).unwrap(),
-            r#"<path d="M 2223639.4731508396 -15878634.348995442 Z"/>"#
+            r#"<circle cx="2223639.4731508396" cy="-15878634.348995442" r="1"/>"#
         );
 
         // geoz_lod1_gebaeude_max_3d_extract.fgb, first feature
@@ -236,7 +434,11 @@ mod test {
             ]]
         }"#;
         let mut out: Vec<u8> = Vec::new();
-        assert!(read_geojson(geojson.as_bytes(), &mut SvgWriter::new(&mut out, invert_y)).is_ok());
+        assert!(read_geojson_geom(
+            &mut geojson.as_bytes(),
+            &mut SvgWriter::with_invert_y(&mut out, invert_y)
+        )
+        .is_ok());
         assert_eq!(
             std::str::from_utf8(&out).unwrap(),
             r#"<path d="M 2683312.339 1247968.33 2683311.496 1247964.044 2683307.761 1247964.745 2683309.16 1247973.337 2683313.003 1247972.616 2683312.339 1247968.33 Z M 2683312.339 1247968.33 2683313.003 1247972.616 2683313.003 1247972.616 2683312.339 1247968.33 2683312.339 1247968.33 Z M 2683307.761 1247964.745 2683311.496 1247964.044 2683311.496 1247964.044 2683307.761 1247964.745 2683307.761 1247964.745 Z M 2683311.496 1247964.044 2683312.339 1247968.33 2683312.339 1247968.33 2683311.496 1247964.044 2683311.496 1247964.044 Z "/><path d="M 2683309.16 1247973.337 2683307.761 1247964.745 2683307.761 1247964.745 2683309.16 1247973.337 2683309.16 1247973.337 Z "/><path d="M 2683312.339 1247968.33 2683311.496 1247964.044 2683307.761 1247964.745 2683309.16 1247973.337 2683313.003 1247972.616 2683312.339 1247968.33 Z M 2683313.003 1247972.616 2683309.16 1247973.337 2683309.16 1247973.337 2683313.003 1247972.616 2683313.003 1247972.616 Z "/>"#
@@ -263,7 +465,7 @@ mod test {
             }
         }"#;
         let mut svg_data: Vec<u8> = Vec::new();
-        let mut svg = SvgWriter::new(&mut svg_data, false);
+        let mut svg = SvgWriter::with_invert_y(&mut svg_data, false);
         // svg.set_dimensions(bbox.get(0), bbox.get(1), bbox.get(2), bbox.get(3), 800, 400);
         assert!(read_geojson(geojson.as_bytes(), &mut svg).is_ok());
         assert_eq!(
@@ -278,6 +480,88 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn styling() -> Result<()> {
+        let geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": { "kind": "lake" },
+                    "geometry": { "type": "Polygon", "coordinates": [[[0, 0], [2, 0], [2, 2], [0, 0]]] }
+                },
+                {
+                    "type": "Feature",
+                    "properties": { "kind": "well" },
+                    "geometry": { "type": "MultiPoint", "coordinates": [[1, 1], [3, 1]] }
+                }
+            ]
+        }"#;
+        let style = SvgStyle {
+            stroke: Some("#000".to_string()),
+            stroke_width: Some(0.5),
+            fill: Some("blue".to_string()),
+            opacity: Some(0.8),
+        };
+        let mut svg_data: Vec<u8> = Vec::new();
+        let mut svg = SvgWriter::with_invert_y(&mut svg_data, false)
+            .with_style(style)
+            .with_point_radius(2.5)
+            .with_feature_class(|props| format!("kind-{}", props["kind"]));
+        read_geojson(geojson.as_bytes(), &mut svg)?;
+        assert_eq!(
+            std::str::from_utf8(&svg_data).unwrap(),
+            r##"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" version="1.2" baseProfile="tiny" stroke-linecap="round" stroke-linejoin="round">
+<g id="">
+<g class="kind-lake"><path stroke="#000" stroke-width="0.5" fill="blue" opacity="0.8" d="M 0 0 2 0 2 2 0 0 Z "/></g>
+<g class="kind-well"><circle stroke="#000" stroke-width="0.5" fill="blue" opacity="0.8" cx="1" cy="1" r="2.5"/><circle stroke="#000" stroke-width="0.5" fill="blue" opacity="0.8" cx="3" cy="1" r="2.5"/></g>
+</g>
+</svg>"##
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn viewbox() -> Result<()> {
+        let geojson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": {},
+                    "geometry": { "type": "LineString", "coordinates": [[8, 47], [10, 48]] }
+                },
+                {
+                    "type": "Feature",
+                    "properties": {},
+                    "geometry": { "type": "Point", "coordinates": [9, 46] }
+                }
+            ]
+        }"#;
+        let mut svg_data: Vec<u8> = Vec::new();
+        let mut svg = SvgWriter::new(&mut svg_data, 400, 300);
+        read_geojson(geojson.as_bytes(), &mut svg)?;
+        assert_eq!(
+            std::str::from_utf8(&svg_data).unwrap(),
+            r#"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" version="1.2" baseProfile="tiny" width="400" height="300" viewBox="8 -48 2 2" stroke-linecap="round" stroke-linejoin="round">
+<g id="">
+<path d="8 -47 10 -48 "/>
+<circle cx="9" cy="-46" r="1"/>
+</g>
+</svg>"#
+        );
+
+        let mut svg_data: Vec<u8> = Vec::new();
+        let mut svg = SvgWriter::new(&mut svg_data, 400, 300).with_viewbox(5.0, 45.0, 11.0, 49.0);
+        read_geojson(geojson.as_bytes(), &mut svg)?;
+        assert!(std::str::from_utf8(&svg_data)
+            .unwrap()
+            .contains(r#"width="400" height="300" viewBox="5 -49 6 4" "#));
+        Ok(())
+    }
+
     #[test]
     fn conversions() {
         let geom: geo_types::Geometry<f64> = polygon![
@@ -0,0 +1,539 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+
+/// Processor passing every callback to two processors, e.g. for writing GeoJSON
+/// and collecting statistics in a single read pass.
+///
+/// Both processors receive each callback, even if the first one fails.
+/// Only one error is returned, the error of the first processor before the error of the second.
+/// Sources usually stop processing after a failing callback, so the remaining callbacks
+/// are not passed to either processor.
+///
+/// Coordinates are processed with the union of the requested dimensions.
+/// Processors without additional dimensions receive them as `xy`.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::{geojson::{GeoJson, GeoJsonWriter}, DatasetStats, GeozeroDatasource, Tee};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut tee = Tee::new(GeoJsonWriter::new(&mut out), DatasetStats::new());
+/// GeoJson(r#"{"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}}"#)
+///     .process(&mut tee)
+///     .unwrap();
+/// let (_, stats) = tee.into_inner();
+/// assert_eq!(stats.report().feature_count, 1);
+/// assert!(String::from_utf8(out).unwrap().contains(r#""coordinates": [1,2]"#));
+/// # }
+/// ```
+pub struct Tee<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> Tee<A, B> {
+    pub fn new(a: A, b: B) -> Self {
+        Tee { a, b }
+    }
+    /// Return the inner processors.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+/// Processor passing every callback to a slice of processors.
+///
+/// Like [`Tee`], but for any number of processors of the same type. All processors
+/// receive each callback and the first error is returned.
+pub struct TeeSlice<'a, P> {
+    sinks: &'a mut [P],
+}
+
+impl<'a, P: GeomProcessor> TeeSlice<'a, P> {
+    pub fn new(sinks: &'a mut [P]) -> Self {
+        TeeSlice { sinks }
+    }
+    /// Call `f` for all processors, returning the first error.
+    fn each(&mut self, mut f: impl FnMut(&mut P) -> Result<()>) -> Result<()> {
+        let mut result = Ok(());
+        for sink in self.sinks.iter_mut() {
+            let sink_result = f(sink);
+            if result.is_ok() {
+                result = sink_result;
+            }
+        }
+        result
+    }
+}
+
+/// Call `$method` on both processors of a `Tee`, returning the first error.
+macro_rules! tee {
+    ($self:ident.$method:ident($($arg:expr),*)) => {{
+        let a = $self.a.$method($($arg),*);
+        let b = $self.b.$method($($arg),*);
+        a.and(b)
+    }};
+}
+
+fn union(a: CoordDimensions, b: CoordDimensions) -> CoordDimensions {
+    CoordDimensions {
+        z: a.z || b.z,
+        m: a.m || b.m,
+        t: a.t || b.t,
+        tm: a.tm || b.tm,
+    }
+}
+
+/// Pass a coordinate with all dimensions to `processor`, or as `xy` if it doesn't request them.
+#[allow(clippy::too_many_arguments)]
+fn coordinate<P: GeomProcessor>(
+    processor: &mut P,
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+    m: Option<f64>,
+    t: Option<f64>,
+    tm: Option<u64>,
+    idx: usize,
+) -> Result<()> {
+    if processor.multi_dim() {
+        processor.coordinate(x, y, z, m, t, tm, idx)
+    } else {
+        processor.xy(x, y, idx)
+    }
+}
+
+impl<A: FeatureProcessor, B: FeatureProcessor> FeatureProcessor for Tee<A, B> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        tee!(self.crs(crs))
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        tee!(self.dataset_begin(name))
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        tee!(self.dataset_end())
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        tee!(self.feature_begin(idx))
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        let a = self.a.feature_id(id.clone());
+        let b = self.b.feature_id(id);
+        a.and(b)
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        tee!(self.feature_end(idx))
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        tee!(self.properties_begin())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        tee!(self.properties_end())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        tee!(self.geometry_begin())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        tee!(self.geometry_end())
+    }
+    /// Skips only if both processors skip.
+    fn skip_geometry(&self) -> bool {
+        self.a.skip_geometry() && self.b.skip_geometry()
+    }
+}
+
+impl<A: GeomProcessor, B: GeomProcessor> GeomProcessor for Tee<A, B> {
+    fn dimensions(&self) -> CoordDimensions {
+        union(self.a.dimensions(), self.b.dimensions())
+    }
+    fn multi_dim(&self) -> bool {
+        self.a.multi_dim() || self.b.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        tee!(self.srid(srid))
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        tee!(self.input_dimensions(dimensions))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        tee!(self.xy(x, y, idx))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        let a = coordinate(&mut self.a, x, y, z, m, t, tm, idx);
+        let b = coordinate(&mut self.b, x, y, z, m, t, tm, idx);
+        a.and(b)
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        tee!(self.empty_point(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        tee!(self.point_begin(idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.point_end(idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        tee!(self.multipoint_begin(size, idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.multipoint_end(idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        tee!(self.linestring_begin(tagged, size, idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        tee!(self.linestring_end(tagged, idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        tee!(self.multilinestring_begin(size, idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.multilinestring_end(idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        tee!(self.polygon_begin(tagged, size, idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        tee!(self.polygon_end(tagged, idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        tee!(self.multipolygon_begin(size, idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.multipolygon_end(idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        tee!(self.geometrycollection_begin(size, idx))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.geometrycollection_end(idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        tee!(self.circularstring_begin(size, idx))
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.circularstring_end(idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        tee!(self.compoundcurve_begin(size, idx))
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.compoundcurve_end(idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        tee!(self.curvepolygon_begin(size, idx))
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.curvepolygon_end(idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        tee!(self.multicurve_begin(size, idx))
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.multicurve_end(idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        tee!(self.multisurface_begin(size, idx))
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.multisurface_end(idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        tee!(self.triangle_begin(tagged, size, idx))
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        tee!(self.triangle_end(tagged, idx))
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        tee!(self.polyhedralsurface_begin(size, idx))
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.polyhedralsurface_end(idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        tee!(self.tin_begin(size, idx))
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        tee!(self.tin_end(idx))
+    }
+}
+
+impl<A: PropertyProcessor, B: PropertyProcessor> PropertyProcessor for Tee<A, B> {
+    /// Aborts only if both processors abort.
+    fn property(&mut self, i: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        let a = self.a.property(i, colname, colval);
+        let b = self.b.property(i, colname, colval);
+        Ok(a? && b?)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for TeeSlice<'_, P> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.each(|p| p.crs(crs))
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.each(|p| p.dataset_begin(name))
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.each(|p| p.dataset_end())
+    }
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.each(|p| p.feature_begin(idx))
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        self.each(|p| p.feature_id(id.clone()))
+    }
+    fn feature_end(&mut self, idx: u64) -> Result<()> {
+        self.each(|p| p.feature_end(idx))
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        self.each(|p| p.properties_begin())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        self.each(|p| p.properties_end())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        self.each(|p| p.geometry_begin())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        self.each(|p| p.geometry_end())
+    }
+    fn skip_geometry(&self) -> bool {
+        self.sinks.iter().all(|p| p.skip_geometry())
+    }
+}
+
+impl<P: GeomProcessor> GeomProcessor for TeeSlice<'_, P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.sinks
+            .iter()
+            .map(|p| p.dimensions())
+            .fold(CoordDimensions::xy(), union)
+    }
+    fn multi_dim(&self) -> bool {
+        self.sinks.iter().any(|p| p.multi_dim())
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.each(|p| p.srid(srid))
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.each(|p| p.input_dimensions(dimensions))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.each(|p| p.xy(x, y, idx))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.each(|p| coordinate(p, x, y, z, m, t, tm, idx))
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.empty_point(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.point_begin(idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.point_end(idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multipoint_begin(size, idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multipoint_end(idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.linestring_begin(tagged, size, idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.each(|p| p.linestring_end(tagged, idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multilinestring_begin(size, idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multilinestring_end(idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.polygon_begin(tagged, size, idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.each(|p| p.polygon_end(tagged, idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multipolygon_begin(size, idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multipolygon_end(idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.geometrycollection_begin(size, idx))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.geometrycollection_end(idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.circularstring_begin(size, idx))
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.circularstring_end(idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.compoundcurve_begin(size, idx))
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.compoundcurve_end(idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.curvepolygon_begin(size, idx))
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.curvepolygon_end(idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multicurve_begin(size, idx))
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multicurve_end(idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.multisurface_begin(size, idx))
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.multisurface_end(idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.triangle_begin(tagged, size, idx))
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.each(|p| p.triangle_end(tagged, idx))
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.polyhedralsurface_begin(size, idx))
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.polyhedralsurface_end(idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.each(|p| p.tin_begin(size, idx))
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.each(|p| p.tin_end(idx))
+    }
+}
+
+impl<P: GeomProcessor + PropertyProcessor> PropertyProcessor for TeeSlice<'_, P> {
+    /// Aborts only if all processors abort.
+    fn property(&mut self, i: usize, colname: &str, colval: &ColumnValue) -> Result<bool> {
+        let mut abort = true;
+        let mut result = Ok(());
+        for sink in self.sinks.iter_mut() {
+            match sink.property(i, colname, colval) {
+                Ok(sink_abort) => abort &= sink_abort,
+                Err(e) if result.is_ok() => result = Err(e),
+                Err(_) => {}
+            }
+        }
+        result.map(|_| abort)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::error::GeozeroError;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::{GeozeroGeometry, ProcessorSink};
+
+    /// Processor failing at the first coordinate
+    struct Failing;
+    impl GeomProcessor for Failing {
+        fn xy(&mut self, _x: f64, _y: f64, _idx: usize) -> Result<()> {
+            Err(GeozeroError::Geometry("failing".to_string()))
+        }
+    }
+    impl PropertyProcessor for Failing {}
+    impl FeatureProcessor for Failing {}
+
+    #[derive(Default)]
+    struct XyCount(usize);
+    impl GeomProcessor for XyCount {
+        fn xy(&mut self, _x: f64, _y: f64, _idx: usize) -> Result<()> {
+            self.0 += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn tee() -> Result<()> {
+        let mut out1: Vec<u8> = Vec::new();
+        let mut out2: Vec<u8> = Vec::new();
+        let mut tee = Tee::new(
+            WktWriter::new(&mut out1),
+            WktWriter::with_dims(&mut out2, CoordDimensions::xyz()),
+        );
+        WktStr("LINESTRING Z (1 2 3,4 5 6)").process_geom(&mut tee)?;
+        drop(tee);
+        assert_eq!(std::str::from_utf8(&out1).unwrap(), "LINESTRING(1 2,4 5)");
+        assert_eq!(
+            std::str::from_utf8(&out2).unwrap(),
+            "LINESTRING Z (1 2 3,4 5 6)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn tee_error() {
+        let mut tee = Tee::new(Failing, XyCount::default());
+        let err = WktStr("MULTIPOINT(1 2,3 4)")
+            .process_geom(&mut tee)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "processing geometry `failing`");
+        // The second processor still receives the failing callback, but processing stops
+        let (_, count) = tee.into_inner();
+        assert_eq!(count.0, 1);
+
+        let mut tee = Tee::new(ProcessorSink::new(), Failing);
+        assert!(WktStr("POINT(1 2)").process_geom(&mut tee).is_err());
+    }
+
+    #[test]
+    fn tee_slice() -> Result<()> {
+        let mut out1: Vec<u8> = Vec::new();
+        let mut out2: Vec<u8> = Vec::new();
+        let mut out3: Vec<u8> = Vec::new();
+        let mut writers = [
+            WktWriter::new(&mut out1),
+            WktWriter::new(&mut out2),
+            WktWriter::new(&mut out3),
+        ];
+        WktStr("MULTIPOINT(1 2,3 4)").process_geom(&mut TeeSlice::new(&mut writers))?;
+        drop(writers);
+        for out in [out1, out2, out3] {
+            assert_eq!(std::str::from_utf8(&out).unwrap(), "MULTIPOINT(1 2,3 4)");
+        }
+        Ok(())
+    }
+}
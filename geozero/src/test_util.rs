@@ -0,0 +1,75 @@
+//! Helpers for processor tests.
+
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+/// Output buffer, which can still be read after passing it to a writer.
+#[derive(Clone, Default)]
+pub(crate) struct Output(Rc<RefCell<Vec<u8>>>);
+
+impl Output {
+    /// Written output as UTF-8 string.
+    pub(crate) fn into_string(self) -> String {
+        String::from_utf8(self.0.take()).unwrap()
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Process `wkt` with the processor created for the output and return the written output.
+#[cfg(feature = "with-wkt")]
+pub(crate) fn process_wkt<P: crate::GeomProcessor>(
+    wkt: &str,
+    processor: impl FnOnce(Output) -> P,
+) -> String {
+    process_wkt_with(wkt, processor, |_| ()).0
+}
+
+/// Process `wkt` like [`process_wkt`], additionally returning `result` of the processor.
+#[cfg(feature = "with-wkt")]
+pub(crate) fn process_wkt_with<P: crate::GeomProcessor, T>(
+    wkt: &str,
+    processor: impl FnOnce(Output) -> P,
+    result: impl FnOnce(&P) -> T,
+) -> (String, T) {
+    use crate::{wkt::WktStr, GeozeroGeometry};
+    let out = Output::default();
+    let mut processor = processor(out.clone());
+    WktStr(wkt).process_geom(&mut processor).unwrap();
+    let result = result(&processor);
+    drop(processor);
+    (out.into_string(), result)
+}
+
+/// Process `geojson` with the processor created for the output and return the written output.
+#[cfg(feature = "with-geojson")]
+pub(crate) fn process_geojson<P: crate::FeatureProcessor>(
+    geojson: &str,
+    processor: impl FnOnce(Output) -> P,
+) -> String {
+    process_geojson_with(geojson, processor, |_| ()).0
+}
+
+/// Process `geojson` like [`process_geojson`], additionally returning `result` of the processor.
+#[cfg(feature = "with-geojson")]
+pub(crate) fn process_geojson_with<P: crate::FeatureProcessor, T>(
+    geojson: &str,
+    processor: impl FnOnce(Output) -> P,
+    result: impl FnOnce(&P) -> T,
+) -> (String, T) {
+    use crate::{geojson::GeoJson, GeozeroDatasource};
+    let out = Output::default();
+    let mut processor = processor(out.clone());
+    GeoJson(geojson).process(&mut processor).unwrap();
+    let result = result(&processor);
+    drop(processor);
+    (out.into_string(), result)
+}
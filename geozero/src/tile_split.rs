@@ -0,0 +1,640 @@
+use crate::clip_mask::ConvexRegion;
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geom_type_filter::{Event, GeomEvent, Value};
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+use crate::GeomType;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+/// Half the width of the Web Mercator world in meters
+const WEB_MERCATOR_EXTENT: f64 = 20037508.342789244;
+
+/// Tile address within a [`TileScheme`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TileId {
+    /// Zoom level, always 0 for [`TileScheme::Grid`]
+    pub z: u8,
+    pub x: i64,
+    pub y: i64,
+}
+
+/// Partitioning of the coordinate space into tiles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TileScheme {
+    /// Regular grid of `width` x `height` tiles. Tile `(0, 0)` has its lower left corner at
+    /// `origin`, `x` increases to the east and `y` to the north. Tiles west or south of the
+    /// origin have negative numbers.
+    Grid {
+        origin: (f64, f64),
+        width: f64,
+        height: f64,
+    },
+    /// XYZ tiles at `zoom` for Web Mercator (EPSG:3857) coordinates. Tile `(0, 0)` is the
+    /// north-west corner, `x` increases to the east and `y` to the south.
+    WebMercator { zoom: u8 },
+}
+
+impl TileScheme {
+    /// Bounds of `tile` as `(min_x, min_y, max_x, max_y)`.
+    pub fn tile_bounds(&self, tile: TileId) -> (f64, f64, f64, f64) {
+        match *self {
+            TileScheme::Grid {
+                origin,
+                width,
+                height,
+            } => {
+                let left = origin.0 + tile.x as f64 * width;
+                let bottom = origin.1 + tile.y as f64 * height;
+                (left, bottom, left + width, bottom + height)
+            }
+            TileScheme::WebMercator { zoom } => {
+                let size = 2.0 * WEB_MERCATOR_EXTENT / 2f64.powi(zoom.into());
+                let left = -WEB_MERCATOR_EXTENT + tile.x as f64 * size;
+                let top = WEB_MERCATOR_EXTENT - tile.y as f64 * size;
+                (left, top - size, left + size, top)
+            }
+        }
+    }
+
+    /// Tiles overlapping the bounding box `(min_x, min_y, max_x, max_y)`.
+    ///
+    /// Tiles contain their edges with the lower coordinate on each axis, i.e. the west and
+    /// south edge for grids and the west and north edge for XYZ tiles. A point on a tile
+    /// boundary is therefore in exactly one tile, the one with the higher `x` and `y` number.
+    /// A box ending on a tile boundary does not overlap the tiles beyond.
+    /// XYZ tiles outside of the Web Mercator world are omitted.
+    pub fn tiles(&self, bbox: (f64, f64, f64, f64)) -> impl Iterator<Item = TileId> {
+        let (min_x, min_y, max_x, max_y) = bbox;
+        let (z, xs, ys) = match *self {
+            TileScheme::Grid {
+                origin,
+                width,
+                height,
+            } => (
+                0,
+                axis_tiles(min_x, max_x, origin.0, width),
+                axis_tiles(min_y, max_y, origin.1, height),
+            ),
+            TileScheme::WebMercator { zoom } => {
+                let count = 2f64.powi(zoom.into());
+                let size = 2.0 * WEB_MERCATOR_EXTENT / count;
+                let last = count as i64 - 1;
+                let clamp = |(first, end): (i64, i64)| (first.max(0), end.min(last));
+                (
+                    zoom,
+                    clamp(axis_tiles(min_x, max_x, -WEB_MERCATOR_EXTENT, size)),
+                    // Rows are counted from the north
+                    clamp(axis_tiles(-max_y, -min_y, -WEB_MERCATOR_EXTENT, size)),
+                )
+            }
+        };
+        (xs.0..=xs.1).flat_map(move |x| (ys.0..=ys.1).map(move |y| TileId { z, x, y }))
+    }
+}
+
+/// First and last tile of `size` starting at `origin` overlapping `min..=max`
+fn axis_tiles(min: f64, max: f64, origin: f64, size: f64) -> (i64, i64) {
+    let first = ((min - origin) / size).floor() as i64;
+    let last = ((max - origin) / size).ceil() as i64 - 1;
+    (first, last.max(first))
+}
+
+/// Feature processor routing features to per-tile processors.
+///
+/// Every feature is passed to the processors of all tiles overlapped by its bounding box,
+/// see [`TileScheme::tiles`] for the handling of tile boundaries. A feature spanning many
+/// tiles is copied to each of them, so a long line at a high zoom level can produce a large
+/// number of copies. Without clipping, the copies are unchanged and bounding box routing
+/// includes tiles which the geometry itself does not touch, e.g. for a diagonal line.
+/// Features without coordinates are dropped.
+///
+/// The tile processors are created on first use by calling `new_sink` with the tile id.
+/// Each of them receives the `crs`, `dataset_begin` and `dataset_end` callbacks, with the
+/// features of its tile numbered contiguously. [`TileSplit::into_sinks`] returns the
+/// processors after processing. Geometries processed outside of features are ignored.
+///
+/// Unlike a callback `Fn(TileId) -> &mut P` returning caller-owned sinks, `new_sink` returns
+/// owned processors: a closure can't hand out mutable references into its own captured state,
+/// and the split has to keep every sink alive until `dataset_end`. Sinks writing to
+/// caller-managed destinations, like one file per tile, can be opened in `new_sink`.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{GeozeroDatasource, TileScheme, TileSplit};
+///
+/// let scheme = TileScheme::Grid {
+///     origin: (0.0, 0.0),
+///     width: 10.0,
+///     height: 10.0,
+/// };
+/// let mut split = TileSplit::new(scheme, |_tile| GeoJsonWriter::new(Vec::new()));
+/// GeoJson(r#"{"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[5, 5], [15, 5]]}}"#)
+///     .process(&mut split)
+///     .unwrap();
+/// assert_eq!(split.into_sinks().len(), 2);
+/// # }
+/// ```
+pub struct TileSplit<P: FeatureProcessor, F: FnMut(TileId) -> P> {
+    scheme: TileScheme,
+    new_sink: F,
+    /// Buffer around the tile bounds for clipping
+    clip_buffer: Option<f64>,
+    dims: CoordDimensions,
+    sinks: BTreeMap<TileId, Sink<P>>,
+    crs: Option<String>,
+    dataset_name: Option<String>,
+    /// Buffered callbacks of the current feature
+    feature: Option<Vec<Event>>,
+    geometry: Vec<GeomEvent>,
+}
+
+/// Processor of a tile
+struct Sink<P> {
+    processor: P,
+    /// Number of emitted features
+    next_idx: u64,
+}
+
+impl<P: FeatureProcessor> Sink<P> {
+    fn emit_feature(&mut self, events: &[Event], geometry: &[GeomEvent]) -> Result<()> {
+        let idx = self.next_idx;
+        self.next_idx += 1;
+        let p = &mut self.processor;
+        p.feature_begin(idx)?;
+        for event in events {
+            match event {
+                Event::FeatureId(id) => p.feature_id(id.as_column_value())?,
+                Event::PropertiesBegin => p.properties_begin()?,
+                Event::Property(i, name, value) => {
+                    p.property(*i, name, &value.as_column_value())?;
+                }
+                Event::PropertiesEnd => p.properties_end()?,
+                Event::Geometry => {
+                    p.geometry_begin()?;
+                    for geom_event in geometry {
+                        geom_event.replay(p)?;
+                    }
+                    p.geometry_end()?;
+                }
+            }
+        }
+        p.feature_end(idx)
+    }
+}
+
+impl<P: FeatureProcessor, F: FnMut(TileId) -> P> TileSplit<P, F> {
+    /// Route features to the tiles of `scheme`, with processors created by `new_sink`.
+    pub fn new(scheme: TileScheme, new_sink: F) -> Self {
+        TileSplit {
+            scheme,
+            new_sink,
+            clip_buffer: None,
+            dims: CoordDimensions::default(),
+            sinks: BTreeMap::new(),
+            crs: None,
+            dataset_name: None,
+            feature: None,
+            geometry: Vec::new(),
+        }
+    }
+    /// Clip geometries to the tile bounds extended by `buffer` on every side.
+    ///
+    /// Points outside of the clip rectangle are removed, lines are cut at its edges and
+    /// polygon rings are clipped with the Sutherland–Hodgman algorithm. Parts of concave
+    /// polygons outside of the rectangle can leave zero-width spikes along its edges.
+    /// Z and M values of new vertices are interpolated. Curves and surfaces are not clipped.
+    /// Features with nothing left after clipping are not passed to the tile.
+    pub fn with_clip(mut self, buffer: f64) -> Self {
+        self.clip_buffer = Some(buffer);
+        self
+    }
+    /// Coordinate dimensions requested from the data source. Default is XY.
+    pub fn with_dimensions(mut self, dims: CoordDimensions) -> Self {
+        self.dims = dims;
+        self
+    }
+    /// Return the tile processors created so far.
+    pub fn into_sinks(self) -> BTreeMap<TileId, P> {
+        self.sinks
+            .into_iter()
+            .map(|(tile, sink)| (tile, sink.processor))
+            .collect()
+    }
+    fn sink(&mut self, tile: TileId) -> Result<&mut Sink<P>> {
+        Ok(match self.sinks.entry(tile) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let mut processor = (self.new_sink)(tile);
+                if let Some(crs) = &self.crs {
+                    processor.crs(crs)?;
+                }
+                processor.dataset_begin(self.dataset_name.as_deref())?;
+                entry.insert(Sink {
+                    processor,
+                    next_idx: 0,
+                })
+            }
+        })
+    }
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        if self.feature.is_some() {
+            self.geometry.push(event);
+        }
+        Ok(())
+    }
+}
+
+/// Bounding box of the coordinates in `geometry`
+fn bbox(geometry: &[GeomEvent]) -> Option<(f64, f64, f64, f64)> {
+    geometry
+        .iter()
+        .filter_map(GeomEvent::xy)
+        .fold(None, |bbox, (x, y)| match bbox {
+            None => Some((x, y, x, y)),
+            Some((min_x, min_y, max_x, max_y)) => {
+                Some((min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)))
+            }
+        })
+}
+
+impl<P: FeatureProcessor, F: FnMut(TileId) -> P> FeatureProcessor for TileSplit<P, F> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        for sink in self.sinks.values_mut() {
+            sink.processor.crs(crs)?;
+        }
+        self.crs = Some(crs.to_string());
+        Ok(())
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        for sink in self.sinks.values_mut() {
+            sink.next_idx = 0;
+            sink.processor.dataset_begin(name)?;
+        }
+        self.dataset_name = name.map(str::to_string);
+        Ok(())
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        for sink in self.sinks.values_mut() {
+            sink.processor.dataset_end()?;
+        }
+        Ok(())
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.feature = Some(Vec::new());
+        self.geometry.clear();
+        Ok(())
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::FeatureId(Value::from(&id)));
+        }
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        let Some(events) = self.feature.take() else {
+            return Ok(());
+        };
+        let geometry = std::mem::take(&mut self.geometry);
+        let Some(bbox) = bbox(&geometry) else {
+            return Ok(());
+        };
+        let scheme = self.scheme;
+        for tile in scheme.tiles(bbox) {
+            let Some(buffer) = self.clip_buffer else {
+                self.sink(tile)?.emit_feature(&events, &geometry)?;
+                continue;
+            };
+            let (min_x, min_y, max_x, max_y) = scheme.tile_bounds(tile);
+            let region = ConvexRegion::rect(
+                min_x - buffer,
+                min_y - buffer,
+                max_x + buffer,
+                max_y + buffer,
+            );
+            if let Some(clipped) = region.clip(&geometry) {
+                self.sink(tile)?.emit_feature(&events, &clipped)?;
+            }
+        }
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::PropertiesBegin);
+        }
+        Ok(())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::PropertiesEnd);
+        }
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::Geometry);
+        }
+        Ok(())
+    }
+}
+
+impl<P: FeatureProcessor, F: FnMut(TileId) -> P> PropertyProcessor for TileSplit<P, F> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::Property(idx, name.to_string(), Value::from(value)));
+        }
+        Ok(false)
+    }
+}
+
+impl<P: FeatureProcessor, F: FnMut(TileId) -> P> GeomProcessor for TileSplit<P, F> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.geom_event(GeomEvent::Srid(srid))
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.geom_event(GeomEvent::InputDimensions(dimensions))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Xy(x, y, idx))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.geom_event(GeomEvent::Coordinate(x, y, z, m, t, tm, idx))
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::EmptyPoint(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Point, true, 1, idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Point, true, idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPoint, true, size, idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPoint, true, idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::LineString, tagged, size, idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::LineString, tagged, idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiLineString, true, size, idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiLineString, true, idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Polygon, tagged, size, idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Polygon, tagged, idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPolygon, true, size, idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPolygon, true, idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::GeometryCollection,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::GeometryCollection, true, idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CircularString, true, size, idx))
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CircularString, true, idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CompoundCurve, true, size, idx))
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CompoundCurve, true, idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CurvePolygon, true, size, idx))
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CurvePolygon, true, idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiCurve, true, size, idx))
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiCurve, true, idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiSurface, true, size, idx))
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiSurface, true, idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Triangle, tagged, size, idx))
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Triangle, tagged, idx))
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::PolyhedralSurface,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::PolyhedralSurface, true, idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Tin, true, size, idx))
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Tin, true, idx))
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "with-geojson", feature = "with-wkt"))]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJson;
+    use crate::wkt::WktWriter;
+    use crate::GeozeroDatasource;
+
+    const GRID: TileScheme = TileScheme::Grid {
+        origin: (0.0, 0.0),
+        width: 10.0,
+        height: 10.0,
+    };
+
+    fn tile(x: i64, y: i64) -> TileId {
+        TileId { z: 0, x, y }
+    }
+
+    /// WKT of the features of every tile
+    fn split(scheme: TileScheme, clip_buffer: Option<f64>, geojson: &str) -> Vec<(TileId, String)> {
+        let mut split = TileSplit::new(scheme, |_| WktWriter::new(Vec::new()));
+        if let Some(buffer) = clip_buffer {
+            split = split.with_clip(buffer);
+        }
+        GeoJson(geojson).process(&mut split).unwrap();
+        split
+            .into_sinks()
+            .into_iter()
+            .map(|(tile, writer)| (tile, String::from_utf8(writer.out).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn grid_tiles() {
+        let tiles: Vec<_> = GRID.tiles((-5.0, 5.0, 15.0, 20.0)).collect();
+        assert_eq!(
+            tiles,
+            vec![
+                tile(-1, 0),
+                tile(-1, 1),
+                tile(0, 0),
+                tile(0, 1),
+                tile(1, 0),
+                tile(1, 1)
+            ]
+        );
+        assert_eq!(GRID.tile_bounds(tile(-1, 2)), (-10.0, 20.0, 0.0, 30.0));
+    }
+
+    #[test]
+    fn boundary_points() {
+        // Points on a boundary belong to the tile with the higher numbers
+        assert_eq!(
+            GRID.tiles((10.0, 10.0, 10.0, 10.0)).collect::<Vec<_>>(),
+            vec![tile(1, 1)]
+        );
+        let xyz = TileScheme::WebMercator { zoom: 1 };
+        assert_eq!(
+            xyz.tiles((0.0, 0.0, 0.0, 0.0)).collect::<Vec<_>>(),
+            vec![TileId { z: 1, x: 1, y: 1 }]
+        );
+        // Boxes ending on a boundary don't overlap the tiles beyond
+        assert_eq!(
+            GRID.tiles((0.0, 0.0, 10.0, 10.0)).collect::<Vec<_>>(),
+            vec![tile(0, 0)]
+        );
+        assert_eq!(
+            xyz.tiles((-1.0, 0.0, 0.0, 1.0)).collect::<Vec<_>>(),
+            vec![TileId { z: 1, x: 0, y: 0 }]
+        );
+    }
+
+    #[test]
+    fn web_mercator() {
+        let xyz = TileScheme::WebMercator { zoom: 2 };
+        let e = WEB_MERCATOR_EXTENT;
+        assert_eq!(
+            xyz.tile_bounds(TileId { z: 2, x: 0, y: 0 }),
+            (-e, e / 2.0, -e / 2.0, e)
+        );
+        // Tiles outside of the world are omitted
+        let tiles: Vec<_> = xyz.tiles((e * 0.9, -e * 2.0, e * 2.0, -e * 0.9)).collect();
+        assert_eq!(tiles, vec![TileId { z: 2, x: 3, y: 3 }]);
+    }
+
+    #[test]
+    fn routing() {
+        let result = split(
+            GRID,
+            None,
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [5, 5]}},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[5, 5], [15, 5]]}},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [10, 5]}}
+            ]}"#,
+        );
+        assert_eq!(
+            result,
+            vec![
+                (tile(0, 0), "POINT(5 5)LINESTRING(5 5,15 5)".to_string()),
+                (tile(1, 0), "LINESTRING(5 5,15 5)POINT(10 5)".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn clipping() {
+        let result = split(
+            GRID,
+            Some(0.0),
+            r#"{"type": "FeatureCollection", "features": [
+                {"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": [[[5, 5], [15, 5], [15, 8], [5, 8], [5, 5]]]}},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[5, 9], [15, 9], [15, 11], [5, 11]]}},
+                {"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[5, 19], [15, 13]]}}
+            ]}"#,
+        );
+        assert_eq!(
+            result,
+            vec![
+                (
+                    tile(0, 0),
+                    "POLYGON((5 5,10 5,10 8,5 8,5 5))LINESTRING(5 9,10 9)".to_string()
+                ),
+                (
+                    tile(0, 1),
+                    "LINESTRING(10 11,5 11)LINESTRING(5 19,10 16)".to_string()
+                ),
+                (
+                    tile(1, 0),
+                    "POLYGON((10 5,15 5,15 8,10 8,10 5))LINESTRING(10 9,15 9,15 10)".to_string()
+                ),
+                (
+                    tile(1, 1),
+                    "LINESTRING(15 10,15 11,10 11)LINESTRING(10 16,15 13)".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn clipping_to_multi() {
+        // A line leaving and reentering the tile
+        let result = split(
+            GRID,
+            Some(0.0),
+            r#"{"type": "Feature", "properties": {}, "geometry": {"type": "LineString", "coordinates": [[2, 2], [12, 2], [12, 6], [2, 6]]}}"#,
+        );
+        assert_eq!(
+            result[0],
+            (
+                tile(0, 0),
+                "MULTILINESTRING((2 2,10 2),(10 6,2 6))".to_string()
+            )
+        );
+    }
+}
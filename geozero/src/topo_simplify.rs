@@ -0,0 +1,711 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geom_event_processor::Coord;
+use crate::geom_type_filter::{Event, GeomEvent, Value};
+use crate::geometry_processor::{CoordDimensions, GeomProcessor};
+use crate::property_processor::{ColumnValue, PropertyProcessor};
+use crate::simplify::douglas_peucker;
+use crate::GeomType;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+/// Feature processor wrapper simplifying linestrings and polygon rings of a whole dataset
+/// without creating gaps or overlaps between adjacent geometries.
+///
+/// Boundaries shared by several geometries are detected TopoJSON-style: all lines are split
+/// into arcs at junctions, i.e. vertices where lines meet or diverge and end points of lines.
+/// Every distinct arc is simplified once with the Ramer–Douglas–Peucker algorithm, so shared
+/// boundaries are simplified identically. `tolerance` has the same meaning as in
+/// [`Simplify::new`](crate::Simplify::new). Junctions are always kept.
+/// Rings which would collapse to less than 4 points are kept unchanged, together with all
+/// boundaries they share. Points and curve geometries are passed unchanged.
+///
+/// All features are buffered until `dataset_end`, when the simplified features are emitted,
+/// numbered contiguously. The memory usage grows with the size of the dataset: every buffered
+/// coordinate takes about 100 bytes, and every distinct vertex another 50 bytes for junction
+/// detection. A dataset with 10 million vertices needs about 1.5 GB.
+/// Geometries processed outside of features are passed unchanged.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-geojson")]
+/// # {
+/// use geozero::geojson::{GeoJson, GeoJsonWriter};
+/// use geozero::{GeozeroDatasource, TopoSimplify};
+///
+/// let mut out: Vec<u8> = Vec::new();
+/// let mut processor = TopoSimplify::new(GeoJsonWriter::new(&mut out), 1.0);
+/// GeoJson(r#"{"type": "FeatureCollection", "features": [
+///     {"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [10, 0], [10.4, 5], [10, 10], [0, 10], [0, 0]]]}},
+///     {"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": [[[10, 0], [20, 0], [20, 10], [10, 10], [10.4, 5], [10, 0]]]}}
+/// ]}"#)
+///     .process(&mut processor)
+///     .unwrap();
+/// # }
+/// ```
+pub struct TopoSimplify<P: FeatureProcessor> {
+    inner: P,
+    tolerance: f64,
+    /// Buffered features with their geometry
+    features: Vec<(Vec<Event>, Vec<GeomEvent>)>,
+    /// Buffered callbacks of the current feature
+    feature: Option<Vec<Event>>,
+    geometry: Vec<GeomEvent>,
+}
+
+/// Vertex identity by coordinate bits
+type Key = (u64, u64);
+
+fn vertex_key(x: f64, y: f64) -> Key {
+    // Adding 0.0 normalizes -0.0
+    ((x + 0.0).to_bits(), (y + 0.0).to_bits())
+}
+
+/// Simplifiable linestring of a buffered feature
+struct Line {
+    feature: usize,
+    /// Index of the `Begin` event
+    begin: usize,
+    /// Index of the `End` event
+    end: usize,
+    /// Indices of the coordinate events
+    coords: Vec<usize>,
+    /// Vertices
+    keys: Vec<Key>,
+    /// Closed polygon ring
+    ring: bool,
+}
+
+impl Line {
+    /// Vertex positions, without the closing vertex of rings
+    fn positions(&self) -> usize {
+        if self.ring {
+            self.keys.len() - 1
+        } else {
+            self.keys.len()
+        }
+    }
+}
+
+/// Linestrings of a geometry, except those within curves, triangles and surfaces.
+fn collect_lines(feature: usize, geometry: &[GeomEvent], lines: &mut Vec<Line>) {
+    let mut parents: Vec<GeomType> = Vec::new();
+    let mut current: Option<Line> = None;
+    for (i, event) in geometry.iter().enumerate() {
+        match event {
+            GeomEvent::Begin(GeomType::LineString, ..) => {
+                let simplifiable = parents.iter().all(|geom_type| {
+                    matches!(
+                        geom_type,
+                        GeomType::MultiLineString
+                            | GeomType::Polygon
+                            | GeomType::MultiPolygon
+                            | GeomType::GeometryCollection
+                    )
+                });
+                if simplifiable {
+                    current = Some(Line {
+                        feature,
+                        begin: i,
+                        end: i,
+                        coords: Vec::new(),
+                        keys: Vec::new(),
+                        ring: parents.last() == Some(&GeomType::Polygon),
+                    });
+                }
+                parents.push(GeomType::LineString);
+            }
+            GeomEvent::Begin(geom_type, ..) => parents.push(*geom_type),
+            GeomEvent::End(GeomType::LineString, ..) => {
+                parents.pop();
+                if let Some(mut line) = current.take() {
+                    line.end = i;
+                    // Unclosed rings are simplified like open lines
+                    line.ring =
+                        line.ring && line.keys.len() >= 4 && line.keys.first() == line.keys.last();
+                    // Short lines are not simplified, but their end points are junctions
+                    if line.keys.len() >= 2 {
+                        lines.push(line);
+                    }
+                }
+            }
+            GeomEvent::End(..) => {
+                parents.pop();
+            }
+            event => {
+                if let (Some(line), Some((x, y))) = (current.as_mut(), event.xy()) {
+                    line.coords.push(i);
+                    line.keys.push(vertex_key(x, y));
+                }
+            }
+        }
+    }
+}
+
+/// Vertices where lines meet, diverge or end.
+fn junctions(lines: &[Line]) -> HashSet<Key> {
+    let mut neighbors: HashMap<Key, (Key, Key)> = HashMap::new();
+    let mut junctions = HashSet::new();
+    let mut visit = |vertex: Key, prev: Key, next: Key| {
+        let pair = if prev < next {
+            (prev, next)
+        } else {
+            (next, prev)
+        };
+        match neighbors.entry(vertex) {
+            Entry::Occupied(entry) => {
+                if *entry.get() != pair {
+                    junctions.insert(vertex);
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(pair);
+            }
+        }
+    };
+    for line in lines {
+        let n = line.positions();
+        let keys = &line.keys;
+        if line.ring {
+            for (i, vertex) in keys[..n].iter().enumerate() {
+                visit(*vertex, keys[(i + n - 1) % n], keys[(i + 1) % n]);
+            }
+        } else {
+            for (i, vertex) in keys.iter().enumerate().take(n - 1).skip(1) {
+                visit(*vertex, keys[i - 1], keys[i + 1]);
+            }
+        }
+    }
+    for line in lines {
+        if !line.ring {
+            junctions.insert(line.keys[0]);
+            junctions.insert(line.keys[line.keys.len() - 1]);
+        }
+    }
+    // Rings without junctions are split at their smallest vertex,
+    // which is the same for identical rings with another start point
+    for line in lines.iter().filter(|line| line.ring) {
+        let ring = &line.keys[..line.positions()];
+        if !ring.iter().any(|key| junctions.contains(key)) {
+            if let Some(min) = ring.iter().min() {
+                junctions.insert(*min);
+            }
+        }
+    }
+    junctions
+}
+
+/// Arcs of a line as ranges of vertex positions (modulo the position count for rings)
+fn split_arcs(line: &Line, junctions: &HashSet<Key>) -> Vec<(usize, usize)> {
+    let n = line.positions();
+    let splits: Vec<usize> = (0..n)
+        .filter(|i| junctions.contains(&line.keys[*i]))
+        .collect();
+    if line.ring {
+        (0..splits.len())
+            .map(|k| {
+                let start = splits[k];
+                let end = splits[(k + 1) % splits.len()];
+                (start, if end <= start { end + n } else { end })
+            })
+            .collect()
+    } else {
+        splits.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    }
+}
+
+/// Shared arc simplification
+struct Arcs {
+    tolerance: f64,
+    /// Kept flags of simplified arcs in canonical direction
+    simplified: HashMap<Vec<Key>, Vec<bool>>,
+    /// Arcs in canonical direction which are not simplified
+    unchanged: HashSet<Vec<Key>>,
+}
+
+impl Arcs {
+    /// Canonical direction of an arc and whether it is reversed
+    fn canonical(arc: Vec<Key>) -> (Vec<Key>, bool) {
+        let reversed: Vec<Key> = arc.iter().rev().copied().collect();
+        if reversed < arc {
+            (reversed, true)
+        } else {
+            (arc, false)
+        }
+    }
+    fn keep(&mut self, arc: Vec<Key>) -> Vec<bool> {
+        let (arc, reversed) = Self::canonical(arc);
+        if self.unchanged.contains(&arc) {
+            return vec![true; arc.len()];
+        }
+        let tolerance = self.tolerance;
+        let mut keep = self
+            .simplified
+            .entry(arc)
+            .or_insert_with_key(|arc| {
+                let coords: Vec<Coord> = arc
+                    .iter()
+                    .map(|(x, y)| Coord {
+                        x: f64::from_bits(*x),
+                        y: f64::from_bits(*y),
+                        extra: None,
+                    })
+                    .collect();
+                douglas_peucker(&coords, tolerance)
+            })
+            .clone();
+        if reversed {
+            keep.reverse();
+        }
+        keep
+    }
+    /// Kept flags of the vertex positions of a line
+    fn line_keep(&mut self, line: &Line, arcs: &[(usize, usize)]) -> Vec<bool> {
+        let n = line.positions();
+        let mut keep = vec![false; n];
+        for (start, end) in arcs {
+            let arc = (*start..=*end).map(|i| line.keys[i % n]).collect();
+            for (i, k) in (*start..=*end).zip(self.keep(arc)) {
+                keep[i % n] |= k;
+            }
+        }
+        keep
+    }
+}
+
+impl<P: FeatureProcessor> TopoSimplify<P> {
+    /// Simplify with maximal distance `tolerance` of removed points to the simplified arcs.
+    pub fn new(inner: P, tolerance: f64) -> Self {
+        TopoSimplify {
+            inner,
+            tolerance,
+            features: Vec::new(),
+            feature: None,
+            geometry: Vec::new(),
+        }
+    }
+    /// Return the inner processor.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+    fn geom_event(&mut self, event: GeomEvent) -> Result<()> {
+        if self.feature.is_some() {
+            self.geometry.push(event);
+            Ok(())
+        } else {
+            event.replay(&mut self.inner)
+        }
+    }
+    /// Simplify and emit all buffered features
+    fn flush(&mut self) -> Result<()> {
+        let features = std::mem::take(&mut self.features);
+        let mut lines = Vec::new();
+        for (i, (_, geometry)) in features.iter().enumerate() {
+            collect_lines(i, geometry, &mut lines);
+        }
+        let junctions = junctions(&lines);
+        let line_arcs: Vec<_> = lines
+            .iter()
+            .map(|line| split_arcs(line, &junctions))
+            .collect();
+        let mut arcs = Arcs {
+            tolerance: self.tolerance,
+            simplified: HashMap::new(),
+            unchanged: HashSet::new(),
+        };
+        // Keep collapsing rings and their shared arcs unchanged until no ring collapses
+        let keep = loop {
+            let keep: Vec<_> = lines
+                .iter()
+                .zip(&line_arcs)
+                .map(|(line, line_arcs)| arcs.line_keep(line, line_arcs))
+                .collect();
+            let mut collapsed = false;
+            for ((line, line_arcs), keep) in lines.iter().zip(&line_arcs).zip(&keep) {
+                if line.ring && keep.iter().filter(|k| **k).count() + 1 < 4 {
+                    let n = line.positions();
+                    for (start, end) in line_arcs {
+                        let arc = (*start..=*end).map(|i| line.keys[i % n]).collect();
+                        collapsed |= arcs.unchanged.insert(Arcs::canonical(arc).0);
+                    }
+                }
+            }
+            if !collapsed {
+                break keep;
+            }
+        };
+        // Coordinate events of the simplified lines, by feature and `Begin` event
+        let mut simplified: HashMap<(usize, usize), (usize, Vec<usize>)> = HashMap::new();
+        for (line, keep) in lines.iter().zip(keep) {
+            let n = line.positions();
+            let kept = if line.ring {
+                // Start with the first kept vertex and close the ring with it
+                let start = keep.iter().position(|k| *k).unwrap_or_default();
+                let mut kept: Vec<usize> = (start..start + n)
+                    .map(|i| i % n)
+                    .filter(|i| keep[*i])
+                    .map(|i| line.coords[i])
+                    .collect();
+                kept.push(line.coords[start]);
+                kept
+            } else {
+                (0..n)
+                    .filter(|i| keep[*i])
+                    .map(|i| line.coords[i])
+                    .collect()
+            };
+            simplified.insert((line.feature, line.begin), (line.end, kept));
+        }
+        for (idx, (events, geometry)) in features.iter().enumerate() {
+            let mut simplified_geometry = Vec::with_capacity(geometry.len());
+            let mut i = 0;
+            while i < geometry.len() {
+                match (simplified.get(&(idx, i)), &geometry[i]) {
+                    (Some((end, kept)), GeomEvent::Begin(geom_type, tagged, _, line_idx)) => {
+                        simplified_geometry.push(GeomEvent::Begin(
+                            *geom_type,
+                            *tagged,
+                            kept.len(),
+                            *line_idx,
+                        ));
+                        for (coord_idx, event) in kept.iter().enumerate() {
+                            simplified_geometry.push(geometry[*event].with_coord_idx(coord_idx));
+                        }
+                        simplified_geometry.push(geometry[*end].clone());
+                        i = *end;
+                    }
+                    (_, event) => simplified_geometry.push(event.clone()),
+                }
+                i += 1;
+            }
+            self.emit_feature(idx as u64, events, &simplified_geometry)?;
+        }
+        Ok(())
+    }
+    fn emit_feature(&mut self, idx: u64, events: &[Event], geometry: &[GeomEvent]) -> Result<()> {
+        self.inner.feature_begin(idx)?;
+        for event in events {
+            match event {
+                Event::FeatureId(id) => self.inner.feature_id(id.as_column_value())?,
+                Event::PropertiesBegin => self.inner.properties_begin()?,
+                Event::Property(i, name, value) => {
+                    self.inner.property(*i, name, &value.as_column_value())?;
+                }
+                Event::PropertiesEnd => self.inner.properties_end()?,
+                Event::Geometry => {
+                    self.inner.geometry_begin()?;
+                    for geom_event in geometry {
+                        geom_event.replay(&mut self.inner)?;
+                    }
+                    self.inner.geometry_end()?;
+                }
+            }
+        }
+        self.inner.feature_end(idx)
+    }
+}
+
+impl<P: FeatureProcessor> FeatureProcessor for TopoSimplify<P> {
+    fn crs(&mut self, crs: &str) -> Result<()> {
+        self.inner.crs(crs)
+    }
+    fn dataset_begin(&mut self, name: Option<&str>) -> Result<()> {
+        self.features.clear();
+        self.inner.dataset_begin(name)
+    }
+    fn dataset_end(&mut self) -> Result<()> {
+        self.flush()?;
+        self.inner.dataset_end()
+    }
+    fn feature_begin(&mut self, _idx: u64) -> Result<()> {
+        self.feature = Some(Vec::new());
+        self.geometry.clear();
+        Ok(())
+    }
+    fn feature_id(&mut self, id: ColumnValue) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::FeatureId(Value::from(&id)));
+        }
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        if let Some(events) = self.feature.take() {
+            let geometry = std::mem::take(&mut self.geometry);
+            self.features.push((events, geometry));
+        }
+        Ok(())
+    }
+    fn properties_begin(&mut self) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::PropertiesBegin);
+        }
+        Ok(())
+    }
+    fn properties_end(&mut self) -> Result<()> {
+        if let Some(events) = self.feature.as_mut() {
+            events.push(Event::PropertiesEnd);
+        }
+        Ok(())
+    }
+    fn geometry_begin(&mut self) -> Result<()> {
+        match self.feature.as_mut() {
+            Some(events) => events.push(Event::Geometry),
+            None => self.inner.geometry_begin()?,
+        }
+        Ok(())
+    }
+    fn geometry_end(&mut self) -> Result<()> {
+        if self.feature.is_none() {
+            self.inner.geometry_end()?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: FeatureProcessor> PropertyProcessor for TopoSimplify<P> {
+    fn property(&mut self, idx: usize, name: &str, value: &ColumnValue) -> Result<bool> {
+        match self.feature.as_mut() {
+            Some(events) => {
+                events.push(Event::Property(idx, name.to_string(), Value::from(value)));
+                Ok(false)
+            }
+            None => self.inner.property(idx, name, value),
+        }
+    }
+}
+
+impl<P: FeatureProcessor> GeomProcessor for TopoSimplify<P> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.inner.dimensions()
+    }
+    fn multi_dim(&self) -> bool {
+        self.inner.multi_dim()
+    }
+    fn srid(&mut self, srid: Option<i32>) -> Result<()> {
+        self.geom_event(GeomEvent::Srid(srid))
+    }
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        self.geom_event(GeomEvent::InputDimensions(dimensions))
+    }
+    fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Xy(x, y, idx))
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        t: Option<f64>,
+        tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.geom_event(GeomEvent::Coordinate(x, y, z, m, t, tm, idx))
+    }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::EmptyPoint(idx))
+    }
+    fn point_begin(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Point, true, 1, idx))
+    }
+    fn point_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Point, true, idx))
+    }
+    fn multipoint_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPoint, true, size, idx))
+    }
+    fn multipoint_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPoint, true, idx))
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::LineString, tagged, size, idx))
+    }
+    fn linestring_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::LineString, tagged, idx))
+    }
+    fn multilinestring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiLineString, true, size, idx))
+    }
+    fn multilinestring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiLineString, true, idx))
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Polygon, tagged, size, idx))
+    }
+    fn polygon_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Polygon, tagged, idx))
+    }
+    fn multipolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiPolygon, true, size, idx))
+    }
+    fn multipolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiPolygon, true, idx))
+    }
+    fn geometrycollection_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::GeometryCollection,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn geometrycollection_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::GeometryCollection, true, idx))
+    }
+    fn circularstring_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CircularString, true, size, idx))
+    }
+    fn circularstring_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CircularString, true, idx))
+    }
+    fn compoundcurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CompoundCurve, true, size, idx))
+    }
+    fn compoundcurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CompoundCurve, true, idx))
+    }
+    fn curvepolygon_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::CurvePolygon, true, size, idx))
+    }
+    fn curvepolygon_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::CurvePolygon, true, idx))
+    }
+    fn multicurve_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiCurve, true, size, idx))
+    }
+    fn multicurve_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiCurve, true, idx))
+    }
+    fn multisurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::MultiSurface, true, size, idx))
+    }
+    fn multisurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::MultiSurface, true, idx))
+    }
+    fn triangle_begin(&mut self, tagged: bool, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Triangle, tagged, size, idx))
+    }
+    fn triangle_end(&mut self, tagged: bool, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Triangle, tagged, idx))
+    }
+    fn polyhedralsurface_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(
+            GeomType::PolyhedralSurface,
+            true,
+            size,
+            idx,
+        ))
+    }
+    fn polyhedralsurface_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::PolyhedralSurface, true, idx))
+    }
+    fn tin_begin(&mut self, size: usize, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::Begin(GeomType::Tin, true, size, idx))
+    }
+    fn tin_end(&mut self, idx: usize) -> Result<()> {
+        self.geom_event(GeomEvent::End(GeomType::Tin, true, idx))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-geojson")]
+mod test {
+    use super::*;
+    use crate::geojson::GeoJsonWriter;
+    use crate::test_util::process_geojson;
+
+    /// Property `n` and coordinates of the simplified features
+    fn topo_simplify(features: &[&str], tolerance: f64) -> Vec<(i64, String)> {
+        let features = features
+            .iter()
+            .enumerate()
+            .map(|(n, geometry)| {
+                format!(
+                    r#"{{"type": "Feature", "properties": {{"n": {n}}}, "geometry": {geometry}}}"#
+                )
+            })
+            .collect::<Vec<_>>();
+        let input = format!(
+            r#"{{"type": "FeatureCollection", "features": [{}]}}"#,
+            features.join(",")
+        );
+        let out = process_geojson(&input, |out| {
+            TopoSimplify::new(GeoJsonWriter::new(out), tolerance)
+        });
+        let json: serde_json::Value = serde_json::from_str(&out).unwrap();
+        json["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| {
+                (
+                    f["properties"]["n"].as_i64().unwrap(),
+                    f["geometry"]["coordinates"].to_string(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shared_boundary() {
+        let result = topo_simplify(
+            &[
+                r#"{"type": "Polygon", "coordinates": [[[0, 0], [10, 0], [10.4, 3], [9.6, 6], [10, 10], [0, 10], [0, 0]]]}"#,
+                r#"{"type": "Polygon", "coordinates": [[[10, 0], [20, 0], [20, 10], [10, 10], [9.6, 6], [10.4, 3], [10, 0]]]}"#,
+            ],
+            1.0,
+        );
+        assert_eq!(
+            result,
+            vec![
+                (0, "[[[0,0],[10,0],[10,10],[0,10],[0,0]]]".to_string()),
+                (1, "[[[10,0],[20,0],[20,10],[10,10],[10,0]]]".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn junctions_kept() {
+        let result = topo_simplify(
+            &[
+                r#"{"type": "LineString", "coordinates": [[0, 0], [5, 0.1], [10, 0]]}"#,
+                r#"{"type": "LineString", "coordinates": [[5, 0.1], [5.2, 5], [5, 10]]}"#,
+                r#"{"type": "Point", "coordinates": [3, 3]}"#,
+            ],
+            1.0,
+        );
+        assert_eq!(
+            result,
+            vec![
+                // The start point of the second line is kept
+                (0, "[[0,0],[5,0.1],[10,0]]".to_string()),
+                (1, "[[5,0.1],[5,10]]".to_string()),
+                (2, "[3,3]".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn collapsing_ring() {
+        // The island would collapse, so the hole with the same boundary is kept as well
+        let result = topo_simplify(
+            &[
+                r#"{"type": "Polygon", "coordinates": [[[1, 1], [2, 1], [2, 2], [1, 2], [1, 1]]]}"#,
+                r#"{"type": "Polygon", "coordinates": [[[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]], [[1, 1], [1, 2], [2, 2], [2, 1], [1, 1]]]}"#,
+            ],
+            5.0,
+        );
+        assert_eq!(
+            result,
+            vec![
+                (0, "[[[1,1],[2,1],[2,2],[1,2],[1,1]]]".to_string()),
+                (
+                    1,
+                    "[[[0,0],[10,0],[10,10],[0,10],[0,0]],[[1,1],[1,2],[2,2],[2,1],[1,1]]]"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,54 @@
+//! Tiny Well-Known Binary (TWKB) conversions.
+//!
+//! See <https://github.com/TWKB/Specification/blob/master/twkb.md>
+//!
+//! # Usage example:
+//!
+//! Convert a TWKB geometry to WKT:
+//!
+//! ```
+//! use geozero::{ToWkt, twkb::Twkb};
+//!
+//! let twkb = Twkb(vec![0x02, 0x00, 0x02, 0x02, 0x02, 0x08, 0x08]);
+//! assert_eq!(twkb.to_wkt().unwrap(), "LINESTRING(1 1,5 5)");
+//! ```
+pub(crate) mod twkb_common;
+pub(crate) mod twkb_reader;
+pub(crate) mod twkb_writer;
+
+pub use twkb_common::*;
+pub use twkb_reader::*;
+pub use twkb_writer::*;
+
+pub(crate) mod conversion {
+    use crate::error::Result;
+    use crate::twkb::TwkbWriter;
+    use crate::{CoordDimensions, GeozeroGeometry};
+
+    /// Convert to TWKB.
+    ///
+    /// # Usage example:
+    ///
+    /// Convert a geo-types `Point` to TWKB:
+    ///
+    /// ```
+    /// use geozero::{CoordDimensions, ToTwkb};
+    ///
+    /// let geom: geo_types::Geometry<f64> = geo_types::Point::new(10.0, -20.0).into();
+    /// let twkb = geom.to_twkb(CoordDimensions::xy(), 0).unwrap();
+    /// assert_eq!(&twkb, &[0x01, 0x00, 0x14, 0x27]);
+    /// ```
+    pub trait ToTwkb {
+        /// Convert to TWKB with given number of decimal digits for X/Y.
+        fn to_twkb(&self, dims: CoordDimensions, xy_precision: i8) -> Result<Vec<u8>>;
+    }
+
+    impl<T: GeozeroGeometry> ToTwkb for T {
+        fn to_twkb(&self, dims: CoordDimensions, xy_precision: i8) -> Result<Vec<u8>> {
+            let mut twkb: Vec<u8> = Vec::new();
+            let mut writer = TwkbWriter::with_opts(&mut twkb, dims, xy_precision, 0, 0);
+            self.process_geom(&mut writer)?;
+            Ok(twkb)
+        }
+    }
+}
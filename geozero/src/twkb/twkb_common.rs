@@ -0,0 +1,102 @@
+use crate::error::{GeozeroError, Result};
+use std::io::{Read, Write};
+
+/// TWKB geometry types
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum TwkbGeometryType {
+    Point = 1,
+    LineString = 2,
+    Polygon = 3,
+    MultiPoint = 4,
+    MultiLineString = 5,
+    MultiPolygon = 6,
+    GeometryCollection = 7,
+}
+
+impl TwkbGeometryType {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(TwkbGeometryType::Point),
+            2 => Some(TwkbGeometryType::LineString),
+            3 => Some(TwkbGeometryType::Polygon),
+            4 => Some(TwkbGeometryType::MultiPoint),
+            5 => Some(TwkbGeometryType::MultiLineString),
+            6 => Some(TwkbGeometryType::MultiPolygon),
+            7 => Some(TwkbGeometryType::GeometryCollection),
+            _ => None,
+        }
+    }
+}
+
+// Metadata header flags
+pub(crate) const BBOX_FLAG: u8 = 0b0000_0001;
+pub(crate) const SIZE_FLAG: u8 = 0b0000_0010;
+pub(crate) const IDLIST_FLAG: u8 = 0b0000_0100;
+pub(crate) const EXTENDED_DIMS_FLAG: u8 = 0b0000_1000;
+pub(crate) const EMPTY_FLAG: u8 = 0b0001_0000;
+
+pub(crate) fn zigzag_encode(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+pub(crate) fn zigzag_decode(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+pub(crate) fn write_uvarint<W: Write>(out: &mut W, mut value: u64) -> Result<()> {
+    while value >= 0x80 {
+        out.write_all(&[(value as u8) | 0x80])?;
+        value >>= 7;
+    }
+    out.write_all(&[value as u8])?;
+    Ok(())
+}
+
+pub(crate) fn write_varint<W: Write>(out: &mut W, value: i64) -> Result<()> {
+    write_uvarint(out, zigzag_encode(value))
+}
+
+pub(crate) fn read_u8<R: Read>(raw: &mut R) -> Result<u8> {
+    let mut buf = [0; 1];
+    raw.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+pub(crate) fn read_uvarint<R: Read>(raw: &mut R) -> Result<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let byte = read_u8(raw)?;
+        if shift > 63 {
+            return Err(GeozeroError::Geometry("TWKB varint overflow".to_string()));
+        }
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+pub(crate) fn read_varint<R: Read>(raw: &mut R) -> Result<i64> {
+    Ok(zigzag_decode(read_uvarint(raw)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn varints() -> Result<()> {
+        for value in [0, 1, -1, 63, -64, 64, 300, -300, i64::MAX, i64::MIN] {
+            let mut buf = Vec::new();
+            write_varint(&mut buf, value)?;
+            assert_eq!(read_varint(&mut buf.as_slice())?, value);
+        }
+        let mut buf = Vec::new();
+        write_varint(&mut buf, -1)?;
+        write_uvarint(&mut buf, 300)?;
+        assert_eq!(buf, [0x01, 0xac, 0x02]);
+        Ok(())
+    }
+}
@@ -0,0 +1,270 @@
+use crate::error::{GeozeroError, Result};
+use crate::twkb::twkb_common::*;
+use crate::{GeomProcessor, GeozeroGeometry};
+use std::io::Read;
+
+/// TWKB reader.
+pub struct Twkb<B: AsRef<[u8]>>(pub B);
+
+impl<B: AsRef<[u8]>> GeozeroGeometry for Twkb<B> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_twkb_geom(&mut self.0.as_ref(), processor)
+    }
+}
+
+/// Process TWKB geometry.
+pub fn process_twkb_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
+    process_twkb_geom_n(raw, 0, processor)
+}
+
+#[derive(Debug)]
+struct TwkbInfo {
+    geom_type: TwkbGeometryType,
+    xy_scale: f64,
+    z_scale: Option<f64>,
+    m_scale: Option<f64>,
+    has_idlist: bool,
+    is_empty: bool,
+}
+
+impl TwkbInfo {
+    fn num_dims(&self) -> usize {
+        2 + usize::from(self.z_scale.is_some()) + usize::from(self.m_scale.is_some())
+    }
+}
+
+fn read_twkb_header<R: Read>(raw: &mut R) -> Result<TwkbInfo> {
+    let type_and_precision = read_u8(raw)?;
+    let geom_type = TwkbGeometryType::from_u8(type_and_precision & 0x0f).ok_or_else(|| {
+        GeozeroError::Geometry(format!(
+            "Unknown TWKB geometry type {}",
+            type_and_precision & 0x0f
+        ))
+    })?;
+    let xy_precision = zigzag_decode(u64::from(type_and_precision >> 4));
+    let metadata = read_u8(raw)?;
+    let (mut z_scale, mut m_scale) = (None, None);
+    if metadata & EXTENDED_DIMS_FLAG != 0 {
+        let extended_dims = read_u8(raw)?;
+        if extended_dims & 0b01 != 0 {
+            z_scale = Some(10f64.powi(i32::from((extended_dims >> 2) & 0b111)));
+        }
+        if extended_dims & 0b10 != 0 {
+            m_scale = Some(10f64.powi(i32::from(extended_dims >> 5)));
+        }
+    }
+    let mut info = TwkbInfo {
+        geom_type,
+        xy_scale: 10f64.powi(xy_precision as i32),
+        z_scale,
+        m_scale,
+        has_idlist: metadata & IDLIST_FLAG != 0,
+        is_empty: metadata & EMPTY_FLAG != 0,
+    };
+    if metadata & SIZE_FLAG != 0 {
+        let _size = read_uvarint(raw)?;
+    }
+    if metadata & BBOX_FLAG != 0 && !info.is_empty {
+        // min and delta for each dimension
+        for _ in 0..info.num_dims() * 2 {
+            read_varint(raw)?;
+        }
+    }
+    if info.is_empty {
+        info.has_idlist = false;
+    }
+    Ok(info)
+}
+
+/// Reader state of delta encoded coordinates
+struct CoordReader<'a> {
+    info: &'a TwkbInfo,
+    prev: [i64; 4],
+}
+
+impl CoordReader<'_> {
+    fn process_coord<R: Read, P: GeomProcessor>(
+        &mut self,
+        raw: &mut R,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        for prev in self.prev.iter_mut().take(self.info.num_dims()) {
+            *prev += read_varint(raw)?;
+        }
+        let x = self.prev[0] as f64 / self.info.xy_scale;
+        let y = self.prev[1] as f64 / self.info.xy_scale;
+        if processor.multi_dim() {
+            let dims = processor.dimensions();
+            let z = match self.info.z_scale {
+                Some(scale) if dims.z => Some(self.prev[2] as f64 / scale),
+                _ => None,
+            };
+            let m_dim = if self.info.z_scale.is_some() { 3 } else { 2 };
+            let m = match self.info.m_scale {
+                Some(scale) if dims.m => Some(self.prev[m_dim] as f64 / scale),
+                _ => None,
+            };
+            processor.coordinate(x, y, z, m, None, None, idx)
+        } else {
+            processor.xy(x, y, idx)
+        }
+    }
+
+    fn process_linestring<R: Read, P: GeomProcessor>(
+        &mut self,
+        raw: &mut R,
+        tagged: bool,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let num_points = if self.info.is_empty {
+            0
+        } else {
+            read_uvarint(raw)? as usize
+        };
+        processor.linestring_begin(tagged, num_points, idx)?;
+        for i in 0..num_points {
+            self.process_coord(raw, i, processor)?;
+        }
+        processor.linestring_end(tagged, idx)
+    }
+
+    fn process_polygon<R: Read, P: GeomProcessor>(
+        &mut self,
+        raw: &mut R,
+        tagged: bool,
+        idx: usize,
+        processor: &mut P,
+    ) -> Result<()> {
+        let num_rings = if self.info.is_empty {
+            0
+        } else {
+            read_uvarint(raw)? as usize
+        };
+        processor.polygon_begin(tagged, num_rings, idx)?;
+        for i in 0..num_rings {
+            self.process_linestring(raw, false, i, processor)?;
+        }
+        processor.polygon_end(tagged, idx)
+    }
+}
+
+/// Read number of geometries of a multi geometry or collection and skip the id list
+fn read_num_geoms<R: Read>(raw: &mut R, info: &TwkbInfo) -> Result<usize> {
+    if info.is_empty {
+        return Ok(0);
+    }
+    let num_geoms = read_uvarint(raw)? as usize;
+    if info.has_idlist {
+        for _ in 0..num_geoms {
+            read_varint(raw)?;
+        }
+    }
+    Ok(num_geoms)
+}
+
+fn process_twkb_geom_n<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    let info = read_twkb_header(raw)?;
+    let mut coords = CoordReader {
+        info: &info,
+        prev: [0; 4],
+    };
+    match info.geom_type {
+        TwkbGeometryType::Point => {
+            if info.is_empty {
+                processor.empty_point(idx)?;
+            } else {
+                processor.point_begin(idx)?;
+                coords.process_coord(raw, 0, processor)?;
+                processor.point_end(idx)?;
+            }
+        }
+        TwkbGeometryType::LineString => {
+            coords.process_linestring(raw, true, idx, processor)?;
+        }
+        TwkbGeometryType::Polygon => {
+            coords.process_polygon(raw, true, idx, processor)?;
+        }
+        TwkbGeometryType::MultiPoint => {
+            let num_points = read_num_geoms(raw, &info)?;
+            processor.multipoint_begin(num_points, idx)?;
+            for i in 0..num_points {
+                coords.process_coord(raw, i, processor)?;
+            }
+            processor.multipoint_end(idx)?;
+        }
+        TwkbGeometryType::MultiLineString => {
+            let num_lines = read_num_geoms(raw, &info)?;
+            processor.multilinestring_begin(num_lines, idx)?;
+            for i in 0..num_lines {
+                coords.process_linestring(raw, false, i, processor)?;
+            }
+            processor.multilinestring_end(idx)?;
+        }
+        TwkbGeometryType::MultiPolygon => {
+            let num_polygons = read_num_geoms(raw, &info)?;
+            processor.multipolygon_begin(num_polygons, idx)?;
+            for i in 0..num_polygons {
+                coords.process_polygon(raw, false, i, processor)?;
+            }
+            processor.multipolygon_end(idx)?;
+        }
+        TwkbGeometryType::GeometryCollection => {
+            let num_geoms = read_num_geoms(raw, &info)?;
+            processor.geometrycollection_begin(num_geoms, idx)?;
+            for i in 0..num_geoms {
+                process_twkb_geom_n(raw, i, processor)?;
+            }
+            processor.geometrycollection_end(idx)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::wkt::WktWriter;
+    use crate::{CoordDimensions, ToWkt};
+
+    #[test]
+    fn postgis_linestring() {
+        // SELECT ST_AsTWKB('LINESTRING(1 1,5 5)'::geometry)
+        let twkb = hex::decode("02000202020808").unwrap();
+        assert_eq!(Twkb(twkb).to_wkt().unwrap(), "LINESTRING(1 1,5 5)");
+    }
+
+    #[test]
+    fn bbox_and_size() {
+        // SELECT ST_AsTWKB('POINT(1 2)'::geometry, 0, 0, 0, true, true)
+        let twkb = hex::decode("010306020004000204").unwrap();
+        assert_eq!(Twkb(twkb).to_wkt().unwrap(), "POINT(1 2)");
+    }
+
+    #[test]
+    fn xyz() -> Result<()> {
+        // LINESTRING Z (1.5 2.5 10,2 3 12) with xy precision 1 and z precision 0
+        let twkb = hex::decode("220801021e32140a0a04").unwrap();
+        let mut wkt_data: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::with_dims(&mut wkt_data, CoordDimensions::xyz());
+        process_twkb_geom(&mut twkb.as_slice(), &mut writer)?;
+        assert_eq!(
+            std::str::from_utf8(&wkt_data).unwrap(),
+            "LINESTRING Z (1.5 2.5 10,2 3 12)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn empty() {
+        let twkb = [0x01, EMPTY_FLAG];
+        assert_eq!(Twkb(twkb).to_wkt().unwrap(), "POINT EMPTY");
+        let twkb = [0x02, EMPTY_FLAG];
+        assert_eq!(Twkb(twkb).to_wkt().unwrap(), "LINESTRING EMPTY");
+    }
+}
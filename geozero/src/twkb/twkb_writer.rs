@@ -0,0 +1,387 @@
+use crate::error::{GeozeroError, Result};
+use crate::twkb::twkb_common::*;
+use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
+use std::io::Write;
+
+/// TWKB writer.
+///
+/// Geometries are buffered until the end of the outermost geometry,
+/// since the optional bbox and size headers precede the coordinates.
+pub struct TwkbWriter<W: Write> {
+    dims: CoordDimensions,
+    /// Number of decimal digits of X/Y (-7 to 7)
+    xy_precision: i8,
+    /// Number of decimal digits of Z (0 to 7)
+    z_precision: u8,
+    /// Number of decimal digits of M (0 to 7)
+    m_precision: u8,
+    /// Write bounding box header
+    bbox: bool,
+    /// Write size header
+    size: bool,
+    /// Geometries in progress (nested in collections)
+    geoms: Vec<GeomBuffer>,
+    out: W,
+}
+
+/// Encoded geometry in progress
+struct GeomBuffer {
+    geom_type: TwkbGeometryType,
+    body: Vec<u8>,
+    /// Previous coordinate for delta encoding
+    prev: [i64; 4],
+    min: [i64; 4],
+    max: [i64; 4],
+    /// Number of coordinates or collection members
+    num_items: usize,
+}
+
+impl GeomBuffer {
+    fn new(geom_type: TwkbGeometryType) -> Self {
+        GeomBuffer {
+            geom_type,
+            body: Vec::new(),
+            prev: [0; 4],
+            min: [i64::MAX; 4],
+            max: [i64::MIN; 4],
+            num_items: 0,
+        }
+    }
+    fn expand(&mut self, min: &[i64; 4], max: &[i64; 4]) {
+        for (cur, val) in self.min.iter_mut().zip(min) {
+            *cur = (*cur).min(*val);
+        }
+        for (cur, val) in self.max.iter_mut().zip(max) {
+            *cur = (*cur).max(*val);
+        }
+    }
+}
+
+impl<W: Write> TwkbWriter<W> {
+    pub fn new(out: W, xy_precision: i8) -> Self {
+        Self::with_opts(out, CoordDimensions::default(), xy_precision, 0, 0)
+    }
+
+    pub fn with_opts(
+        out: W,
+        dims: CoordDimensions,
+        xy_precision: i8,
+        z_precision: u8,
+        m_precision: u8,
+    ) -> Self {
+        TwkbWriter {
+            dims,
+            xy_precision: xy_precision.clamp(-7, 7),
+            z_precision: z_precision.min(7),
+            m_precision: m_precision.min(7),
+            bbox: false,
+            size: false,
+            geoms: Vec::new(),
+            out,
+        }
+    }
+
+    /// Write bounding box header
+    pub fn with_bbox(mut self, bbox: bool) -> Self {
+        self.bbox = bbox;
+        self
+    }
+
+    /// Write size header
+    pub fn with_size(mut self, size: bool) -> Self {
+        self.size = size;
+        self
+    }
+
+    fn num_dims(&self) -> usize {
+        2 + usize::from(self.dims.z) + usize::from(self.dims.m)
+    }
+
+    fn current(&mut self) -> Result<&mut GeomBuffer> {
+        self.geoms
+            .last_mut()
+            .ok_or_else(|| GeozeroError::Geometry("TWKB geometry not started".to_string()))
+    }
+
+    fn geom_begin(&mut self, geom_type: TwkbGeometryType) -> Result<()> {
+        if let Some(parent) = self.geoms.last_mut() {
+            if parent.geom_type != TwkbGeometryType::GeometryCollection {
+                return Err(GeozeroError::Geometry(format!(
+                    "{geom_type:?} can not be nested in {:?}",
+                    parent.geom_type
+                )));
+            }
+            parent.num_items += 1;
+        }
+        self.geoms.push(GeomBuffer::new(geom_type));
+        Ok(())
+    }
+
+    fn write_count(&mut self, size: usize) -> Result<()> {
+        let geom = self.current()?;
+        write_uvarint(&mut geom.body, size as u64)
+    }
+
+    fn geom_end(&mut self) -> Result<()> {
+        let geom = self
+            .geoms
+            .pop()
+            .ok_or_else(|| GeozeroError::Geometry("TWKB geometry not started".to_string()))?;
+        let is_empty = geom.num_items == 0;
+
+        let mut twkb = Vec::with_capacity(geom.body.len() + 16);
+        twkb.push(geom.geom_type as u8 | (zigzag_encode(i64::from(self.xy_precision)) as u8) << 4);
+        let mut metadata = 0;
+        if is_empty {
+            metadata |= EMPTY_FLAG;
+        } else {
+            if self.bbox {
+                metadata |= BBOX_FLAG;
+            }
+            if self.size {
+                metadata |= SIZE_FLAG;
+            }
+        }
+        if self.dims.z || self.dims.m {
+            metadata |= EXTENDED_DIMS_FLAG;
+        }
+        twkb.push(metadata);
+        if self.dims.z || self.dims.m {
+            twkb.push(
+                u8::from(self.dims.z)
+                    | u8::from(self.dims.m) << 1
+                    | self.z_precision << 2
+                    | self.m_precision << 5,
+            );
+        }
+        if !is_empty {
+            let mut content = Vec::with_capacity(geom.body.len() + 16);
+            if self.bbox {
+                for (min, max) in geom.min.iter().zip(&geom.max).take(self.num_dims()) {
+                    write_varint(&mut content, *min)?;
+                    write_varint(&mut content, max - min)?;
+                }
+            }
+            content.extend_from_slice(&geom.body);
+            if self.size {
+                write_uvarint(&mut twkb, content.len() as u64)?;
+            }
+            twkb.extend_from_slice(&content);
+        }
+
+        if let Some(parent) = self.geoms.last_mut() {
+            if !is_empty {
+                parent.expand(&geom.min, &geom.max);
+            }
+            parent.body.extend_from_slice(&twkb);
+        } else {
+            self.out.write_all(&twkb)?;
+        }
+        Ok(())
+    }
+
+    fn write_coord(&mut self, x: f64, y: f64, z: Option<f64>, m: Option<f64>) -> Result<()> {
+        let xy_scale = 10f64.powi(i32::from(self.xy_precision));
+        let mut values = [0i64; 4];
+        values[0] = (x * xy_scale).round() as i64;
+        values[1] = (y * xy_scale).round() as i64;
+        let mut num_dims = 2;
+        if self.dims.z {
+            let z_scale = 10f64.powi(i32::from(self.z_precision));
+            values[num_dims] = (z.unwrap_or(0.0) * z_scale).round() as i64;
+            num_dims += 1;
+        }
+        if self.dims.m {
+            let m_scale = 10f64.powi(i32::from(self.m_precision));
+            values[num_dims] = (m.unwrap_or(0.0) * m_scale).round() as i64;
+            num_dims += 1;
+        }
+        let geom = self.current()?;
+        for (value, prev) in values.iter().zip(geom.prev.iter_mut()).take(num_dims) {
+            write_varint(&mut geom.body, value - *prev)?;
+            *prev = *value;
+        }
+        geom.expand(&values, &values);
+        geom.num_items += 1;
+        Ok(())
+    }
+}
+
+impl<W: Write> GeomProcessor for TwkbWriter<W> {
+    fn dimensions(&self) -> CoordDimensions {
+        self.dims
+    }
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        self.write_coord(x, y, None, None)
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: Option<f64>,
+        m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        _idx: usize,
+    ) -> Result<()> {
+        self.write_coord(x, y, z, m)
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        self.geom_begin(TwkbGeometryType::Point)?;
+        self.geom_end()
+    }
+    fn point_begin(&mut self, _idx: usize) -> Result<()> {
+        self.geom_begin(TwkbGeometryType::Point)
+    }
+    fn point_end(&mut self, _idx: usize) -> Result<()> {
+        self.geom_end()
+    }
+    fn multipoint_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.geom_begin(TwkbGeometryType::MultiPoint)?;
+        self.write_count(size)
+    }
+    fn multipoint_end(&mut self, _idx: usize) -> Result<()> {
+        self.geom_end()
+    }
+    fn linestring_begin(&mut self, tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        if tagged {
+            self.geom_begin(TwkbGeometryType::LineString)?;
+        }
+        self.write_count(size)
+    }
+    fn linestring_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        if tagged {
+            self.geom_end()?;
+        }
+        Ok(())
+    }
+    fn multilinestring_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.geom_begin(TwkbGeometryType::MultiLineString)?;
+        self.write_count(size)
+    }
+    fn multilinestring_end(&mut self, _idx: usize) -> Result<()> {
+        self.geom_end()
+    }
+    fn polygon_begin(&mut self, tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        if tagged {
+            self.geom_begin(TwkbGeometryType::Polygon)?;
+        }
+        self.write_count(size)
+    }
+    fn polygon_end(&mut self, tagged: bool, _idx: usize) -> Result<()> {
+        if tagged {
+            self.geom_end()?;
+        }
+        Ok(())
+    }
+    fn multipolygon_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.geom_begin(TwkbGeometryType::MultiPolygon)?;
+        self.write_count(size)
+    }
+    fn multipolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.geom_end()
+    }
+    fn geometrycollection_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
+        self.geom_begin(TwkbGeometryType::GeometryCollection)?;
+        self.write_count(size)
+    }
+    fn geometrycollection_end(&mut self, _idx: usize) -> Result<()> {
+        self.geom_end()
+    }
+}
+
+impl<W: Write> PropertyProcessor for TwkbWriter<W> {}
+
+impl<W: Write> FeatureProcessor for TwkbWriter<W> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::twkb::Twkb;
+    use crate::wkt::{WktStr, WktWriter};
+    use crate::{GeozeroGeometry, ToWkt};
+
+    fn roundtrip(wkt: &str, dims: CoordDimensions, xy_precision: i8) -> Result<Vec<u8>> {
+        let mut twkb: Vec<u8> = Vec::new();
+        let mut writer = TwkbWriter::with_opts(&mut twkb, dims, xy_precision, 1, 0)
+            .with_bbox(true)
+            .with_size(true);
+        WktStr(wkt).process_geom(&mut writer)?;
+
+        let mut wkt_out: Vec<u8> = Vec::new();
+        Twkb(&twkb).process_geom(&mut WktWriter::with_dims(&mut wkt_out, dims))?;
+        assert_eq!(std::str::from_utf8(&wkt_out).unwrap(), wkt);
+        Ok(twkb)
+    }
+
+    #[test]
+    fn linestring() -> Result<()> {
+        let wkt = "LINESTRING(8.54321 47.36612,8.54335 47.36598,8.54352 47.36601)";
+        let mut twkb: Vec<u8> = Vec::new();
+        WktStr(wkt).process_geom(&mut TwkbWriter::new(&mut twkb, 5))?;
+        assert_eq!(hex::encode(&twkb), "a20003e2a468c899c2041c1b2206");
+        assert_eq!(Twkb(&twkb).to_wkt()?, wkt);
+
+        // Deltas need one byte per ordinate, compared to 57 bytes in WKB
+        assert_eq!(twkb.len(), 14);
+        Ok(())
+    }
+
+    #[test]
+    fn postgis_compat() -> Result<()> {
+        let mut twkb: Vec<u8> = Vec::new();
+        WktStr("LINESTRING(1 1,5 5)").process_geom(&mut TwkbWriter::new(&mut twkb, 0))?;
+        assert_eq!(hex::encode(&twkb), "02000202020808");
+
+        let mut twkb: Vec<u8> = Vec::new();
+        let mut writer = TwkbWriter::new(&mut twkb, 0)
+            .with_bbox(true)
+            .with_size(true);
+        WktStr("POINT(1 2)").process_geom(&mut writer)?;
+        assert_eq!(hex::encode(&twkb), "010306020004000204");
+        Ok(())
+    }
+
+    #[test]
+    fn geometries() -> Result<()> {
+        roundtrip("POINT(1.5 -2)", CoordDimensions::xy(), 1)?;
+        roundtrip("MULTIPOINT(1 2,-3 4)", CoordDimensions::xy(), 0)?;
+        roundtrip(
+            "POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,2 4,4 4,2 2))",
+            CoordDimensions::xy(),
+            0,
+        )?;
+        roundtrip(
+            "MULTILINESTRING((1.25 2.5,3 4),(5 6,7 8.75))",
+            CoordDimensions::xy(),
+            2,
+        )?;
+        roundtrip(
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((10 10,11 10,11 11,10 10)))",
+            CoordDimensions::xy(),
+            0,
+        )?;
+        roundtrip(
+            "GEOMETRYCOLLECTION(POINT(100 200),LINESTRING(1 2,3 4))",
+            CoordDimensions::xy(),
+            0,
+        )?;
+        roundtrip("LINESTRING Z (1 2 3.5,4 5 6.5)", CoordDimensions::xyz(), 0)?;
+        roundtrip("POINT EMPTY", CoordDimensions::xy(), 0)?;
+        roundtrip("LINESTRING EMPTY", CoordDimensions::xy(), 0)?;
+        Ok(())
+    }
+
+    #[test]
+    fn negative_precision() -> Result<()> {
+        // Coordinates are rounded to hundreds
+        let mut twkb: Vec<u8> = Vec::new();
+        WktStr("GEOMETRYCOLLECTION(POINT(140 249),LINESTRING(1 2,351 -449))")
+            .process_geom(&mut TwkbWriter::new(&mut twkb, -2))?;
+        assert_eq!(
+            Twkb(&twkb).to_wkt()?,
+            "GEOMETRYCOLLECTION(POINT(100 200),LINESTRING(0 0,400 -400))"
+        );
+        Ok(())
+    }
+}
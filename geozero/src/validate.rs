@@ -0,0 +1,357 @@
+use crate::error::Result;
+use crate::feature_processor::FeatureProcessor;
+use crate::geometry_processor::GeomProcessor;
+use crate::property_processor::PropertyProcessor;
+use std::fmt;
+
+/// Validity problem found by [`Validate`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationIssue {
+    /// Index of the feature, `None` when processing a single geometry.
+    pub feature_idx: Option<u64>,
+    pub reason: IssueReason,
+}
+
+/// Kind of a [`ValidationIssue`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum IssueReason {
+    /// Ring with less than 4 or line with less than 2 points
+    TooFewPoints(usize),
+    /// Ring with different start and end point
+    UnclosedRing,
+    /// Ring crossing or touching itself
+    SelfIntersection { x: f64, y: f64 },
+    /// Point equal to its predecessor
+    DuplicatePoint { x: f64, y: f64 },
+}
+
+impl fmt::Display for IssueReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueReason::TooFewPoints(n) => write!(f, "too few points ({n})"),
+            IssueReason::UnclosedRing => f.write_str("unclosed ring"),
+            IssueReason::SelfIntersection { x, y } => {
+                write!(f, "ring self-intersection near ({x} {y})")
+            }
+            IssueReason::DuplicatePoint { x, y } => {
+                write!(f, "duplicate consecutive point ({x} {y})")
+            }
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(idx) = self.feature_idx {
+            write!(f, "feature {idx}: ")?;
+        }
+        self.reason.fmt(f)
+    }
+}
+
+/// Processor checking OGC Simple Features validity rules.
+///
+/// Issues are collected instead of aborting processing, so a whole dataset can be checked in one pass.
+///
+/// # Usage example:
+///
+/// ```
+/// # #[cfg(feature = "with-wkt")]
+/// # {
+/// use geozero::{wkt::WktStr, GeozeroGeometry, Validate};
+///
+/// let mut validate = Validate::new();
+/// WktStr("POLYGON((0 0,1 0,1 1,0 1))").process_geom(&mut validate).unwrap();
+/// for issue in validate.into_report() {
+///     println!("{issue}");
+/// }
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Validate {
+    check_self_intersections: bool,
+    feature_idx: Option<u64>,
+    /// Open polygons, whose linestrings are rings
+    polygon_depth: usize,
+    /// Open compound curves, whose linestrings are not rings
+    compoundcurve_depth: usize,
+    /// Coordinates of the current linestring
+    line: Option<Vec<(f64, f64)>>,
+    issues: Vec<ValidationIssue>,
+}
+
+impl Validate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Check polygon rings for self-intersections (default: `false`).
+    ///
+    /// This check is quadratic in the number of ring points.
+    pub fn with_self_intersections(mut self, check: bool) -> Self {
+        self.check_self_intersections = check;
+        self
+    }
+    /// Issues found so far.
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+    /// Consume the processor and return all issues found.
+    pub fn into_report(self) -> Vec<ValidationIssue> {
+        self.issues
+    }
+    fn issue(&mut self, reason: IssueReason) {
+        self.issues.push(ValidationIssue {
+            feature_idx: self.feature_idx,
+            reason,
+        });
+    }
+    fn check_line(&mut self, line: &[(f64, f64)]) {
+        let is_ring = self.polygon_depth > 0 && self.compoundcurve_depth == 0;
+        let min_points = if is_ring { 4 } else { 2 };
+        if line.len() < min_points {
+            self.issue(IssueReason::TooFewPoints(line.len()));
+        }
+        for pair in line.windows(2) {
+            if pair[0] == pair[1] {
+                let (x, y) = pair[1];
+                self.issue(IssueReason::DuplicatePoint { x, y });
+            }
+        }
+        if !is_ring || line.is_empty() {
+            return;
+        }
+        if line.first() != line.last() {
+            self.issue(IssueReason::UnclosedRing);
+        } else if self.check_self_intersections {
+            let mut ring = line.to_vec();
+            ring.dedup();
+            if let Some((x, y)) = ring_self_intersection(&ring) {
+                self.issue(IssueReason::SelfIntersection { x, y });
+            }
+        }
+    }
+}
+
+/// First point where two non-adjacent segments of a closed ring meet.
+fn ring_self_intersection(ring: &[(f64, f64)]) -> Option<(f64, f64)> {
+    let segments = ring.len().saturating_sub(1);
+    for i in 0..segments {
+        for j in i + 2..segments {
+            if i == 0 && j == segments - 1 {
+                // First and last segment share the closing point
+                continue;
+            }
+            let intersection = segments_intersection(ring[i], ring[i + 1], ring[j], ring[j + 1]);
+            if intersection.is_some() {
+                return intersection;
+            }
+        }
+    }
+    None
+}
+
+fn orientation(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Whether `p` lies within the bounding box of segment `a`-`b`.
+fn in_bounds(a: (f64, f64), b: (f64, f64), p: (f64, f64)) -> bool {
+    p.0 >= a.0.min(b.0) && p.0 <= a.0.max(b.0) && p.1 >= a.1.min(b.1) && p.1 <= a.1.max(b.1)
+}
+
+/// Intersection point of two segments, if any.
+fn segments_intersection(
+    p1: (f64, f64),
+    p2: (f64, f64),
+    q1: (f64, f64),
+    q2: (f64, f64),
+) -> Option<(f64, f64)> {
+    let d1 = orientation(q1, q2, p1);
+    let d2 = orientation(q1, q2, p2);
+    let d3 = orientation(p1, p2, q1);
+    let d4 = orientation(p1, p2, q2);
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        let t = d1 / (d1 - d2);
+        return Some((p1.0 + t * (p2.0 - p1.0), p1.1 + t * (p2.1 - p1.1)));
+    }
+    // Touching or collinear overlapping segments
+    if d1 == 0.0 && in_bounds(q1, q2, p1) {
+        Some(p1)
+    } else if d2 == 0.0 && in_bounds(q1, q2, p2) {
+        Some(p2)
+    } else if d3 == 0.0 && in_bounds(p1, p2, q1) {
+        Some(q1)
+    } else if d4 == 0.0 && in_bounds(p1, p2, q2) {
+        Some(q2)
+    } else {
+        None
+    }
+}
+
+impl GeomProcessor for Validate {
+    fn xy(&mut self, x: f64, y: f64, _idx: usize) -> Result<()> {
+        if let Some(line) = self.line.as_mut() {
+            line.push((x, y));
+        }
+        Ok(())
+    }
+    fn coordinate(
+        &mut self,
+        x: f64,
+        y: f64,
+        _z: Option<f64>,
+        _m: Option<f64>,
+        _t: Option<f64>,
+        _tm: Option<u64>,
+        idx: usize,
+    ) -> Result<()> {
+        self.xy(x, y, idx)
+    }
+    fn empty_point(&mut self, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn linestring_begin(&mut self, _tagged: bool, size: usize, _idx: usize) -> Result<()> {
+        self.line = Some(Vec::with_capacity(size));
+        Ok(())
+    }
+    fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        if let Some(line) = self.line.take() {
+            self.check_line(&line);
+        }
+        Ok(())
+    }
+    fn polygon_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.polygon_depth += 1;
+        Ok(())
+    }
+    fn polygon_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.polygon_depth -= 1;
+        Ok(())
+    }
+    fn circularstring_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn compoundcurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.compoundcurve_depth += 1;
+        Ok(())
+    }
+    fn compoundcurve_end(&mut self, _idx: usize) -> Result<()> {
+        self.compoundcurve_depth -= 1;
+        Ok(())
+    }
+    fn curvepolygon_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        self.polygon_depth += 1;
+        Ok(())
+    }
+    fn curvepolygon_end(&mut self, _idx: usize) -> Result<()> {
+        self.polygon_depth -= 1;
+        Ok(())
+    }
+    fn multicurve_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn multisurface_begin(&mut self, _size: usize, _idx: usize) -> Result<()> {
+        Ok(())
+    }
+    fn triangle_begin(&mut self, _tagged: bool, _size: usize, _idx: usize) -> Result<()> {
+        self.polygon_depth += 1;
+        Ok(())
+    }
+    fn triangle_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        self.polygon_depth -= 1;
+        Ok(())
+    }
+}
+
+impl PropertyProcessor for Validate {}
+
+impl FeatureProcessor for Validate {
+    fn feature_begin(&mut self, idx: u64) -> Result<()> {
+        self.feature_idx = Some(idx);
+        Ok(())
+    }
+    fn feature_end(&mut self, _idx: u64) -> Result<()> {
+        self.feature_idx = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::WktStr;
+    use crate::GeozeroGeometry;
+
+    fn validate(wkt: &str) -> Vec<IssueReason> {
+        let mut validate = Validate::new().with_self_intersections(true);
+        WktStr(wkt).process_geom(&mut validate).unwrap();
+        validate
+            .into_report()
+            .into_iter()
+            .map(|issue| {
+                assert_eq!(issue.feature_idx, None);
+                issue.reason
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(validate("POLYGON((0 0,2 0,2 2,0 2,0 0))"), vec![]);
+        assert_eq!(validate("LINESTRING(0 0,2 0,0 0)"), vec![]);
+        assert_eq!(validate("MULTIPOINT(0 0,0 0)"), vec![]);
+    }
+
+    #[test]
+    fn rings() {
+        assert_eq!(
+            validate("POLYGON((0 0,1 0,1 1,0 1))"),
+            vec![IssueReason::UnclosedRing]
+        );
+        assert_eq!(
+            validate("POLYGON((0 0,1 0,0 0))"),
+            vec![IssueReason::TooFewPoints(3)]
+        );
+        assert_eq!(
+            validate("POLYGON((0 0,2 2,2 0,0 2,0 0))"),
+            vec![IssueReason::SelfIntersection { x: 1.0, y: 1.0 }]
+        );
+        assert_eq!(
+            validate("LINESTRING(0 0,1 1,1 1)"),
+            vec![IssueReason::DuplicatePoint { x: 1.0, y: 1.0 }]
+        );
+    }
+
+    #[test]
+    fn self_intersections_optional() {
+        let mut validate = Validate::new();
+        WktStr("POLYGON((0 0,2 2,2 0,0 2,0 0))")
+            .process_geom(&mut validate)
+            .unwrap();
+        assert!(validate.issues().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "with-geojson")]
+    fn dataset_report() {
+        use crate::geojson::GeoJson;
+        use crate::GeozeroDatasource;
+
+        let geojson = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 1]}},
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1]]]}}
+        ]}"#;
+        let mut validate = Validate::new();
+        GeoJson(geojson).process(&mut validate).unwrap();
+        let report = validate.into_report();
+        assert_eq!(
+            report,
+            vec![ValidationIssue {
+                feature_idx: Some(1),
+                reason: IssueReason::UnclosedRing
+            }]
+        );
+        assert_eq!(report[0].to_string(), "feature 1: unclosed ring");
+    }
+}
@@ -11,15 +11,18 @@
 //! assert_eq!(wkb.to_wkt().unwrap(), "POINT(10 -20)");
 //! ```
 pub(crate) mod wkb_common;
+pub(crate) mod wkb_hex;
 pub(crate) mod wkb_reader;
 pub(crate) mod wkb_writer;
 
 pub use wkb_common::*;
+pub use wkb_hex::*;
 pub use wkb_reader::*;
 pub use wkb_writer::*;
 
 pub(crate) mod conversion {
     use crate::error::Result;
+    use crate::wkb::wkb_hex::encode_hex;
     use crate::wkb::{WkbDialect, WkbWriter};
     use crate::{CoordDimensions, GeozeroGeometry};
 
@@ -53,6 +56,16 @@ pub(crate) mod conversion {
         fn to_ewkb(&self, dims: CoordDimensions, srid: Option<i32>) -> Result<Vec<u8>> {
             self.to_wkb_dialect(WkbDialect::Ewkb, dims, srid, Vec::new())
         }
+        /// Convert to uppercase hex encoded OGC WKB.
+        ///
+        /// Use `to_ascii_lowercase` on the result for lowercase hex digits.
+        fn to_wkb_hex(&self, dims: CoordDimensions) -> Result<String> {
+            Ok(encode_hex(&self.to_wkb(dims)?))
+        }
+        /// Convert to uppercase hex encoded EWKB, like the text output of a PostGIS geometry.
+        fn to_ewkb_hex(&self, dims: CoordDimensions, srid: Option<i32>) -> Result<String> {
+            Ok(encode_hex(&self.to_ewkb(dims, srid)?))
+        }
         /// Convert to GeoPackage WKB.
         fn to_gpkg_wkb(
             &self,
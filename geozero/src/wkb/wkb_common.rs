@@ -37,12 +37,23 @@ pub trait FromWkb {
 }
 
 /// WKB dialect.
-#[derive(PartialEq, Debug, Clone, Copy)]
+///
+/// Selects the flavor for reading with [process_wkb_type_geom](crate::wkb::process_wkb_type_geom)
+/// and writing with [WkbWriter](crate::wkb::WkbWriter).
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
 pub enum WkbDialect {
+    /// OGC/ISO WKB. Z and M are encoded in the type code (+1000, +2000, +3000), no SRID.
+    #[default]
     Wkb,
+    /// PostGIS EWKB. Z, M and SRID presence are flags in the high bits of the type code,
+    /// the SRID follows the type code of the outermost geometry.
     Ewkb,
+    /// GeoPackage binary. Header with magic, flags, SRID and optional envelope, followed by ISO WKB.
     Geopackage,
+    /// MySQL internal format. 4 byte SRID, followed by ISO WKB.
     MySQL,
+    /// SpatiaLite BLOB geometry. Header with SRID and MBR, nested geometries with
+    /// entity marks and an end mark.
     SpatiaLite,
 }
 
@@ -196,6 +207,25 @@ impl WKBGeometryType {
     }
 }
 
+/// Byte order of written WKB.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum ByteOrder {
+    /// Little endian (NDR)
+    #[default]
+    LittleEndian,
+    /// Big endian (XDR)
+    BigEndian,
+}
+
+impl From<ByteOrder> for scroll::Endian {
+    fn from(byte_order: ByteOrder) -> Self {
+        match byte_order {
+            ByteOrder::LittleEndian => scroll::LE,
+            ByteOrder::BigEndian => scroll::BE,
+        }
+    }
+}
+
 pub(crate) enum WKBByteOrder {
     Xdr = 0, // Big Endian
     Ndr = 1, // Little Endian
@@ -0,0 +1,119 @@
+use crate::error::{GeozeroError, Result};
+use crate::wkb::{process_ewkb_geom, process_wkb_geom};
+use crate::{GeomProcessor, GeozeroGeometry};
+
+/// Hex encoded WKB reader, e.g. for geometries in JSON APIs.
+pub struct WkbHex<S: AsRef<str>>(pub S);
+
+impl<S: AsRef<str>> GeozeroGeometry for WkbHex<S> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_wkb_hex(self.0.as_ref(), processor)
+    }
+}
+
+/// Hex encoded EWKB reader, e.g. for the text output of a PostGIS geometry.
+pub struct EwkbHex<S: AsRef<str>>(pub S);
+
+impl<S: AsRef<str>> GeozeroGeometry for EwkbHex<S> {
+    fn process_geom<P: GeomProcessor>(&self, processor: &mut P) -> Result<()> {
+        process_ewkb_hex(self.0.as_ref(), processor)
+    }
+}
+
+/// Process hex encoded WKB geometry.
+///
+/// Upper and lower case hex digits are accepted.
+pub fn process_wkb_hex<P: GeomProcessor>(hex: &str, processor: &mut P) -> Result<()> {
+    process_wkb_geom(&mut decode_hex(hex)?.as_slice(), processor)
+}
+
+/// Process hex encoded EWKB geometry.
+///
+/// Upper and lower case hex digits are accepted.
+pub fn process_ewkb_hex<P: GeomProcessor>(hex: &str, processor: &mut P) -> Result<()> {
+    process_ewkb_geom(&mut decode_hex(hex)?.as_slice(), processor)
+}
+
+/// Uppercase hex encoding, like the text output of PostGIS.
+pub(crate) fn encode_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        hex.push(DIGITS[(b >> 4) as usize] as char);
+        hex.push(DIGITS[(b & 0xf) as usize] as char);
+    }
+    hex
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>> {
+    fn digit(c: u8) -> Result<u8> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            b'A'..=b'F' => Ok(c - b'A' + 10),
+            _ => Err(GeozeroError::Geometry(format!(
+                "invalid hex digit `{}`",
+                c as char
+            ))),
+        }
+    }
+    let hex = hex.trim().as_bytes();
+    if hex.len() % 2 == 1 {
+        return Err(GeozeroError::Geometry(
+            "hex string with odd number of digits".to_string(),
+        ));
+    }
+    hex.chunks_exact(2)
+        .map(|pair| Ok(digit(pair[0])? << 4 | digit(pair[1])?))
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(feature = "with-wkt")]
+mod test {
+    use super::*;
+    use crate::wkt::WktStr;
+    use crate::{CoordDimensions, ToWkb, ToWkt};
+
+    // SELECT 'SRID=4326;POINT(10 -20)'::geometry
+    const POSTGIS_HEX: &str = "0101000020E6100000000000000000244000000000000034C0";
+
+    #[test]
+    fn ewkb_hex_roundtrip() -> Result<()> {
+        assert_eq!(EwkbHex(POSTGIS_HEX).to_wkt()?, "POINT(10 -20)");
+        assert_eq!(
+            WktStr("POINT(10 -20)").to_ewkb_hex(CoordDimensions::xy(), Some(4326))?,
+            POSTGIS_HEX
+        );
+        assert_eq!(
+            EwkbHex(POSTGIS_HEX.to_lowercase()).to_ewkb_hex(CoordDimensions::xy(), Some(4326))?,
+            POSTGIS_HEX
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn wkb_hex_roundtrip() -> Result<()> {
+        // SELECT ST_AsBinary('LINESTRING(1 2,3 4)'::geometry)
+        let hex =
+            "010200000002000000000000000000F03F000000000000004000000000000008400000000000001040";
+        assert_eq!(WkbHex(hex).to_wkt()?, "LINESTRING(1 2,3 4)");
+        assert_eq!(
+            WktStr("LINESTRING(1 2,3 4)").to_wkb_hex(CoordDimensions::xy())?,
+            hex
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_hex() {
+        assert_eq!(
+            WkbHex("0101000").to_wkt().unwrap_err().to_string(),
+            "processing geometry `hex string with odd number of digits`"
+        );
+        assert_eq!(
+            WkbHex("01XY").to_wkt().unwrap_err().to_string(),
+            "processing geometry `invalid hex digit `X``"
+        );
+    }
+}
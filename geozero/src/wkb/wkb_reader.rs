@@ -1,6 +1,6 @@
 use crate::error::{GeozeroError, Result};
 use crate::wkb::{WKBGeometryType, WkbDialect};
-use crate::{GeomProcessor, GeozeroGeometry};
+use crate::{CoordDimensions, GeomProcessor, GeozeroGeometry};
 use scroll::ctx::{FromCtx, SizeWith};
 use scroll::{Endian, IOread};
 use std::io::Read;
@@ -62,24 +62,113 @@ impl GeozeroGeometry for MySQLWkb {
 }
 
 /// Process WKB geometry.
+///
+/// WKB is read in small chunks, so files should be wrapped in a `BufReader`,
+/// e.g. created with `BufReader::with_capacity` for slow storage.
 pub fn process_wkb_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
+    process_wkb_geom_with_max_depth(raw, DEFAULT_MAX_DEPTH, processor)
+}
+
+/// Default maximum nesting depth of GeometryCollections.
+pub const DEFAULT_MAX_DEPTH: usize = 100;
+
+/// Process WKB geometry with GeometryCollections nested at most `max_depth` levels.
+///
+/// Returns [`GeozeroError::MaxDepthExceeded`] for deeper nested input, instead of
+/// overflowing the stack with untrusted data. The other WKB readers use [`DEFAULT_MAX_DEPTH`].
+pub fn process_wkb_geom_with_max_depth<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    max_depth: usize,
+    processor: &mut P,
+) -> Result<()> {
     let info = read_wkb_header(raw)?;
-    processor.srid(info.srid)?;
-    process_wkb_geom_n(raw, &info, read_wkb_nested_header, 0, processor)
+    process_header(&info, processor)?;
+    process_wkb_geom_depth(raw, &info, read_wkb_nested_header, 0, max_depth, processor)
+}
+
+/// Read WKB geometry from an async reader and process it.
+///
+/// Only reading is async, the processor is called synchronously once the geometry is read.
+/// The whole geometry is buffered in memory, see [`process_wkb_async_with_limit`] for
+/// untrusted input.
+#[cfg(feature = "with-tokio")]
+pub async fn process_wkb_async<R: tokio::io::AsyncRead + Unpin, P: GeomProcessor>(
+    raw: &mut R,
+    processor: &mut P,
+) -> Result<()> {
+    process_wkb_async_with_limit(raw, None, processor).await
+}
+
+/// Read WKB geometry of at most `limit` bytes from an async reader and process it.
+///
+/// Larger input fails with [`GeozeroError::LimitExceeded`].
+#[cfg(feature = "with-tokio")]
+pub async fn process_wkb_async_with_limit<R: tokio::io::AsyncRead + Unpin, P: GeomProcessor>(
+    raw: &mut R,
+    limit: Option<u64>,
+    processor: &mut P,
+) -> Result<()> {
+    use tokio::io::AsyncReadExt;
+    let mut wkb = Vec::new();
+    match limit {
+        Some(limit) => {
+            raw.take(limit.saturating_add(1))
+                .read_to_end(&mut wkb)
+                .await?;
+            if wkb.len() as u64 > limit {
+                return Err(GeozeroError::LimitExceeded(format!(
+                    "more than {limit} bytes of WKB input"
+                )));
+            }
+        }
+        None => {
+            raw.read_to_end(&mut wkb).await?;
+        }
+    }
+    process_wkb_geom(&mut wkb.as_slice(), processor)
 }
 
 /// Process EWKB geometry.
 pub fn process_ewkb_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
     let info = read_ewkb_header(raw)?;
-    processor.srid(info.srid)?;
+    process_header(&info, processor)?;
     process_wkb_geom_n(raw, &info, read_ewkb_nested_header, 0, processor)
 }
 
+/// Process EWKB geometry and return its SRID.
+///
+/// The SRID of the outermost geometry is returned, SRIDs of nested geometries are ignored.
+pub fn process_ewkb_with_srid<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    processor: &mut P,
+) -> Result<Option<i32>> {
+    let info = read_ewkb_header(raw)?;
+    process_header(&info, processor)?;
+    process_wkb_geom_n(raw, &info, read_ewkb_nested_header, 0, processor)?;
+    Ok(info.srid)
+}
+
 /// Process GPKG geometry.
 pub fn process_gpkg_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
-    let info = read_gpkg_header(raw)?;
-    processor.srid(info.srid)?;
-    process_wkb_geom_n(raw, &info, read_wkb_nested_header, 0, processor)
+    process_gpkg_geom_with_header(raw, processor)?;
+    Ok(())
+}
+
+/// Process GPKG geometry and return its binary header.
+pub fn process_gpkg_geom_with_header<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    processor: &mut P,
+) -> Result<GpkgGeometryHeader> {
+    let (header, info) = read_gpkg_geometry_header(raw)?;
+    process_header(&info, processor)?;
+    if header.is_empty && info.base_type == WKBGeometryType::Point {
+        // Empty points may be encoded with arbitrary coordinates
+        read_coord_as::<R, f64>(raw, &info)?;
+        processor.empty_point(0)?;
+    } else {
+        process_wkb_geom_n(raw, &info, read_wkb_nested_header, 0, processor)?;
+    }
+    Ok(header)
 }
 
 /// Process MySQL WKB geometry.
@@ -88,14 +177,14 @@ pub fn process_spatialite_geom<R: Read, P: GeomProcessor>(
     processor: &mut P,
 ) -> Result<()> {
     let info = read_spatialite_header(raw)?;
-    processor.srid(info.srid)?;
+    process_header(&info, processor)?;
     process_wkb_geom_n(raw, &info, read_spatialite_nested_header, 0, processor)
 }
 
 /// Process MySQL WKB geometry.
 pub fn process_mysql_geom<R: Read, P: GeomProcessor>(raw: &mut R, processor: &mut P) -> Result<()> {
     let info = read_mysql_header(raw)?;
-    processor.srid(info.srid)?;
+    process_header(&info, processor)?;
     process_wkb_geom_n(raw, &info, read_wkb_nested_header, 0, processor)
 }
 
@@ -114,13 +203,22 @@ pub fn process_wkb_type_geom<R: Read, P: GeomProcessor>(
     }
 }
 
+/// Pass SRID and dimensions of the outermost geometry to the processor
+fn process_header<P: GeomProcessor>(info: &WkbInfo, processor: &mut P) -> Result<()> {
+    processor.srid(info.srid)?;
+    processor.input_dimensions(CoordDimensions {
+        z: info.has_z,
+        m: info.has_m,
+        ..CoordDimensions::xy()
+    })
+}
+
 #[derive(Debug)]
 pub(crate) struct WkbInfo {
     endian: Endian,
     base_type: WKBGeometryType,
     has_z: bool,
     has_m: bool,
-    #[allow(dead_code)]
     srid: Option<i32>,
     #[allow(dead_code)]
     envelope: Vec<f64>,
@@ -179,17 +277,32 @@ pub(crate) fn read_ewkb_nested_header<R: Read>(raw: &mut R, _info: &WkbInfo) ->
     read_ewkb_header(raw)
 }
 
-/// GPKG geometry header according to http://www.geopackage.org/spec/#gpb_format
-fn read_gpkg_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
+/// GeoPackage binary geometry header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpkgGeometryHeader {
+    /// GeoPackage binary version (0 = version 1)
+    pub version: u8,
+    pub srid: i32,
+    /// Envelope as `[minx, maxx, miny, maxy]`, followed by `[minz, maxz]` and/or `[minm, maxm]`.
+    /// Empty if the blob contains no envelope.
+    pub envelope: Vec<f64>,
+    /// Empty geometry flag
+    pub is_empty: bool,
+}
+
+/// Read GPKG geometry header according to http://www.geopackage.org/spec/#gpb_format
+///
+/// Only the binary header is consumed, `raw` is left at the start of the WKB geometry.
+pub fn read_gpkg_header<R: Read>(raw: &mut R) -> Result<GpkgGeometryHeader> {
     let magic = [raw.ioread::<u8>()?, raw.ioread::<u8>()?];
     if &magic != b"GP" {
         return Err(GeozeroError::GeometryFormat);
     }
-    let _version = raw.ioread::<u8>()?;
+    let version = raw.ioread::<u8>()?;
     let flags = raw.ioread::<u8>()?;
     // println!("flags: {flags:#010b}");
     let _extended = (flags & 0b0010_0000) >> 5 == 1;
-    let _empty = (flags & 0b0001_0000) >> 4 == 1;
+    let is_empty = (flags & 0b0001_0000) >> 4 == 1;
     let env_len = match (flags & 0b0000_1110) >> 1 {
         0 => 0,
         1 => 4,
@@ -205,18 +318,28 @@ fn read_gpkg_header<R: Read>(raw: &mut R) -> Result<WkbInfo> {
         .collect();
     let envelope = envelope?;
 
+    Ok(GpkgGeometryHeader {
+        version,
+        srid,
+        envelope,
+        is_empty,
+    })
+}
+
+fn read_gpkg_geometry_header<R: Read>(raw: &mut R) -> Result<(GpkgGeometryHeader, WkbInfo)> {
+    let header = read_gpkg_header(raw)?;
     let ogc_info = read_wkb_header(raw)?;
 
     let info = WkbInfo {
-        endian,
+        endian: ogc_info.endian,
         base_type: ogc_info.base_type,
         has_z: ogc_info.has_z,
         has_m: ogc_info.has_m,
-        srid: Some(srid),
+        srid: Some(header.srid),
         is_compressed: false,
-        envelope,
+        envelope: header.envelope.clone(),
     };
-    Ok(info)
+    Ok((header, info))
 }
 
 /// Spatialite WKB header.
@@ -311,6 +434,18 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
     read_header: fn(&mut R, info: &WkbInfo) -> Result<WkbInfo>,
     idx: usize,
     processor: &mut P,
+) -> Result<()> {
+    process_wkb_geom_depth(raw, info, read_header, idx, DEFAULT_MAX_DEPTH, processor)
+}
+
+/// Process a geometry, with at most `max_depth` levels of nested GeometryCollections
+fn process_wkb_geom_depth<R: Read, P: GeomProcessor>(
+    raw: &mut R,
+    info: &WkbInfo,
+    read_header: fn(&mut R, info: &WkbInfo) -> Result<WkbInfo>,
+    idx: usize,
+    max_depth: usize,
+    processor: &mut P,
 ) -> Result<()> {
     match info.base_type {
         WKBGeometryType::Point => {
@@ -410,11 +545,14 @@ pub(crate) fn process_wkb_geom_n<R: Read, P: GeomProcessor>(
         }
 
         WKBGeometryType::GeometryCollection => {
+            let max_depth = max_depth
+                .checked_sub(1)
+                .ok_or(GeozeroError::MaxDepthExceeded)?;
             let n_geoms = raw.ioread_with::<u32>(info.endian)? as usize;
             processor.geometrycollection_begin(n_geoms, idx)?;
             for i in 0..n_geoms {
                 let info = read_header(raw, info)?;
-                process_wkb_geom_n(raw, &info, read_header, i, processor)?;
+                process_wkb_geom_depth(raw, &info, read_header, i, max_depth, processor)?;
             }
             processor.geometrycollection_end(idx)
         }
@@ -622,7 +760,23 @@ fn process_curvepolygon<R: Read, P: GeomProcessor>(
 mod test {
     use super::*;
     use crate::wkt::WktWriter;
-    use crate::{CoordDimensions, ToWkt};
+    use crate::{CoordDimensions, ProcessorSink, ToWkt};
+
+    #[cfg(feature = "with-tokio")]
+    #[tokio::test]
+    async fn async_reader() -> Result<()> {
+        // SELECT 'POINT(10 -20)'::geometry
+        let wkb = hex::decode("0101000000000000000000244000000000000034C0").unwrap();
+        let mut wkt_data: Vec<u8> = Vec::new();
+        process_wkb_async(&mut wkb.as_slice(), &mut WktWriter::new(&mut wkt_data)).await?;
+        assert_eq!(std::str::from_utf8(&wkt_data).unwrap(), "POINT(10 -20)");
+
+        let limit = Some(wkb.len() as u64 - 1);
+        let result =
+            process_wkb_async_with_limit(&mut wkb.as_slice(), limit, &mut ProcessorSink).await;
+        assert!(matches!(result, Err(GeozeroError::LimitExceeded(_))));
+        Ok(())
+    }
 
     #[test]
     fn ewkb_format() {
@@ -650,7 +804,7 @@ mod test {
         assert!(process_ewkb_geom(&mut ewkb.as_slice(), &mut writer).is_ok());
         assert_eq!(
             std::str::from_utf8(&wkt_data).unwrap(),
-            "POINT(10 -20 100 1)"
+            "POINT ZM (10 -20 100 1)"
         );
 
         // SELECT 'SRID=4326;MULTIPOINT ((10 -20 100), (0 -0.5 101))'::geometry
@@ -667,8 +821,36 @@ mod test {
         assert!(process_ewkb_geom(&mut ewkb.as_slice(), &mut writer).is_ok());
         assert_eq!(
             std::str::from_utf8(&wkt_data).unwrap(),
-            "MULTIPOINT(10 -20 100,0 -0.5 101)"
+            "MULTIPOINT Z (10 -20 100,0 -0.5 101)"
+        );
+    }
+
+    #[test]
+    fn ewkb_srid() {
+        // SELECT 'SRID=4326;POINT(10 -20)'::geometry
+        let ewkb = hex::decode("0101000020E6100000000000000000244000000000000034C0").unwrap();
+        let mut wkt_data: Vec<u8> = Vec::new();
+        let srid = process_ewkb_with_srid(&mut ewkb.as_slice(), &mut WktWriter::new(&mut wkt_data));
+        assert_eq!(srid.unwrap(), Some(4326));
+        assert_eq!(std::str::from_utf8(&wkt_data).unwrap(), "POINT(10 -20)");
+
+        // SRID=4326 multipoint with a nested SRID=3857 point
+        let ewkb = hex::decode(
+            "0104000020E6100000010000000101000020110F0000000000000000244000000000000034C0",
+        )
+        .unwrap();
+        let mut wkt_data: Vec<u8> = Vec::new();
+        let srid = process_ewkb_with_srid(&mut ewkb.as_slice(), &mut WktWriter::new(&mut wkt_data));
+        assert_eq!(srid.unwrap(), Some(4326));
+        assert_eq!(
+            std::str::from_utf8(&wkt_data).unwrap(),
+            "MULTIPOINT(10 -20)"
         );
+
+        // SELECT 'POINT(10 -20)'::geometry
+        let ewkb = hex::decode("0101000000000000000000244000000000000034C0").unwrap();
+        let srid = process_ewkb_with_srid(&mut ewkb.as_slice(), &mut ProcessorSink::new());
+        assert_eq!(srid.unwrap(), None);
     }
 
     #[test]
@@ -687,6 +869,19 @@ mod test {
             ),
             "POINT EMPTY"
         );
+        // Dimensions are known from the geometry type, also without coordinates
+        assert_eq!(
+            &ewkb_to_wkt(
+                "0101000080000000000000f87f000000000000f87f000000000000f87f",
+                true
+            ),
+            "POINT Z EMPTY"
+        );
+        // SELECT 'LINESTRINGZ EMPTY'::geometry
+        assert_eq!(
+            &ewkb_to_wkt("010200008000000000", true),
+            "LINESTRING Z EMPTY"
+        );
 
         // SELECT 'POINT(10 -20)'::geometry
         assert_eq!(
@@ -697,7 +892,7 @@ mod test {
         // SELECT 'SRID=4326;MULTIPOINT (10 -20 100, 0 -0.5 101)'::geometry
         assert_eq!(
             &ewkb_to_wkt("01040000A0E6100000020000000101000080000000000000244000000000000034C0000000000000594001010000800000000000000000000000000000E0BF0000000000405940", true),
-            "MULTIPOINT(10 -20 100,0 -0.5 101)"
+            "MULTIPOINT Z (10 -20 100,0 -0.5 101)"
             //OGR: MULTIPOINT ((10 -20 100),(0 -0.5 101))
         );
 
@@ -710,7 +905,7 @@ mod test {
         // SELECT 'SRID=4326;LINESTRING (10 -20 100, 0 -0.5 101)'::geometry
         assert_eq!(
             &ewkb_to_wkt("01020000A0E610000002000000000000000000244000000000000034C000000000000059400000000000000000000000000000E0BF0000000000405940", true),
-            "LINESTRING(10 -20 100,0 -0.5 101)"
+            "LINESTRING Z (10 -20 100,0 -0.5 101)"
         );
 
         // SELECT 'SRID=4326;MULTILINESTRING ((10 -20, 0 -0.5), (0 0, 2 0))'::geometry
@@ -776,12 +971,12 @@ mod test {
         // SELECT 'POLYHEDRALSURFACE(((0 0 0,0 0 1,0 1 1,0 1 0,0 0 0)),((0 0 0,0 1 0,1 1 0,1 0 0,0 0 0)),((0 0 0,1 0 0,1 0 1,0 0 1,0 0 0)),((1 1 0,1 1 1,1 0 1,1 0 0,1 1 0)),((0 1 0,0 1 1,1 1 1,1 1 0,0 1 0)),((0 0 1,1 0 1,1 1 1,0 1 1,0 0 1)))'::geometry
         assert_eq!(
             &ewkb_to_wkt("010F000080060000000103000080010000000500000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000F03F0000000000000000000000000000F03F000000000000F03F0000000000000000000000000000F03F0000000000000000000000000000000000000000000000000000000000000000010300008001000000050000000000000000000000000000000000000000000000000000000000000000000000000000000000F03F0000000000000000000000000000F03F000000000000F03F0000000000000000000000000000F03F0000000000000000000000000000000000000000000000000000000000000000000000000000000001030000800100000005000000000000000000000000000000000000000000000000000000000000000000F03F00000000000000000000000000000000000000000000F03F0000000000000000000000000000F03F00000000000000000000000000000000000000000000F03F00000000000000000000000000000000000000000000000001030000800100000005000000000000000000F03F000000000000F03F0000000000000000000000000000F03F000000000000F03F000000000000F03F000000000000F03F0000000000000000000000000000F03F000000000000F03F00000000000000000000000000000000000000000000F03F000000000000F03F0000000000000000010300008001000000050000000000000000000000000000000000F03F00000000000000000000000000000000000000000000F03F000000000000F03F000000000000F03F000000000000F03F000000000000F03F000000000000F03F000000000000F03F00000000000000000000000000000000000000000000F03F00000000000000000103000080010000000500000000000000000000000000000000000000000000000000F03F000000000000F03F0000000000000000000000000000F03F000000000000F03F000000000000F03F000000000000F03F0000000000000000000000000000F03F000000000000F03F00000000000000000000000000000000000000000000F03F", true),
-            "POLYHEDRALSURFACE(((0 0 0,0 0 1,0 1 1,0 1 0,0 0 0)),((0 0 0,0 1 0,1 1 0,1 0 0,0 0 0)),((0 0 0,1 0 0,1 0 1,0 0 1,0 0 0)),((1 1 0,1 1 1,1 0 1,1 0 0,1 1 0)),((0 1 0,0 1 1,1 1 1,1 1 0,0 1 0)),((0 0 1,1 0 1,1 1 1,0 1 1,0 0 1)))"
+            "POLYHEDRALSURFACE Z (((0 0 0,0 0 1,0 1 1,0 1 0,0 0 0)),((0 0 0,0 1 0,1 1 0,1 0 0,0 0 0)),((0 0 0,1 0 0,1 0 1,0 0 1,0 0 0)),((1 1 0,1 1 1,1 0 1,1 0 0,1 1 0)),((0 1 0,0 1 1,1 1 1,1 1 0,0 1 0)),((0 0 1,1 0 1,1 1 1,0 1 1,0 0 1)))"
         );
         // SELECT 'TIN(((0 0 0,0 0 1,0 1 0,0 0 0)),((0 0 0,0 1 0,1 1 0,0 0 0)))'::geometry
         assert_eq!(
             &ewkb_to_wkt("0110000080020000000111000080010000000400000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000F03F0000000000000000000000000000F03F0000000000000000000000000000000000000000000000000000000000000000011100008001000000040000000000000000000000000000000000000000000000000000000000000000000000000000000000F03F0000000000000000000000000000F03F000000000000F03F0000000000000000000000000000000000000000000000000000000000000000", true),
-            "TIN(((0 0 0,0 0 1,0 1 0,0 0 0)),((0 0 0,0 1 0,1 1 0,0 0 0)))"
+            "TIN Z (((0 0 0,0 0 1,0 1 0,0 0 0)),((0 0 0,0 1 0,1 1 0,0 0 0)))"
         );
 
         // SELECT 'TRIANGLE((0 0,0 9,9 0,0 0))'::geometry
@@ -832,7 +1027,7 @@ mod test {
         assert!(process_spatialite_geom(&mut ewkb.as_slice(), &mut writer).is_ok());
         assert_eq!(
             std::str::from_utf8(&wkt_data).unwrap(),
-            "POINT(10 -20 100 1)"
+            "POINT ZM (10 -20 100 1)"
         );
 
         // SELECT HEX(TinyPointEncode(ST_GeomFromText('POINTZM(10 -20 100 1)', 4326)));
@@ -866,7 +1061,7 @@ mod test {
         assert!(process_spatialite_geom(&mut ewkb.as_slice(), &mut writer).is_ok());
         assert_eq!(
             std::str::from_utf8(&wkt_data).unwrap(),
-            "LINESTRING(0 0 0 0,10 0 2 20,10 10 1 -40,51 69 13 37)"
+            "LINESTRING ZM (0 0 0 0,10 0 2 20,10 10 1 -40,51 69 13 37)"
         );
 
         // SELECT HEX(ST_GeomFromText('MULTILINESTRINGZM((20 10 5 1,10 20 30 40))'));
@@ -956,7 +1151,7 @@ mod test {
     fn gpkg_geometries() {
         // pt2d
         let wkb = hex::decode("47500003E61000009A9999999999F13F9A9999999999F13F9A9999999999F13F9A9999999999F13F01010000009A9999999999F13F9A9999999999F13F").unwrap();
-        let info = read_gpkg_header(&mut wkb.as_slice()).unwrap();
+        let (_, info) = read_gpkg_geometry_header(&mut wkb.as_slice()).unwrap();
         assert_eq!(info.base_type, WKBGeometryType::Point);
         assert!(!info.has_z);
         assert!(!info.has_m);
@@ -968,7 +1163,7 @@ mod test {
 
         // mln3dzm
         let wkb = hex::decode("47500003E6100000000000000000244000000000000034400000000000002440000000000000344001BD0B00000100000001BA0B0000020000000000000000003440000000000000244000000000000008400000000000001440000000000000244000000000000034400000000000001C400000000000000040").unwrap();
-        let info = read_gpkg_header(&mut wkb.as_slice()).unwrap();
+        let (_, info) = read_gpkg_geometry_header(&mut wkb.as_slice()).unwrap();
         assert_eq!(info.base_type, WKBGeometryType::MultiLineString);
         assert!(info.has_z);
         assert!(info.has_m);
@@ -984,7 +1179,7 @@ mod test {
 
         // gc2d
         let wkb = hex::decode("47500003e6100000000000000000f03f0000000000003640000000000000084000000000000036400107000000020000000101000000000000000000f03f00000000000008400103000000010000000400000000000000000035400000000000003540000000000000364000000000000035400000000000003540000000000000364000000000000035400000000000003540").unwrap();
-        let info = read_gpkg_header(&mut wkb.as_slice()).unwrap();
+        let (_, info) = read_gpkg_geometry_header(&mut wkb.as_slice()).unwrap();
         assert_eq!(info.base_type, WKBGeometryType::GeometryCollection);
         assert_eq!(info.envelope, vec![1.0, 22.0, 3.0, 22.0]);
 
@@ -996,6 +1191,65 @@ mod test {
         );
     }
 
+    #[test]
+    fn gpkg_header() {
+        // mln3dzm
+        let wkb = hex::decode("47500003E6100000000000000000244000000000000034400000000000002440000000000000344001BD0B00000100000001BA0B0000020000000000000000003440000000000000244000000000000008400000000000001440000000000000244000000000000034400000000000001C400000000000000040").unwrap();
+        let header =
+            process_gpkg_geom_with_header(&mut wkb.as_slice(), &mut ProcessorSink::new()).unwrap();
+        assert_eq!(
+            header,
+            GpkgGeometryHeader {
+                version: 0,
+                srid: 4326,
+                envelope: vec![10.0, 20.0, 10.0, 20.0],
+                is_empty: false,
+            }
+        );
+        assert_eq!(read_gpkg_header(&mut wkb.as_slice()).unwrap(), header);
+
+        // Empty point flag set, no envelope, non-NaN coordinates
+        let wkb =
+            hex::decode("47500011E61000000101000000000000000000244000000000000034C0").unwrap();
+        let mut wkt_data: Vec<u8> = Vec::new();
+        let header =
+            process_gpkg_geom_with_header(&mut wkb.as_slice(), &mut WktWriter::new(&mut wkt_data))
+                .unwrap();
+        assert!(header.is_empty);
+        assert!(header.envelope.is_empty());
+        assert_eq!(std::str::from_utf8(&wkt_data).unwrap(), "POINT EMPTY");
+    }
+
+    #[test]
+    fn max_depth() {
+        // GEOMETRYCOLLECTION(GEOMETRYCOLLECTION(...(POINT(10 -20))))
+        let nested_wkb = |depth: usize| {
+            let mut wkb = [1, 7, 0, 0, 0, 1, 0, 0, 0].repeat(depth);
+            wkb.extend(hex::decode("0101000000000000000000244000000000000034C0").unwrap());
+            wkb
+        };
+        let wkb = nested_wkb(DEFAULT_MAX_DEPTH);
+        assert!(process_wkb_geom(&mut wkb.as_slice(), &mut ProcessorSink::new()).is_ok());
+
+        for depth in [DEFAULT_MAX_DEPTH + 1, 100_000] {
+            let wkb = nested_wkb(depth);
+            let err = process_wkb_geom(&mut wkb.as_slice(), &mut ProcessorSink::new()).unwrap_err();
+            assert!(matches!(err, GeozeroError::MaxDepthExceeded));
+        }
+
+        let wkb = nested_wkb(3);
+        let result =
+            process_wkb_geom_with_max_depth(&mut wkb.as_slice(), 2, &mut ProcessorSink::new());
+        assert!(matches!(result, Err(GeozeroError::MaxDepthExceeded)));
+        let mut wkt_data: Vec<u8> = Vec::new();
+        process_wkb_geom_with_max_depth(&mut wkb.as_slice(), 3, &mut WktWriter::new(&mut wkt_data))
+            .unwrap();
+        assert_eq!(
+            std::str::from_utf8(&wkt_data).unwrap(),
+            "GEOMETRYCOLLECTION(GEOMETRYCOLLECTION(GEOMETRYCOLLECTION(POINT(10 -20))))"
+        );
+    }
+
     #[test]
     fn scroll_error() {
         let err = read_ewkb_header(&mut std::io::Cursor::new(b"")).unwrap_err();
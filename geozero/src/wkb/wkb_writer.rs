@@ -1,5 +1,5 @@
 use crate::error::{GeozeroError, Result};
-use crate::wkb::{WKBByteOrder, WKBGeometryType, WkbDialect};
+use crate::wkb::{ByteOrder, WKBByteOrder, WKBGeometryType, WkbDialect};
 use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
 use scroll::IOwrite;
 use std::io::Write;
@@ -65,6 +65,27 @@ impl<W: Write> WkbWriter<W> {
         )
     }
 
+    /// Return the output.
+    #[cfg(any(feature = "with-arrow", feature = "with-gpkg"))]
+    pub(crate) fn into_inner(self) -> W {
+        self.out
+    }
+
+    /// Set the SRID, which is written into the header of the outermost geometry
+    /// for dialects supporting it (e.g. [WkbDialect::Ewkb]).
+    pub fn with_srid(mut self, srid: i32) -> Self {
+        self.srid = Some(srid);
+        self
+    }
+
+    /// Set the byte order of the written WKB (default: little endian).
+    ///
+    /// Applies to all headers and coordinates, including nested geometries.
+    pub fn with_byte_order(mut self, byte_order: ByteOrder) -> Self {
+        self.endian = byte_order.into();
+        self
+    }
+
     #[doc(hidden)]
     // Temporary constructor. To be replaced with builder pattern.
     #[allow(clippy::too_many_arguments)]
@@ -211,7 +232,7 @@ impl<W: Write> WkbWriter<W> {
             self.out.iowrite::<u8>(0)?;
             let byte_order: WKBByteOrder = self.endian.into();
             self.out.iowrite(byte_order as u8)?;
-            self.out.iowrite(self.srid.unwrap_or(0))?;
+            self.out.iowrite_with(self.srid.unwrap_or(0), self.endian)?;
 
             let envelope = Some(&self.envelope).filter(|e| !e.is_empty());
             for val in envelope.unwrap_or(&vec![0.0, 0.0, 0.0, 0.0]) {
@@ -296,6 +317,16 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
         }
         Ok(())
     }
+    fn empty_point(&mut self, idx: usize) -> Result<()> {
+        // Empty points are encoded with NaN coordinates
+        let nan = Some(f64::NAN);
+        if self.geom_state == GeomState::MultiPointGeom {
+            return self.coordinate(f64::NAN, f64::NAN, nan, nan, None, None, idx);
+        }
+        self.point_begin(idx)?;
+        self.coordinate(f64::NAN, f64::NAN, nan, nan, None, None, idx)?;
+        self.point_end(idx)
+    }
     fn point_begin(&mut self, _idx: usize) -> Result<()> {
         self.write_header(WKBGeometryType::Point)
     }
@@ -322,6 +353,9 @@ impl<W: Write> GeomProcessor for WkbWriter<W> {
         Ok(())
     }
     fn linestring_end(&mut self, _tagged: bool, _idx: usize) -> Result<()> {
+        if self.geom_state == GeomState::RingGeom {
+            return Ok(());
+        }
         self.write_footer()
     }
     fn multilinestring_begin(&mut self, size: usize, _idx: usize) -> Result<()> {
@@ -444,11 +478,12 @@ impl<W: Write> FeatureProcessor for WkbWriter<W> {}
 mod test {
     use super::*;
     use crate::wkb::process_wkb_type_geom;
-    use crate::wkb::WkbDialect::{Ewkb, Geopackage, MySQL, SpatiaLite};
+    use crate::wkb::WkbDialect::{Ewkb, Geopackage, MySQL, SpatiaLite, Wkb};
     use crate::ToWkb;
 
     const DIM_XY: CoordDimensions = CoordDimensions::xy();
     const DIM_XYZ: CoordDimensions = CoordDimensions::xyz();
+    const DIM_XYM: CoordDimensions = CoordDimensions::xym();
     const DIM_XYZM: CoordDimensions = CoordDimensions::xyzm();
 
     fn roundtrip(
@@ -496,6 +531,19 @@ mod test {
 
     #[test]
     fn ewkb_geometries() {
+        // SELECT 'POINT EMPTY'::geometry
+        roundtrip(
+            Ewkb,
+            DIM_XY,
+            None,
+            Vec::new(),
+            "0101000000000000000000f87f000000000000f87f",
+        );
+
+        // SELECT 'MULTIPOINT(1 2, EMPTY, 3 4)'::geometry
+        roundtrip(Ewkb, DIM_XY, None, Vec::new(),
+                  "0104000000030000000101000000000000000000f03f00000000000000400101000000000000000000f87f000000000000f87f010100000000000000000008400000000000001040");
+
         // SELECT 'POINT(10 -20)'::geometry
         roundtrip(
             Ewkb,
@@ -530,6 +578,126 @@ mod test {
                   "01070000000300000001010000000000000000002440000000000000244001010000000000000000003E400000000000003E400102000000020000000000000000002E400000000000002E4000000000000034400000000000003440");
     }
 
+    #[test]
+    fn iso_wkb_dimensions() {
+        // SELECT ST_AsBinary('POINT Z (1 2 3)'::geometry)
+        roundtrip(
+            Wkb,
+            DIM_XYZ,
+            None,
+            Vec::new(),
+            "01e9030000000000000000f03f00000000000000400000000000000840",
+        );
+
+        // SELECT ST_AsBinary('POINT M (1 2 4)'::geometry)
+        roundtrip(
+            Wkb,
+            DIM_XYM,
+            None,
+            Vec::new(),
+            "01d1070000000000000000f03f00000000000000400000000000001040",
+        );
+
+        // SELECT ST_AsBinary('POINT ZM (1 2 3 4)'::geometry)
+        roundtrip(
+            Wkb,
+            DIM_XYZM,
+            None,
+            Vec::new(),
+            "01b90b0000000000000000f03f000000000000004000000000000008400000000000001040",
+        );
+
+        // SELECT ST_AsBinary('LINESTRING ZM (1 2 3 4,5 6 7 8)'::geometry)
+        roundtrip(Wkb, DIM_XYZM, None, Vec::new(),
+                  "01ba0b000002000000000000000000f03f000000000000004000000000000008400000000000001040000000000000144000000000000018400000000000001c400000000000002040");
+    }
+
+    #[test]
+    fn ewkb_srid() -> Result<()> {
+        use crate::wkt::{WktDialect, WktStr, WktWriter};
+        use crate::GeozeroGeometry;
+
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, Ewkb).with_srid(4326);
+        WktStr("GEOMETRYCOLLECTION(POINT(10 10),LINESTRING(15 15,20 20))")
+            .process_geom(&mut writer)?;
+        // SRID flag and value in outermost geometry only
+        assert_eq!(hex::encode(&wkb_out), "0107000020e6100000020000000101000000000000000000244000000000000024400102000000020000000000000000002e400000000000002e4000000000000034400000000000003440");
+
+        let mut wkt_out: Vec<u8> = Vec::new();
+        let mut wkt_writer = WktWriter::with_opts(&mut wkt_out, WktDialect::Ewkt, DIM_XY, None);
+        crate::wkb::process_ewkb_geom(&mut wkb_out.as_slice(), &mut wkt_writer)?;
+        assert_eq!(
+            std::str::from_utf8(&wkt_out).unwrap(),
+            "SRID=4326;GEOMETRYCOLLECTION(POINT(10 10),LINESTRING(15 15,20 20))"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn big_endian() -> Result<()> {
+        use crate::wkt::WktStr;
+        use crate::{GeozeroGeometry, ToWkt};
+
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, Wkb).with_byte_order(ByteOrder::BigEndian);
+        WktStr("POINT(10 -20)").process_geom(&mut writer)?;
+        assert_eq!(
+            hex::encode(&wkb_out),
+            "00000000014024000000000000c034000000000000"
+        );
+
+        let wkt = "GEOMETRYCOLLECTION(POINT(10 10),MULTILINESTRING((15 15,20 20),(1 2,3 4)))";
+        let mut wkb_out: Vec<u8> = Vec::new();
+        let mut writer = WkbWriter::new(&mut wkb_out, Ewkb)
+            .with_byte_order(ByteOrder::BigEndian)
+            .with_srid(4326);
+        WktStr(wkt).process_geom(&mut writer)?;
+        // Nested geometries use the same byte order
+        assert_eq!(wkb_out[..9], [0, 0x20, 0, 0, 7, 0, 0, 0x10, 0xe6]);
+        assert_eq!(wkb_out[13..18], [0, 0, 0, 0, 1]);
+        assert_eq!(crate::wkb::Ewkb(wkb_out).to_wkt()?, wkt);
+        Ok(())
+    }
+
+    #[test]
+    fn cross_dialect() -> Result<()> {
+        use crate::wkt::{WktStr, WktWriter};
+        use crate::GeozeroGeometry;
+
+        let dialects = [Wkb, Ewkb, Geopackage, MySQL, SpatiaLite];
+        let geometries = [
+            "POINT(10 -20)",
+            "LINESTRING(0 0,10 10,20 0)",
+            "POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,2 4,4 4,2 2))",
+            "MULTIPOINT(1 2,3 4)",
+            "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5)))",
+        ];
+        for wkt in geometries {
+            for from in dialects {
+                let mut wkb_from: Vec<u8> = Vec::new();
+                let mut writer = WkbWriter::new(&mut wkb_from, from).with_srid(4326);
+                WktStr(wkt).process_geom(&mut writer)?;
+                for to in dialects {
+                    let mut wkb_to: Vec<u8> = Vec::new();
+                    let mut writer = WkbWriter::new(&mut wkb_to, to).with_srid(4326);
+                    process_wkb_type_geom(&mut wkb_from.as_slice(), &mut writer, from)?;
+
+                    let mut wkt_out: Vec<u8> = Vec::new();
+                    let mut wkt_writer = WktWriter::new(&mut wkt_out);
+                    process_wkb_type_geom(&mut wkb_to.as_slice(), &mut wkt_writer, to)?;
+                    assert_eq!(
+                        std::str::from_utf8(&wkt_out).unwrap(),
+                        wkt,
+                        "{from:?} -> {to:?}"
+                    );
+                }
+            }
+        }
+        assert_eq!(WkbDialect::default(), Wkb);
+        Ok(())
+    }
+
     #[test]
     fn ewkb_curves() {
         // SELECT 'CIRCULARSTRING(0 0,1 1,2 0)'::geometry
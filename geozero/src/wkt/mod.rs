@@ -1,6 +1,11 @@
 //! Well-Known Text (WKT) conversions.
 //!
 //! OpenGIS Simple Features Specification For SQL Revision 1.1, Chapter 3.2.5
+//!
+//! Coordinates with Z and M values (`POINT ZM (1 2 3 4)`) and curve geometries (`CIRCULARSTRING`,
+//! `COMPOUNDCURVE`, `CURVEPOLYGON`, `MULTICURVE`, `MULTISURFACE`) according to ISO SQL/MM Part 3
+//! are supported.
+pub(crate) mod wkt_parser;
 pub(crate) mod wkt_reader;
 pub(crate) mod wkt_writer;
 
@@ -0,0 +1,486 @@
+//! WKT parser supporting dimension tags and SQL/MM curve geometries, which are not supported by
+//! the `wkt` crate.
+use crate::error::{GeozeroError, Result};
+use crate::GeomProcessor;
+
+/// Coordinate with optional Z and M values
+struct Coord {
+    x: f64,
+    y: f64,
+    z: Option<f64>,
+    m: Option<f64>,
+}
+
+/// Read and process WKT geometry as geometry `idx`.
+pub(crate) fn read_wkt_str<P: GeomProcessor>(
+    wkt: &str,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    let mut parser = WktParser {
+        tokens: tokenize(wkt)?,
+        pos: 0,
+    };
+    let geometry = parser.geometry(false)?;
+    if parser.pos < parser.tokens.len() {
+        return Err(parse_error("unexpected text after geometry"));
+    }
+    geometry.process(true, idx, processor)
+}
+
+#[derive(PartialEq, Debug)]
+enum Token {
+    Word(String),
+    Number(f64),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(wkt: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = wkt.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            '(' => tokens.push(Token::LParen),
+            ')' => tokens.push(Token::RParen),
+            ',' => tokens.push(Token::Comma),
+            c if c.is_whitespace() => {}
+            c if c.is_ascii_alphabetic() => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphabetic()) {
+                    word.push(c.to_ascii_uppercase());
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+                continue;
+            }
+            c if c.is_ascii_digit() || matches!(c, '-' | '+' | '.') => {
+                let mut number = String::new();
+                while let Some(&c) = chars
+                    .peek()
+                    .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.'))
+                {
+                    number.push(c);
+                    chars.next();
+                }
+                let value = number
+                    .parse::<f64>()
+                    .map_err(|_| parse_error(&format!("invalid number `{number}`")))?;
+                tokens.push(Token::Number(value));
+                continue;
+            }
+            c => return Err(parse_error(&format!("unexpected character `{c}`"))),
+        }
+        chars.next();
+    }
+    Ok(tokens)
+}
+
+fn parse_error(msg: &str) -> GeozeroError {
+    GeozeroError::Geometry(format!("invalid WKT: {msg}"))
+}
+
+/// Parsed geometry, collected before processing to know the part counts.
+enum WktGeometry {
+    /// `None` for `POINT EMPTY`
+    Point(Option<Coord>),
+    LineString(Vec<Coord>),
+    Polygon(Vec<WktGeometry>),
+    /// Empty points are skipped
+    MultiPoint(Vec<Coord>),
+    MultiLineString(Vec<WktGeometry>),
+    MultiPolygon(Vec<WktGeometry>),
+    GeometryCollection(Vec<WktGeometry>),
+    CircularString(Vec<Coord>),
+    CompoundCurve(Vec<WktGeometry>),
+    CurvePolygon(Vec<WktGeometry>),
+    MultiCurve(Vec<WktGeometry>),
+    MultiSurface(Vec<WktGeometry>),
+}
+
+impl WktGeometry {
+    /// Process geometry. Parts of multi geometries, polygons and curves are untagged.
+    fn process<P: GeomProcessor>(&self, tagged: bool, idx: usize, processor: &mut P) -> Result<()> {
+        match self {
+            WktGeometry::Point(None) => processor.empty_point(idx),
+            WktGeometry::Point(Some(coord)) => {
+                processor.point_begin(idx)?;
+                process_coord(coord, processor.multi_dim(), 0, processor)?;
+                processor.point_end(idx)
+            }
+            WktGeometry::LineString(coords) => {
+                processor.linestring_begin(tagged, coords.len(), idx)?;
+                process_coords(coords, processor)?;
+                processor.linestring_end(tagged, idx)
+            }
+            WktGeometry::Polygon(rings) => {
+                processor.polygon_begin(tagged, rings.len(), idx)?;
+                process_parts(rings, false, processor)?;
+                processor.polygon_end(tagged, idx)
+            }
+            WktGeometry::MultiPoint(coords) => {
+                processor.multipoint_begin(coords.len(), idx)?;
+                process_coords(coords, processor)?;
+                processor.multipoint_end(idx)
+            }
+            WktGeometry::MultiLineString(lines) => {
+                processor.multilinestring_begin(lines.len(), idx)?;
+                process_parts(lines, false, processor)?;
+                processor.multilinestring_end(idx)
+            }
+            WktGeometry::MultiPolygon(polygons) => {
+                processor.multipolygon_begin(polygons.len(), idx)?;
+                process_parts(polygons, false, processor)?;
+                processor.multipolygon_end(idx)
+            }
+            WktGeometry::GeometryCollection(geometries) => {
+                processor.geometrycollection_begin(geometries.len(), idx)?;
+                process_parts(geometries, true, processor)?;
+                processor.geometrycollection_end(idx)
+            }
+            WktGeometry::CircularString(coords) => {
+                processor.circularstring_begin(coords.len(), idx)?;
+                process_coords(coords, processor)?;
+                processor.circularstring_end(idx)
+            }
+            WktGeometry::CompoundCurve(parts) => {
+                processor.compoundcurve_begin(parts.len(), idx)?;
+                process_parts(parts, false, processor)?;
+                processor.compoundcurve_end(idx)
+            }
+            WktGeometry::CurvePolygon(rings) => {
+                processor.curvepolygon_begin(rings.len(), idx)?;
+                process_parts(rings, false, processor)?;
+                processor.curvepolygon_end(idx)
+            }
+            WktGeometry::MultiCurve(curves) => {
+                processor.multicurve_begin(curves.len(), idx)?;
+                process_parts(curves, false, processor)?;
+                processor.multicurve_end(idx)
+            }
+            WktGeometry::MultiSurface(surfaces) => {
+                processor.multisurface_begin(surfaces.len(), idx)?;
+                process_parts(surfaces, false, processor)?;
+                processor.multisurface_end(idx)
+            }
+        }
+    }
+}
+
+fn process_coord<P: GeomProcessor>(
+    coord: &Coord,
+    multi_dim: bool,
+    idx: usize,
+    processor: &mut P,
+) -> Result<()> {
+    if multi_dim {
+        processor.coordinate(coord.x, coord.y, coord.z, coord.m, None, None, idx)
+    } else {
+        processor.xy(coord.x, coord.y, idx)
+    }
+}
+
+fn process_coords<P: GeomProcessor>(coords: &[Coord], processor: &mut P) -> Result<()> {
+    let multi_dim = processor.multi_dim();
+    for (idxc, coord) in coords.iter().enumerate() {
+        process_coord(coord, multi_dim, idxc, processor)?;
+    }
+    Ok(())
+}
+
+fn process_parts<P: GeomProcessor>(
+    parts: &[WktGeometry],
+    tagged: bool,
+    processor: &mut P,
+) -> Result<()> {
+    for (idxp, part) in parts.iter().enumerate() {
+        part.process(tagged, idxp, processor)?;
+    }
+    Ok(())
+}
+
+struct WktParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl WktParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        self.pos += 1;
+        self.tokens.get(self.pos - 1)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        match self.advance() {
+            Some(token) if *token == expected => Ok(()),
+            _ => Err(parse_error(&format!("expected {expected:?}"))),
+        }
+    }
+
+    fn word(&mut self) -> Result<String> {
+        match self.advance() {
+            Some(Token::Word(word)) => Ok(word.clone()),
+            _ => Err(parse_error("expected geometry type")),
+        }
+    }
+
+    /// Geometry type with optional dimension tag. Returns the type and if 3D coordinates are measured.
+    fn keyword(&mut self, measured: bool) -> Result<(String, bool)> {
+        let keyword = self.word()?;
+        let measured = match self.peek() {
+            Some(Token::Word(tag)) if matches!(tag.as_str(), "Z" | "M" | "ZM") => {
+                let measured = tag == "M";
+                self.pos += 1;
+                measured
+            }
+            _ => measured,
+        };
+        Ok((keyword, measured))
+    }
+
+    /// Consume `EMPTY` if present.
+    fn empty(&mut self) -> bool {
+        if matches!(self.peek(), Some(Token::Word(word)) if word == "EMPTY") {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Parse `EMPTY` or a parenthesized, comma separated list of elements.
+    fn list<T>(&mut self, mut element: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let mut elements = Vec::new();
+        if self.empty() {
+            return Ok(elements);
+        }
+        self.expect(Token::LParen)?;
+        loop {
+            elements.push(element(self)?);
+            match self.advance() {
+                Some(Token::Comma) => {}
+                Some(Token::RParen) => return Ok(elements),
+                _ => return Err(parse_error("expected `,` or `)`")),
+            }
+        }
+    }
+
+    fn coord(&mut self, measured: bool) -> Result<Coord> {
+        let mut ordinates = Vec::with_capacity(4);
+        while let Some(Token::Number(value)) = self.peek() {
+            ordinates.push(*value);
+            self.pos += 1;
+        }
+        let coord = match (ordinates.as_slice(), measured) {
+            ([x, y], _) => (*x, *y, None, None),
+            ([x, y, m], true) => (*x, *y, None, Some(*m)),
+            ([x, y, z], false) => (*x, *y, Some(*z), None),
+            ([x, y, z, m], _) => (*x, *y, Some(*z), Some(*m)),
+            _ => return Err(parse_error("expected 2 to 4 coordinate values")),
+        };
+        Ok(Coord {
+            x: coord.0,
+            y: coord.1,
+            z: coord.2,
+            m: coord.3,
+        })
+    }
+
+    fn coords(&mut self, measured: bool) -> Result<Vec<Coord>> {
+        self.list(|p| p.coord(measured))
+    }
+
+    /// Point of a `MultiPoint`, with or without parentheses. Returns `None` for `EMPTY`.
+    fn multipoint_element(&mut self, measured: bool) -> Result<Option<Coord>> {
+        if self.peek() == Some(&Token::LParen) {
+            Ok(self.coords(measured)?.pop())
+        } else if self.empty() {
+            Ok(None)
+        } else {
+            self.coord(measured).map(Some)
+        }
+    }
+
+    fn linestring(&mut self, measured: bool) -> Result<WktGeometry> {
+        Ok(WktGeometry::LineString(self.coords(measured)?))
+    }
+
+    fn polygon(&mut self, measured: bool) -> Result<WktGeometry> {
+        Ok(WktGeometry::Polygon(self.list(|p| p.linestring(measured))?))
+    }
+
+    fn geometry(&mut self, measured: bool) -> Result<WktGeometry> {
+        let (keyword, measured) = self.keyword(measured)?;
+        match keyword.as_str() {
+            "POINT" => {
+                let mut coords = self.coords(measured)?;
+                if coords.len() > 1 {
+                    return Err(parse_error("expected a single coordinate"));
+                }
+                Ok(WktGeometry::Point(coords.pop()))
+            }
+            "LINESTRING" => self.linestring(measured),
+            "POLYGON" => self.polygon(measured),
+            "MULTIPOINT" => Ok(WktGeometry::MultiPoint(
+                self.list(|p| p.multipoint_element(measured))?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+            )),
+            "MULTILINESTRING" => Ok(WktGeometry::MultiLineString(
+                self.list(|p| p.linestring(measured))?,
+            )),
+            "MULTIPOLYGON" => Ok(WktGeometry::MultiPolygon(
+                self.list(|p| p.polygon(measured))?,
+            )),
+            "GEOMETRYCOLLECTION" => Ok(WktGeometry::GeometryCollection(
+                self.list(|p| p.geometry(measured))?,
+            )),
+            "CIRCULARSTRING" => Ok(WktGeometry::CircularString(self.coords(measured)?)),
+            "COMPOUNDCURVE" => Ok(WktGeometry::CompoundCurve(
+                self.list(|p| p.curve(measured, false))?,
+            )),
+            "CURVEPOLYGON" => Ok(WktGeometry::CurvePolygon(
+                self.list(|p| p.curve(measured, true))?,
+            )),
+            "MULTICURVE" => Ok(WktGeometry::MultiCurve(
+                self.list(|p| p.curve(measured, true))?,
+            )),
+            "MULTISURFACE" => Ok(WktGeometry::MultiSurface(
+                self.list(|p| p.surface(measured))?,
+            )),
+            _ => Err(parse_error(&format!(
+                "unexpected geometry type `{keyword}`"
+            ))),
+        }
+    }
+
+    /// Untagged `LineString`, `CircularString` or (if allowed) `CompoundCurve`.
+    fn curve(&mut self, measured: bool, compound: bool) -> Result<WktGeometry> {
+        if self.peek() == Some(&Token::LParen) {
+            return self.linestring(measured);
+        }
+        let start = self.pos;
+        match self.keyword(measured)?.0.as_str() {
+            "CIRCULARSTRING" => {}
+            "COMPOUNDCURVE" if compound => {}
+            keyword => return Err(parse_error(&format!("unexpected curve type `{keyword}`"))),
+        }
+        self.pos = start;
+        self.geometry(measured)
+    }
+
+    /// Untagged `Polygon` or `CurvePolygon`.
+    fn surface(&mut self, measured: bool) -> Result<WktGeometry> {
+        if self.peek() == Some(&Token::LParen) {
+            return self.polygon(measured);
+        }
+        let start = self.pos;
+        match self.keyword(measured)?.0.as_str() {
+            "CURVEPOLYGON" => {}
+            keyword => return Err(parse_error(&format!("unexpected surface type `{keyword}`"))),
+        }
+        self.pos = start;
+        self.geometry(measured)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::wkt::WktStr;
+    use crate::{CoordDimensions, ToWkt};
+
+    #[test]
+    fn curves() {
+        for wkt in [
+            "CIRCULARSTRING(0 0,1 1,2 0)",
+            "COMPOUNDCURVE(CIRCULARSTRING(0 0,1 1,2 0),(2 0,3 0))",
+            "CURVEPOLYGON(COMPOUNDCURVE(CIRCULARSTRING(0 0,1 1,2 0),(2 0,3 0,3 -1,0 -1,0 0)))",
+            "MULTICURVE((0 0,5 5),CIRCULARSTRING(4 0,4 4,8 4))",
+            "MULTISURFACE(CURVEPOLYGON(CIRCULARSTRING(0 0,4 0,4 4,0 4,0 0)),((10 10,14 12,11 10,10 10)))",
+            "CIRCULARSTRING EMPTY",
+        ] {
+            assert_eq!(WktStr(wkt).to_wkt().unwrap(), wkt);
+        }
+
+        // PostGIS style with whitespace and lower case
+        assert_eq!(
+            WktStr("circularstring (0 0, 1 1, 2 0)").to_wkt().unwrap(),
+            "CIRCULARSTRING(0 0,1 1,2 0)"
+        );
+    }
+
+    #[test]
+    fn curve_dimensions() {
+        let wkt = "CIRCULARSTRING Z (0 0 1,1 1 2,2 0 3)";
+        assert_eq!(
+            WktStr(wkt).to_wkt_ndim(CoordDimensions::xyz()).unwrap(),
+            wkt
+        );
+        let wkt = "COMPOUNDCURVE M (CIRCULARSTRING M (0 0 1,1 1 2,2 0 3),(2 0 3,3 0 4))";
+        assert_eq!(
+            WktStr(wkt).to_wkt_ndim(CoordDimensions::xym()).unwrap(),
+            wkt
+        );
+    }
+
+    #[test]
+    fn dimension_tags() {
+        for wkt in [
+            "POINT Z (1 2 3)",
+            "LINESTRING ZM (1 2 3 4,5 6 7 8)",
+            "MULTIPOINT Z (1 2 3,4 5 6)",
+            "GEOMETRYCOLLECTION Z (POINT Z (1 2 3),POLYGON Z ((0 0 1,1 0 1,1 1 1,0 0 1)))",
+        ] {
+            assert_eq!(
+                WktStr(wkt).to_wkt_ndim(CoordDimensions::xyzm()).unwrap(),
+                wkt
+            );
+        }
+        assert_eq!(
+            WktStr("POINT M (1 2 3)")
+                .to_wkt_ndim(CoordDimensions::xyzm())
+                .unwrap(),
+            "POINT M (1 2 3)"
+        );
+        // Untagged 3D coordinates
+        assert_eq!(
+            WktStr("POINT(1 2 3)")
+                .to_wkt_ndim(CoordDimensions::xyz())
+                .unwrap(),
+            "POINT Z (1 2 3)"
+        );
+    }
+
+    #[test]
+    fn invalid_curves() {
+        assert!(WktStr("CIRCULARSTRING(0 0,1)").to_wkt().is_err());
+        assert!(WktStr("COMPOUNDCURVE(COMPOUNDCURVE((0 0,1 1)))")
+            .to_wkt()
+            .is_err());
+        assert!(WktStr("CIRCULARSTRING(0 0,1 1,2 0) x").to_wkt().is_err());
+    }
+
+    #[test]
+    fn sink_accepts_curves() {
+        use crate::{GeozeroGeometry, ProcessorSink};
+        WktStr("MULTISURFACE(CURVEPOLYGON(COMPOUNDCURVE(CIRCULARSTRING(0 0,1 1,2 0),(2 0,0 0))))")
+            .process_geom(&mut ProcessorSink)
+            .unwrap();
+        WktStr("MULTICURVE(CIRCULARSTRING(4 0,4 4,8 4))")
+            .process_geom(&mut ProcessorSink)
+            .unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "with-geo")]
+    fn unsupported_output() {
+        use crate::geo_types::conversion::ToGeo;
+        assert!(WktStr("CIRCULARSTRING(0 0,1 1,2 0)").to_geo().is_err());
+    }
+}
@@ -1,9 +1,8 @@
-use crate::error::{GeozeroError, Result};
+use crate::error::Result;
+use crate::wkt::wkt_parser::read_wkt_str;
 use crate::{FeatureProcessor, GeomProcessor, GeozeroDatasource, GeozeroGeometry};
 
 use std::io::Read;
-use wkt::types::{Coord, LineString, Polygon};
-use wkt::Geometry;
 
 /// WKT String.
 #[derive(Debug)]
@@ -46,115 +45,13 @@ impl<R: Read> GeozeroDatasource for WktReader<R> {
 }
 
 /// Read and process WKT geometry.
+///
+/// Keywords are case-insensitive and any whitespace between tokens is ignored.
+/// Coordinates may have Z and M values, declared with `Z`, `M` or `ZM` dimension tags.
 pub fn read_wkt<R: Read, P: GeomProcessor>(reader: &mut R, processor: &mut P) -> Result<()> {
-    use std::str::FromStr;
-    // PERF: it would be good to avoid copying data into this string when we already
-    // have a string as input. Maybe the wkt crate needs a from_reader implementation.
     let mut wkt_string = String::new();
     reader.read_to_string(&mut wkt_string)?;
-    let wkt = wkt::Wkt::from_str(&wkt_string).map_err(|e| GeozeroError::Geometry(e.to_string()))?;
-    process_wkt_geom(&wkt.item, processor)
-}
-
-/// Process WKT geometry
-fn process_wkt_geom<P: GeomProcessor>(geometry: &Geometry<f64>, processor: &mut P) -> Result<()> {
-    process_wkt_geom_n(geometry, 0, processor)
-}
-
-pub(crate) fn process_wkt_geom_n<P: GeomProcessor>(
-    geometry: &Geometry<f64>,
-    idx: usize,
-    processor: &mut P,
-) -> Result<()> {
-    let multi_dim = processor.multi_dim();
-    match geometry {
-        Geometry::Point(g) => {
-            if let Some(ref coord) = g.0 {
-                processor.point_begin(idx)?;
-                process_coord(coord, multi_dim, 0, processor)?;
-                processor.point_end(idx)
-            } else {
-                processor.empty_point(idx)
-            }
-        }
-        Geometry::MultiPoint(g) => {
-            processor.multipoint_begin(g.0.len(), idx)?;
-            let multi_dim1 = processor.multi_dim();
-            for (idxc, point) in g.0.iter().enumerate() {
-                if let Some(ref coord) = point.0 {
-                    process_coord(coord, multi_dim1, idxc, processor)?;
-                } else {
-                    // skip processing of the untagged empty POINT, since no other formats support it.
-                    // Alternatively we could error here, but likely omitting the empty coord won't affect
-                    // the output of most computations (area, length, etc.)
-                }
-            }
-            processor.multipoint_end(idx)
-        }
-        Geometry::LineString(g) => process_linestring(g, true, idx, processor),
-        Geometry::MultiLineString(g) => {
-            processor.multilinestring_begin(g.0.len(), idx)?;
-            for (idxc, linestring) in g.0.iter().enumerate() {
-                process_linestring(linestring, false, idxc, processor)?;
-            }
-            processor.multilinestring_end(idx)
-        }
-        Geometry::Polygon(g) => process_polygon(g, true, idx, processor),
-        Geometry::MultiPolygon(g) => {
-            processor.multipolygon_begin(g.0.len(), idx)?;
-            for (idx2, polygon) in g.0.iter().enumerate() {
-                process_polygon(polygon, false, idx2, processor)?;
-            }
-            processor.multipolygon_end(idx)
-        }
-        Geometry::GeometryCollection(g) => {
-            processor.geometrycollection_begin(g.0.len(), idx)?;
-            for (idx2, geometry) in g.0.iter().enumerate() {
-                process_wkt_geom_n(geometry, idx2, processor)?;
-            }
-            processor.geometrycollection_end(idx)
-        }
-    }
-}
-
-fn process_coord<P: GeomProcessor>(
-    coord: &Coord<f64>,
-    multi_dim: bool,
-    idx: usize,
-    processor: &mut P,
-) -> Result<()> {
-    if multi_dim {
-        processor.coordinate(coord.x, coord.y, coord.z, coord.m, None, None, idx)
-    } else {
-        processor.xy(coord.x, coord.y, idx)
-    }
-}
-
-fn process_linestring<P: GeomProcessor>(
-    linestring: &LineString<f64>,
-    tagged: bool,
-    idx: usize,
-    processor: &mut P,
-) -> Result<()> {
-    processor.linestring_begin(tagged, linestring.0.len(), idx)?;
-    let multi_dim = processor.multi_dim();
-    for (idxc, coord) in linestring.0.iter().enumerate() {
-        process_coord(coord, multi_dim, idxc, processor)?;
-    }
-    processor.linestring_end(tagged, idx)
-}
-
-fn process_polygon<P: GeomProcessor>(
-    polygon: &Polygon<f64>,
-    tagged: bool,
-    idx: usize,
-    processor: &mut P,
-) -> Result<()> {
-    processor.polygon_begin(tagged, polygon.0.len(), idx)?;
-    for (idx2, linestring_type) in polygon.0.iter().enumerate() {
-        process_linestring(linestring_type, false, idx2, processor)?;
-    }
-    processor.polygon_end(tagged, idx)
+    read_wkt_str(&wkt_string, 0, processor)
 }
 
 #[cfg(all(test, feature = "with-geo"))]
@@ -305,14 +202,62 @@ mod test {
         assert_eq!(str, &round_tripped);
     }
 
+    #[test]
+    fn relaxed_syntax() {
+        for (relaxed, canonical) in [
+            ("  point (1 2) ", "POINT(1 2)"),
+            ("Point(1 2)", "POINT(1 2)"),
+            ("MultiLineString((0 0,1 1))", "MULTILINESTRING((0 0,1 1))"),
+            (
+                "multipolygon (\n  ((0 0, 1 0, 1 1, 0 0)),\n\t((5 5,6 5,6 6,5 5))\n)\n",
+                "MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((5 5,6 5,6 6,5 5)))",
+            ),
+            (
+                "GeometryCollection( point(1 2) , LineString ( 0 0 , 1 1 ) )",
+                "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(0 0,1 1))",
+            ),
+            (
+                "circularString (0 0,1 1,2 0)",
+                "CIRCULARSTRING(0 0,1 1,2 0)",
+            ),
+        ] {
+            assert_eq!(
+                WktStr(relaxed).to_wkt().unwrap(),
+                WktStr(canonical).to_wkt().unwrap(),
+                "{relaxed}"
+            );
+        }
+    }
+
     mod empties {
         use super::*;
 
+        #[test]
+        fn empty_keyword_case() {
+            for geometry_type in [
+                "POINT",
+                "LINESTRING",
+                "POLYGON",
+                "MULTIPOINT",
+                "MULTILINESTRING",
+                "MULTIPOLYGON",
+                "GEOMETRYCOLLECTION",
+                "CIRCULARSTRING",
+            ] {
+                let canonical = format!("{geometry_type} EMPTY");
+                let relaxed = format!("  {}\n empty ", geometry_type.to_lowercase());
+                assert_eq!(WktStr(&canonical).to_wkt().unwrap(), canonical);
+                assert_eq!(WktStr(&relaxed).to_wkt().unwrap(), canonical);
+            }
+        }
+
         #[test]
         fn empty_point() {
+            // geo-types has no empty Point, GeoWriter maps it to an empty MultiPoint
             let wkt = WktStr("POINT EMPTY");
-            let actual = wkt.to_geo().unwrap_err();
-            assert!(matches!(actual, GeozeroError::Geometry(_)));
+            let actual = wkt.to_geo().unwrap();
+            let expected: geo_types::Geometry<f64> = geo_types::MultiPoint(vec![]).into();
+            assert_eq!(expected, actual);
         }
 
         #[test]
@@ -1,4 +1,5 @@
 use crate::error::Result;
+use crate::formatting::{write_rounded, MAX_PRECISION};
 use crate::{CoordDimensions, FeatureProcessor, GeomProcessor, PropertyProcessor};
 use std::io::Write;
 use std::vec;
@@ -11,11 +12,33 @@ pub struct WktWriter<W: Write> {
     srid: Option<i32>,
     dialect: WktDialect,
     first_header: bool,
-    /// Stack of in-progress geometry sizes
-    geometry_sizes: Vec<usize>,
+    precision: Option<usize>,
+    comma_space: bool,
+    /// Stack of in-progress geometries
+    geometry_stack: Vec<OpenGeometry>,
+    /// Text of the current geometry, written to `out` when the outermost geometry ends
+    buf: Vec<u8>,
+    /// Positions in `buf` where the dimension tag is inserted, with empty flag
+    tag_positions: Vec<(usize, bool)>,
+    /// Z values seen in the current geometry
+    has_z: bool,
+    /// M values seen in the current geometry
+    has_m: bool,
+    /// Requested Z and M dimensions of the input, for tagging geometries without coordinates
+    input_dims: (bool, bool),
+    /// Coordinates written in the current geometry
+    has_coords: bool,
     pub(crate) out: W,
 }
 
+/// In-progress geometry
+struct OpenGeometry {
+    size: usize,
+    tagged: bool,
+    /// Position of the opening parenthesis in the buffer
+    paren_pos: usize,
+}
+
 impl<W: Write> WktWriter<W> {
     pub fn new(out: W) -> Self {
         Self::with_opts(out, WktDialect::Wkt, CoordDimensions::default(), None)
@@ -36,52 +59,160 @@ impl<W: Write> WktWriter<W> {
             srid,
             dialect,
             first_header: true,
-            geometry_sizes: vec![],
+            precision: None,
+            comma_space: false,
+            geometry_stack: vec![],
+            buf: Vec::new(),
+            tag_positions: Vec::new(),
+            has_z: false,
+            has_m: false,
+            input_dims: (false, false),
+            has_coords: false,
             out,
         }
     }
 
+    /// Round coordinates to the given number of decimal places.
+    ///
+    /// Trailing zeros are omitted, e.g. `1.50` is written as `1.5`.
+    pub fn with_precision(mut self, decimals: usize) -> Self {
+        self.precision = Some(decimals.min(MAX_PRECISION));
+        self
+    }
+
+    /// Write a space after commas, e.g. `LINESTRING(1 2, 3 4)` instead of `LINESTRING(1 2,3 4)`.
+    pub fn with_comma_space(mut self, enabled: bool) -> Self {
+        self.comma_space = enabled;
+        self
+    }
+
+    /// Write the EWKT SRID prefix again for the next geometry
+    #[cfg(feature = "with-csv")]
+    pub(crate) fn reset_header(&mut self) {
+        self.first_header = true;
+    }
+
+    /// Geometry output not yet written to `out`
+    #[cfg(feature = "with-csv")]
+    pub(crate) fn has_pending_geometry(&self) -> bool {
+        !self.buf.is_empty()
+    }
+
     fn header(&mut self, srid: Option<i32>) -> Result<()> {
         if self.first_header && self.dialect == WktDialect::Ewkt {
             self.first_header = false;
             match srid {
                 None | Some(0) => (),
-                Some(srid) => self.out.write_all(format!("SRID={srid};").as_bytes())?,
+                Some(srid) => self.buf.write_all(format!("SRID={srid};").as_bytes())?,
             }
         }
         Ok(())
     }
     fn comma(&mut self, idx: usize) -> Result<()> {
         if idx > 0 {
-            self.out.write_all(b",")?;
+            let delimiter: &[u8] = if self.comma_space { b", " } else { b"," };
+            self.buf.write_all(delimiter)?;
         }
         Ok(())
     }
+    fn write_num(&mut self, v: f64) -> Result<()> {
+        match self.precision {
+            Some(decimals) => write_rounded(&mut self.buf, v, decimals),
+            None => {
+                write!(self.buf, "{v}")?;
+                Ok(())
+            }
+        }
+    }
+    fn write_xy(&mut self, x: f64, y: f64) -> Result<()> {
+        self.write_num(x)?;
+        self.buf.write_all(b" ")?;
+        self.write_num(y)
+    }
     fn geom_begin(&mut self, tag: &[u8], tagged: bool, size: usize, idx: usize) -> Result<()> {
         self.header(self.srid)?;
         self.comma(idx)?;
         if tagged {
-            self.out.write_all(tag)?;
+            self.buf.write_all(tag)?;
+            self.tag_positions.push((self.buf.len(), size == 0));
         }
-        self.geometry_sizes.push(size);
+        self.geometry_stack.push(OpenGeometry {
+            size,
+            tagged,
+            paren_pos: self.buf.len(),
+        });
         if size == 0 {
-            if tagged {
-                self.out.write_all(b" ")?;
-            };
-            self.out.write_all(b"EMPTY")?;
+            self.write_empty(tagged)?;
         } else {
-            self.out.write_all(b"(")?;
+            self.buf.write_all(b"(")?;
         }
         Ok(())
     }
+    fn write_empty(&mut self, tagged: bool) -> Result<()> {
+        if tagged {
+            self.buf.write_all(b" ")?;
+        };
+        self.buf.write_all(b"EMPTY")?;
+        Ok(())
+    }
     fn geom_end(&mut self) -> Result<()> {
-        if let Some(geometry_size) = self.geometry_sizes.pop() {
-            if geometry_size > 0 {
-                self.out.write_all(b")")?;
+        if let Some(geom) = self.geometry_stack.pop() {
+            if geom.size > 0 && self.buf.len() == geom.paren_pos + 1 {
+                // No parts written, e.g. a MultiPoint with skipped empty points
+                self.buf.truncate(geom.paren_pos);
+                self.write_empty(geom.tagged)?;
+                if let Some(tag_pos) = self.tag_positions.last_mut() {
+                    if tag_pos.0 == geom.paren_pos {
+                        tag_pos.1 = true;
+                    }
+                }
+            } else if geom.size > 0 {
+                self.buf.write_all(b")")?;
             }
         } else {
             debug_assert!(false, "ended geometry that didn't start");
         }
+        if self.geometry_stack.is_empty() {
+            self.flush_geometry()?;
+        }
+        Ok(())
+    }
+    /// Dimension tag for the current geometry, e.g. `POINT ZM (1 2 3 4)`
+    fn dimension_tag(&self) -> &'static [u8] {
+        let (has_z, has_m) = if self.has_coords {
+            (self.has_z, self.has_m)
+        } else {
+            self.input_dims
+        };
+        match (self.dialect, has_z, has_m) {
+            (WktDialect::Wkt, true, false) => b" Z ",
+            (WktDialect::Wkt, false, true) => b" M ",
+            (WktDialect::Wkt, true, true) => b" ZM ",
+            // EWKT only tags measured geometries without Z, e.g. `POINTM(1 2 3)`
+            (WktDialect::Ewkt, false, true) => b"M",
+            _ => b"",
+        }
+    }
+    /// Write buffered geometry with dimension tags inserted
+    fn flush_geometry(&mut self) -> Result<()> {
+        let tag = self.dimension_tag();
+        let mut start = 0;
+        for &(pos, empty) in &self.tag_positions {
+            self.out.write_all(&self.buf[start..pos])?;
+            if empty {
+                self.out.write_all(tag.strip_suffix(b" ").unwrap_or(tag))?;
+            } else {
+                self.out.write_all(tag)?;
+            }
+            start = pos;
+        }
+        self.out.write_all(&self.buf[start..])?;
+        self.buf.clear();
+        self.tag_positions.clear();
+        self.has_z = false;
+        self.has_m = false;
+        self.input_dims = (false, false);
+        self.has_coords = false;
         Ok(())
     }
 }
@@ -96,12 +227,19 @@ impl<W: Write> GeomProcessor for WktWriter<W> {
         Ok(())
     }
 
+    fn input_dimensions(&mut self, dimensions: CoordDimensions) -> Result<()> {
+        // Geometries with coordinates are tagged with the dimensions of the coordinates
+        self.input_dims = (dimensions.z && self.dims.z, dimensions.m && self.dims.m);
+        Ok(())
+    }
+
     fn xy(&mut self, x: f64, y: f64, idx: usize) -> Result<()> {
         self.comma(idx)?;
         if f64::is_nan(x) && f64::is_nan(y) {
-            self.out.write_all(b"EMPTY")?;
+            self.buf.write_all(b"EMPTY")?;
         } else {
-            self.out.write_all(format!("{x} {y}").as_bytes())?;
+            self.write_xy(x, y)?;
+            self.has_coords = true;
         }
         Ok(())
     }
@@ -122,14 +260,19 @@ impl<W: Write> GeomProcessor for WktWriter<W> {
             && z.map(f64::is_nan).unwrap_or(true)
             && m.map(f64::is_nan).unwrap_or(true)
         {
-            self.out.write_all(b"EMPTY")?;
+            self.buf.write_all(b"EMPTY")?;
         } else {
-            self.out.write_all(format!("{x} {y}").as_bytes())?;
+            self.write_xy(x, y)?;
+            self.has_coords = true;
             if let Some(z) = z {
-                self.out.write_all(format!(" {z}").as_bytes())?;
+                self.buf.write_all(b" ")?;
+                self.write_num(z)?;
+                self.has_z = true;
             }
             if let Some(m) = m {
-                self.out.write_all(format!(" {m}").as_bytes())?;
+                self.buf.write_all(b" ")?;
+                self.write_num(m)?;
+                self.has_m = true;
             }
         }
         Ok(())
@@ -259,4 +402,73 @@ mod test {
         let ewkt = EwktString::from_wkb(&mut cursor, WkbDialect::Ewkb).unwrap();
         assert_eq!(ewkt.0, "SRID=4326;MULTIPOINT(10 -20 100,0 -0.5 101)")
     }
+
+    #[test]
+    fn formatting_options() -> crate::error::Result<()> {
+        use crate::wkt::{WktStr, WktWriter};
+        use crate::{GeomProcessor, GeozeroGeometry};
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::new(&mut out)
+            .with_precision(2)
+            .with_comma_space(true);
+        WktStr("MULTILINESTRING((1.5 -0.001,2.12345 -2.999),(-1.005 1e20,3 4))")
+            .process_geom(&mut writer)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "MULTILINESTRING((1.5 0, 2.12 -3), (-1 100000000000000000000, 3 4))"
+        );
+
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::new(&mut out).with_precision(0);
+        WktStr("POLYGON EMPTY").process_geom(&mut writer)?;
+        WktStr("POINT(0.4 0.6)").process_geom(&mut writer)?;
+        assert_eq!(
+            std::str::from_utf8(&out).unwrap(),
+            "POLYGON EMPTYPOINT(0 1)"
+        );
+
+        // MultiPoint without written points
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = WktWriter::new(&mut out);
+        writer.multipoint_begin(1, 0)?;
+        writer.multipoint_end(0)?;
+        assert_eq!(std::str::from_utf8(&out).unwrap(), "MULTIPOINT EMPTY");
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "with-wkb")]
+    fn dimension_tags() -> crate::error::Result<()> {
+        use crate::wkb::Wkb;
+        use crate::wkt::WktStr;
+        use crate::{CoordDimensions, ToWkb};
+
+        for (wkt, dims) in [
+            ("POINT(1 2)", CoordDimensions::xy()),
+            ("POINT Z (1 2 3)", CoordDimensions::xyz()),
+            ("POINT M (1 2 4)", CoordDimensions::xym()),
+            ("POINT ZM (1 2 3 4)", CoordDimensions::xyzm()),
+            ("LINESTRING ZM (1 2 3 4,5 6 7 8)", CoordDimensions::xyzm()),
+            (
+                "MULTIPOLYGON M (((0 0 1,1 0 2,1 1 3,0 0 1)))",
+                CoordDimensions::xym(),
+            ),
+            (
+                "GEOMETRYCOLLECTION Z (POINT Z (1 2 3),LINESTRING Z (1 2 3,4 5 6))",
+                CoordDimensions::xyz(),
+            ),
+        ] {
+            let wkb = WktStr(wkt).to_wkb(dims)?;
+            assert_eq!(Wkb(&wkb).to_wkt_ndim(dims)?, wkt);
+        }
+
+        // Tags reflect the written coordinates, not the requested dimensions
+        assert_eq!(
+            WktStr("POINT(1 2)").to_wkt_ndim(CoordDimensions::xyzm())?,
+            "POINT(1 2)"
+        );
+        assert_eq!(WktStr("POINT M (1 2 4)").to_ewkt(None)?, "POINTM(1 2 4)");
+        Ok(())
+    }
 }
@@ -0,0 +1,62 @@
+use geozero::geojson::GeoJson;
+use geozero::wkb::Wkb;
+use geozero::wkt::WktStr;
+use geozero::{CoordDimensions, ToGeo, ToJson, ToWkb, ToWkt};
+
+const EMPTY_GEOMETRIES: [&str; 9] = [
+    "POINT EMPTY",
+    "LINESTRING EMPTY",
+    "POLYGON EMPTY",
+    "MULTIPOINT EMPTY",
+    "MULTILINESTRING EMPTY",
+    "MULTIPOLYGON EMPTY",
+    "GEOMETRYCOLLECTION EMPTY",
+    "GEOMETRYCOLLECTION(POINT EMPTY,LINESTRING EMPTY,MULTIPOLYGON EMPTY)",
+    "MULTILINESTRING(EMPTY,(1 2,3 4))",
+];
+
+#[test]
+fn wkb_roundtrip() -> geozero::error::Result<()> {
+    for wkt in EMPTY_GEOMETRIES {
+        let wkb = WktStr(wkt).to_wkb(CoordDimensions::xy())?;
+        assert_eq!(Wkb(wkb).to_wkt()?, wkt);
+    }
+    Ok(())
+}
+
+#[test]
+fn geojson_roundtrip() -> geozero::error::Result<()> {
+    for wkt in EMPTY_GEOMETRIES {
+        let json = WktStr(wkt).to_json()?;
+        assert_eq!(GeoJson(&json).to_wkt()?, wkt, "{json}");
+    }
+    Ok(())
+}
+
+#[test]
+fn geo_types_roundtrip() -> geozero::error::Result<()> {
+    for wkt in EMPTY_GEOMETRIES {
+        let expected = match wkt {
+            // geo-types has no empty Point
+            "POINT EMPTY" => "MULTIPOINT EMPTY",
+            "GEOMETRYCOLLECTION(POINT EMPTY,LINESTRING EMPTY,MULTIPOLYGON EMPTY)" => {
+                "GEOMETRYCOLLECTION(MULTIPOINT EMPTY,LINESTRING EMPTY,MULTIPOLYGON EMPTY)"
+            }
+            _ => wkt,
+        };
+        assert_eq!(WktStr(wkt).to_geo()?.to_wkt()?, expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn multipoint_with_empty_member() -> geozero::error::Result<()> {
+    // SELECT 'MULTIPOINT(1 2, EMPTY, 3 4)'::geometry
+    let wkb = hex::decode("0104000000030000000101000000000000000000f03f00000000000000400101000000000000000000f87f000000000000f87f010100000000000000000008400000000000001040").unwrap();
+    assert_eq!(Wkb(wkb.clone()).to_wkt()?, "MULTIPOINT(1 2,EMPTY,3 4)");
+    assert_eq!(
+        Wkb(wkb).to_json()?,
+        r#"{"type": "MultiPoint", "coordinates": [[1,2],[3,4]]}"#
+    );
+    Ok(())
+}
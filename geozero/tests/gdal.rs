@@ -15,11 +15,26 @@ fn ogr_to_svg() -> Result<(), gdal::errors::GdalError> {
         let svg = geom.to_svg();
         assert!(svg.is_ok());
         // concatenate SVG geometries
-        assert!(process_geom(geom, &mut SvgWriter::new(&mut out, true)).is_ok());
+        assert!(process_geom(geom, &mut SvgWriter::with_invert_y(&mut out, true)).is_ok());
     }
     assert_eq!(
         &std::str::from_utf8(&out).unwrap()[..53],
-        r#"<path d="M 32.533299524864844 -0.583299105614628 Z"/>"#
+        r#"<circle cx="32.533299524864844" cy="-0.583299105614628" r="1"/>"#
     );
     Ok(())
 }
+
+#[test]
+fn layer_to_geojson() -> geozero::error::Result<()> {
+    use geozero::gdal::GdalDatasource;
+    use geozero::ProcessToJson;
+
+    let dataset = Dataset::open(Path::new("tests/data/places.json"))?;
+    let mut reader = GdalDatasource::new(dataset.layer(0)?);
+    assert!(reader.crs().is_some());
+    let json = reader.to_json()?;
+    assert!(json.contains(
+        r#"{"type": "Feature", "id": 0, "properties": {"NAME": "Bombo", "ADM0_A3": "UGA"}, "geometry": {"type": "Point", "coordinates": [32.533299524864844,0.583299105614628]}}"#
+    ));
+    Ok(())
+}
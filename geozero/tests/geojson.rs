@@ -1,9 +1,9 @@
-use flatgeobuf::{FgbReader, HttpFgbReader};
+use flatgeobuf::{FgbReader, FgbWriter, GeometryType, HttpFgbReader};
 use geozero::geojson::GeoJsonWriter;
 use geozero::ProcessToJson;
 use seek_bufread::BufReader;
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{BufWriter, Cursor, Read, Seek, SeekFrom};
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
 #[test]
@@ -21,6 +21,84 @@ fn fgb_to_geojson() -> Result<()> {
     Ok(())
 }
 
+/// Reader counting the number of bytes read
+struct CountingReader<R> {
+    inner: R,
+    bytes_read: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bytes_read += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for CountingReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[derive(Default)]
+struct FeatureCount(usize);
+
+impl geozero::GeomProcessor for FeatureCount {}
+impl geozero::PropertyProcessor for FeatureCount {}
+impl geozero::FeatureProcessor for FeatureCount {
+    fn feature_end(&mut self, _idx: u64) -> geozero::error::Result<()> {
+        self.0 += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn fgb_select_bbox_with_index() -> Result<()> {
+    let mut all = CountingReader {
+        inner: File::open("tests/data/countries.fgb")?,
+        bytes_read: 0,
+    };
+    let mut count = FeatureCount::default();
+    FgbReader::open(&mut all)?
+        .select_all()?
+        .process_features(&mut count)?;
+    assert_eq!(count.0, 179);
+
+    let mut bbox = CountingReader {
+        inner: File::open("tests/data/countries.fgb")?,
+        bytes_read: 0,
+    };
+    let mut count = FeatureCount::default();
+    FgbReader::open(&mut bbox)?
+        .select_bbox(8.8, 47.2, 9.5, 55.3)?
+        .process_features(&mut count)?;
+    assert_eq!(count.0, 6);
+
+    // Only the index and the selected features are read
+    assert!(bbox.bytes_read * 4 < all.bytes_read);
+    Ok(())
+}
+
+#[test]
+fn fgb_writer_roundtrip() -> Result<()> {
+    let mut filein = BufReader::new(File::open("tests/data/countries.fgb")?);
+    let mut fgb = FgbWriter::create("countries", GeometryType::Unknown)?;
+    FgbReader::open(&mut filein)?
+        .select_all()?
+        .process_features(&mut fgb)?;
+    let mut out: Vec<u8> = Vec::new();
+    fgb.write(&mut out)?;
+
+    // Written file includes a spatial index
+    let mut count = FeatureCount::default();
+    FgbReader::open(&mut Cursor::new(out))?
+        .select_bbox(8.8, 47.2, 9.5, 55.3)?
+        .process_features(&mut count)?;
+    assert_eq!(count.0, 6);
+    Ok(())
+}
+
 #[allow(dead_code)]
 // #[tokio::test]
 async fn http_fbg_to_json() -> Result<()> {
@@ -106,3 +106,198 @@ async fn geos_query() -> Result<(), sqlx::Error> {
 
     Ok(())
 }
+
+/// Collect `name` properties of processed features
+#[derive(Default)]
+struct FeatureNames(Vec<String>);
+
+impl geozero::GeomProcessor for FeatureNames {}
+
+impl geozero::PropertyProcessor for FeatureNames {
+    fn property(
+        &mut self,
+        _idx: usize,
+        name: &str,
+        value: &geozero::ColumnValue,
+    ) -> geozero::error::Result<bool> {
+        if name == "name" {
+            self.0.push(value.to_string());
+        }
+        Ok(false)
+    }
+}
+
+impl geozero::FeatureProcessor for FeatureNames {}
+
+#[tokio::test]
+async fn reader_select_bbox() -> Result<(), sqlx::Error> {
+    use geozero::gpkg::GpkgReader;
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(5)
+        .connect("sqlite://tests/data/gpkg_test.gpkg")
+        .await?;
+    let reader = GpkgReader::new(pool);
+
+    let mut names = FeatureNames::default();
+    reader.select_all("pt2d", &mut names).await.unwrap();
+    assert_eq!(names.0, ["alpha", "beta", "gamma", "delta"]);
+
+    let mut names = FeatureNames::default();
+    reader
+        .select_bbox("pt2d", 1.5, 2.0, 2.0, 2.2, &mut names)
+        .await
+        .unwrap();
+    assert_eq!(names.0, ["beta"]);
+
+    let mut names = FeatureNames::default();
+    reader
+        .select_bbox("ln2d", 0.5, 0.5, 1.5, 1.5, &mut names)
+        .await
+        .unwrap();
+    assert_eq!(names.0, ["alpha", "beta", "gamma"]);
+
+    assert!(reader
+        .select_bbox("missing", 0.0, 0.0, 1.0, 1.0, &mut FeatureNames::default())
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn reader_select_bbox_without_rtree() -> Result<(), sqlx::Error> {
+    use geozero::gpkg::GpkgReader;
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+    // A plain file name would be attached as in-memory database like the main connection
+    sqlx::query("ATTACH DATABASE 'file:tests/data/gpkg_test.gpkg?mode=ro' AS src")
+        .execute(&pool)
+        .await?;
+    sqlx::query("CREATE TABLE gpkg_geometry_columns AS SELECT * FROM src.gpkg_geometry_columns")
+        .execute(&pool)
+        .await?;
+    sqlx::query("CREATE TABLE pt2d AS SELECT * FROM src.pt2d")
+        .execute(&pool)
+        .await?;
+    let reader = GpkgReader::new(pool);
+
+    let mut names = FeatureNames::default();
+    reader
+        .select_bbox("pt2d", 1.5, 2.0, 2.0, 2.2, &mut names)
+        .await
+        .unwrap();
+    assert_eq!(names.0, ["beta"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn writer_roundtrip() -> Result<(), sqlx::Error> {
+    use geozero::gpkg::{GpkgReader, GpkgWriter};
+    use geozero::ToWkt;
+
+    async fn geometries(pool: &sqlx::SqlitePool, table: &str) -> Vec<(i64, String, String)> {
+        let rows: Vec<(i64, String, Option<wkb::GpkgWkb>)> =
+            sqlx::query_as(&format!("SELECT id, name, geom FROM {table} ORDER BY id"))
+                .fetch_all(pool)
+                .await
+                .unwrap();
+        rows.into_iter()
+            .map(|(id, name, geom)| {
+                (
+                    id,
+                    name,
+                    geom.map(|g| g.to_wkt().unwrap()).unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    let src = SqlitePoolOptions::new()
+        .max_connections(5)
+        .connect("sqlite://tests/data/gpkg_test.gpkg")
+        .await?;
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+    let mut writer = GpkgWriter::new("lines");
+    writer.begin(&pool).await.unwrap();
+    GpkgReader::new(src.clone())
+        .select_all("ln2d", &mut writer)
+        .await
+        .unwrap();
+    writer.commit().await.unwrap();
+
+    let expected = geometries(&src, "ln2d").await;
+    assert_eq!(expected.len(), 4);
+    assert_eq!(geometries(&pool, "lines").await, expected);
+
+    let row: (String, String, i32, i32, i32) = sqlx::query_as(
+        "SELECT column_name, geometry_type_name, srs_id, z, m FROM gpkg_geometry_columns WHERE table_name = 'lines'",
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(
+        row,
+        ("geom".to_string(), "GEOMETRY".to_string(), 4326, 0, 0)
+    );
+    let row: (String, f64, f64, f64, f64) = sqlx::query_as(
+        "SELECT data_type, min_x, min_y, max_x, max_y FROM gpkg_contents WHERE table_name = 'lines'",
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(row, ("features".to_string(), 0.0, 0.0, 10.0, 10.0));
+    let (application_id,): (i32,) = sqlx::query_as("PRAGMA application_id")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(application_id, 0x47504B47);
+
+    // Written features can be queried with the reader
+    let mut names = FeatureNames::default();
+    GpkgReader::new(pool)
+        .select_bbox("lines", 0.5, 0.5, 1.5, 1.5, &mut names)
+        .await
+        .unwrap();
+    assert_eq!(names.0, ["alpha", "beta", "gamma"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn writer_srs() -> Result<(), sqlx::Error> {
+    use geozero::error::GeozeroError;
+    use geozero::gpkg::GpkgWriter;
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await?;
+
+    // SRIDs other than -1, 0 and 4326 require a definition
+    let mut writer = GpkgWriter::new("unknown").with_srid(2056);
+    assert!(matches!(
+        writer.begin(&pool).await,
+        Err(GeozeroError::Srid(2056))
+    ));
+
+    let definition = r#"PROJCS["CH1903+ / LV95",GEOGCS["CH1903+",DATUM["CH1903+",SPHEROID["Bessel 1841",6377397.155,299.1528128]],PRIMEM["Greenwich",0],UNIT["degree",0.0174532925199433]],PROJECTION["Hotine_Oblique_Mercator_Azimuth_Center"],UNIT["metre",1],AUTHORITY["EPSG","2056"]]"#;
+    let mut writer = GpkgWriter::new("swiss").with_srs(2056, definition);
+    writer.begin(&pool).await.unwrap();
+    writer.commit().await.unwrap();
+
+    let rows: Vec<(i32, String)> =
+        sqlx::query_as("SELECT srs_id, definition FROM gpkg_spatial_ref_sys ORDER BY srs_id")
+            .fetch_all(&pool)
+            .await?;
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[3].0, 4326);
+    assert!(rows[3].1.starts_with("GEOGCS[\"WGS 84\""));
+    assert_eq!(rows[2], (2056, definition.to_string()));
+
+    Ok(())
+}
@@ -12,3 +12,13 @@ fn prepared_geom() {
     let geom2 = geos::Geometry::new_from_wkt("POINT (2.5 2.5)").expect("Invalid geometry");
     assert_eq!(prepared_geom.contains(&geom2), Ok(true));
 }
+
+#[test]
+fn buffer_to_geojson() {
+    use geozero::ToJson;
+    let geojson = GeoJson(r#"{"type": "Point", "coordinates": [1, 1]}"#);
+    let geom = geojson.to_geos().expect("GEOS conversion failed");
+    let buffered = geom.buffer(1.0, 8).expect("buffer failed");
+    let json = buffered.to_json().expect("GeoJSON conversion failed");
+    assert!(json.starts_with(r#"{"type": "Polygon", "coordinates": [[[2,1],"#));
+}
@@ -153,7 +153,7 @@ mod extensive_conversion {
 
         use geozero::ToSvg;
         let actual_svg = reader.to_svg().unwrap();
-        let expected_svg: &str = r#"<path d="M -1.5153741828293 47.253146555709 Z"/><path d="M -1.5482325613225 47.235331031612 Z"/><path d="M -1.5521714646550901 47.2278526991611 -1.5504753767742476 47.229236980562256 Z M -1.5493804339650867 47.2301112449252 -1.5485645942249218 47.230562942529104 Z "/><path d="M -1.5521714646550901 47.2278526991611 -1.5504753767742476 47.229236980562256 -1.5493804339650867 47.2301112449252 Z "/>"#;
+        let expected_svg: &str = r#"<circle cx="-1.5153741828293" cy="47.253146555709" r="1"/><circle cx="-1.5482325613225" cy="47.235331031612" r="1"/><path d="M -1.5521714646550901 47.2278526991611 -1.5504753767742476 47.229236980562256 Z M -1.5493804339650867 47.2301112449252 -1.5485645942249218 47.230562942529104 Z "/><path d="M -1.5521714646550901 47.2278526991611 -1.5504753767742476 47.229236980562256 -1.5493804339650867 47.2301112449252 Z "/>"#;
         assert_eq!(expected_svg, actual_svg);
     }
 
@@ -168,3 +168,35 @@ mod extensive_conversion {
         assert_eq!(expected_wkt, wkt);
     }
 }
+
+mod features {
+    use geozero::geojson::GeoJsonWriter;
+    use geozero::{CoordDimensions, GeozeroDatasource};
+
+    #[test]
+    fn extensive() {
+        let gpx_str = include_str!("data/extensive.gpx");
+        let mut reader = geozero::gpx::GpxFeatureReader(gpx_str.as_bytes());
+        let mut out: Vec<u8> = Vec::new();
+        let mut writer = GeoJsonWriter::with_dims(&mut out, CoordDimensions::xyz());
+        reader.process(&mut writer).unwrap();
+        let geojson = std::str::from_utf8(&out).unwrap();
+
+        assert_eq!(geojson.matches(r#""type": "Feature""#).count(), 4);
+        assert!(geojson.contains(r#"{"type": "Feature", "properties": {"kind": "waypoint", "name": "Porte de Carquefou", "description": "Route", "time": "2020-02-02T07:54:30.000000000Z", "elevation": 35}, "geometry": {"type": "Point", "coordinates": [-1.5153741828293,47.253146555709,35]}}"#));
+        assert!(geojson.contains(r#""geometry": {"type": "LineString", "coordinates": [[-1.5521714646550901,47.2278526991611,12.36],[-1.5504753767742476,47.229236980562256,7.08],[-1.5493804339650867,47.2301112449252,7.07]]}"#));
+        // Track with two segments
+        assert!(geojson.contains(r#"{"type": "Feature", "properties": {"kind": "track", "name": "Track", "description": "Demo track", "time": "2020-02-02T07:54:30.000000000Z"}, "geometry": {"type": "MultiLineString", "coordinates": [[[-1.5521714646550901,47.2278526991611,12.36],[-1.5504753767742476,47.229236980562256,7.08]],[[-1.5493804339650867,47.2301112449252,7.07],[-1.5485645942249218,47.230562942529104,7.44]]]}}"#));
+    }
+
+    #[test]
+    fn wikipedia_example() {
+        let gpx_str = include_str!("data/wikipedia_example.gpx");
+        let mut reader = geozero::gpx::GpxFeatureReader(gpx_str.as_bytes());
+
+        use geozero::ProcessToJson;
+        let geojson = reader.to_json().unwrap();
+        // Single track segment as LineString
+        assert!(geojson.contains(r#""geometry": {"type": "LineString", "coordinates": [[-122.326897,47.644548],[-122.326897,47.644548],[-122.326897,47.644548]]}"#));
+    }
+}
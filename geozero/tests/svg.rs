@@ -14,8 +14,8 @@ fn json_to_svg() -> Result<()> {
     println!("{svg}");
     assert_eq!(
         &svg[svg.len() - 100..],
-        r#"387481909902 1.294979325105942 Z"/>
-<path d="M 114.18306345846304 22.30692675357551 Z"/>
+        r#".294979325105942" r="1"/>
+<circle cx="114.18306345846304" cy="22.30692675357551" r="1"/>
 </g>
 </svg>"#
     );
@@ -33,7 +33,7 @@ fn invert_y(header: &Header) -> bool {
 }
 
 fn svg_writer<W: Write>(header: &Header, width: u32, height: u32, out: W) -> SvgWriter<W> {
-    let mut svg = SvgWriter::new(out, invert_y(header));
+    let mut svg = SvgWriter::with_invert_y(out, invert_y(header));
     if let Some(envelope) = header.envelope() {
         svg.set_dimensions(
             envelope.get(0),